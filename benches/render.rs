@@ -0,0 +1,100 @@
+// Dependencies of the library are also visible to benchmarks
+#![allow(unused_crate_dependencies)]
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use el::{bench_support, html, Element, Render};
+
+fn bench_tree(c: &mut Criterion, name: &str, sizes: &[usize], generate: impl Fn(usize) -> Element) {
+    let mut group = c.benchmark_group(name);
+    for &size in sizes {
+        group.bench_with_input(BenchmarkId::new("construct", size), &size, |b, &size| {
+            b.iter(|| generate(black_box(size)))
+        });
+
+        let element = generate(size);
+        let len = element.render_to_string().unwrap().len();
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(BenchmarkId::new("render", size), &element, |b, element| {
+            b.iter(|| element.render_to_string().unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn deep_tree(c: &mut Criterion) {
+    bench_tree(c, "deep_tree", &[10, 100, 1000], bench_support::deep_tree);
+}
+
+fn wide_table(c: &mut Criterion) {
+    bench_tree(c, "wide_table", &[10, 100, 1000], |rows| {
+        bench_support::wide_table(rows, 10)
+    });
+}
+
+fn attribute_heavy(c: &mut Criterion) {
+    bench_tree(c, "attribute_heavy", &[10, 100, 1000], |elements| {
+        bench_support::attribute_heavy(elements, 10)
+    });
+}
+
+fn text_heavy(c: &mut Criterion) {
+    bench_tree(c, "text_heavy", &[10, 100, 1000], bench_support::text_heavy);
+}
+
+fn long_list(c: &mut Criterion) {
+    let mut group = c.benchmark_group("long_list");
+    for size in [1000, 10000, 100000] {
+        group.throughput(Throughput::Elements(size as u64));
+
+        // Adding one child at a time, for comparison
+        group.bench_with_input(
+            BenchmarkId::new("construct_add", size),
+            &size,
+            |b, &size| {
+                b.iter(|| {
+                    let mut list = html::ul(());
+                    for i in 0..black_box(size) {
+                        list.add(html::li(format!("Item {i}")));
+                    }
+                    list
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("construct_extend", size),
+            &size,
+            |b, &size| b.iter(|| bench_support::long_list(black_box(size))),
+        );
+    }
+    group.finish();
+}
+
+fn page(c: &mut Criterion) {
+    let mut group = c.benchmark_group("page");
+    for size in [1, 10, 100] {
+        group.bench_with_input(BenchmarkId::new("construct", size), &size, |b, &size| {
+            b.iter(|| bench_support::page(black_box(size)))
+        });
+
+        let page = bench_support::page(size);
+        let len = page.render_to_string().unwrap().len();
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(BenchmarkId::new("render", size), &page, |b, page| {
+            b.iter(|| page.render_to_string().unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    deep_tree,
+    wide_table,
+    attribute_heavy,
+    text_heavy,
+    long_list,
+    page
+);
+criterion_main!(benches);
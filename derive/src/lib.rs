@@ -0,0 +1,223 @@
+//! Derive macros for [el](https://crates.io/crates/el).
+//!
+//! Don't depend on this crate directly. Enable the `derive` feature of `el`
+//! instead, which re-exports the macros.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, LitStr, Path, Variant};
+
+/// A field of a struct, with its `#[el(...)]` options applied.
+struct Field {
+    ident: Ident,
+    label: String,
+    with: Option<Path>,
+}
+
+impl Field {
+    /// Tokens converting the field's value into an element component.
+    fn value(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        match &self.with {
+            Some(with) => quote! { #with(&self.#ident) },
+            None => quote! { ::el::tabular::ToCell::to_cell(&self.#ident) },
+        }
+    }
+}
+
+/// Convert a field name like `user_id` into a label like `User id`.
+fn label(ident: &Ident) -> String {
+    let name = ident.to_string();
+    let name = name.strip_prefix("r#").unwrap_or(&name);
+    let mut words = name.split('_').filter(|w| !w.is_empty());
+    let mut label = String::new();
+    if let Some(first) = words.next() {
+        let mut chars = first.chars();
+        label.extend(chars.next().into_iter().flat_map(char::to_uppercase));
+        label.extend(chars);
+    }
+    for word in words {
+        label.push(' ');
+        label.push_str(word);
+    }
+    label
+}
+
+fn fields(input: &DeriveInput) -> syn::Result<Vec<Field>> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "only structs can be derived",
+        ));
+    };
+    let Fields::Named(named) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "only structs with named fields can be derived",
+        ));
+    };
+
+    let mut fields = vec![];
+    for field in &named.named {
+        let ident = field.ident.clone().expect("named field");
+        let mut result = Field {
+            label: label(&ident),
+            ident,
+            with: None,
+        };
+        let mut skip = false;
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("el")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                } else if meta.path.is_ident("rename") {
+                    result.label = meta.value()?.parse::<LitStr>()?.value();
+                } else if meta.path.is_ident("with") {
+                    result.with = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                } else {
+                    return Err(meta.error("expected `skip`, `rename` or `with`"));
+                }
+                Ok(())
+            })?;
+        }
+        if !skip {
+            fields.push(result);
+        }
+    }
+    Ok(fields)
+}
+
+/// Tokens adding a variant of an enum to `element`, as a match arm.
+fn variant_arm(variant: &Variant) -> syn::Result<TokenStream2> {
+    let mut with = None::<Path>;
+    for attr in variant.attrs.iter().filter(|a| a.path().is_ident("el")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("with") {
+                with = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+            } else {
+                return Err(meta.error("expected `with`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let ident = &variant.ident;
+    let bindings = (0..variant.fields.len())
+        .map(|i| format_ident!("field_{i}"))
+        .collect::<Vec<_>>();
+    let pattern = match &variant.fields {
+        Fields::Unit => quote! { Self::#ident },
+        Fields::Unnamed(_) => quote! { Self::#ident(#(#bindings),*) },
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|f| &f.ident);
+            quote! { Self::#ident { #(#names: #bindings),* } }
+        }
+    };
+    let component = match with {
+        Some(with) => quote! { #with(#(#bindings),*) },
+        None => quote! { (#(#bindings,)*) },
+    };
+    Ok(quote! {
+        #pattern => ::el::ElementComponent::add_to_element(#component, element),
+    })
+}
+
+/// Derive `el::ElementComponent` for an enum.
+///
+/// See the documentation of `el::IntoElement` for the supported attributes.
+#[proc_macro_derive(IntoElement, attributes(el))]
+pub fn derive_into_element(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let Data::Enum(data) = &input.data else {
+        return syn::Error::new_spanned(&input.ident, "only enums can be derived")
+            .to_compile_error()
+            .into();
+    };
+    let arms = match data
+        .variants
+        .iter()
+        .map(variant_arm)
+        .collect::<syn::Result<Vec<_>>>()
+    {
+        Ok(arms) => arms,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote! {
+        #[automatically_derived]
+        #[allow(single_use_lifetimes)]
+        impl #impl_generics ::el::ElementComponent for #name #ty_generics #where_clause {
+            fn add_to_element(self, element: &mut ::el::Element) {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    }
+    .into()
+}
+
+/// Derive `el::tabular::ToHtmlTable` for a struct with named fields.
+///
+/// See the documentation of `el::tabular` for the supported attributes.
+#[proc_macro_derive(ToHtmlTable, attributes(el))]
+pub fn derive_to_html_table(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let fields = match fields(&input) {
+        Ok(fields) => fields,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let labels = fields.iter().map(|f| &f.label);
+    let values = fields.iter().map(Field::value);
+    quote! {
+        #[automatically_derived]
+        #[allow(single_use_lifetimes)]
+        impl #impl_generics ::el::tabular::ToHtmlTable for #name #ty_generics #where_clause {
+            fn table_header() -> ::std::vec::Vec<::el::Element> {
+                ::std::vec![#(
+                    ::el::html::th((::el::html::attr::Scope::Col, #labels))
+                ),*]
+            }
+
+            fn table_cells(&self) -> ::std::vec::Vec<::el::Element> {
+                ::std::vec![#( ::el::html::td(#values) ),*]
+            }
+        }
+    }
+    .into()
+}
+
+/// Derive `el::tabular::ToDl` for a struct with named fields.
+///
+/// See the documentation of `el::tabular` for the supported attributes.
+#[proc_macro_derive(ToDl, attributes(el))]
+pub fn derive_to_dl(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let fields = match fields(&input) {
+        Ok(fields) => fields,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let labels = fields.iter().map(|f| &f.label);
+    let values = fields.iter().map(Field::value);
+    quote! {
+        #[automatically_derived]
+        #[allow(single_use_lifetimes)]
+        impl #impl_generics ::el::tabular::ToDl for #name #ty_generics #where_clause {
+            fn dl_entries(&self) -> ::std::vec::Vec<(::el::Element, ::el::Element)> {
+                ::std::vec![#(
+                    (::el::html::dt(#labels), ::el::html::dd(#values))
+                ),*]
+            }
+        }
+    }
+    .into()
+}
@@ -0,0 +1,26 @@
+#![no_main]
+
+use el::{Document, Placeholder, Render, RenderOptions};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (Document, RenderOptions, Placeholder)| {
+    let (document, options, placeholder) = input;
+
+    let mut strict = String::new();
+    let result = document.render_with(&mut strict, &options);
+
+    // Lenient rendering only fails if the writer fails, and agrees with strict
+    // rendering if there are no errors.
+    let mut lenient = String::new();
+    let errors = document
+        .render_lenient(&mut lenient, &options, &placeholder)
+        .unwrap();
+    if result.is_ok() {
+        assert!(errors.is_empty());
+        assert_eq!(strict, lenient);
+    } else {
+        assert!(!errors.is_empty());
+    }
+
+    let _ = document.render_chunks_with(&options);
+});
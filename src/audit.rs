@@ -0,0 +1,225 @@
+//! Listing everything in a tree that executes code or bypasses escaping.
+//!
+//! el escapes text and attribute values, so most of a rendered page is inert.
+//! The exceptions are few, but easy to overlook when reviewing a large
+//! template: unescaped [`Content::Raw`] and [`Content::Trusted`] content,
+//! `<script>` and `<style>` elements, inline event handler attributes like
+//! `onclick`, and `javascript:` URLs. [`Element::security_report`] and
+//! [`Document::security_report`] find all of them in one call, e.g. for code
+//! review or for automated policy checks in tests.
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Attr, TrustedHtml, html::*};
+//!
+//! let page = html(body((
+//!     button((Attr::set("onclick", "buy()"), "Buy")),
+//!     TrustedHtml::from_static("<hr>"),
+//!     script(attr::src("/shop.js")),
+//! )))
+//! .into_document();
+//!
+//! let report = page.security_report();
+//! assert_eq!(
+//!     report.to_string(),
+//!     concat!(
+//!         "/0(body)/0(button): event handler attribute \"onclick\"\n",
+//!         "/0(body)/1: trusted HTML\n",
+//!         "/0(body)/2(script): external script \"/shop.js\"\n",
+//!     ),
+//! );
+//! ```
+
+use std::fmt;
+
+use crate::{
+    lint::is_event_handler,
+    render::format_path,
+    url::{has_javascript_scheme, URL_ATTRIBUTES},
+    Content, Document, Element,
+};
+
+/// The kind of a [`Finding`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FindingKind {
+    /// A [`Content::Raw`], which is included in the output unescaped.
+    Raw,
+    /// A [`Content::Trusted`], which is included in the output unescaped.
+    Trusted,
+    /// A `<script>` element with inline code.
+    InlineScript,
+    /// A `<script>` element loading code from a URL.
+    ExternalScript {
+        /// The URL of the script.
+        src: String,
+    },
+    /// A `<style>` element.
+    InlineStyle,
+    /// An event handler attribute like `onclick`.
+    EventHandler {
+        /// The name of the attribute.
+        attribute: String,
+    },
+    /// An attribute containing a `javascript:` URL.
+    JavascriptUrl {
+        /// The name of the attribute.
+        attribute: String,
+    },
+}
+
+impl fmt::Display for FindingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Raw => write!(f, "raw content"),
+            Self::Trusted => write!(f, "trusted HTML"),
+            Self::InlineScript => write!(f, "inline script"),
+            Self::ExternalScript { src } => write!(f, "external script {src:?}"),
+            Self::InlineStyle => write!(f, "inline style"),
+            Self::EventHandler { attribute } => {
+                write!(f, "event handler attribute {attribute:?}")
+            }
+            Self::JavascriptUrl { attribute } => write!(f, "javascript: URL in {attribute:?}"),
+        }
+    }
+}
+
+/// A single entry of a [`SecurityReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    kind: FindingKind,
+    path: String,
+}
+
+impl Finding {
+    /// What was found.
+    pub fn kind(&self) -> &FindingKind {
+        &self.kind
+    }
+
+    /// A human-readable path from the topmost element to the finding.
+    ///
+    /// The path has the same format as [`crate::Error::path`]. It points to
+    /// the element for findings about elements or attributes, and to the
+    /// content itself for [`FindingKind::Raw`] and [`FindingKind::Trusted`].
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.kind)
+    }
+}
+
+/// Everything in a tree that executes code or bypasses escaping, see the
+/// [module documentation](self).
+///
+/// When displayed, the report lists one finding per line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SecurityReport {
+    findings: Vec<Finding>,
+}
+
+impl SecurityReport {
+    /// All findings, in document order.
+    pub fn findings(&self) -> &[Finding] {
+        &self.findings
+    }
+
+    /// Whether nothing was found.
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    fn add(&mut self, path: &[(usize, Option<String>)], kind: FindingKind) {
+        self.findings.push(Finding {
+            kind,
+            path: format_path(path),
+        });
+    }
+
+    fn element(&mut self, element: &Element, path: &mut Vec<(usize, Option<String>)>) {
+        if element.name.eq_ignore_ascii_case("script") {
+            match element.attr_ci("src") {
+                Some(src) => self.add(
+                    path,
+                    FindingKind::ExternalScript {
+                        src: src.to_string(),
+                    },
+                ),
+                None => self.add(path, FindingKind::InlineScript),
+            }
+        } else if element.name.eq_ignore_ascii_case("style") {
+            self.add(path, FindingKind::InlineStyle);
+        }
+
+        for (attr, value) in &element.attributes {
+            if is_event_handler(attr) {
+                let attribute = attr.to_string();
+                self.add(path, FindingKind::EventHandler { attribute });
+            } else if has_javascript_scheme(value)
+                && URL_ATTRIBUTES.iter().any(|a| a.eq_ignore_ascii_case(attr))
+            {
+                let attribute = attr.to_string();
+                self.add(path, FindingKind::JavascriptUrl { attribute });
+            }
+        }
+
+        for (i, child) in element.children.iter().enumerate() {
+            self.content(child, i, path);
+        }
+    }
+
+    #[allow(deprecated)]
+    fn content(&mut self, content: &Content, i: usize, path: &mut Vec<(usize, Option<String>)>) {
+        match content {
+            Content::Raw(_) => {
+                path.push((i, None));
+                self.add(path, FindingKind::Raw);
+                path.pop();
+            }
+            Content::Trusted(_) => {
+                path.push((i, None));
+                self.add(path, FindingKind::Trusted);
+                path.pop();
+            }
+            Content::Element(element) => {
+                path.push((i, Some(element.name.clone())));
+                self.element(element, path);
+                path.pop();
+            }
+            // Trailers are rendered elsewhere, but still reported here
+            Content::Trailer(content) => self.content(content, i, path),
+            Content::Text(_) | Content::Comment(_) | Content::Flush => {}
+        }
+    }
+}
+
+impl fmt::Display for SecurityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for finding in &self.findings {
+            writeln!(f, "{finding}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Element {
+    /// List everything in the element and its descendants that executes code
+    /// or bypasses escaping, see [`crate::audit`].
+    pub fn security_report(&self) -> SecurityReport {
+        let mut report = SecurityReport::default();
+        report.element(self, &mut vec![]);
+        report
+    }
+}
+
+impl Document {
+    /// List everything in the document that executes code or bypasses
+    /// escaping, see [`crate::audit`].
+    pub fn security_report(&self) -> SecurityReport {
+        self.0.security_report()
+    }
+}
@@ -1,26 +1,394 @@
-use axum_core::response::IntoResponse;
-use http::{header, HeaderValue, StatusCode};
+//! Types for responding from axum handlers.
+//!
+//! This module requires the `axum` feature flag. Besides the types defined
+//! here, [`Document`], [`Fragment`], [`ErrorPage`], [`OobResponse`] and
+//! [`EventStream`] implement axum's `IntoResponse` trait.
+//!
+//! [`ErrorPage`]: crate::pages::ErrorPage
+//! [`OobResponse`]: crate::htmx::OobResponse
+//! [`EventStream`]: crate::sse::EventStream
 
-use crate::{Document, Render};
+use std::{fmt, time::Duration};
+
+use axum_core::response::{IntoResponse, Response};
+use http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode};
+
+use crate::{
+    csp::{self, Csp, Source},
+    html::attr::Referrerpolicy,
+    htmx::OobResponse,
+    pages::ErrorPage,
+    sse::EventStream,
+    Document, Fragment, Render, RenderOptions,
+};
 
 // https://github.com/hyperium/mime/blob/ce5062d216bf757a0ed3fc70f0fe255d1c8d74ae/src/lib.rs#L753
 const TEXT_HTML_UTF_8: &str = "text/html; charset=utf-8";
+const TEXT_EVENT_STREAM: &str = "text/event-stream";
+
+fn html_response(html: crate::Result<String>) -> Response {
+    match html {
+        // Keeping dependency churn low by manually reimplementing
+        // https://github.com/tokio-rs/axum/blob/b5a01092216d0fa5ab950cbd7030ebcc925ceb33/axum/src/response/mod.rs#L40-L54
+        Ok(html) => (
+            [(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(TEXT_HTML_UTF_8),
+            )],
+            html,
+        )
+            .into_response(),
+
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+    }
+}
 
 impl IntoResponse for Document {
-    fn into_response(self) -> axum_core::response::Response {
-        match self.render_to_string() {
-            // Keeping dependency churn low by manually reimplementing
-            // https://github.com/tokio-rs/axum/blob/b5a01092216d0fa5ab950cbd7030ebcc925ceb33/axum/src/response/mod.rs#L40-L54
-            Ok(html) => (
-                [(
+    fn into_response(self) -> Response {
+        html_response(self.render_to_string())
+    }
+}
+
+impl IntoResponse for Fragment {
+    fn into_response(self) -> Response {
+        html_response(self.render_to_string())
+    }
+}
+
+impl IntoResponse for OobResponse {
+    fn into_response(self) -> Response {
+        html_response(self.render_to_string())
+    }
+}
+
+impl IntoResponse for ErrorPage {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (status, html_response(self.render_to_string())).into_response()
+    }
+}
+
+impl IntoResponse for EventStream {
+    fn into_response(self) -> Response {
+        (
+            [
+                (
                     header::CONTENT_TYPE,
-                    HeaderValue::from_static(TEXT_HTML_UTF_8),
-                )],
-                html,
-            )
-                .into_response(),
+                    HeaderValue::from_static(TEXT_EVENT_STREAM),
+                ),
+                (header::CACHE_CONTROL, HeaderValue::from_static("no-cache")),
+            ],
+            self.to_string(),
+        )
+            .into_response()
+    }
+}
 
-            Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+/// How clients and proxies may cache a [`Page`], sent as its
+/// `Cache-Control` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// The page must not be stored at all, e.g. because it contains
+    /// sensitive data.
+    NoStore,
+    /// The page may be stored, but must be revalidated before every use.
+    NoCache,
+    /// The page may be stored by the client, but not by shared caches like
+    /// proxies, and is fresh for the given duration.
+    Private(Duration),
+    /// The page may be stored by any cache and is fresh for the given
+    /// duration.
+    Public(Duration),
+}
+
+impl fmt::Display for CachePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoStore => write!(f, "no-store"),
+            Self::NoCache => write!(f, "no-cache"),
+            Self::Private(max_age) => write!(f, "private, max-age={}", max_age.as_secs()),
+            Self::Public(max_age) => write!(f, "public, max-age={}", max_age.as_secs()),
+        }
+    }
+}
+
+/// Security headers for a [`Page`].
+///
+/// Configuring these headers separately from the markup makes it easy for
+/// them to drift apart, e.g. when a script with a new nonce is added, but the
+/// policy isn't updated. Instead, the `Content-Security-Policy` is built from
+/// a base policy and the nonces used in the document, see
+/// [`Csp::nonces_from`].
+///
+/// [`Self::new`] uses recommended defaults:
+///
+/// - A policy with `object-src 'none'` and `base-uri 'self'`, plus the
+///   document's nonces
+/// - `X-Content-Type-Options: nosniff`
+/// - `Referrer-Policy: strict-origin-when-cross-origin`
+///
+/// Headers added using [`Page::header`] replace these.
+///
+/// # Example
+///
+/// ```
+/// use el::{Document, axum::{Page, SecurityHeaders}, csp::{Csp, Source}, html::*};
+///
+/// async fn handler() -> Page {
+///     let page = Document::minimal("Hi", script((attr::nonce("r4nd0m"), attr::src("/app.js"))));
+///     let csp = Csp::new().default_src(Source::Self_).frame_ancestors(Source::None);
+///     Page::new(page).security(SecurityHeaders::new().csp(csp))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityHeaders {
+    /// The base policy for the `Content-Security-Policy` header. If it is
+    /// [`None`], no policy is sent, even if the document uses nonces.
+    pub csp: Option<Csp>,
+    /// Send the policy as `Content-Security-Policy-Report-Only` instead, so
+    /// violations are only reported, not blocked.
+    pub report_only: bool,
+    /// Send `X-Content-Type-Options: nosniff`.
+    pub nosniff: bool,
+    /// The value of the `Referrer-Policy` header. If it is [`None`], no
+    /// header is sent.
+    pub referrer_policy: Option<Referrerpolicy>,
+}
+
+impl SecurityHeaders {
+    /// The recommended security headers, see above.
+    pub fn new() -> Self {
+        Self {
+            csp: Some(Csp::new().object_src(Source::None).base_uri(Source::Self_)),
+            report_only: false,
+            nosniff: true,
+            referrer_policy: Some(Referrerpolicy::StrictOriginWhenCrossOrigin),
+        }
+    }
+
+    /// Set the base policy.
+    pub fn csp(mut self, csp: impl Into<Option<Csp>>) -> Self {
+        self.csp = csp.into();
+        self
+    }
+
+    /// Set whether the policy is only reported.
+    pub fn report_only(mut self, report_only: bool) -> Self {
+        self.report_only = report_only;
+        self
+    }
+
+    /// Set whether `X-Content-Type-Options: nosniff` is sent.
+    pub fn nosniff(mut self, nosniff: bool) -> Self {
+        self.nosniff = nosniff;
+        self
+    }
+
+    /// Set the referrer policy.
+    pub fn referrer_policy(mut self, referrer_policy: impl Into<Option<Referrerpolicy>>) -> Self {
+        self.referrer_policy = referrer_policy.into();
+        self
+    }
+
+    /// The headers for a document.
+    ///
+    /// Values that aren't valid in a header are skipped.
+    pub fn headers(&self, document: &Document) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(csp) = &self.csp {
+            let csp = csp.clone().nonces_from(&document.0);
+            let name = if self.report_only {
+                csp::REPORT_ONLY_HEADER_NAME
+            } else {
+                csp::HEADER_NAME
+            };
+            let name = HeaderName::from_bytes(name.as_bytes());
+            let value = HeaderValue::try_from(csp.to_string());
+            if let (false, Ok(name), Ok(value)) = (csp.is_empty(), name, value) {
+                headers.insert(name, value);
+            }
+        }
+        if self.nosniff {
+            headers.insert(
+                header::X_CONTENT_TYPE_OPTIONS,
+                HeaderValue::from_static("nosniff"),
+            );
+        }
+        if let Some(policy) = &self.referrer_policy {
+            if let Ok(value) = HeaderValue::try_from(policy.to_string()) {
+                headers.insert(header::REFERRER_POLICY, value);
+            }
         }
+        headers
+    }
+}
+
+impl Default for SecurityHeaders {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A [`Document`] together with the rest of its HTTP response.
+///
+/// Handlers can return a `Page` instead of a tuple of status code, headers
+/// and document. The response always has an HTML content type. Headers added
+/// using [`Self::header`] are sent after the content type, cache policy and
+/// [security headers](SecurityHeaders), replacing them if they have the same
+/// name.
+///
+/// If the document fails to render, the response is an internal server error
+/// like for a plain [`Document`], and the status, headers, cache policy and
+/// security headers of the page are not used.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use el::{Document, axum::{CachePolicy, Page}, html::*};
+/// use http::{StatusCode, header, HeaderValue};
+///
+/// async fn handler() -> Page {
+///     Page::new(Document::minimal("Created", p("Your post was created.")))
+///         .status(StatusCode::CREATED)
+///         .header(header::LOCATION, HeaderValue::from_static("/posts/1"))
+///         .cache(CachePolicy::Private(Duration::from_secs(60)))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Page {
+    /// The page itself.
+    pub document: Document,
+    /// The status code of the response, `200 OK` by default.
+    pub status: StatusCode,
+    /// Additional headers of the response.
+    pub headers: HeaderMap,
+    /// The cache policy of the response. If it is [`None`], no
+    /// `Cache-Control` header is sent.
+    pub cache: Option<CachePolicy>,
+    /// The security headers of the response. If it is [`None`], none are
+    /// sent.
+    pub security: Option<SecurityHeaders>,
+}
+
+impl Page {
+    /// Create a successful response containing a document.
+    pub fn new(document: impl Into<Document>) -> Self {
+        Self {
+            document: document.into(),
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            cache: None,
+            security: None,
+        }
+    }
+
+    /// Set the status code of the response.
+    pub fn status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Add a header to the response.
+    ///
+    /// Adding a header multiple times sends it multiple times, e.g. for
+    /// several `Set-Cookie` headers.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.append(name, value);
+        self
+    }
+
+    /// Set the cache policy of the response.
+    pub fn cache(mut self, cache: CachePolicy) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Send security headers matching the document, see [`SecurityHeaders`].
+    pub fn security(mut self, security: SecurityHeaders) -> Self {
+        self.security = Some(security);
+        self
+    }
+}
+
+impl From<Document> for Page {
+    fn from(document: Document) -> Self {
+        Self::new(document)
+    }
+}
+
+impl Render for Page {
+    fn render<W: fmt::Write>(&self, w: &mut W) -> crate::Result<()> {
+        self.document.render(w)
+    }
+
+    fn render_with<W: fmt::Write>(&self, w: &mut W, options: &RenderOptions) -> crate::Result<()> {
+        self.document.render_with(w, options)
+    }
+}
+
+impl IntoResponse for Page {
+    fn into_response(self) -> Response {
+        let html = match self.document.render_to_string() {
+            Ok(html) => html,
+            Err(err) => return html_response(Err(err)),
+        };
+
+        let mut response = html_response(Ok(html));
+        *response.status_mut() = self.status;
+        if let Some(cache) = self.cache {
+            if let Ok(value) = HeaderValue::try_from(cache.to_string()) {
+                response.headers_mut().insert(header::CACHE_CONTROL, value);
+            }
+        }
+        if let Some(security) = &self.security {
+            response
+                .headers_mut()
+                .extend(security.headers(&self.document));
+        }
+        response.headers_mut().extend(self.headers);
+        response
+    }
+}
+
+/// A response containing partial HTML, e.g. for htmx requests.
+///
+/// Unlike [`Document`], partial HTML has no doctype and is usually swapped
+/// into an existing page. Since single elements are rarely meant to be sent
+/// on their own, [`Element`](crate::Element) doesn't implement
+/// `IntoResponse`. Wrap it (or anything else implementing [`Render`]) in a
+/// `Partial` to send it deliberately. A [`Fragment`] can also be returned
+/// directly.
+///
+/// # Example
+///
+/// ```
+/// use el::{axum::Partial, html::*, Element, Fragment};
+///
+/// async fn counter() -> Partial<Element> {
+///     Partial(span((attr::id("count"), "42")))
+/// }
+///
+/// async fn rows() -> Fragment {
+///     (1..=3).map(|i| tr(td(i.to_string()))).collect()
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Partial<R>(pub R);
+
+impl<R: Render> Render for Partial<R> {
+    fn render<W: fmt::Write>(&self, w: &mut W) -> crate::Result<()> {
+        self.0.render(w)
+    }
+
+    fn render_with<W: fmt::Write>(&self, w: &mut W, options: &RenderOptions) -> crate::Result<()> {
+        self.0.render_with(w, options)
+    }
+}
+
+impl<R: Render> IntoResponse for Partial<R> {
+    fn into_response(self) -> Response {
+        html_response(self.0.render_to_string())
     }
 }
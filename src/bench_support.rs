@@ -0,0 +1,154 @@
+//! Generators for representative element trees, used for benchmarking.
+//!
+//! The crate's own benchmarks (in the `benches` directory) measure
+//! construction and rendering of these trees. They are public so that users
+//! can compare configurations (e.g. different [`RenderOptions`]) or the cost
+//! of their own components against a common baseline.
+//!
+//! All generators are deterministic: the same arguments always produce the
+//! same tree.
+//!
+//! [`RenderOptions`]: crate::RenderOptions
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Render, bench_support};
+//!
+//! let table = bench_support::wide_table(2, 3);
+//! assert_eq!(
+//!     table.render_to_string().unwrap(),
+//!     concat!(
+//!         "<table><thead><tr><th>Column 0</th><th>Column 1</th><th>Column 2</th></tr></thead>",
+//!         "<tbody><tr><td>0 &amp; 0</td><td>0 &amp; 1</td><td>0 &amp; 2</td></tr>",
+//!         "<tr><td>1 &amp; 0</td><td>1 &amp; 1</td><td>1 &amp; 2</td></tr></tbody></table>",
+//!     ),
+//! );
+//! ```
+
+use crate::{
+    html::{self, attr},
+    Attr, Document, Element,
+};
+
+/// A chain of `depth` nested `<div>` elements, each containing a short text
+/// before its child.
+///
+/// Stresses recursion and per-element overhead.
+pub fn deep_tree(depth: usize) -> Element {
+    let mut element = html::span("Leaf");
+    for i in (0..depth).rev() {
+        element = html::div((
+            attr::class(format!("level-{i}")),
+            format!("Level {i}"),
+            element,
+        ));
+    }
+    element
+}
+
+/// A `<table>` with a header row and `rows` rows of `columns` cells each.
+///
+/// Stresses wide element lists and text escaping, since every cell contains
+/// an `&`.
+pub fn wide_table(rows: usize, columns: usize) -> Element {
+    let header = (0..columns)
+        .map(|c| html::th(format!("Column {c}")))
+        .collect::<Vec<_>>();
+    let body = (0..rows)
+        .map(|r| {
+            html::tr(
+                (0..columns)
+                    .map(|c| html::td(format!("{r} & {c}")))
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect::<Vec<_>>();
+    html::table((html::thead(html::tr(header)), html::tbody(body)))
+}
+
+/// A `<div>` containing `elements` `<span>` elements with `attributes` data
+/// attributes each, in addition to an `id` and a few classes.
+///
+/// Stresses attribute construction, sorting and escaping, since every value
+/// contains a `"`.
+pub fn attribute_heavy(elements: usize, attributes: usize) -> Element {
+    let children = (0..elements)
+        .map(|e| {
+            let data = (0..attributes)
+                .map(|a| attr::data_x(format!("attribute-{a}"), format!("\"{e}-{a}\"")))
+                .collect::<Vec<_>>();
+            html::span((
+                attr::id(format!("element-{e}")),
+                attr::class(if e % 2 == 0 { "item even" } else { "item odd" }),
+                Attr::yes("hidden"),
+                data,
+            ))
+        })
+        .collect::<Vec<_>>();
+    html::div(children)
+}
+
+/// A `<div>` containing `paragraphs` paragraphs of text with inline markup.
+///
+/// Stresses text escaping, since the text contains many characters that need
+/// to be escaped.
+pub fn text_heavy(paragraphs: usize) -> Element {
+    let children = (0..paragraphs)
+        .map(|i| {
+            html::p((
+                format!("Paragraph {i}"),
+                " compares <a> & <b> using \"quotes\" and 'apostrophes'. ",
+                html::em("Emphasis"),
+                " and ",
+                html::a((attr::href(format!("/page?a={i}&b=2")), "a link")),
+                " follow. ",
+                "Lorem ipsum dolor sit amet, consectetur adipiscing elit. ".repeat(4),
+            ))
+        })
+        .collect::<Vec<_>>();
+    html::div(children)
+}
+
+/// A `<ul>` containing `items` short list items, added using
+/// [`Element::extend_children`].
+///
+/// Stresses construction of very long lists of children.
+pub fn long_list(items: usize) -> Element {
+    let mut list = html::ul(());
+    list.reserve_children(items);
+    list.extend_children((0..items).map(|i| html::li(format!("Item {i}"))));
+    list
+}
+
+/// A complete web page combining all other generators, scaled by `size`.
+///
+/// A `size` of 10 results in roughly 30 KB of HTML.
+pub fn page(size: usize) -> Document {
+    html::html((
+        html::head((
+            html::meta(attr::charset("utf-8")),
+            html::meta((
+                attr::name("viewport"),
+                attr::content("width=device-width, initial-scale=1"),
+            )),
+            html::title("Benchmark page"),
+            html::link((attr::rel("stylesheet"), attr::href("/style.css"))),
+        )),
+        html::body((
+            html::header(html::nav(html::ul(
+                (0..size)
+                    .map(|i| html::li(html::a((attr::href(format!("/{i}")), format!("Page {i}")))))
+                    .collect::<Vec<_>>(),
+            ))),
+            html::main((
+                text_heavy(size * 3),
+                wide_table(size * 5, 5),
+                attribute_heavy(size * 5, 5),
+                deep_tree(size),
+            )),
+            html::footer(html::p("Footer")),
+        )),
+    ))
+    .into_document()
+}
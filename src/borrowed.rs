@@ -0,0 +1,178 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use crate::{Content, Element, ElementKind};
+
+/// A single bit of [`ElementRef`] content, see [`Content`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentRef<'a> {
+    /// A raw string to be rendered without any checks, see [`Content::Raw`].
+    ///
+    /// # Warning
+    ///
+    /// This is an escape hatch for including arbitrary text. Using it
+    /// incorrectly may result in security vulnerabilities in the rendered HTML.
+    #[cfg_attr(
+        feature = "deprecate-raw",
+        deprecated = "raw content is disallowed by the `deprecate-raw` feature of el"
+    )]
+    Raw(&'a str),
+    /// Plain text, see [`Content::Text`].
+    Text(&'a str),
+    /// An HTML comment, see [`Content::Comment`].
+    Comment(&'a str),
+    /// A child [`ElementRef`].
+    Element(ElementRef<'a>),
+    /// A borrowed [`Element`], e.g. a shared fragment built once and included
+    /// in many borrowed trees.
+    Owned(&'a Element),
+}
+
+impl<'a> From<&'a str> for ContentRef<'a> {
+    fn from(value: &'a str) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl<'a> From<&'a String> for ContentRef<'a> {
+    fn from(value: &'a String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl<'a> From<ElementRef<'a>> for ContentRef<'a> {
+    fn from(value: ElementRef<'a>) -> Self {
+        Self::Element(value)
+    }
+}
+
+impl<'a> From<&'a Element> for ContentRef<'a> {
+    fn from(value: &'a Element) -> Self {
+        Self::Owned(value)
+    }
+}
+
+#[allow(deprecated)]
+impl From<ContentRef<'_>> for Content {
+    fn from(value: ContentRef<'_>) -> Self {
+        match value {
+            ContentRef::Raw(str) => Self::raw(str),
+            ContentRef::Text(str) => Self::text(str),
+            ContentRef::Comment(str) => Self::comment(str),
+            ContentRef::Element(element) => Self::Element(element.to_element()),
+            ContentRef::Owned(element) => Self::Element(element.clone()),
+        }
+    }
+}
+
+/// An element that borrows its name, attributes and text instead of owning
+/// them.
+///
+/// Rendering an [`Element`] requires copying all text into the tree first.
+/// For large, text-heavy pages built from request-scoped data (e.g. rows
+/// loaded from a database), an `ElementRef` can reference the data directly.
+/// It is rendered with the same checks and escaping as an [`Element`] via
+/// [`crate::Render`], and owned content can be mixed in using
+/// [`ContentRef::Owned`].
+///
+/// Unlike [`Element::new`], the tag name is not converted to lowercase.
+/// Labels, [`Content::Trailer`]s and lenient rendering are only supported for
+/// owned content.
+///
+/// # Example
+///
+/// ```
+/// use el::{ElementRef, Render, html::*};
+///
+/// let rows = vec![("Alice".to_string(), "a & b".to_string())];
+/// let header = tr((th("Name"), th("Notes")));
+///
+/// let mut table = ElementRef::normal("table").child(&header);
+/// for (name, notes) in &rows {
+///     table.add_child(
+///         ElementRef::normal("tr")
+///             .child(ElementRef::normal("td").attr("class", "name").child(name))
+///             .child(ElementRef::normal("td").child(notes)),
+///     );
+/// }
+///
+/// assert_eq!(
+///     table.render_to_string().unwrap(),
+///     concat!(
+///         "<table><tr><th>Name</th><th>Notes</th></tr>",
+///         r#"<tr><td class="name">Alice</td><td>a &amp; b</td></tr></table>"#,
+///     ),
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElementRef<'a> {
+    /// The tag name of the element, see [`Element::name`].
+    pub name: &'a str,
+    /// What kind of element this is, see [`Element::kind`].
+    ///
+    /// # Warning
+    ///
+    /// The element kind affects the correctness of the rendered output.
+    /// Choosing an incorrect kind may result in security vulnerabilities in the
+    /// rendered HTML. See [`ElementKind`] for more details.
+    pub kind: ElementKind,
+    /// The attributes of the element, see [`Element::attributes`].
+    pub attributes: BTreeMap<&'a str, &'a str>,
+    /// The children of the element, see [`Element::children`].
+    pub children: Vec<ContentRef<'a>>,
+}
+
+impl<'a> ElementRef<'a> {
+    /// Create a new element without attributes or children, see
+    /// [`Element::new`].
+    ///
+    /// # Warning
+    ///
+    /// The element kind affects the correctness of the rendered output.
+    /// Choosing an incorrect kind may result in security vulnerabilities in the
+    /// rendered HTML. See [`ElementKind`] for more details.
+    pub fn new(name: &'a str, kind: ElementKind) -> Self {
+        Self {
+            name,
+            kind,
+            attributes: BTreeMap::new(),
+            children: vec![],
+        }
+    }
+
+    /// Create a new element of the kind [`ElementKind::Normal`], see
+    /// [`Element::normal`].
+    pub fn normal(name: &'a str) -> Self {
+        Self::new(name, ElementKind::Normal)
+    }
+
+    /// Set an attribute, replacing any previous value.
+    ///
+    /// Boolean attributes have an empty value.
+    pub fn attr(mut self, name: &'a str, value: &'a str) -> Self {
+        self.attributes.insert(name, value);
+        self
+    }
+
+    /// Add a child in-place.
+    pub fn add_child(&mut self, child: impl Into<ContentRef<'a>>) {
+        self.children.push(child.into());
+    }
+
+    /// A more builder-pattern-like version of [`Self::add_child`].
+    pub fn child(mut self, child: impl Into<ContentRef<'a>>) -> Self {
+        self.add_child(child);
+        self
+    }
+
+    /// Copy the element and its descendants into an [`Element`].
+    #[cfg_attr(feature = "track-caller", track_caller)]
+    pub fn to_element(&self) -> Element {
+        let mut element = Element::new(self.name, self.kind);
+        element.name = self.name.to_string();
+        for (name, value) in &self.attributes {
+            (element.attributes).insert(Cow::Owned(name.to_string()), value.to_string());
+        }
+        element.children = self.children.iter().cloned().map(Content::from).collect();
+        element
+    }
+}
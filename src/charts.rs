@@ -0,0 +1,272 @@
+//! Small inline SVG charts.
+//!
+//! A [`Chart`] holds the size, colors and accessible text of a chart. It can
+//! then be drawn as a [sparkline](Chart::sparkline), a [bar chart](Chart::bar)
+//! or a [donut chart](Chart::donut). The charts are plain [`svg`] elements
+//! without any scripts or external dependencies, so they work well in
+//! server-rendered dashboards and emails.
+//!
+//! Every chart has a title, which is rendered as the `<title>` of the `<svg>`
+//! and used by assistive technology as the name of the image. Longer
+//! descriptions can be added using [`Chart::description`].
+//!
+//! Values that are not finite (NaN or infinite) are ignored.
+//!
+//! This module requires the `charts` feature flag.
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Render, charts::Chart, html::*};
+//!
+//! let chart = Chart::new("Visitors").size(60.0, 20.0).color("#333");
+//! let html = p(("Visitors this week: ", chart.sparkline(&[3.0, 5.0, 4.0])));
+//! assert_eq!(
+//!     html.render_to_string().unwrap(),
+//!     concat!(
+//!         "<p>Visitors this week: ",
+//!         r#"<svg height="20" role="img" viewBox="0 0 60 20" width="60"><title>Visitors</title>"#,
+//!         r##"<polyline fill="none" points="1,19 30,1 59,10" stroke="#333" "##,
+//!         r#"stroke-linecap="round" stroke-linejoin="round" stroke-width="2" /></svg></p>"#,
+//!     ),
+//! );
+//! ```
+
+use std::f64::consts::PI;
+
+use crate::{
+    length::Number,
+    svg::{attr::ViewBox, circle, desc, polyline, rect, svg, title},
+    Attr, Element,
+};
+
+/// The default colors of a chart, in order.
+pub const PALETTE: &[&str] = &[
+    "#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948", "#b07aa1", "#ff9da7",
+    "#9c755f", "#bab0ac",
+];
+
+/// The options for drawing a chart, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chart {
+    width: f64,
+    height: f64,
+    title: String,
+    description: Option<String>,
+    labels: Vec<String>,
+    colors: Vec<String>,
+}
+
+impl Chart {
+    /// Create a new chart with the given accessible title.
+    ///
+    /// The chart is 120 by 40 units large and uses the colors from
+    /// [`PALETTE`].
+    pub fn new(title: impl ToString) -> Self {
+        Self {
+            width: 120.0,
+            height: 40.0,
+            title: title.to_string(),
+            description: None,
+            labels: vec![],
+            colors: PALETTE.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    /// Set the width and height of the chart.
+    ///
+    /// The values are used both for the `width` and `height` attributes and
+    /// for the `viewBox`, so the chart can be scaled using CSS.
+    pub fn size(mut self, width: f64, height: f64) -> Self {
+        self.width = width.max(0.0);
+        self.height = height.max(0.0);
+        self
+    }
+
+    /// Set a longer description of the chart, rendered as its `<desc>`.
+    pub fn description(mut self, description: impl ToString) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Set labels for the values of a bar or donut chart.
+    ///
+    /// Each bar or segment gets a `<title>` containing its label and value,
+    /// which is shown as a tooltip by most browsers.
+    pub fn labels(mut self, labels: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.labels = labels.into_iter().map(|l| l.to_string()).collect();
+        self
+    }
+
+    /// Use a single color for the whole chart.
+    pub fn color(self, color: impl ToString) -> Self {
+        self.colors([color])
+    }
+
+    /// Set the colors used by the chart.
+    ///
+    /// Sparklines and bar charts only use the first color. Donut charts cycle
+    /// through all colors. If the list is empty, `currentColor` is used.
+    pub fn colors(mut self, colors: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.colors = colors.into_iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    fn color_at(&self, i: usize) -> &str {
+        match self.colors.len() {
+            0 => "currentColor",
+            n => &self.colors[i % n],
+        }
+    }
+
+    fn item_title(&self, i: usize, value: f64) -> Element {
+        match self.labels.get(i) {
+            Some(label) => title(format!("{label}: {}", number(value))),
+            None => title(number(value)),
+        }
+    }
+
+    fn frame(&self, content: Vec<Element>) -> Element {
+        svg((
+            Attr::set("width", number(self.width)),
+            Attr::set("height", number(self.height)),
+            ViewBox::size(self.width, self.height),
+            Attr::set("role", "img"),
+            title(&self.title[..]),
+            self.description.as_deref().map(desc),
+            content,
+        ))
+    }
+
+    /// Draw the values as a line without axes, e.g. for use inside text.
+    pub fn sparkline(&self, values: &[f64]) -> Element {
+        let values = values
+            .iter()
+            .copied()
+            .filter(|v| v.is_finite())
+            .collect::<Vec<_>>();
+        if values.is_empty() {
+            return self.frame(vec![]);
+        }
+
+        // Keep the line inside the chart despite its width
+        let pad = 1.0;
+        let (lo, hi) = bounds(values.iter().copied());
+        let dx = (self.width - 2.0 * pad) / (values.len().max(2) - 1) as f64;
+        let dy = (self.height - 2.0 * pad) / (hi - lo);
+        let points = (values.iter().enumerate())
+            .map(|(i, v)| {
+                let x = pad + i as f64 * dx;
+                let y = if hi > lo {
+                    self.height - pad - (v - lo) * dy
+                } else {
+                    self.height / 2.0
+                };
+                format!("{},{}", number(x), number(y))
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        self.frame(vec![polyline((
+            Attr::set("points", points),
+            Attr::set("fill", "none"),
+            Attr::set("stroke", self.color_at(0)),
+            Attr::set("stroke-width", "2"),
+            Attr::set("stroke-linecap", "round"),
+            Attr::set("stroke-linejoin", "round"),
+        ))])
+    }
+
+    /// Draw the values as vertical bars.
+    ///
+    /// Bars start at zero, so negative values point downwards.
+    pub fn bar(&self, values: &[f64]) -> Element {
+        let items = (values.iter().copied().enumerate())
+            .filter(|(_, v)| v.is_finite())
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            return self.frame(vec![]);
+        }
+
+        let (lo, hi) = bounds(items.iter().map(|(_, v)| *v).chain([0.0]));
+        let hi = if hi > lo { hi } else { lo + 1.0 };
+        let slot = self.width / items.len() as f64;
+        let gap = slot * 0.1;
+        let scale = self.height / (hi - lo);
+        let zero = self.height - (0.0 - lo) * scale;
+        let bars = (items.iter().enumerate())
+            .map(|(slot_i, &(i, v))| {
+                let y = self.height - (v - lo) * scale;
+                rect((
+                    Attr::set("x", number(slot_i as f64 * slot + gap / 2.0)),
+                    Attr::set("y", number(y.min(zero))),
+                    Attr::set("width", number(slot - gap)),
+                    Attr::set("height", number((y - zero).abs())),
+                    Attr::set("fill", self.color_at(0)),
+                    self.item_title(i, v),
+                ))
+            })
+            .collect();
+
+        self.frame(bars)
+    }
+
+    /// Draw the values as segments of a ring, proportional to their share of
+    /// the total.
+    ///
+    /// The ring is centered in the chart and as large as fits. Negative values
+    /// are ignored.
+    pub fn donut(&self, values: &[f64]) -> Element {
+        let items = (values.iter().copied().enumerate())
+            .filter(|(_, v)| v.is_finite() && *v > 0.0)
+            .collect::<Vec<_>>();
+        let total = items.iter().map(|(_, v)| v).sum::<f64>();
+        if total <= 0.0 {
+            return self.frame(vec![]);
+        }
+
+        let size = self.width.min(self.height);
+        let thickness = size * 0.2;
+        let r = (size - thickness) / 2.0;
+        let (cx, cy) = (number(self.width / 2.0), number(self.height / 2.0));
+        let circumference = 2.0 * PI * r;
+        let mut offset = 0.0;
+        let segments = (items.iter())
+            .map(|&(i, v)| {
+                let length = v / total * circumference;
+                let segment = circle((
+                    Attr::set("cx", &cx[..]),
+                    Attr::set("cy", &cy[..]),
+                    Attr::set("r", number(r)),
+                    Attr::set("fill", "none"),
+                    Attr::set("stroke", self.color_at(i)),
+                    Attr::set("stroke-width", number(thickness)),
+                    Attr::set(
+                        "stroke-dasharray",
+                        format!("{} {}", number(length), number(circumference - length)),
+                    ),
+                    Attr::set("stroke-dashoffset", number(-offset)),
+                    // Start at the top and go clockwise
+                    Attr::set("transform", format!("rotate(-90 {cx} {cy})")),
+                    self.item_title(i, v),
+                ));
+                offset += length;
+                segment
+            })
+            .collect();
+
+        self.frame(segments)
+    }
+}
+
+/// The smallest and largest value.
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+        (lo.min(v), hi.max(v))
+    })
+}
+
+/// Format a number with at most two decimal places.
+fn number(n: f64) -> String {
+    Number(n, 2).to_string()
+}
@@ -37,42 +37,80 @@ pub fn is_valid_attribute_name(name: &str) -> bool {
             .all(|c| is_ascii_alphanumeric(c) || c == '-' || c == '_')
 }
 
+/// <https://html.spec.whatwg.org/multipage/parsing.html#html-integration-point>
+/// and <https://html.spec.whatwg.org/multipage/parsing.html#mathml-text-integration-point>
+///
+/// Children of these foreign elements are parsed as HTML again. The names are
+/// unique across the SVG and MathML namespaces, so the namespace of the element
+/// doesn't need to be known. Browsers adjust the case of SVG tag names, so the
+/// name is compared case-insensitively.
+pub fn is_integration_point(name: &str, encoding: Option<&str>) -> bool {
+    const NAMES: &[&str] = &[
+        "foreignObject",
+        "desc",
+        "title",
+        "mi",
+        "mo",
+        "mn",
+        "ms",
+        "mtext",
+    ];
+    if NAMES.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+        return true;
+    }
+
+    // "A MathML annotation-xml element whose start tag token had an attribute
+    // with the name "encoding" whose value was an ASCII case-insensitive match
+    // for the string "text/html" [or] "application/xhtml+xml""
+    name.eq_ignore_ascii_case("annotation-xml")
+        && encoding.is_some_and(|encoding| {
+            encoding.eq_ignore_ascii_case("text/html")
+                || encoding.eq_ignore_ascii_case("application/xhtml+xml")
+        })
+}
+
 /// https://html.spec.whatwg.org/multipage/syntax.html#cdata-rcdata-restrictions
 ///
-/// The tag name must be ascii-only.
+/// Text is never considered valid for non-ascii tag names.
 pub fn is_valid_raw_text(tag_name: &str, text: &str) -> bool {
-    // In case we ever decide to relax tag name ascii requirements.
-    assert!(tag_name.is_ascii());
+    // In case we ever decide to relax tag name ascii requirements. Rendering
+    // must not panic, so we're conservative instead.
+    if !tag_name.is_ascii() {
+        return false;
+    }
 
     // "The text in raw text and escapable raw text elements must not contain
     // any occurrences of the string "</" (U+003C LESS-THAN SIGN, U+002F
     // SOLIDUS) [...]"
+    let tag_name = tag_name.as_bytes();
     for (i, _) in text.match_indices("</") {
-        let start = i + "</".len();
-
-        let potential_tag_name = text[start..]
-            .chars()
-            .take(tag_name.chars().count())
-            .collect::<String>();
+        let rest = &text.as_bytes()[i + "</".len()..];
 
         // "[...] followed by characters that case-insensitively match the tag
         // name of the element [...]"
         //
         // Note: Since we know that tag names are ascii-only, we can use an
         // ASCII-based case insensitive comparison without unicode shenanigans.
+        // Bytes of non-ascii characters never match.
+        let Some(potential_tag_name) = rest.get(..tag_name.len()) else {
+            continue;
+        };
         if !potential_tag_name.eq_ignore_ascii_case(tag_name) {
             continue;
         }
 
         // "[...] followed by [...]"
-        let Some(trailing) = text[start + potential_tag_name.len()..].chars().next() else {
+        let Some(trailing) = rest.get(tag_name.len()) else {
             continue;
         };
 
         // "[...] one of U+0009 CHARACTER TABULATION (tab), U+000A LINE FEED
         // (LF), U+000C FORM FEED (FF), U+000D CARRIAGE RETURN (CR), U+0020
         // SPACE, U+003E GREATER-THAN SIGN (>), or U+002F SOLIDUS (/)."
-        if matches!(trailing, '\t' | '\n' | '\x0C' | '\r' | ' ' | '>' | '/') {
+        if matches!(
+            trailing,
+            b'\t' | b'\n' | b'\x0C' | b'\r' | b' ' | b'>' | b'/'
+        ) {
             return false;
         }
     }
@@ -0,0 +1,135 @@
+//! Prerendering huge lists of children in chunks.
+//!
+//! Every [`Element`] owns its name, attributes and children, so a document
+//! with hundreds of thousands of elements is expensive to build, to move
+//! around and to drop, even though it is only rendered once. A
+//! [`ChunkedChildren`] avoids this by rendering each child as soon as it is
+//! added. The output is collected in a few large chunks of [`TrustedHtml`],
+//! which the parent element then contains instead of the original children.
+//! Rendering the parent only copies the chunks.
+//!
+//! Children are rendered as if they were at the top level. In particular,
+//! [`Content::Trailer`]s are rendered in place and the children are not
+//! treated as descendants of foreign elements (see [`ElementKind::Foreign`]),
+//! so chunked children should only be used inside regular HTML elements.
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Render, chunked::ChunkedChildren, html::*};
+//!
+//! let mut rows = ChunkedChildren::new();
+//! for i in 0..100_000 {
+//!     rows.push(tr((td(i.to_string()), td("Row")))).unwrap();
+//! }
+//! assert_eq!(rows.len(), 100_000);
+//! let body = tbody(rows);
+//!
+//! // Only a few chunks instead of 100 000 elements
+//! assert!(body.children.len() < 100);
+//! assert!(table(body).render_to_string().unwrap().ends_with(
+//!     "<tr><td>99999</td><td>Row</td></tr></tbody></table>"
+//! ));
+//! ```
+//!
+//! [`ElementKind::Foreign`]: crate::ElementKind::Foreign
+
+use crate::{Content, Element, ElementComponent, Render, RenderOptions, Result, TrustedHtml};
+
+/// Children that are rendered as soon as they are added, see the [module
+/// documentation](self).
+#[derive(Debug, Clone)]
+pub struct ChunkedChildren {
+    options: RenderOptions,
+    chunk_size: usize,
+    chunks: Vec<TrustedHtml>,
+    current: String,
+    len: usize,
+}
+
+impl Default for ChunkedChildren {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChunkedChildren {
+    /// The default size of a chunk in bytes.
+    pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+    /// Create a new empty list of children rendered with the default render
+    /// options.
+    pub fn new() -> Self {
+        Self::with_options(RenderOptions::default())
+    }
+
+    /// Create a new empty list of children rendered with custom render
+    /// options.
+    ///
+    /// The options should match those used for rendering the parent, since
+    /// the children aren't rendered again.
+    pub fn with_options(options: RenderOptions) -> Self {
+        Self {
+            options,
+            chunk_size: Self::DEFAULT_CHUNK_SIZE,
+            chunks: vec![],
+            current: String::new(),
+            len: 0,
+        }
+    }
+
+    /// Set the size in bytes after which a new chunk is started.
+    ///
+    /// Chunks are only split between children, so they may be larger than
+    /// this size.
+    pub fn chunk_size(mut self, bytes: usize) -> Self {
+        self.chunk_size = bytes;
+        self
+    }
+
+    /// Render a child and append its output.
+    ///
+    /// If rendering fails, nothing is appended.
+    pub fn push(&mut self, child: impl Into<Content>) -> Result<()> {
+        let start = self.current.len();
+        if let Err(error) = child.into().render_with(&mut self.current, &self.options) {
+            self.current.truncate(start);
+            return Err(error);
+        }
+
+        self.len += 1;
+        if self.current.len() >= self.chunk_size {
+            self.finish_chunk();
+        }
+        Ok(())
+    }
+
+    fn finish_chunk(&mut self) {
+        if !self.current.is_empty() {
+            let chunk = std::mem::take(&mut self.current);
+            self.chunks.push(TrustedHtml::assume_trusted(chunk));
+        }
+    }
+
+    /// The number of children added so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no children were added so far.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Convert into the rendered chunks, as [`Content::Trusted`].
+    pub fn into_contents(mut self) -> Vec<Content> {
+        self.finish_chunk();
+        self.chunks.into_iter().map(Content::Trusted).collect()
+    }
+}
+
+impl ElementComponent for ChunkedChildren {
+    fn add_to_element(self, element: &mut Element) {
+        element.extend_children(self.into_contents());
+    }
+}
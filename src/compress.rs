@@ -0,0 +1,385 @@
+//! Compressing rendered output on the fly.
+//!
+//! The writers in this module implement [`fmt::Write`], so elements and
+//! documents can be rendered into them directly using
+//! [`Render::render`]. The output is compressed as it
+//! is written and passed on to an underlying [`io::Write`], e.g. a socket or a
+//! [`Vec<u8>`] holding the next chunk of a streaming response, without
+//! buffering the uncompressed page first.
+//!
+//! Each writer requires its own feature flag: `gzip` for `GzipWriter` and
+//! `brotli` for `BrotliWriter`.
+//!
+//! # Streaming
+//!
+//! Compressors buffer their input, so the compressed bytes for some text may
+//! only be written much later. The writers implement [`FlushWrite`], so when
+//! rendering with [`Render::render_streaming`], they are flushed at every
+//! [`Content::Flush`](crate::Content::Flush) marker and the client can
+//! decompress everything rendered so far. [`Compression::render_to_writer`]
+//! and [`Compression::render_chunks`] do this for an algorithm chosen at
+//! runtime.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "gzip")] {
+//! use el::{Render, compress::GzipWriter, html::*};
+//!
+//! let mut writer = GzipWriter::new(vec![]);
+//! p("Hello world").render(&mut writer).unwrap();
+//! let compressed: Vec<u8> = writer.finish().unwrap();
+//! # }
+//! ```
+
+use std::{fmt, io, mem};
+
+use crate::{FlushWrite, Render, RenderOptions};
+
+/// Implements the methods shared by all writers.
+macro_rules! compress_writer {
+    ( $name:ident ) => {
+        impl<W: io::Write> $name<W> {
+            /// A reference to the underlying writer.
+            pub fn get_ref(&self) -> &W {
+                self.encoder.get_ref()
+            }
+
+            /// A mutable reference to the underlying writer.
+            ///
+            /// Writing to it directly corrupts the compressed output, but
+            /// taking the bytes written so far (e.g. out of a [`Vec<u8>`]
+            /// after [`Self::flush`]) is fine.
+            pub fn get_mut(&mut self) -> &mut W {
+                self.encoder.get_mut()
+            }
+
+            /// Write all compressed data so far to the underlying writer, so
+            /// that it can be decompressed without the rest of the output.
+            ///
+            /// Flushing too often makes the compression worse, so only flush
+            /// at streaming boundaries.
+            ///
+            /// Returns the first error that occurred while writing, if any.
+            /// [`fmt::Write`] can't represent I/O errors, so rendering only
+            /// fails with a generic [`fmt::Error`] in that case.
+            pub fn flush(&mut self) -> io::Result<()> {
+                if let Some(error) = self.error.take() {
+                    return Err(error);
+                }
+                io::Write::flush(&mut self.encoder)
+            }
+        }
+
+        impl<W: io::Write> FlushWrite for $name<W> {
+            fn flush_chunk(&mut self) -> fmt::Result {
+                self.flush().map_err(|error| {
+                    self.error = Some(error);
+                    fmt::Error
+                })
+            }
+        }
+
+        impl<W: io::Write> fmt::Write for $name<W> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                if self.error.is_some() {
+                    return Err(fmt::Error);
+                }
+                io::Write::write_all(&mut self.encoder, s.as_bytes()).map_err(|error| {
+                    self.error = Some(error);
+                    fmt::Error
+                })
+            }
+        }
+    };
+}
+
+/// A [`fmt::Write`] that compresses everything written to it with gzip, see
+/// the [module documentation](self).
+///
+/// Requires the `gzip` feature.
+#[cfg(feature = "gzip")]
+pub struct GzipWriter<W: io::Write> {
+    encoder: flate2::write::GzEncoder<W>,
+    error: Option<io::Error>,
+}
+
+#[cfg(feature = "gzip")]
+impl<W: io::Write> GzipWriter<W> {
+    /// Create a new writer using the default compression level.
+    pub fn new(inner: W) -> Self {
+        Self::with_level(inner, 6)
+    }
+
+    /// Create a new writer using a compression level from 0 (no compression)
+    /// to 9 (best compression).
+    pub fn with_level(inner: W, level: u32) -> Self {
+        Self {
+            encoder: flate2::write::GzEncoder::new(inner, flate2::Compression::new(level.min(9))),
+            error: None,
+        }
+    }
+
+    /// Finish the compressed stream and return the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        self.encoder.finish()
+    }
+}
+
+#[cfg(feature = "gzip")]
+compress_writer!(GzipWriter);
+
+/// A [`fmt::Write`] that compresses everything written to it with brotli, see
+/// the [module documentation](self).
+///
+/// Requires the `brotli` feature.
+#[cfg(feature = "brotli")]
+pub struct BrotliWriter<W: io::Write> {
+    encoder: brotli::CompressorWriter<W>,
+    error: Option<io::Error>,
+}
+
+#[cfg(feature = "brotli")]
+impl<W: io::Write> BrotliWriter<W> {
+    /// Create a new writer using a compression level suitable for compressing
+    /// responses on the fly.
+    pub fn new(inner: W) -> Self {
+        Self::with_level(inner, 5)
+    }
+
+    /// Create a new writer using a compression level from 0 (fastest) to 11
+    /// (best compression).
+    pub fn with_level(inner: W, level: u32) -> Self {
+        Self {
+            encoder: brotli::CompressorWriter::new(inner, 4096, level.min(11), 22),
+            error: None,
+        }
+    }
+
+    /// Finish the compressed stream and return the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush()?;
+        Ok(self.encoder.into_inner())
+    }
+}
+
+#[cfg(feature = "brotli")]
+compress_writer!(BrotliWriter);
+
+/// A compression algorithm supported by this module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// gzip, see [`GzipWriter`].
+    #[cfg(feature = "gzip")]
+    Gzip,
+    /// brotli, see [`BrotliWriter`].
+    #[cfg(feature = "brotli")]
+    Brotli,
+}
+
+impl Compression {
+    /// The value of the `Content-Encoding` header for this algorithm.
+    pub fn content_encoding(self) -> &'static str {
+        match self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip => "gzip",
+            #[cfg(feature = "brotli")]
+            Self::Brotli => "br",
+        }
+    }
+
+    /// Pick the best supported algorithm accepted by a client, based on the
+    /// value of its `Accept-Encoding` header.
+    ///
+    /// Brotli is preferred over gzip. Quality values are only used to exclude
+    /// algorithms (`q=0`), not to rank them. An algorithm excluded by name is
+    /// never used, even if `*` accepts everything else.
+    pub fn negotiate(accept_encoding: &str) -> Option<Self> {
+        let entries = (accept_encoding.split(','))
+            .filter_map(|entry| {
+                let mut parts = entry.split(';').map(str::trim);
+                let name = parts.next().filter(|n| !n.is_empty())?;
+                let excluded = parts.any(|param| {
+                    let param = param.replace(' ', "");
+                    param
+                        .strip_prefix("q=")
+                        .and_then(|q| q.parse::<f32>().ok())
+                        .is_some_and(|q| q <= 0.0)
+                });
+                Some((name, excluded))
+            })
+            .collect::<Vec<_>>();
+        let accepted = |name: &str| {
+            let named = |n: &&str| n.eq_ignore_ascii_case(name);
+            if entries.iter().any(|(n, _)| named(n)) {
+                (entries.iter()).all(|(n, excluded)| !named(n) || !excluded)
+            } else {
+                (entries.iter()).any(|(n, excluded)| *n == "*" && !excluded)
+            }
+        };
+        #[cfg(feature = "brotli")]
+        if accepted("br") {
+            return Some(Self::Brotli);
+        }
+        #[cfg(feature = "gzip")]
+        if accepted("gzip") {
+            return Some(Self::Gzip);
+        }
+        let _ = accepted;
+        None
+    }
+
+    /// Render a value into a compressed stream written to `w` and return `w`.
+    ///
+    /// The compressor is flushed at every
+    /// [`Content::Flush`](crate::Content::Flush) marker while rendering, see
+    /// [`Render::render_streaming`]. Neither the uncompressed nor the
+    /// compressed page is buffered, so `w` can write directly to the response.
+    /// Render errors are returned like in
+    /// [`Render::render_to_writer_with_options`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "gzip")] {
+    /// use el::{Render, RenderOptions, compress::Compression, html::*};
+    ///
+    /// let page = html(body("Hello"));
+    /// let compressed = Compression::Gzip
+    ///     .render_to_writer(&page, &RenderOptions::default(), vec![])
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub fn render_to_writer<R: Render + ?Sized, W: io::Write>(
+        self,
+        value: &R,
+        options: &RenderOptions,
+        w: W,
+    ) -> io::Result<W> {
+        match self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip => {
+                let mut writer = GzipWriter::new(w);
+                render_streaming(value, options, &mut writer, GzipWriter::flush)?;
+                writer.finish()
+            }
+            #[cfg(feature = "brotli")]
+            Self::Brotli => {
+                let mut writer = BrotliWriter::new(w);
+                render_streaming(value, options, &mut writer, BrotliWriter::flush)?;
+                writer.finish()
+            }
+        }
+    }
+
+    /// Render a value into a compressed stream, split into one chunk per
+    /// [`Content::Flush`](crate::Content::Flush) marker.
+    ///
+    /// This is the compressed version of
+    /// [`Render::render_chunks_with`]. The compressor is flushed at every
+    /// marker while rendering, so the uncompressed page is never buffered and
+    /// everything up to the end of a chunk can be decompressed by the client.
+    /// The end of the stream is appended to the last chunk.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "gzip")] {
+    /// use el::{Content, RenderOptions, compress::Compression, html::*};
+    ///
+    /// let page = html((head(title("Hi")), Content::Flush, body("Hello")));
+    /// let chunks = Compression::Gzip
+    ///     .render_chunks(&page, &RenderOptions::default())
+    ///     .unwrap();
+    /// assert_eq!(chunks.len(), 2);
+    /// # }
+    /// ```
+    pub fn render_chunks<R: Render + ?Sized>(
+        self,
+        value: &R,
+        options: &RenderOptions,
+    ) -> io::Result<Vec<Vec<u8>>> {
+        match self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip => {
+                let mut chunks = Chunks::new(GzipWriter::new(vec![]), GzipWriter::get_mut);
+                render_streaming(value, options, &mut chunks, |c| c.writer.flush())?;
+                chunks.finish(GzipWriter::finish)
+            }
+            #[cfg(feature = "brotli")]
+            Self::Brotli => {
+                let mut chunks = Chunks::new(BrotliWriter::new(vec![]), BrotliWriter::get_mut);
+                render_streaming(value, options, &mut chunks, |c| c.writer.flush())?;
+                chunks.finish(BrotliWriter::finish)
+            }
+        }
+    }
+}
+
+/// Render a value to a compressing writer, returning the writer's I/O error
+/// if it failed.
+fn render_streaming<R: Render + ?Sized, C: FlushWrite>(
+    value: &R,
+    options: &RenderOptions,
+    writer: &mut C,
+    flush: fn(&mut C) -> io::Result<()>,
+) -> io::Result<()> {
+    match value.render_streaming(writer, options) {
+        Ok(()) => Ok(()),
+        Err(error) => {
+            // Flushing returns the I/O error that made rendering fail, if any.
+            flush(writer)?;
+            Err(io::Error::new(io::ErrorKind::InvalidData, error))
+        }
+    }
+}
+
+/// Splits the output of a compressing writer into chunks, see
+/// [`Compression::render_chunks`].
+struct Chunks<C> {
+    writer: C,
+    get_mut: fn(&mut C) -> &mut Vec<u8>,
+    chunks: Vec<Vec<u8>>,
+}
+
+impl<C: FlushWrite> Chunks<C> {
+    fn new(writer: C, get_mut: fn(&mut C) -> &mut Vec<u8>) -> Self {
+        Self {
+            writer,
+            get_mut,
+            chunks: vec![],
+        }
+    }
+
+    fn finish(mut self, finish: fn(C) -> io::Result<Vec<u8>>) -> io::Result<Vec<Vec<u8>>> {
+        // Errors while flushing are stored in the writer and returned by
+        // `finish`.
+        let _ = self.flush_chunk();
+        let rest = finish(self.writer)?;
+        match self.chunks.last_mut() {
+            Some(last) => last.extend(rest),
+            None => self.chunks.push(rest),
+        }
+        Ok(self.chunks)
+    }
+}
+
+impl<C: FlushWrite> fmt::Write for Chunks<C> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.writer.write_str(s)
+    }
+}
+
+impl<C: FlushWrite> FlushWrite for Chunks<C> {
+    fn flush_chunk(&mut self) -> fmt::Result {
+        self.writer.flush_chunk()?;
+        let chunk = mem::take((self.get_mut)(&mut self.writer));
+        if !chunk.is_empty() {
+            self.chunks.push(chunk);
+        }
+        Ok(())
+    }
+}
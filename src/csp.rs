@@ -0,0 +1,355 @@
+//! Building a [Content Security Policy][csp].
+//!
+//! A policy restricts which resources a page may load and execute. It can be
+//! delivered as a `Content-Security-Policy` HTTP header (the recommended way)
+//! or as a `<meta http-equiv>` element in the document's `<head>`. A [`Csp`]
+//! can produce both, and takes care of quoting keywords and separating
+//! directives and sources.
+//!
+//! [csp]: https://developer.mozilla.org/en-US/docs/Web/HTTP/CSP
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Render, csp::{Csp, Source::*}};
+//!
+//! let csp = Csp::new()
+//!     .default_src(Self_)
+//!     .script_src([Nonce("r4nd0m".to_string()), StrictDynamic])
+//!     .img_src([Self_, Scheme("data".to_string())])
+//!     .frame_ancestors(None);
+//!
+//! // The header value
+//! assert_eq!(
+//!     csp.to_string(),
+//!     "default-src 'self'; script-src 'nonce-r4nd0m' 'strict-dynamic'; \
+//!      img-src 'self' data:; frame-ancestors 'none'",
+//! );
+//!
+//! // Browsers ignore some directives in a <meta> element, so they are omitted
+//! assert_eq!(
+//!     csp.meta().render_to_string().unwrap(),
+//!     "<meta content=\"default-src 'self'; script-src 'nonce-r4nd0m' 'strict-dynamic'; \
+//!      img-src 'self' data:\" http-equiv=\"content-security-policy\">",
+//! );
+//! ```
+
+use std::{fmt, iter};
+
+use crate::{
+    html::{attr, meta},
+    Element,
+};
+
+/// The name of the HTTP header used to deliver a [`Csp`].
+pub const HEADER_NAME: &str = "Content-Security-Policy";
+
+/// The name of the HTTP header used to deliver a [`Csp`] that is only
+/// reported, not enforced.
+pub const REPORT_ONLY_HEADER_NAME: &str = "Content-Security-Policy-Report-Only";
+
+/// Directives that have no effect when delivered via a `<meta>` element.
+const HEADER_ONLY_DIRECTIVES: &[&str] = &["frame-ancestors", "report-to", "report-uri", "sandbox"];
+
+/// A single entry in the source list of a [`Csp`] directive.
+///
+/// Characters that would break the policy syntax (whitespace, `;`, `,` and
+/// `'`) are removed from the values of variants that contain a [`String`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// No source at all (`'none'`). Should be the only source of a directive.
+    None,
+    /// All sources, except for `data:`, `blob:` and `filesystem:` URLs (`*`).
+    Any,
+    /// The page's own origin (`'self'`).
+    Self_,
+    /// Allow inline scripts and styles (`'unsafe-inline'`).
+    UnsafeInline,
+    /// Allow `eval()` and similar (`'unsafe-eval'`).
+    UnsafeEval,
+    /// Allow inline event handlers matching a hash source
+    /// (`'unsafe-hashes'`).
+    UnsafeHashes,
+    /// Allow WebAssembly compilation without allowing `eval()`
+    /// (`'wasm-unsafe-eval'`).
+    WasmUnsafeEval,
+    /// Trust scripts loaded by already trusted scripts (`'strict-dynamic'`).
+    StrictDynamic,
+    /// Include a sample of the violating code in reports (`'report-sample'`).
+    ReportSample,
+    /// Scripts or styles with a matching `nonce` attribute (`'nonce-...'`),
+    /// see [`attr::nonce`].
+    Nonce(String),
+    /// Scripts or styles with a matching base64-encoded SHA-256 hash
+    /// (`'sha256-...'`).
+    Sha256(String),
+    /// Scripts or styles with a matching base64-encoded SHA-384 hash
+    /// (`'sha384-...'`).
+    Sha384(String),
+    /// Scripts or styles with a matching base64-encoded SHA-512 hash
+    /// (`'sha512-...'`).
+    Sha512(String),
+    /// All URLs with a scheme, e.g. `https` or `data`. The trailing `:` is
+    /// added automatically.
+    Scheme(String),
+    /// A host, optionally with scheme, port and path, e.g. `example.com`,
+    /// `*.example.com` or `https://example.com:8080/assets/`.
+    Host(String),
+}
+
+impl Source {
+    /// Construct [`Self::Nonce`] from a nonce.
+    pub fn nonce(nonce: impl ToString) -> Self {
+        Self::Nonce(nonce.to_string())
+    }
+
+    /// Construct [`Self::Host`] from a host.
+    pub fn host(host: impl ToString) -> Self {
+        Self::Host(host.to_string())
+    }
+}
+
+/// The directive that the nonce of a `<link>` element belongs to, depending on
+/// what it loads.
+fn link_directive(link: &Element) -> Option<&'static str> {
+    let has_rel = |rel: &str| (link.get_tokens("rel", " ")).any(|r| r.eq_ignore_ascii_case(rel));
+    let as_ = link.attr_ci("as").unwrap_or("");
+    if has_rel("modulepreload") || (has_rel("preload") && as_.eq_ignore_ascii_case("script")) {
+        Some("script-src")
+    } else if has_rel("stylesheet") || (has_rel("preload") && as_.eq_ignore_ascii_case("style")) {
+        Some("style-src")
+    } else {
+        None
+    }
+}
+
+/// Write a value without the characters that would end it early in a policy.
+pub(crate) fn write_sanitized(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    for c in value.chars() {
+        if !(c.is_whitespace() || matches!(c, ';' | ',' | '\'')) {
+            write!(f, "{c}")?;
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (prefix, value, suffix) = match self {
+            Self::None => return "'none'".fmt(f),
+            Self::Any => return "*".fmt(f),
+            Self::Self_ => return "'self'".fmt(f),
+            Self::UnsafeInline => return "'unsafe-inline'".fmt(f),
+            Self::UnsafeEval => return "'unsafe-eval'".fmt(f),
+            Self::UnsafeHashes => return "'unsafe-hashes'".fmt(f),
+            Self::WasmUnsafeEval => return "'wasm-unsafe-eval'".fmt(f),
+            Self::StrictDynamic => return "'strict-dynamic'".fmt(f),
+            Self::ReportSample => return "'report-sample'".fmt(f),
+            Self::Nonce(nonce) => ("'nonce-", &nonce[..], "'"),
+            Self::Sha256(hash) => ("'sha256-", &hash[..], "'"),
+            Self::Sha384(hash) => ("'sha384-", &hash[..], "'"),
+            Self::Sha512(hash) => ("'sha512-", &hash[..], "'"),
+            Self::Scheme(scheme) => ("", scheme.strip_suffix(':').unwrap_or(scheme), ":"),
+            Self::Host(host) => ("", &host[..], ""),
+        };
+        write!(f, "{prefix}")?;
+        write_sanitized(f, value)?;
+        write!(f, "{suffix}")
+    }
+}
+
+/// Allows passing a single [`Source`] where a list is expected.
+impl IntoIterator for Source {
+    type Item = Self;
+    type IntoIter = iter::Once<Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        iter::once(self)
+    }
+}
+
+/// A Content Security Policy, see the [module documentation](self).
+///
+/// Directives are rendered in the order they were first added. Adding sources
+/// to a directive that is already present appends them to its source list,
+/// skipping sources that are already included.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Csp {
+    directives: Vec<(String, Vec<String>)>,
+}
+
+macro_rules! fetch_directive {
+    ( $name:ident, $actual:expr ) => {
+        #[doc = concat!("Add sources to the `", $actual, "` directive")]
+        #[doc = concat!("([MDN](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/", $actual, ")).")]
+        pub fn $name(self, sources: impl IntoIterator<Item = Source>) -> Self {
+            self.directive($actual, sources)
+        }
+    };
+}
+
+impl Csp {
+    /// Create a new policy without any directives.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add sources to an arbitrary directive.
+    ///
+    /// Prefer the methods named after the directives where possible.
+    pub fn directive(
+        mut self,
+        name: impl ToString,
+        sources: impl IntoIterator<Item = Source>,
+    ) -> Self {
+        self.add_values(name, sources.into_iter().map(|s| s.to_string()));
+        self
+    }
+
+    fn add_values(&mut self, name: impl ToString, values: impl IntoIterator<Item = String>) {
+        let name = name.to_string();
+        let index = match self.directives.iter().position(|(n, _)| *n == name) {
+            Some(index) => index,
+            None => {
+                self.directives.push((name, vec![]));
+                self.directives.len() - 1
+            }
+        };
+        let existing = &mut self.directives[index].1;
+        for value in values {
+            if !existing.contains(&value) {
+                existing.push(value);
+            }
+        }
+    }
+
+    fetch_directive!(default_src, "default-src");
+    fetch_directive!(script_src, "script-src");
+    fetch_directive!(script_src_elem, "script-src-elem");
+    fetch_directive!(script_src_attr, "script-src-attr");
+    fetch_directive!(style_src, "style-src");
+    fetch_directive!(style_src_elem, "style-src-elem");
+    fetch_directive!(style_src_attr, "style-src-attr");
+    fetch_directive!(img_src, "img-src");
+    fetch_directive!(font_src, "font-src");
+    fetch_directive!(connect_src, "connect-src");
+    fetch_directive!(media_src, "media-src");
+    fetch_directive!(object_src, "object-src");
+    fetch_directive!(frame_src, "frame-src");
+    fetch_directive!(child_src, "child-src");
+    fetch_directive!(worker_src, "worker-src");
+    fetch_directive!(manifest_src, "manifest-src");
+    fetch_directive!(base_uri, "base-uri");
+    fetch_directive!(form_action, "form-action");
+    fetch_directive!(frame_ancestors, "frame-ancestors");
+
+    /// Add the `upgrade-insecure-requests` directive
+    /// ([MDN](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/upgrade-insecure-requests)).
+    pub fn upgrade_insecure_requests(mut self) -> Self {
+        self.add_values("upgrade-insecure-requests", []);
+        self
+    }
+
+    /// Add a reporting endpoint group to the `report-to` directive
+    /// ([MDN](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy/report-to)).
+    pub fn report_to(mut self, group: impl ToString) -> Self {
+        self.add_values("report-to", [sanitized(group)]);
+        self
+    }
+
+    /// Allow all scripts and styles in an element and its descendants that
+    /// have a `nonce` attribute, see [`attr::nonce`].
+    ///
+    /// The nonces of `<script>` elements and of `<link>` elements loading
+    /// scripts (`rel="modulepreload"` or `rel="preload" as="script"`) are
+    /// added to `script-src`, and those of `<style>` elements and of `<link>`
+    /// elements loading styles (`rel="stylesheet"` or `rel="preload"
+    /// as="style"`) to `style-src`. Note that once either directive exists, it
+    /// replaces `default-src` for its resource type, so other sources may need
+    /// to be added explicitly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{csp::{Csp, Source}, html::*};
+    ///
+    /// let page = html((
+    ///     head(style((attr::nonce("abc"), "p { color: red }"))),
+    ///     body(script((attr::nonce("abc"), attr::src("/app.js")))),
+    /// ));
+    /// let csp = Csp::new().default_src(Source::Self_).nonces_from(&page);
+    /// assert_eq!(
+    ///     csp.to_string(),
+    ///     "default-src 'self'; style-src 'nonce-abc'; script-src 'nonce-abc'",
+    /// );
+    /// ```
+    pub fn nonces_from(mut self, element: &Element) -> Self {
+        self.add_nonces(element);
+        self
+    }
+
+    fn add_nonces(&mut self, element: &Element) {
+        for element in iter::once(element).chain(element.descendants()) {
+            let Some(nonce) = element.attr_ci("nonce").filter(|n| !n.is_empty()) else {
+                continue;
+            };
+            let directive = match &element.name[..] {
+                "script" => Some("script-src"),
+                "style" => Some("style-src"),
+                "link" => link_directive(element),
+                _ => None,
+            };
+            if let Some(directive) = directive {
+                self.add_values(directive, [Source::nonce(nonce).to_string()]);
+            }
+        }
+    }
+
+    /// Whether the policy contains no directives.
+    pub fn is_empty(&self) -> bool {
+        self.directives.is_empty()
+    }
+
+    /// A `<meta http-equiv="content-security-policy">` element containing the
+    /// policy.
+    ///
+    /// Directives that browsers ignore in `<meta>` elements (e.g.
+    /// `frame-ancestors`) are omitted. Deliver the policy as an HTTP header
+    /// instead if you need them.
+    pub fn meta(&self) -> Element {
+        let policy = Self {
+            directives: (self.directives.iter())
+                .filter(|(name, _)| !HEADER_ONLY_DIRECTIVES.contains(&name.as_str()))
+                .cloned()
+                .collect(),
+        };
+        meta((
+            attr::HttpEquiv::ContentSecurityPolicy,
+            attr::content(policy),
+        ))
+    }
+}
+
+fn sanitized(value: impl ToString) -> String {
+    let value = value.to_string();
+    value.replace(
+        |c: char| c.is_whitespace() || matches!(c, ';' | ',' | '\''),
+        "",
+    )
+}
+
+/// Renders the policy as an HTTP header value, see [`HEADER_NAME`].
+impl fmt::Display for Csp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, values)) in self.directives.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write_sanitized(f, name)?;
+            for value in values {
+                write!(f, " {value}")?;
+            }
+        }
+        Ok(())
+    }
+}
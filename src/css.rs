@@ -0,0 +1,283 @@
+//! Checking and escaping CSS in `style` attributes.
+//!
+//! el escapes attribute values for HTML, so a `style` attribute can never end
+//! early or add other attributes. The CSS inside it is not checked, though,
+//! and some CSS constructs execute code in older browsers, e.g.
+//! `url(javascript:...)` or `expression(...)`. Malformed CSS like unbalanced
+//! quotes can also make a browser interpret the remaining declarations
+//! differently than intended, which matters when a style contains user input.
+//!
+//! [`check_style`] rejects such values. It is used by
+//! [`html::attr::style_checked`](crate::html::attr::style_checked) and, if
+//! [`RenderOptions::check_styles`](crate::RenderOptions::check_styles) is
+//! enabled, for every `style` attribute during rendering. To include user
+//! input in a declaration, [`quote`] it as a CSS string.
+//!
+//! # Example
+//!
+//! ```
+//! use el::css::{check_style, quote, StyleError};
+//!
+//! assert_eq!(check_style("color: red; margin: 0 auto"), Ok(()));
+//! assert_eq!(
+//!     check_style("background: url(j\\61vascript:alert(1))"),
+//!     Err(StyleError::ScriptUrl),
+//! );
+//! assert_eq!(check_style("font-family: \"Fira"), Err(StyleError::UnterminatedString));
+//!
+//! let name = "Evil\"; background: url(javascript:alert(1))";
+//! let style = format!("font-family: {}", quote(name));
+//! assert_eq!(check_style(&style), Ok(()));
+//! ```
+
+use std::{error, fmt};
+
+use crate::url::url_scheme;
+
+/// A reason why a CSS value is unsafe, see [`check_style`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StyleError {
+    /// A control character other than whitespace.
+    ControlCharacter(char),
+    /// A string without its closing quote.
+    UnterminatedString,
+    /// A comment without its closing `*/`.
+    UnterminatedComment,
+    /// A `(` without matching `)` or the other way around.
+    UnbalancedParentheses,
+    /// A `url(...)` with a `javascript:` or `vbscript:` URL.
+    ScriptUrl,
+    /// An `expression(...)`, which executes code in old versions of Internet
+    /// Explorer.
+    Expression,
+    /// A `behavior` or `-moz-binding` property, which loads code in old
+    /// browsers.
+    Binding,
+}
+
+impl fmt::Display for StyleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ControlCharacter(c) => write!(f, "control character {c:?}"),
+            Self::UnterminatedString => write!(f, "unterminated string"),
+            Self::UnterminatedComment => write!(f, "unterminated comment"),
+            Self::UnbalancedParentheses => write!(f, "unbalanced parentheses"),
+            Self::ScriptUrl => write!(f, "script URL"),
+            Self::Expression => write!(f, "CSS expression"),
+            Self::Binding => write!(f, "behavior binding"),
+        }
+    }
+}
+
+impl error::Error for StyleError {}
+
+/// Whether a character is whitespace according to the CSS syntax.
+fn is_css_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\n' | '\r' | '\x0C')
+}
+
+/// Decode a CSS escape sequence, assuming the backslash was already consumed.
+fn unescape(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Option<char> {
+    let mut hex = String::new();
+    while hex.len() < 6 {
+        match chars.peek() {
+            Some(c) if c.is_ascii_hexdigit() => hex.push(chars.next()?),
+            _ => break,
+        }
+    }
+    if hex.is_empty() {
+        return chars.next();
+    }
+    if chars.peek().copied().is_some_and(is_css_whitespace) {
+        chars.next();
+    }
+    let code = u32::from_str_radix(&hex, 16).ok()?;
+    Some(
+        char::from_u32(code)
+            .filter(|c| *c != '\0')
+            .unwrap_or('\u{FFFD}'),
+    )
+}
+
+/// Check the syntax of a CSS value and return it with escapes decoded,
+/// comments and the contents of strings (except URLs) removed, and letters in
+/// lowercase.
+fn normalize(style: &str) -> Result<String, StyleError> {
+    let mut result = String::with_capacity(style.len());
+    let mut chars = style.chars().peekable();
+    let mut depth = 0_usize;
+    while let Some(c) = chars.next() {
+        if c.is_control() && !is_css_whitespace(c) {
+            return Err(StyleError::ControlCharacter(c));
+        }
+        match c {
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if previous == '*' => break,
+                        Some(c) => previous = c,
+                        None => return Err(StyleError::UnterminatedComment),
+                    }
+                }
+            }
+            '"' | '\'' => {
+                // Only the contents of URL strings are checked further.
+                let url = result.trim_end_matches(is_css_whitespace).ends_with("url(");
+                let start = result.len();
+                result.push(c);
+                loop {
+                    match chars.next() {
+                        Some(q) if q == c => break,
+                        Some('\\') => match chars.peek() {
+                            // An escaped newline continues the string.
+                            Some('\n') => {
+                                chars.next();
+                            }
+                            Some(_) => result.extend(
+                                unescape(&mut chars)
+                                    .into_iter()
+                                    .flat_map(char::to_lowercase),
+                            ),
+                            None => return Err(StyleError::UnterminatedString),
+                        },
+                        Some('\n' | '\r' | '\x0C') | None => {
+                            return Err(StyleError::UnterminatedString);
+                        }
+                        Some(c) if c.is_control() && c != '\t' => {
+                            return Err(StyleError::ControlCharacter(c));
+                        }
+                        Some(c) => result.extend(c.to_lowercase()),
+                    }
+                }
+                if !url {
+                    result.truncate(start + 1);
+                }
+                result.push(c);
+            }
+            '\\' => result.extend(
+                unescape(&mut chars)
+                    .into_iter()
+                    .flat_map(char::to_lowercase),
+            ),
+            '(' => {
+                depth += 1;
+                result.push(c);
+            }
+            ')' => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or(StyleError::UnbalancedParentheses)?;
+                result.push(c);
+            }
+            c => result.extend(c.to_lowercase()),
+        }
+    }
+    if depth > 0 {
+        return Err(StyleError::UnbalancedParentheses);
+    }
+    Ok(result)
+}
+
+/// Whether `text` starts with `name` followed by optional whitespace and
+/// `next`.
+fn starts_with_token(text: &str, name: &str, next: char) -> bool {
+    text.strip_prefix(name)
+        .is_some_and(|rest| rest.trim_start_matches(is_css_whitespace).starts_with(next))
+}
+
+/// Check whether a CSS value, e.g. the value of a `style` attribute, is safe.
+///
+/// A value is rejected if it contains control characters other than
+/// whitespace, unterminated strings or comments, or unbalanced parentheses.
+/// It is also rejected if it contains constructs that execute code in some
+/// browsers: `url(...)` with a `javascript:` or `vbscript:` URL,
+/// `expression(...)`, and the `behavior` and `-moz-binding` properties.
+/// Escape sequences and comments can't be used to hide these constructs.
+///
+/// The check is conservative. It doesn't fully parse CSS and may reject a few
+/// harmless values, e.g. a URL containing the text `expression(`.
+///
+/// # Example
+///
+/// ```
+/// use el::css::{check_style, StyleError};
+///
+/// assert_eq!(check_style("background: url('/img/bg.png')"), Ok(()));
+/// assert_eq!(check_style("width: calc(100% - (2 * 1em))"), Ok(()));
+/// assert_eq!(check_style("width: expr/**/ession(alert(1))"), Err(StyleError::Expression));
+/// assert_eq!(check_style("width: calc(100%"), Err(StyleError::UnbalancedParentheses));
+/// assert_eq!(check_style("color: red\0"), Err(StyleError::ControlCharacter('\0')));
+/// ```
+pub fn check_style(style: &str) -> Result<(), StyleError> {
+    let normalized = normalize(style)?;
+    for (i, _) in normalized.char_indices() {
+        let rest = &normalized[i..];
+        // Only look at the start of identifiers.
+        let start = normalized[..i]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !(c.is_alphanumeric() || c == '-' || c == '_'));
+        if !start {
+            continue;
+        }
+
+        if starts_with_token(rest, "url", '(') {
+            let url = rest[3..].trim_start_matches(is_css_whitespace)[1..]
+                .trim_start_matches(is_css_whitespace)
+                .trim_start_matches(['"', '\'']);
+            let url = url.trim_start_matches(is_css_whitespace);
+            if matches!(url_scheme(url).as_deref(), Some("javascript" | "vbscript")) {
+                return Err(StyleError::ScriptUrl);
+            }
+        } else if starts_with_token(rest, "expression", '(') {
+            return Err(StyleError::Expression);
+        } else if starts_with_token(rest, "behavior", ':')
+            || starts_with_token(rest, "-moz-binding", ':')
+        {
+            return Err(StyleError::Binding);
+        }
+    }
+    Ok(())
+}
+
+/// Quote a value as a CSS string, e.g. for `font-family` or `content`.
+///
+/// The result is surrounded by double quotes. Quotes, backslashes, control
+/// characters and the characters `<`, `>` and `&` are escaped, so the string
+/// can't end early and is safe in both `style` attributes and `<style>`
+/// elements.
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, css::quote, html::*};
+///
+/// assert_eq!(quote("Fira Sans"), r#""Fira Sans""#);
+/// assert_eq!(quote("a\"b\\c\n</style>"), r#""a\"b\\c\a \3c /style\3e ""#);
+///
+/// let p = p((attr::style(format!("font-family: {}", quote("O'Neil \"Sans\""))), "Hi"));
+/// assert_eq!(
+///     p.render_to_string().unwrap(),
+///     r#"<p style="font-family: &quot;O'Neil \&quot;Sans\&quot;&quot;">Hi</p>"#,
+/// );
+/// ```
+pub fn quote(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => {
+                result.push('\\');
+                result.push(c);
+            }
+            '\0' => result.push('\u{FFFD}'),
+            '<' | '>' | '&' => result.push_str(&format!("\\{:x} ", c as u32)),
+            c if c.is_control() => result.push_str(&format!("\\{:x} ", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
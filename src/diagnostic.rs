@@ -0,0 +1,87 @@
+//! Rich diagnostics for render errors using [miette].
+//!
+//! [miette]: https://crates.io/crates/miette
+
+use std::{fmt, fs, iter};
+
+use miette::{Diagnostic, LabeledSpan, NamedSource, SourceCode, SourceOffset};
+
+use crate::{Error, ErrorCause};
+
+fn help(cause: &ErrorCause) -> &'static str {
+    match cause {
+        ErrorCause::Format(_) => "a `Display` implementation returned an error",
+        ErrorCause::InvalidTagName { .. } => {
+            "tag names must start with an ASCII letter and only contain ASCII letters, digits and `-`"
+        }
+        ErrorCause::InvalidAttrName { .. } => {
+            "attribute names must start with an ASCII letter and only contain ASCII letters, digits, `-` and `_`"
+        }
+        ErrorCause::InvalidChild => {
+            "void elements like `<br>` can't have children, and raw text elements like `<script>` can only contain text"
+        }
+        ErrorCause::InvalidRawText { .. } => {
+            "text inside raw text elements like `<script>` must not contain something that looks like their end tag"
+        }
+        ErrorCause::Unencodable { .. } => {
+            "unescaped content must only contain characters representable in the output encoding"
+        }
+        ErrorCause::BudgetExceeded => "increase `RenderOptions::max_nodes` or `RenderOptions::time_limit`",
+        ErrorCause::UnsafeStyle { .. } => {
+            "include user input in styles using `css::quote` and avoid script URLs and `expression()`"
+        }
+    }
+}
+
+impl Error {
+    fn named_source(&self) -> Option<&NamedSource<String>> {
+        self.source_code
+            .get_or_init(|| {
+                let file = self.location()?.file();
+                let source = fs::read_to_string(file).ok()?;
+                Some(Box::new(
+                    NamedSource::new(file, source).with_language("Rust"),
+                ))
+            })
+            .as_deref()
+    }
+}
+
+/// Requires the `miette` feature flag.
+///
+/// The diagnostic includes the [`Error::code`] and a hint on how to fix the
+/// error. If the `track-caller` feature is enabled and the source file of the
+/// element that caused the error is available (e.g. when running a static
+/// site generator with `cargo run`), the place where the element was
+/// constructed is highlighted in the source code.
+impl Diagnostic for Error {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(self.code()))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(help(self.cause())))
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        self.named_source().map(|source| source as &dyn SourceCode)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let location = self.location()?;
+        let source = self.named_source()?.inner();
+        let line = location.line() as usize;
+        let column = location.column() as usize;
+        let offset = SourceOffset::from_location(source, line, column).offset();
+        // Highlight the path of the function, e.g. `html::div`
+        let len = source[offset..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+            .unwrap_or(source.len() - offset);
+        let label = match self.label() {
+            Some(label) => format!("element constructed here (in {label})"),
+            None => "element constructed here".to_string(),
+        };
+        let span = LabeledSpan::new_primary_with_span(Some(label), (offset, len));
+        Some(Box::new(iter::once(span)))
+    }
+}
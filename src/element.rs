@@ -1,22 +1,147 @@
-use std::collections::{btree_map::Entry, BTreeMap, HashMap};
+use std::{
+    borrow::Cow,
+    collections::{btree_map::Entry, BTreeMap, HashMap},
+    fmt,
+    panic::Location,
+};
+
+use crate::{
+    tokens::{split_tokens, TokenList},
+    TrustedHtml,
+};
 
 /// The kind of an element.
 ///
 /// Follows the [definitions from the HTML standard][spec].
 ///
 /// [spec]: https://html.spec.whatwg.org/multipage/syntax.html#elements-2
+///
+/// The kind determines which children an element may have and how its text is
+/// escaped during rendering. The methods [`Self::allows_children`],
+/// [`Self::allows_elements`] and [`Self::escapes_text`] describe this contract
+/// and are used by the renderer itself.
+///
+/// | Kind                       | Children      | Text                    |
+/// |----------------------------|---------------|-------------------------|
+/// | [`Self::Void`]             | none          | -                       |
+/// | [`Self::RawText`]          | text and raw  | checked, but unescaped  |
+/// | [`Self::EscapableRawText`] | text and raw  | escaped                 |
+/// | all others                 | any           | escaped                 |
+///
+/// Escaped text has `&`, `<`, and `>` replaced by character references.
+/// [`Content::Raw`] and [`Content::Trusted`] are never escaped or checked.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ElementKind {
+    /// An element that can't have any children, e.g. `<br>`.
     Void,
+    /// The `<template>` element.
+    ///
+    /// Its children are inert and may contain elements that are only allowed
+    /// in other contexts, e.g. `<tr>` outside of a table. See
+    /// [`crate::template`] for working with template contents.
     Template,
+    /// An element whose text is not escaped, e.g. `<script>` or `<style>`.
     RawText,
+    /// An element that can only contain text, e.g. `<textarea>` or `<title>`.
     EscapableRawText,
+    /// An element from the SVG or MathML namespace.
+    ///
+    /// When rendering, all descendants of a foreign element are treated as
+    /// foreign elements as well, regardless of their own kind. This matches
+    /// how browsers parse the output. For example, an [`html::a`](crate::html::a)
+    /// inside an [`svg::svg`](crate::svg::svg) is rendered as an SVG `<a>`,
+    /// and the text of an [`html::style`](crate::html::style) inside it is
+    /// escaped.
+    ///
+    /// The exceptions are the children of HTML integration points, which are
+    /// rendered according to their own kind again. These are the SVG elements
+    /// `<foreignObject>`, `<desc>` and `<title>`, the MathML text elements
+    /// `<mi>`, `<mo>`, `<mn>`, `<ms>` and `<mtext>`, and MathML
+    /// `<annotation-xml>` elements whose `encoding` is `text/html` or
+    /// `application/xhtml+xml`.
     Foreign,
+    /// Any other element.
     Normal,
 }
 
+impl ElementKind {
+    /// Whether elements of this kind may have children.
+    pub fn allows_children(self) -> bool {
+        self != Self::Void
+    }
+
+    /// Whether elements of this kind may have [`Content::Element`] or
+    /// [`Content::Comment`] children.
+    pub fn allows_elements(self) -> bool {
+        !matches!(self, Self::Void | Self::RawText | Self::EscapableRawText)
+    }
+
+    /// Whether [`Content::Text`] children of elements of this kind are
+    /// escaped.
+    ///
+    /// Unescaped text is checked during rendering instead and results in an
+    /// error if it would end the element early.
+    pub fn escapes_text(self) -> bool {
+        self != Self::RawText
+    }
+
+    /// The kind of the HTML element with the given tag name.
+    ///
+    /// The name is compared case-insensitively. Unknown names (including
+    /// custom elements) are [`Self::Normal`]. The result matches the kinds
+    /// used by the constructors in the [`html`](crate::html) module, with a
+    /// few deprecated elements that the HTML parser still treats specially
+    /// (e.g. `<xmp>` or `<param>`) added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::ElementKind;
+    ///
+    /// assert_eq!(ElementKind::for_html_tag("script"), ElementKind::RawText);
+    /// assert_eq!(ElementKind::for_html_tag("BR"), ElementKind::Void);
+    /// assert_eq!(ElementKind::for_html_tag("my-element"), ElementKind::Normal);
+    /// ```
+    pub fn for_html_tag(name: &str) -> Self {
+        // Including deprecated elements that are still parsed specially
+        const VOID: &[&str] = &[
+            "area", "base", "basefont", "bgsound", "br", "col", "embed", "frame", "hr", "img",
+            "input", "keygen", "link", "meta", "param", "source", "track", "wbr",
+        ];
+        const RAW_TEXT: &[&str] = &["noembed", "noframes", "script", "style", "xmp"];
+        const ESCAPABLE_RAW_TEXT: &[&str] = &["textarea", "title"];
+
+        let is = |names: &[&str]| names.iter().any(|n| n.eq_ignore_ascii_case(name));
+        if is(VOID) {
+            Self::Void
+        } else if is(RAW_TEXT) {
+            Self::RawText
+        } else if is(ESCAPABLE_RAW_TEXT) {
+            Self::EscapableRawText
+        } else if name.eq_ignore_ascii_case("template") {
+            Self::Template
+        } else {
+            Self::Normal
+        }
+    }
+}
+
+/// The namespace of an element, see [`Element::from_tag_name`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Namespace {
+    /// HTML elements.
+    Html,
+    /// SVG elements.
+    Svg,
+    /// MathML elements.
+    MathMl,
+}
+
 /// A single bit of [`Element`] content.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Content {
     /// A raw string to be rendered without any checks.
     ///
@@ -26,7 +151,14 @@ pub enum Content {
     ///
     /// This is an escape hatch for including arbitrary text. Using it
     /// incorrectly may result in security vulnerabilities in the rendered HTML.
+    #[cfg_attr(
+        feature = "deprecate-raw",
+        deprecated = "raw content is disallowed by the `deprecate-raw` feature of el"
+    )]
     Raw(String),
+    /// HTML that is rendered like [`Self::Raw`], but can only be created
+    /// through the sanctioned constructors of [`TrustedHtml`].
+    Trusted(TrustedHtml),
     /// Plain text.
     ///
     /// Can also be constructed using [`Self::text`].
@@ -39,6 +171,15 @@ pub enum Content {
     ///
     /// Can also be constructed using [`Self::element`].
     Element(Element),
+    /// A point at which the rendered output may be flushed early.
+    ///
+    /// Renders as nothing. See [`crate::Render::render_streaming`].
+    Flush,
+    /// Content that is moved to the end of the `<body>` when rendering a
+    /// [`Document`].
+    ///
+    /// Can also be constructed using [`Self::trailer`].
+    Trailer(Box<Self>),
 }
 
 impl Content {
@@ -49,6 +190,11 @@ impl Content {
     ///
     /// This is an escape hatch for including arbitrary text. Using it
     /// incorrectly may result in security vulnerabilities in the rendered HTML.
+    #[cfg_attr(
+        feature = "deprecate-raw",
+        deprecated = "raw content is disallowed by the `deprecate-raw` feature of el"
+    )]
+    #[allow(deprecated)]
     pub fn raw(str: impl ToString) -> Self {
         Self::Raw(str.to_string())
     }
@@ -71,6 +217,38 @@ impl Content {
         Self::Element(e.into())
     }
 
+    /// Construct [`Content::Trailer`], content that is moved to the end of the
+    /// `<body>` when rendering a [`Document`].
+    ///
+    /// This is useful for scripts that should only run after the rest of the
+    /// page has loaded, but are declared by a component deep inside the tree.
+    /// Trailers render immediately before the closing tag of the `<body>`
+    /// element, in the order they appear in the document. If the document has
+    /// no `<body>`, they render at the end of the topmost element instead.
+    ///
+    /// When rendering anything other than a [`Document`], trailers are rendered
+    /// in place.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Content, Render, html::*};
+    ///
+    /// let widget = div(("Widget", Content::trailer(script("init()"))));
+    /// let page = html(body((widget, p("Footer")))).into_document();
+    /// assert_eq!(
+    ///     page.render_to_string().unwrap(),
+    ///     concat!(
+    ///         "<!DOCTYPE html><html><body>",
+    ///         "<div>Widget</div><p>Footer</p><script>init()</script>",
+    ///         "</body></html>",
+    ///     ),
+    /// );
+    /// ```
+    pub fn trailer(c: impl Into<Self>) -> Self {
+        Self::Trailer(Box::new(c.into()))
+    }
+
     /// Construct a doctype of the form `<!DOCTYPE html>`.
     ///
     /// # Example
@@ -80,11 +258,89 @@ impl Content {
     /// let doctype = Content::doctype();
     /// assert_eq!(doctype, Content::raw("<!DOCTYPE html>"));
     /// ```
+    #[allow(deprecated)]
     pub fn doctype() -> Self {
         Self::raw("<!DOCTYPE html>")
     }
 }
 
+// Not derived, so that generating raw content doesn't trigger the
+// `deprecate-raw` warnings.
+#[cfg(feature = "arbitrary")]
+#[allow(deprecated)]
+impl<'a> arbitrary::Arbitrary<'a> for Content {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=6_u8)? {
+            0 => Self::Raw(u.arbitrary()?),
+            1 => Self::Trusted(u.arbitrary()?),
+            2 => Self::Text(u.arbitrary()?),
+            3 => Self::Comment(u.arbitrary()?),
+            4 => Self::Element(u.arbitrary()?),
+            5 => Self::Flush,
+            _ => Self::Trailer(u.arbitrary()?),
+        })
+    }
+}
+
+/// Plain text, formatted using [`fmt::Display`].
+///
+/// This is the canonical way to include values like numbers or dates in an
+/// element. Text is always safe: it is escaped when rendered, or, inside
+/// elements like `<script>` that can't contain escaped text (see
+/// [`ElementKind`]), checked so it can't end the element early. Strings can
+/// also be used as text directly.
+///
+/// See also [`texts`] for multiple values and [`raw()`] for unescaped content.
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, html::*, text};
+///
+/// let price = 4.5;
+/// let p = p(("Price: ", text(price), text(" <b>€</b>")));
+/// assert_eq!(
+///     p.render_to_string().unwrap(),
+///     "<p>Price: 4.5 &lt;b&gt;€&lt;/b&gt;</p>",
+/// );
+/// ```
+pub fn text(value: impl fmt::Display) -> Content {
+    Content::Text(value.to_string())
+}
+
+/// Multiple pieces of plain text, see [`text()`].
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, html::*, texts};
+///
+/// let p = p(texts([1, 2, 3]));
+/// assert_eq!(p.render_to_string().unwrap(), "<p>123</p>");
+/// ```
+pub fn texts(values: impl IntoIterator<Item = impl fmt::Display>) -> Vec<Content> {
+    values.into_iter().map(text).collect()
+}
+
+/// Raw content, formatted using [`fmt::Display`] and rendered without any
+/// checks.
+///
+/// This is the same as [`Content::raw`]. Prefer [`text()`] or, for HTML that is
+/// actually needed, [`TrustedHtml`].
+///
+/// # Warning
+///
+/// This is an escape hatch for including arbitrary text. Using it incorrectly
+/// may result in security vulnerabilities in the rendered HTML.
+#[cfg_attr(
+    feature = "deprecate-raw",
+    deprecated = "raw content is disallowed by the `deprecate-raw` feature of el"
+)]
+#[allow(deprecated)]
+pub fn raw(value: impl fmt::Display) -> Content {
+    Content::raw(value)
+}
+
 impl From<String> for Content {
     fn from(value: String) -> Self {
         Self::Text(value)
@@ -116,7 +372,8 @@ impl From<Element> for Content {
 /// Errors (e.g. illegal characters or an element of [`ElementKind::Void`]
 /// having children) are deferred until rendering and are not checked during
 /// element construction. See also [`crate::Render`] and [`crate::Error`].
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Element {
     /// The tag name of the element.
     pub name: String,
@@ -132,9 +389,58 @@ pub struct Element {
     ///
     /// This map does not take into account case insensitivity of attributes.
     /// Any attributes contained in the map will appear in the rendered output.
-    pub attributes: BTreeMap<String, String>,
+    ///
+    /// Names of well-known attributes are stored without allocating.
+    pub attributes: BTreeMap<Cow<'static, str>, String>,
     /// The children of the element.
     pub children: Vec<Content>,
+    /// A label identifying the code that created the element, e.g. the name
+    /// of a component.
+    ///
+    /// Labels don't affect the output unless [`crate::RenderOptions::debug_labels`]
+    /// is enabled. See [`Self::labelled`].
+    pub label: Option<String>,
+    /// The source location where the element was constructed.
+    ///
+    /// This is only set if the `track-caller` feature is enabled. It is
+    /// included in render [`crate::Error`]s and ignored when comparing
+    /// elements.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub location: Option<&'static Location<'static>>,
+    /// How whitespace inside the element is treated when rendering with
+    /// [`crate::RenderOptions::pretty`]. See [`NoIndent`] and
+    /// [`PreserveWhitespace`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub whitespace: Whitespace,
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Element {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            name: u.arbitrary()?,
+            kind: u.arbitrary()?,
+            attributes: (u.arbitrary::<BTreeMap<String, String>>()?)
+                .into_iter()
+                .map(|(name, value)| (name.into(), value))
+                .collect(),
+            children: u.arbitrary()?,
+            label: u.arbitrary()?,
+            location: None,
+            whitespace: u.arbitrary()?,
+        })
+    }
+}
+
+impl PartialEq for Element {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.kind == other.kind
+            && self.attributes == other.attributes
+            && self.children == other.children
+            && self.label == other.label
+            && self.whitespace == other.whitespace
+    }
 }
 
 impl Element {
@@ -164,10 +470,16 @@ impl Element {
     /// assert_eq!(script, html::script(()));
     /// assert_eq!(svg, svg::svg(()));
     /// ```
+    #[cfg_attr(feature = "track-caller", track_caller)]
     pub fn new(name: impl ToString, kind: ElementKind) -> Self {
-        let mut name = name.to_string();
+        Self::new_string(name.to_string(), kind)
+    }
+
+    /// Non-generic implementation of [`Self::new`].
+    #[cfg_attr(feature = "track-caller", track_caller)]
+    fn new_string(mut name: String, kind: ElementKind) -> Self {
         if kind != ElementKind::Foreign {
-            name = name.to_ascii_lowercase()
+            name.make_ascii_lowercase();
         }
 
         Self {
@@ -175,9 +487,35 @@ impl Element {
             kind,
             attributes: BTreeMap::new(),
             children: vec![],
+            label: None,
+            location: if cfg!(feature = "track-caller") {
+                Some(Location::caller())
+            } else {
+                None
+            },
+            whitespace: Whitespace::Auto,
         }
     }
 
+    /// Create a new element and add components to it using a callback.
+    ///
+    /// This is the entry point for the tag constructors in [`crate::html`],
+    /// [`crate::svg`] and [`crate::mathml`]. Since it is not generic, the
+    /// constructors only contain the code for adding their components, and the
+    /// code for creating the element exists only once in the binary, no matter
+    /// how many different tags and component types are used.
+    #[cfg_attr(feature = "track-caller", track_caller)]
+    #[inline(never)]
+    pub(crate) fn new_with(
+        name: &'static str,
+        kind: ElementKind,
+        components: &mut dyn FnMut(&mut Self),
+    ) -> Self {
+        let mut element = Self::new_string(name.to_string(), kind);
+        components(&mut element);
+        element
+    }
+
     /// Create a new element of the kind [`ElementKind::Normal`].
     ///
     /// `Element::normal(foo)` is equivalent to calling `Element::new(foo,
@@ -196,10 +534,39 @@ impl Element {
     /// let element = Element::normal("custom");
     /// assert_eq!(element.kind, ElementKind::Normal);
     /// ```
+    #[cfg_attr(feature = "track-caller", track_caller)]
     pub fn normal(name: impl ToString) -> Self {
         Self::new(name, ElementKind::Normal)
     }
 
+    /// Create a new element, choosing its kind based on its tag name and
+    /// namespace.
+    ///
+    /// HTML elements use [`ElementKind::for_html_tag`], SVG and MathML elements
+    /// are always [`ElementKind::Foreign`]. Prefer this over [`Self::new`] or
+    /// [`Self::normal`] when the tag name is not known at compile time (e.g.
+    /// when it comes from a config file), since an incorrect kind may break
+    /// the escaping guarantees of el.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Element, Namespace, html, svg};
+    ///
+    /// assert_eq!(Element::from_tag_name("script", Namespace::Html), html::script(()));
+    /// assert_eq!(Element::from_tag_name("Input", Namespace::Html), html::input(()));
+    /// assert_eq!(Element::from_tag_name("script", Namespace::Svg), svg::script(()));
+    /// ```
+    #[cfg_attr(feature = "track-caller", track_caller)]
+    pub fn from_tag_name(name: impl ToString, namespace: Namespace) -> Self {
+        let name = name.to_string();
+        let kind = match namespace {
+            Namespace::Html => ElementKind::for_html_tag(&name),
+            Namespace::Svg | Namespace::MathMl => ElementKind::Foreign,
+        };
+        Self::new_string(name, kind)
+    }
+
     /// Add components to the element in-place.
     ///
     /// To add multiple components, either call this function repeatedly or use
@@ -246,6 +613,334 @@ impl Element {
         self
     }
 
+    /// Append many children at once.
+    ///
+    /// Unlike [`Self::add`], this takes [`Content`] instead of arbitrary
+    /// [`ElementComponent`]s, so the children are appended directly. Together
+    /// with [`Self::reserve_children`], this avoids most overhead when adding
+    /// large numbers of children, e.g. the rows of a big table.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Render, html::*};
+    ///
+    /// let rows = (0..10_000).map(|i| tr(td(i.to_string())));
+    /// let mut body = tbody(());
+    /// body.reserve_children(rows.len());
+    /// body.extend_children(rows);
+    /// assert_eq!(body.children.len(), 10_000);
+    /// ```
+    pub fn extend_children(&mut self, children: impl IntoIterator<Item = impl Into<Content>>) {
+        self.children.extend(children.into_iter().map(Into::into));
+    }
+
+    /// Reserve capacity for at least `additional` more children, see
+    /// [`Self::extend_children`].
+    pub fn reserve_children(&mut self, additional: usize) {
+        self.children.reserve(additional);
+    }
+
+    /// Split the element into its name, kind, attributes and children.
+    ///
+    /// This is useful for transforms that rebuild elements, since the parts
+    /// can be moved independently. The [`Self::label`], [`Self::location`]
+    /// and [`Self::whitespace`] are dropped, so markers like
+    /// [`PreserveWhitespace`] must be added again after reassembling the
+    /// element. See [`Self::from_parts`] for the inverse.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Content, Element, html::*};
+    ///
+    /// // Replace every <b> with a <strong>
+    /// fn strong_instead_of_b(element: Element) -> Element {
+    ///     let (name, kind, attributes, children) = element.into_parts();
+    ///     let name = if name == "b" { "strong".to_string() } else { name };
+    ///     let children = (children.into_iter())
+    ///         .map(|child| match child {
+    ///             Content::Element(e) => Content::Element(strong_instead_of_b(e)),
+    ///             child => child,
+    ///         })
+    ///         .collect();
+    ///     Element::from_parts(name, kind, attributes, children)
+    /// }
+    ///
+    /// let element = p(("A ", b("bold"), " move"));
+    /// assert_eq!(strong_instead_of_b(element), p(("A ", strong("bold"), " move")));
+    /// ```
+    pub fn into_parts(
+        self,
+    ) -> (
+        String,
+        ElementKind,
+        BTreeMap<Cow<'static, str>, String>,
+        Vec<Content>,
+    ) {
+        (self.name, self.kind, self.attributes, self.children)
+    }
+
+    /// Assemble an element from its name, kind, attributes and children, e.g.
+    /// ones obtained from [`Self::into_parts`].
+    ///
+    /// Unlike [`Self::new`], the name is used exactly as given, without
+    /// converting it to lowercase. The element has no [`Self::label`], and its
+    /// [`Self::whitespace`] is [`Whitespace::Auto`].
+    ///
+    /// # Warning
+    ///
+    /// The element kind affects the correctness of the rendered output.
+    /// Choosing an incorrect kind may result in security vulnerabilities in the
+    /// rendered HTML. See [`ElementKind`] for more details.
+    #[cfg_attr(feature = "track-caller", track_caller)]
+    pub fn from_parts(
+        name: String,
+        kind: ElementKind,
+        attributes: BTreeMap<Cow<'static, str>, String>,
+        children: Vec<Content>,
+    ) -> Self {
+        Self {
+            name,
+            kind,
+            attributes,
+            children,
+            label: None,
+            location: if cfg!(feature = "track-caller") {
+                Some(Location::caller())
+            } else {
+                None
+            },
+            whitespace: Whitespace::Auto,
+        }
+    }
+
+    /// Label the element, e.g. with the name of the component that created it.
+    ///
+    /// When rendering with [`crate::RenderOptions::debug_labels`], labelled
+    /// elements are surrounded by comments containing the label. This makes it
+    /// easier to map the rendered output back to the code that produced it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Render, RenderOptions, html::*};
+    ///
+    /// let card = div("Alice").labelled("UserCard");
+    ///
+    /// let options = RenderOptions {
+    ///     debug_labels: true,
+    ///     ..RenderOptions::default()
+    /// };
+    /// let mut result = String::new();
+    /// card.render_with(&mut result, &options).unwrap();
+    /// assert_eq!(result, "<!-- UserCard --><div>Alice</div><!-- /UserCard -->");
+    ///
+    /// // Without the option, labels have no effect
+    /// assert_eq!(card.render_to_string().unwrap(), "<div>Alice</div>");
+    /// ```
+    pub fn labelled(mut self, label: impl ToString) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Attach a [declarative shadow root][dsd] to the element.
+    ///
+    /// The shadow root is rendered as a `<template>` with a `shadowrootmode`
+    /// attribute. It is inserted as the first child because browsers only
+    /// attach the shadow root while parsing if it comes before any other
+    /// children. Additional attributes like
+    /// [`crate::html::attr::shadowrootdelegatesfocus`] can be passed along
+    /// with the contents of the shadow root.
+    ///
+    /// [dsd]: https://developer.mozilla.org/en-US/docs/Web/HTML/Element/template#declarative_shadow_dom
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Element, Render, html::*};
+    ///
+    /// let card = Element::normal("user-card")
+    ///     .with(span((attr::slot("name"), "Alice")))
+    ///     .with_shadow_root(attr::Shadowrootmode::Open, div(slot(attr::name("name"))));
+    /// assert_eq!(
+    ///     card.render_to_string().unwrap(),
+    ///     concat!(
+    ///         r#"<user-card><template shadowrootmode="open">"#,
+    ///         r#"<div><slot name="name"></slot></div>"#,
+    ///         r#"</template><span slot="name">Alice</span></user-card>"#,
+    ///     ),
+    /// );
+    /// ```
+    pub fn with_shadow_root(
+        mut self,
+        mode: crate::html::attr::Shadowrootmode,
+        c: impl ElementComponent,
+    ) -> Self {
+        let root = crate::html::template((mode, c));
+        self.children.insert(0, root.into());
+        self
+    }
+
+    /// The value of an attribute, if the element has it.
+    pub fn get_attr(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(|v| &v[..])
+    }
+
+    /// Whether the element has a (boolean) attribute, regardless of its
+    /// value.
+    pub fn get_flag(&self, name: &str) -> bool {
+        self.attributes.contains_key(name)
+    }
+
+    /// The value of an attribute, ignoring ASCII case in its name.
+    ///
+    /// Attribute names of non-foreign elements are lowercased when added
+    /// through an [`Attr`], but [`Self::attributes`] can also be modified
+    /// directly. Prefer an exact match if there are multiple candidates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::html::*;
+    ///
+    /// let mut a = a(());
+    /// a.attributes.insert("HREF".into(), "/".to_string());
+    /// assert_eq!(a.get_attr("href"), None);
+    /// assert_eq!(a.attr_ci("href"), Some("/"));
+    /// ```
+    pub fn attr_ci(&self, name: &str) -> Option<&str> {
+        if let Some(value) = self.attributes.get(name) {
+            return Some(value);
+        }
+        (self.attributes.iter())
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| &v[..])
+    }
+
+    /// A mutable reference to the value of an attribute, ignoring ASCII case
+    /// in its name, see [`Self::attr_ci`].
+    pub fn attr_ci_mut(&mut self, name: &str) -> Option<&mut String> {
+        let key = self.attr_ci_key(name)?;
+        self.attributes.get_mut(&key)
+    }
+
+    /// Whether the element has an attribute, ignoring ASCII case in its name.
+    pub fn has_attr_ci(&self, name: &str) -> bool {
+        self.attr_ci(name).is_some()
+    }
+
+    /// The first element in this subtree (including the element itself) whose
+    /// `id` is exactly the given value, in document order.
+    ///
+    /// The contents of [`Content::Trailer`]s are searched too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::html::*;
+    ///
+    /// let page = main((h1("Shop"), section((attr::id("cart"), p("Empty")))));
+    /// assert_eq!(page.find_by_id("cart").unwrap().name, "section");
+    /// assert!(page.find_by_id("Cart").is_none());
+    /// ```
+    pub fn find_by_id(&self, id: &str) -> Option<&Self> {
+        std::iter::once(self)
+            .chain(self.descendants())
+            .find(|element| element.attr_ci("id") == Some(id))
+    }
+
+    /// Remove an attribute, ignoring ASCII case in its name, and return its
+    /// value.
+    ///
+    /// Unlike [`Self::attr_ci`], this removes all attributes whose names only
+    /// differ in case, returning the value of the exact match if there is one.
+    pub fn remove_attr_ci(&mut self, name: &str) -> Option<String> {
+        let exact = self.attributes.remove(name);
+        let mut other = None;
+        while let Some(key) = self.attr_ci_key(name) {
+            other = other.or(self.attributes.remove(&key));
+        }
+        exact.or(other)
+    }
+
+    fn attr_ci_key(&self, name: &str) -> Option<Cow<'static, str>> {
+        (self.attributes.keys())
+            .find(|n| n.eq_ignore_ascii_case(name))
+            .cloned()
+    }
+
+    /// The value of the `id` attribute, if the element has it.
+    pub fn get_id(&self) -> Option<&str> {
+        self.get_attr("id")
+    }
+
+    /// The tokens of the `class` attribute, see [`Self::get_tokens`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::html::*;
+    ///
+    /// let p = p(attr::class("  lead   muted "));
+    /// assert_eq!(p.get_classes().collect::<Vec<_>>(), ["lead", "muted"]);
+    /// ```
+    pub fn get_classes(&self) -> impl Iterator<Item = &str> {
+        self.get_tokens("class", " ")
+    }
+
+    /// The tokens of an attribute, split at the separator like in a
+    /// [`TokenList`].
+    ///
+    /// Yields nothing if the element doesn't have the attribute.
+    pub fn get_tokens<'a>(&'a self, name: &str, separator: &str) -> impl Iterator<Item = &'a str> {
+        split_tokens(self.get_attr(name).unwrap_or(""), separator)
+    }
+
+    /// The value of an attribute parsed as an integer, e.g. `colspan`.
+    ///
+    /// Parsing follows the [HTML standard][spec]: leading whitespace is
+    /// skipped, and anything after the digits is ignored. Returns [`None`] if
+    /// the element doesn't have the attribute or the value doesn't start with
+    /// an integer.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#rules-for-parsing-integers
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::html::*;
+    ///
+    /// let td = td((attr::colspan(" 3px"), attr::rowspan("wide")));
+    /// assert_eq!(td.get_int("colspan"), Some(3));
+    /// assert_eq!(td.get_int("rowspan"), None);
+    /// assert_eq!(td.get_int("tabindex"), None);
+    /// ```
+    pub fn get_int(&self, name: &str) -> Option<i64> {
+        let value = self.get_attr(name)?.trim_start_matches(is_html_whitespace);
+        let (negative, value) = match value.as_bytes().first() {
+            Some(b'-') => (true, &value[1..]),
+            Some(b'+') => (false, &value[1..]),
+            _ => (false, value),
+        };
+        let end = value
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(value.len());
+        let number = value[..end].parse::<i64>().ok()?;
+        Some(if negative { -number } else { number })
+    }
+
+    /// The value of an attribute parsed as a floating-point number, e.g.
+    /// `step`.
+    ///
+    /// Leading and trailing whitespace is ignored. Returns [`None`] if the
+    /// element doesn't have the attribute, the value is not a number, or it is
+    /// not finite.
+    pub fn get_float(&self, name: &str) -> Option<f64> {
+        let value = self.get_attr(name)?.trim_matches(is_html_whitespace);
+        value.parse::<f64>().ok().filter(|n| n.is_finite())
+    }
+
     /// Convert this element into a [`Document`].
     ///
     /// This function is equivalent to calling `self.into()` but may be more
@@ -280,6 +975,65 @@ pub trait ElementComponent {
     fn add_to_element(self, element: &mut Element);
 }
 
+/// Derive [`ElementComponent`] for an enum, e.g. of page states.
+///
+/// This derive macro requires the `derive` feature flag.
+///
+/// Each variant is rendered by the function given in its
+/// `#[el(with = "...")]` attribute. The function receives the fields of the
+/// variant by value (in order) and returns an [`ElementComponent`]. Variants
+/// without the attribute add their fields directly, and unit variants without
+/// it add nothing.
+///
+/// # Example
+///
+/// ```
+/// use el::{Element, IntoElement, Render, html::*};
+///
+/// struct User {
+///     name: String,
+/// }
+///
+/// #[derive(IntoElement)]
+/// enum State {
+///     #[el(with = "loading")]
+///     Loading,
+///     #[el(with = "error")]
+///     Error(String),
+///     #[el(with = "ready")]
+///     Ready { user: User, unread: u32 },
+///     Custom(Element),
+/// }
+///
+/// fn loading() -> Element {
+///     p((attr::class("spinner"), "Loading..."))
+/// }
+///
+/// fn error(message: String) -> Element {
+///     p((attr::class("error"), message))
+/// }
+///
+/// fn ready(user: User, unread: u32) -> Element {
+///     p(format!("Hi {}, you have {unread} new messages.", user.name))
+/// }
+///
+/// let state = State::Error("Not found".to_string());
+/// assert_eq!(
+///     main(state).render_to_string().unwrap(),
+///     r#"<main><p class="error">Not found</p></main>"#,
+/// );
+///
+/// let state = State::Ready { user: User { name: "Ada".to_string() }, unread: 2 };
+/// assert_eq!(
+///     main(state).render_to_string().unwrap(),
+///     "<main><p>Hi Ada, you have 2 new messages.</p></main>",
+/// );
+///
+/// assert_eq!(main(State::Custom(hr(()))), main(hr(())));
+/// ```
+#[cfg(feature = "derive")]
+pub use el_derive::IntoElement;
+
 /// An element attribute, used during [`Element`] construction.
 ///
 /// # Example
@@ -289,33 +1043,165 @@ pub trait ElementComponent {
 /// let p = p(Attr::class("foo"));
 /// assert_eq!(p.attributes["class"], "foo");
 /// ```
+#[derive(Debug, Clone)]
 pub struct Attr {
-    name: String,
-    value: String,
-    append_by: Option<String>,
+    name: Cow<'static, str>,
+    value: Option<String>,
+    mode: Mode,
+}
+
+/// What to do when an [`Attr`] is added to an element that already has an
+/// attribute of the same name.
+#[derive(Debug, Clone)]
+enum Mode {
+    Set,
+    Append(Cow<'static, str>),
+    Tokens(Cow<'static, str>),
 }
 
 impl Attr {
+    pub(crate) fn set_static(name: &'static str, value: impl ToString) -> Self {
+        Self {
+            name: Cow::Borrowed(name),
+            value: Some(value.to_string()),
+            mode: Mode::Set,
+        }
+    }
+
+    pub(crate) fn set_static_value(name: &'static str, value: impl IntoAttrValue) -> Self {
+        Self {
+            name: Cow::Borrowed(name),
+            value: value.into_attr_value(),
+            mode: Mode::Set,
+        }
+    }
+
+    pub(crate) fn append_static(
+        name: &'static str,
+        value: impl ToString,
+        separator: &'static str,
+    ) -> Self {
+        Self {
+            name: Cow::Borrowed(name),
+            value: Some(value.to_string()),
+            mode: Mode::Append(Cow::Borrowed(separator)),
+        }
+    }
+
+    pub(crate) fn tokens_static(
+        name: &'static str,
+        value: impl ToString,
+        separator: &'static str,
+    ) -> Self {
+        Self {
+            name: Cow::Borrowed(name),
+            value: Some(value.to_string()),
+            mode: Mode::Tokens(Cow::Borrowed(separator)),
+        }
+    }
+
     /// Create or replace an attribute.
     ///
     /// When this attribute is added to an [`Element`] through
     /// [`ElementComponent::add_to_element`] and an attribute of the same name
     /// already exists, it replaces that attribute's value.
-    pub fn set(name: impl ToString, value: impl ToString) -> Self {
+    ///
+    /// The value is converted using [`IntoAttrValue`]. If it converts to no
+    /// value at all (e.g. [`None`]), the attribute is removed from the element
+    /// instead. For [boolean attributes][spec] like `required` or `hidden`,
+    /// `true` and `false` control whether the attribute is present. For all
+    /// other attributes, they are written as `"true"` and `"false"`, like
+    /// enumerated attributes such as `aria-expanded` expect. Use
+    /// [`Self::flag`] for boolean attributes of custom elements, and
+    /// [`Self::display`] for other values implementing [`fmt::Display`].
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#boolean-attributes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Attr, Render, html::*};
+    ///
+    /// let title: Option<&str> = None;
+    /// let input = input((
+    ///     Attr::set("value", 42),
+    ///     Attr::set("required", true),
+    ///     Attr::set("disabled", false),
+    ///     Attr::set("aria-expanded", false),
+    ///     Attr::set("title", title),
+    /// ));
+    /// assert_eq!(
+    ///     input.render_to_string().unwrap(),
+    ///     r#"<input aria-expanded="false" required value="42">"#,
+    /// );
+    /// ```
+    pub fn set(name: impl ToString, value: impl IntoAttrValue) -> Self {
+        let name = intern(name.to_string());
+        let value = if is_boolean_attribute(&name) {
+            value.into_boolean_attr_value()
+        } else {
+            value.into_attr_value()
+        };
         Self {
-            name: name.to_string(),
-            value: value.to_string(),
-            append_by: None,
+            name,
+            value,
+            mode: Mode::Set,
         }
     }
 
+    /// Add or remove a boolean attribute, depending on whether `present` is
+    /// true.
+    ///
+    /// [`Self::set`] already does this for the boolean attributes defined by
+    /// HTML. This is for other attributes, e.g. of custom elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Attr, Element, Render};
+    ///
+    /// let dialog = Element::normal("my-dialog").with((
+    ///     Attr::flag("dismissible", true),
+    ///     Attr::flag("modal", false),
+    /// ));
+    /// assert_eq!(
+    ///     dialog.render_to_string().unwrap(),
+    ///     "<my-dialog dismissible></my-dialog>",
+    /// );
+    /// ```
+    pub fn flag(name: impl ToString, present: bool) -> Self {
+        Self {
+            name: intern(name.to_string()),
+            value: present.into_boolean_attr_value(),
+            mode: Mode::Set,
+        }
+    }
+
+    /// Create or replace an attribute whose value is formatted using
+    /// [`fmt::Display`].
+    ///
+    /// Like [`Self::set`], but for values that don't implement
+    /// [`IntoAttrValue`], e.g. dates or custom types.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Attr, Render, html::*};
+    ///
+    /// let input = input(Attr::display("value", 1.5));
+    /// assert_eq!(input.render_to_string().unwrap(), r#"<input value="1.5">"#);
+    /// ```
+    pub fn display(name: impl ToString, value: impl fmt::Display) -> Self {
+        Self::set(name, value.to_string())
+    }
+
     /// Create or replace an attribute.
     ///
     /// When this attribute is added to an [`Element`] through
     /// [`ElementComponent::add_to_element`] and an attribute of the same name
     /// already exists, it replaces that attribute's value.
     #[deprecated = "use `Attr::set` instead"]
-    pub fn new(name: impl ToString, value: impl ToString) -> Self {
+    pub fn new(name: impl ToString, value: impl IntoAttrValue) -> Self {
         Self::set(name, value)
     }
 
@@ -327,9 +1213,32 @@ impl Attr {
     /// attribute's value.
     pub fn append(name: impl ToString, value: impl ToString, separator: impl ToString) -> Self {
         Self {
-            name: name.to_string(),
-            value: value.to_string(),
-            append_by: Some(separator.to_string()),
+            name: intern(name.to_string()),
+            value: Some(value.to_string()),
+            mode: Mode::Append(separator.to_string().into()),
+        }
+    }
+
+    /// Create or add to a token list attribute.
+    ///
+    /// The value and the attribute's existing value are split into tokens at
+    /// the separator, see [`TokenList`]. When this attribute is added to an
+    /// [`Element`] through [`ElementComponent::add_to_element`] and an
+    /// attribute of the same name already exists, only tokens not yet present
+    /// are appended to that attribute's value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Attr, html::*};
+    /// let p = p((attr::class("a b"), Attr::tokens("class", "b c", " ")));
+    /// assert_eq!(p.attributes["class"], "a b c");
+    /// ```
+    pub fn tokens(name: impl ToString, value: impl ToString, separator: impl ToString) -> Self {
+        Self {
+            name: intern(name.to_string()),
+            value: Some(value.to_string()),
+            mode: Mode::Tokens(separator.to_string().into()),
         }
     }
 
@@ -348,7 +1257,7 @@ impl Attr {
     /// `Attr::id(id)` is equivalent to `Attr::new("id", id)`.
     #[deprecated = "use `html::attr::id` instead"]
     pub fn id(id: impl ToString) -> Self {
-        Self::set("id", id)
+        Self::set_static("id", id)
     }
 
     /// Create (or append to) a `class` attribute.
@@ -357,7 +1266,7 @@ impl Attr {
     /// `Attr::append("class", class, " ")`.
     #[deprecated = "use `html::attr::class` instead"]
     pub fn class(class: impl ToString) -> Self {
-        Self::append("class", class, " ")
+        Self::append_static("class", class, " ")
     }
 
     /// Create (or append to) a `style` attribute.
@@ -366,7 +1275,7 @@ impl Attr {
     /// `Attr::append("style", style, ";")`.
     #[deprecated = "use `html::attr::style` instead"]
     pub fn style(style: impl ToString) -> Self {
-        Self::append("style", style, ";")
+        Self::append_static("style", style, ";")
     }
 
     /// Create (or replace) a new [`data-*` attribute][mdn].
@@ -377,34 +1286,155 @@ impl Attr {
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/data-*
     #[deprecated = "use `html::attr::data_x` instead"]
     pub fn data(name: impl ToString, value: impl ToString) -> Self {
-        Self::set(format!("data-{}", name.to_string()), value)
+        Self::set(format!("data-{}", name.to_string()), value.to_string())
     }
 }
 
+/// A value that can be used for an attribute, see [`Attr::set`].
+///
+/// Strings and characters are used verbatim and numbers are formatted with
+/// [`ToString`]. Booleans are written as `"true"` and `"false"`, except for
+/// boolean attributes, where `true` produces an empty value (the attribute is
+/// present) and `false` no value at all (the attribute is absent). [`None`]
+/// always produces no value.
+pub trait IntoAttrValue {
+    /// Convert into an attribute value, or [`None`] if the attribute should be
+    /// absent.
+    fn into_attr_value(self) -> Option<String>;
+
+    /// Convert into the value of a boolean attribute like `hidden`.
+    ///
+    /// This is implemented by default and uses [`Self::into_attr_value`].
+    fn into_boolean_attr_value(self) -> Option<String>
+    where
+        Self: Sized,
+    {
+        self.into_attr_value()
+    }
+}
+
+impl IntoAttrValue for String {
+    fn into_attr_value(self) -> Option<String> {
+        Some(self)
+    }
+}
+
+impl IntoAttrValue for &str {
+    fn into_attr_value(self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl IntoAttrValue for &String {
+    fn into_attr_value(self) -> Option<String> {
+        Some(self.clone())
+    }
+}
+
+impl IntoAttrValue for Cow<'_, str> {
+    fn into_attr_value(self) -> Option<String> {
+        Some(self.into_owned())
+    }
+}
+
+impl IntoAttrValue for char {
+    fn into_attr_value(self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl IntoAttrValue for bool {
+    fn into_attr_value(self) -> Option<String> {
+        Some(self.to_string())
+    }
+
+    fn into_boolean_attr_value(self) -> Option<String> {
+        self.then(String::new)
+    }
+}
+
+impl<T: IntoAttrValue> IntoAttrValue for Option<T> {
+    fn into_attr_value(self) -> Option<String> {
+        self.and_then(T::into_attr_value)
+    }
+
+    fn into_boolean_attr_value(self) -> Option<String> {
+        self.and_then(T::into_boolean_attr_value)
+    }
+}
+
+macro_rules! into_attr_value_via_to_string {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl IntoAttrValue for $t {
+                fn into_attr_value(self) -> Option<String> {
+                    Some(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+into_attr_value_via_to_string!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+
 impl ElementComponent for Attr {
     fn add_to_element(mut self, element: &mut Element) {
-        if element.kind != ElementKind::Foreign {
-            self.name = self.name.to_ascii_lowercase();
+        if element.kind != ElementKind::Foreign && self.name.bytes().any(|b| b.is_ascii_uppercase())
+        {
+            self.name = intern(self.name.to_ascii_lowercase());
         }
+        let Some(new) = self.value else {
+            if let Mode::Set = self.mode {
+                element.attributes.remove(&self.name);
+            }
+            return;
+        };
         match element.attributes.entry(self.name) {
             Entry::Vacant(entry) => {
-                entry.insert(self.value);
+                entry.insert(new);
             }
-            Entry::Occupied(mut entry) => match self.append_by {
-                None => {
-                    entry.insert(self.value);
+            Entry::Occupied(mut entry) => match self.mode {
+                Mode::Set => {
+                    entry.insert(new);
                 }
-                Some(sep) => {
+                Mode::Append(sep) => {
                     let value = entry.get_mut();
                     value.push_str(&sep);
-                    value.push_str(&self.value);
+                    value.push_str(&new);
+                }
+                Mode::Tokens(sep) => {
+                    let value = entry.get_mut();
+                    let mut list = TokenList::parse("", sep.clone(), value.as_str());
+                    for token in split_tokens(&new, &sep) {
+                        if !list.contains(token) {
+                            if !value.is_empty() {
+                                value.push_str(&sep);
+                            }
+                            value.push_str(token);
+                            list.insert(token);
+                        }
+                    }
                 }
             },
         }
     }
 }
 
+/// Sets each attribute, in the order of their names.
+///
+/// The iteration order of a [`HashMap`] is random, so the attributes are
+/// sorted first. Otherwise, names that only differ in case (which are
+/// converted to lowercase for most elements) would overwrite each other in a
+/// different order every time, making the output nondeterministic.
 impl ElementComponent for HashMap<String, String> {
+    fn add_to_element(self, element: &mut Element) {
+        BTreeMap::from_iter(self).add_to_element(element);
+    }
+}
+
+impl ElementComponent for BTreeMap<String, String> {
     fn add_to_element(self, element: &mut Element) {
         for (name, value) in self {
             Attr::set(name, value).add_to_element(element);
@@ -412,14 +1442,164 @@ impl ElementComponent for HashMap<String, String> {
     }
 }
 
-impl ElementComponent for BTreeMap<String, String> {
+/// Attributes from a list of name-value pairs, added in order.
+///
+/// Unlike a [`HashMap`], a list preserves the order of its entries, so
+/// duplicate names are handled predictably: `class` values are merged like
+/// [`html::attr::class`](crate::html::attr::class), `style` values are appended
+/// like [`html::attr::style`](crate::html::attr::style), and for all other
+/// names, the last value wins like with [`Attr::set`].
+///
+/// A `Vec<(K, V)>` can't be used as a component directly, since it is already
+/// a list of tuple components. Lists of string pairs can also be added as
+/// slices, e.g. `&[("name", "value")][..]`.
+///
+/// # Example
+///
+/// ```
+/// use el::{AttrList, Render, html::*};
+///
+/// let attrs = vec![
+///     ("class", "card"),
+///     ("title", "first"),
+///     ("class", "wide card"),
+///     ("title", "second"),
+///     ("style", "color: red"),
+///     ("style", "margin: 0"),
+/// ];
+/// assert_eq!(
+///     div(AttrList(attrs)).render_to_string().unwrap(),
+///     r#"<div class="card wide" style="color: red; margin: 0" title="second"></div>"#,
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AttrList<K, V>(pub Vec<(K, V)>);
+
+impl<K, V> From<Vec<(K, V)>> for AttrList<K, V> {
+    fn from(value: Vec<(K, V)>) -> Self {
+        Self(value)
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for AttrList<K, V> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<K: ToString, V: IntoAttrValue> ElementComponent for AttrList<K, V> {
+    fn add_to_element(self, element: &mut Element) {
+        for (name, value) in self.0 {
+            list_attr(name.to_string(), value).add_to_element(element);
+        }
+    }
+}
+
+/// Adds attributes in order, see [`AttrList`].
+impl ElementComponent for &[(&str, &str)] {
     fn add_to_element(self, element: &mut Element) {
         for (name, value) in self {
-            Attr::set(name, value).add_to_element(element);
+            list_attr(name.to_string(), *value).add_to_element(element);
         }
     }
 }
 
+fn list_attr(name: String, value: impl IntoAttrValue) -> Attr {
+    let value = if is_boolean_attribute(&name) {
+        value.into_boolean_attr_value()
+    } else {
+        value.into_attr_value()
+    };
+    let Some(value) = value else {
+        return Attr::set(name, None::<String>);
+    };
+    if name.eq_ignore_ascii_case("class") {
+        Attr::tokens(name, value, " ")
+    } else if name.eq_ignore_ascii_case("style") {
+        Attr::append(name, value, "; ")
+    } else {
+        Attr::set(name, value)
+    }
+}
+
+/// Whether an attribute is a [boolean attribute][spec] of an HTML element,
+/// see [`Attr::set`].
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/indices.html#attributes-3
+fn is_boolean_attribute(name: &str) -> bool {
+    const BOOLEAN_ATTRIBUTES: &[&str] = &[
+        "allowfullscreen",
+        "alpha",
+        "async",
+        "autofocus",
+        "autoplay",
+        "checked",
+        "controls",
+        "default",
+        "defer",
+        "disabled",
+        "formnovalidate",
+        "hidden",
+        "inert",
+        "ismap",
+        "itemscope",
+        "loop",
+        "multiple",
+        "muted",
+        "nomodule",
+        "novalidate",
+        "open",
+        "playsinline",
+        "readonly",
+        "required",
+        "reversed",
+        "selected",
+        "shadowrootclonable",
+        "shadowrootdelegatesfocus",
+        "shadowrootserializable",
+    ];
+    BOOLEAN_ATTRIBUTES
+        .iter()
+        .any(|n| n.eq_ignore_ascii_case(name))
+}
+
+/// Whether a character is [ASCII whitespace][spec] as defined by the HTML
+/// standard.
+///
+/// [spec]: https://infra.spec.whatwg.org/#ascii-whitespace
+fn is_html_whitespace(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\x0C' | '\r' | ' ')
+}
+
+/// Names of well-known attributes, sorted.
+#[rustfmt::skip]
+pub(crate) const KNOWN_ATTRIBUTE_NAMES: &[&str] = &[
+    "accept", "accesskey", "action", "allow", "alt", "as", "async", "autocapitalize",
+    "autocomplete", "autofocus", "autoplay", "capture", "charset", "checked", "cite", "class",
+    "cols", "colspan", "content", "contenteditable", "controls", "coords", "crossorigin", "data",
+    "datetime", "decoding", "default", "defer", "dir", "dirname", "disabled", "download",
+    "draggable", "enctype", "enterkeyhint", "exportparts", "for", "form", "formaction",
+    "formenctype", "formmethod", "formnovalidate", "formtarget", "headers", "height", "hidden",
+    "high", "href", "hreflang", "http-equiv", "id", "inert", "inputmode", "integrity", "is",
+    "ismap", "itemid", "itemprop", "itemref", "itemscope", "itemtype", "kind", "lang", "list",
+    "loading", "loop", "low", "max", "maxlength", "media", "method", "min", "minlength", "multiple", "muted",
+    "name", "nonce", "novalidate", "open", "optimum", "part", "pattern", "ping", "placeholder",
+    "playsinline", "popover", "poster", "preload", "readonly", "referrerpolicy", "rel", "required",
+    "reversed", "role", "rows", "rowspan", "sandbox", "scope", "selected", "shadowrootclonable",
+    "shadowrootdelegatesfocus", "shadowrootmode", "shadowrootserializable", "shape", "size",
+    "sizes", "slot", "span", "spellcheck", "src", "srcdoc", "srclang", "srcset", "start", "step",
+    "style", "tabindex", "target", "title", "translate", "type", "usemap", "value", "width", "wrap",
+    "writingsuggestions",
+];
+
+/// Store well-known attribute names without allocating.
+fn intern(name: String) -> Cow<'static, str> {
+    match KNOWN_ATTRIBUTE_NAMES.binary_search(&&*name) {
+        Ok(i) => Cow::Borrowed(KNOWN_ATTRIBUTE_NAMES[i]),
+        Err(_) => Cow::Owned(name),
+    }
+}
+
 // Children
 
 impl<T: Into<Content>> ElementComponent for T {
@@ -463,6 +1643,79 @@ impl<const L: usize, T: ElementComponent> ElementComponent for [T; L] {
     }
 }
 
+/// How the pretty-printer treats whitespace inside an element, see
+/// [`crate::RenderOptions::pretty`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Whitespace {
+    /// Put each child on its own indented line if that doesn't change the
+    /// meaning of the document, i.e. if the element only contains
+    /// block-level elements and whitespace.
+    #[default]
+    Auto,
+    /// Like [`Self::Auto`], but don't indent the children relative to the
+    /// element.
+    NoIndent,
+    /// Render the element and all its descendants exactly as they are,
+    /// without adding or removing any whitespace.
+    Preserve,
+}
+
+/// Don't indent the children of an element when pretty-printing.
+///
+/// This is useful for wrappers like `<html>` whose children would otherwise
+/// needlessly be indented by one level.
+///
+/// # Example
+///
+/// ```
+/// use el::{html::*, NoIndent, Render, RenderOptions};
+///
+/// let page = html((NoIndent, head(title("Hi")), body(p("Hello"))));
+/// let options = RenderOptions::new().pretty(true);
+/// assert_eq!(
+///     page.render_to_string_with(&options).unwrap(),
+///     "<html>\n<head>\n  <title>Hi</title>\n</head>\n<body>\n  <p>Hello</p>\n</body>\n</html>",
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NoIndent;
+
+impl ElementComponent for NoIndent {
+    fn add_to_element(self, element: &mut Element) {
+        element.whitespace = Whitespace::NoIndent;
+    }
+}
+
+/// Never reformat an element or its descendants when pretty-printing.
+///
+/// Elements whose whitespace is obviously significant (e.g. `<pre>`) and
+/// elements with mixed content are already left alone. This marker is for
+/// everything else, e.g. elements styled with `white-space: pre` or inline
+/// layouts where the space between two blocks matters.
+///
+/// # Example
+///
+/// ```
+/// use el::{html::*, PreserveWhitespace, Render, RenderOptions};
+///
+/// let list = ul((PreserveWhitespace, li("a"), li("b")));
+/// let options = RenderOptions::new().pretty(true);
+/// assert_eq!(
+///     list.render_to_string_with(&options).unwrap(),
+///     "<ul><li>a</li><li>b</li></ul>",
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PreserveWhitespace;
+
+impl ElementComponent for PreserveWhitespace {
+    fn add_to_element(self, element: &mut Element) {
+        element.whitespace = Whitespace::Preserve;
+    }
+}
+
 // Varargs emulation with tuples
 
 impl ElementComponent for () {
@@ -509,6 +1762,12 @@ element_component_tuple!(C1, C2, C3, C4, C5, C6, C7, C8, C9, C10, C11, C12, C13,
 /// A `Document(el)` is basically the same as `[Content::doctype(), el.into()]`
 /// for the purposes of the [`Render`][crate::Render] trait.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Document(pub Element);
 
 impl From<Element> for Document {
@@ -516,3 +1775,211 @@ impl From<Element> for Document {
         Self(value)
     }
 }
+
+impl Document {
+    /// Create a minimal page with the usual boilerplate in its `<head>`.
+    ///
+    /// The head contains a UTF-8 charset declaration, the default viewport
+    /// and the title.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Document, Render, html::*};
+    ///
+    /// assert_eq!(
+    ///     Document::minimal("Hi", p("Hello world")).render_to_string().unwrap(),
+    ///     concat!(
+    ///         r#"<!DOCTYPE html><html><head><meta charset="utf-8">"#,
+    ///         r#"<meta content="width=device-width, initial-scale=1" name="viewport">"#,
+    ///         "<title>Hi</title></head><body><p>Hello world</p></body></html>",
+    ///     ),
+    /// );
+    /// ```
+    pub fn minimal(title: impl ToString, body: impl ElementComponent) -> Self {
+        use crate::html::{self, helpers};
+
+        Self(html::html((
+            html::head((
+                helpers::meta_charset_utf8(),
+                helpers::meta_viewport_default(),
+                html::title(title.to_string()),
+            )),
+            html::body(body),
+        )))
+    }
+
+    /// Create a page that immediately redirects to a URL.
+    ///
+    /// The page uses a `<meta http-equiv="refresh">`, declares the URL as
+    /// canonical and contains a link to it for clients that don't follow the
+    /// refresh. Prefer an HTTP redirect where possible.
+    ///
+    /// Returns [`None`] under the same conditions as
+    /// [`meta_refresh`](crate::html::helpers::meta_refresh), i.e. if the URL is
+    /// empty or uses a scheme that can execute code.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Document, Render};
+    ///
+    /// let page = Document::redirect("/login").unwrap();
+    /// let html = page.render_to_string().unwrap();
+    /// assert!(html.contains(r#"<meta content="0; url='/login'" http-equiv="refresh">"#));
+    /// assert!(html.contains(r#"<link href="/login" rel="canonical">"#));
+    /// assert!(html.contains(r#"<a href="/login">/login</a>"#));
+    ///
+    /// assert!(Document::redirect("javascript:alert(1)").is_none());
+    /// ```
+    pub fn redirect(url: &str) -> Option<Self> {
+        use crate::html::{self, attr, helpers};
+
+        let refresh = helpers::meta_refresh(0, Some(url))?;
+        Some(Self(html::html((
+            html::head((
+                helpers::meta_charset_utf8(),
+                refresh,
+                html::link((attr::Rel::Canonical, attr::href(url))),
+                html::title("Redirecting"),
+            )),
+            html::body(html::p((
+                "Redirecting to ",
+                html::a((attr::href(url), url)),
+                ".",
+            ))),
+        ))))
+    }
+
+    /// Render only the element with the given id and its descendants, see
+    /// [`Element::find_by_id`].
+    ///
+    /// This is useful for endpoints that re-render a single part of a page in
+    /// response to a partial update, without having to construct that part
+    /// separately. Unlike when rendering the whole document, no doctype is
+    /// included and [`Content::Trailer`]s inside the element are rendered in
+    /// place.
+    ///
+    /// Returns [`None`] if there is no element with that id.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Document, html::*};
+    ///
+    /// let page = Document::minimal("Shop", (
+    ///     h1("Shop"),
+    ///     section((attr::id("cart"), p("2 items"))),
+    /// ));
+    /// assert_eq!(
+    ///     page.render_fragment("cart").unwrap().unwrap(),
+    ///     r#"<section id="cart"><p>2 items</p></section>"#,
+    /// );
+    /// assert!(page.render_fragment("checkout").is_none());
+    /// ```
+    pub fn render_fragment(&self, id: &str) -> Option<crate::Result<String>> {
+        self.render_fragment_with(id, &crate::RenderOptions::default())
+    }
+
+    /// Like [`Self::render_fragment`], but with custom [`crate::RenderOptions`].
+    pub fn render_fragment_with(
+        &self,
+        id: &str,
+        options: &crate::RenderOptions,
+    ) -> Option<crate::Result<String>> {
+        use crate::Render;
+
+        let element = self.0.find_by_id(id)?;
+        Some(element.render_to_string_with(options))
+    }
+}
+
+/// A list of sibling nodes without a wrapping element.
+///
+/// Like fragments in React, this lets helper functions return several
+/// elements, e.g. table rows or list items, without an artificial `<div>`
+/// around them. When added to an element, the contents become children of
+/// that element. When rendered on their own, they are rendered one after
+/// another.
+///
+/// # Example
+///
+/// ```
+/// use el::{Fragment, Render, html::*};
+///
+/// fn rows(items: &[(&str, u32)]) -> Fragment {
+///     items
+///         .iter()
+///         .map(|(name, amount)| tr((td(*name), td(amount.to_string()))))
+///         .collect()
+/// }
+///
+/// let rows = rows(&[("Apples", 3), ("Pears", 5)]);
+/// assert_eq!(
+///     rows.render_to_string().unwrap(),
+///     "<tr><td>Apples</td><td>3</td></tr><tr><td>Pears</td><td>5</td></tr>",
+/// );
+/// assert_eq!(
+///     table(tbody(rows)).render_to_string().unwrap(),
+///     "<table><tbody><tr><td>Apples</td><td>3</td></tr><tr><td>Pears</td><td>5</td></tr></tbody></table>",
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Fragment(pub Vec<Content>);
+
+impl Fragment {
+    /// Create an empty fragment.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append content to the fragment.
+    pub fn push(&mut self, content: impl Into<Content>) {
+        self.0.push(content.into());
+    }
+
+    /// Append content to the fragment, builder style.
+    pub fn with(mut self, content: impl Into<Content>) -> Self {
+        self.push(content);
+        self
+    }
+}
+
+impl From<Vec<Content>> for Fragment {
+    fn from(value: Vec<Content>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Into<Content>> FromIterator<T> for Fragment {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Content>> Extend<T> for Fragment {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.0.extend(iter.into_iter().map(Into::into));
+    }
+}
+
+impl IntoIterator for Fragment {
+    type Item = Content;
+    type IntoIter = std::vec::IntoIter<Content>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl ElementComponent for Fragment {
+    fn add_to_element(self, element: &mut Element) {
+        element.children.extend(self.0);
+    }
+}
@@ -0,0 +1,129 @@
+//! Rendering flash messages, the one-shot notifications shown after an action.
+//!
+//! Web apps commonly store a few messages in the session when handling a form
+//! submission (e.g. "Settings saved" or "Invalid password") and show them once
+//! on the next page. [`messages`] renders such messages grouped by [`Level`],
+//! with the roles that make screen readers announce them: errors use
+//! `role="alert"`, which interrupts the user, while all other levels use
+//! `role="status"`, which is announced when the user is idle.
+//!
+//! The elements only carry classes (`flash` and e.g. `flash-error`), so they
+//! can be styled by the app.
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Render, flash::{self, Level}, html::*};
+//!
+//! let messages = vec![
+//!     (Level::Success, "Settings saved.".to_string()),
+//!     (Level::Error, "Invalid e-mail address.".to_string()),
+//! ];
+//! let html = body(flash::messages(messages)).render_to_string().unwrap();
+//! assert_eq!(
+//!     html,
+//!     concat!(
+//!         "<body>",
+//!         r#"<div aria-live="assertive" class="flash flash-error" role="alert">"#,
+//!         "<ul><li>Invalid e-mail address.</li></ul></div>",
+//!         r#"<div aria-live="polite" class="flash flash-success" role="status">"#,
+//!         "<ul><li>Settings saved.</li></ul></div>",
+//!         "</body>",
+//!     ),
+//! );
+//! ```
+
+use std::fmt;
+
+use crate::{
+    html::{attr, div, li, ul},
+    Attr, Element,
+};
+
+/// The severity of a flash message.
+///
+/// Levels are ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    /// A successfully completed action.
+    Success,
+    /// Neutral information.
+    Info,
+    /// Something the user should pay attention to.
+    Warning,
+    /// A failed action.
+    Error,
+}
+
+impl Level {
+    /// All levels, from most to least severe.
+    ///
+    /// This is the order in which [`messages`] renders the levels.
+    pub const ALL: [Self; 4] = [Self::Error, Self::Warning, Self::Info, Self::Success];
+
+    /// The name of the level, used in the `flash-*` class.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+
+    fn role(self) -> (attr::Role, &'static str) {
+        match self {
+            Self::Error => (attr::Role::Alert, "assertive"),
+            _ => (attr::Role::Status, "polite"),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A list of messages of a single level, see the [module
+/// documentation](self).
+///
+/// The list is rendered even if it contains no messages, so it can be used
+/// as a live region that is filled in later (e.g. by htmx).
+pub fn list(level: Level, messages: impl IntoIterator<Item = impl ToString>) -> Element {
+    let (role, live) = level.role();
+    div((
+        attr::class("flash"),
+        attr::class(format!("flash-{level}")),
+        role,
+        Attr::set("aria-live", live),
+        ul(messages
+            .into_iter()
+            .map(|m| li(m.to_string()))
+            .collect::<Vec<_>>()),
+    ))
+}
+
+/// Lists of messages grouped by level, see the [module
+/// documentation](self).
+///
+/// The levels are ordered from most to least severe, see [`Level::ALL`].
+/// Messages of the same level keep their order. Levels without messages are
+/// omitted, so no messages result in no elements.
+pub fn messages(messages: impl IntoIterator<Item = (Level, impl ToString)>) -> Vec<Element> {
+    let messages = messages
+        .into_iter()
+        .map(|(level, message)| (level, message.to_string()))
+        .collect::<Vec<_>>();
+
+    Level::ALL
+        .into_iter()
+        .filter_map(|level| {
+            let texts = (messages.iter())
+                .filter(|(l, _)| *l == level)
+                .map(|(_, m)| m)
+                .collect::<Vec<_>>();
+            (!texts.is_empty()).then(|| list(level, texts))
+        })
+        .collect()
+}
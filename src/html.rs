@@ -3,7 +3,14 @@
 //!
 //! Deprecated HTML elements are not included.
 
+pub mod aria;
 pub mod attr;
+pub mod dataset;
+pub mod helpers;
+pub mod ids;
+pub mod microdata;
+pub mod rdfa;
+pub mod typed;
 
 use crate::{Element, ElementComponent, ElementKind};
 
@@ -14,8 +21,14 @@ macro_rules! element {
     ( $name:ident, $kind:expr ) => {
         #[doc = concat!("The `<", stringify!($name), ">` tag")]
         #[doc = concat!("([MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/", stringify!($name), ")).")]
+        #[cfg_attr(feature = "track-caller", track_caller)]
         pub fn $name(c: impl ElementComponent) -> Element {
-            Element::new(stringify!($name), $kind).with(c)
+            let mut c = Some(c);
+            Element::new_with(stringify!($name), $kind, &mut |element| {
+                if let Some(c) = c.take() {
+                    c.add_to_element(element);
+                }
+            })
         }
     };
 }
@@ -0,0 +1,403 @@
+//! Definitions for WAI-ARIA attributes
+//! ([MDN](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes)).
+//!
+//! The functions and types in this module are named after the attribute they
+//! create, e.g. [`aria_label`] creates an `aria-label` attribute. Values are
+//! formatted the way the ARIA specification expects, e.g. booleans as `true`
+//! and `false` instead of as boolean attributes. Attributes taking a list of
+//! IDs (like [`aria_describedby`]) add them to any IDs already present.
+//!
+//! The `role` attribute is available as [`Role`], which is the same type as
+//! [`html::attr::Role`](crate::html::attr::Role).
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Render, html::{*, aria::*}};
+//!
+//! let menu = div((
+//!     button((
+//!         aria_expanded(false),
+//!         aria_controls("menu"),
+//!         AriaHaspopup::Menu,
+//!         "Options",
+//!     )),
+//!     ul((
+//!         attr::id("menu"),
+//!         Role::Menu,
+//!         aria_labelledby("menu-title"),
+//!         aria_describedby("menu-help"),
+//!         aria_describedby("menu-help menu-hint"),
+//!         aria_hidden(true),
+//!     )),
+//! ));
+//! assert_eq!(
+//!     menu.render_to_string().unwrap(),
+//!     concat!(
+//!         r#"<div><button aria-controls="menu" aria-expanded="false" aria-haspopup="menu">"#,
+//!         "Options</button>",
+//!         r#"<ul aria-describedby="menu-help menu-hint" aria-hidden="true" "#,
+//!         r#"aria-labelledby="menu-title" id="menu" role="menu"></ul></div>"#,
+//!     ),
+//! );
+//! ```
+
+use std::fmt;
+
+pub use super::attr::Role;
+use crate::{length::Number, Attr, Element, ElementComponent};
+
+macro_rules! url {
+    ( $name:expr ) => {
+        concat!(
+            "[MDN](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/",
+            $name,
+            ")"
+        )
+    };
+}
+
+macro_rules! aria_string {
+    ( $( $name:ident => $actual:expr; )* ) => {
+        $(
+            #[doc = concat!("Create (or replace) an `", $actual, "` attribute")]
+            #[doc = concat!("(", url!($actual), ").")]
+            pub fn $name(value: impl ToString) -> Attr {
+                Attr::set_static($actual, value)
+            }
+        )*
+    };
+}
+
+macro_rules! aria_ids {
+    ( $( $name:ident => $actual:expr; )* ) => {
+        $(
+            #[doc = concat!("Create (or add IDs to) an `", $actual, "` attribute")]
+            #[doc = concat!("(", url!($actual), ").")]
+            ///
+            /// The value is a space-separated list of element IDs. IDs already
+            /// present in the attribute are not added again.
+            pub fn $name(ids: impl ToString) -> Attr {
+                Attr::tokens_static($actual, ids, " ")
+            }
+        )*
+    };
+}
+
+macro_rules! aria_bool {
+    ( $( $name:ident => $actual:expr; )* ) => {
+        $(
+            #[doc = concat!("Create (or replace) an `", $actual, "` attribute")]
+            #[doc = concat!("(", url!($actual), ").")]
+            pub fn $name(value: bool) -> Attr {
+                Attr::set_static($actual, value)
+            }
+        )*
+    };
+}
+
+macro_rules! aria_int {
+    ( $( $name:ident => $actual:expr; )* ) => {
+        $(
+            #[doc = concat!("Create (or replace) an `", $actual, "` attribute")]
+            #[doc = concat!("(", url!($actual), ").")]
+            pub fn $name(value: i32) -> Attr {
+                Attr::set_static($actual, value)
+            }
+        )*
+    };
+}
+
+macro_rules! aria_number {
+    ( $( $name:ident => $actual:expr; )* ) => {
+        $(
+            #[doc = concat!("Create (or replace) an `", $actual, "` attribute")]
+            #[doc = concat!("(", url!($actual), ").")]
+            ///
+            /// The value is formatted like a [`Length`](crate::Length) without
+            /// unit.
+            pub fn $name(value: f64) -> Attr {
+                Attr::set_static($actual, Number(value, 4))
+            }
+        )*
+    };
+}
+
+macro_rules! aria_enum {
+    (
+        $name:ident as $actual:expr;
+        $( $valname:ident => $valstr:expr, )*
+    ) => {
+        #[doc = concat!("Create (or replace) an `", $actual, "` attribute")]
+        #[doc = concat!("(", url!($actual), ").")]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $(
+                #[doc = concat!("The value `", stringify!($valstr), "`.")]
+                $valname,
+            )*
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    $( Self::$valname => $valstr.fmt(f), )*
+                }
+            }
+        }
+
+        impl ElementComponent for $name {
+            fn add_to_element(self, element: &mut Element) {
+                Attr::set_static($actual, self).add_to_element(element);
+            }
+        }
+    };
+}
+
+macro_rules! from_bool {
+    ( $( $name:ident ),* ) => {
+        $(
+            impl From<bool> for $name {
+                fn from(value: bool) -> Self {
+                    if value {
+                        Self::True
+                    } else {
+                        Self::False
+                    }
+                }
+            }
+        )*
+    };
+}
+
+// ARIA attributes A to Z
+
+aria_string! {
+    aria_activedescendant => "aria-activedescendant";
+}
+
+aria_bool! {
+    aria_atomic => "aria-atomic";
+}
+
+aria_enum! {
+    AriaAutocomplete as "aria-autocomplete";
+    None => "none",
+    Inline => "inline",
+    List => "list",
+    Both => "both",
+}
+
+aria_string! {
+    aria_braillelabel => "aria-braillelabel";
+    aria_brailleroledescription => "aria-brailleroledescription";
+}
+
+aria_bool! {
+    aria_busy => "aria-busy";
+}
+
+aria_enum! {
+    AriaChecked as "aria-checked";
+    False => "false",
+    True => "true",
+    Mixed => "mixed",
+}
+
+aria_int! {
+    aria_colcount => "aria-colcount";
+    aria_colindex => "aria-colindex";
+}
+
+aria_string! {
+    aria_colindextext => "aria-colindextext";
+}
+
+aria_int! {
+    aria_colspan => "aria-colspan";
+}
+
+aria_ids! {
+    aria_controls => "aria-controls";
+}
+
+aria_enum! {
+    AriaCurrent as "aria-current";
+    False => "false",
+    True => "true",
+    Page => "page",
+    Step => "step",
+    Location => "location",
+    Date => "date",
+    Time => "time",
+}
+
+aria_ids! {
+    aria_describedby => "aria-describedby";
+}
+
+aria_string! {
+    aria_description => "aria-description";
+}
+
+aria_ids! {
+    aria_details => "aria-details";
+}
+
+aria_bool! {
+    aria_disabled => "aria-disabled";
+}
+
+aria_ids! {
+    aria_errormessage => "aria-errormessage";
+}
+
+aria_bool! {
+    aria_expanded => "aria-expanded";
+}
+
+aria_ids! {
+    aria_flowto => "aria-flowto";
+}
+
+aria_enum! {
+    AriaHaspopup as "aria-haspopup";
+    False => "false",
+    True => "true",
+    Menu => "menu",
+    Listbox => "listbox",
+    Tree => "tree",
+    Grid => "grid",
+    Dialog => "dialog",
+}
+
+aria_bool! {
+    aria_hidden => "aria-hidden";
+}
+
+aria_enum! {
+    AriaInvalid as "aria-invalid";
+    False => "false",
+    True => "true",
+    Grammar => "grammar",
+    Spelling => "spelling",
+}
+
+aria_string! {
+    aria_keyshortcuts => "aria-keyshortcuts";
+    aria_label => "aria-label";
+}
+
+aria_ids! {
+    aria_labelledby => "aria-labelledby";
+}
+
+aria_int! {
+    aria_level => "aria-level";
+}
+
+aria_enum! {
+    AriaLive as "aria-live";
+    Off => "off",
+    Polite => "polite",
+    Assertive => "assertive",
+}
+
+aria_bool! {
+    aria_modal => "aria-modal";
+    aria_multiline => "aria-multiline";
+    aria_multiselectable => "aria-multiselectable";
+}
+
+aria_enum! {
+    AriaOrientation as "aria-orientation";
+    Horizontal => "horizontal",
+    Vertical => "vertical",
+}
+
+aria_ids! {
+    aria_owns => "aria-owns";
+}
+
+aria_string! {
+    aria_placeholder => "aria-placeholder";
+}
+
+aria_int! {
+    aria_posinset => "aria-posinset";
+}
+
+aria_enum! {
+    AriaPressed as "aria-pressed";
+    False => "false",
+    True => "true",
+    Mixed => "mixed",
+}
+
+aria_bool! {
+    aria_readonly => "aria-readonly";
+}
+
+/// Create (or add tokens to) an `aria-relevant` attribute
+#[doc = concat!("(", url!("aria-relevant"), ").")]
+///
+/// The value is a space-separated list of `additions`, `removals`, `text`
+/// and `all`.
+pub fn aria_relevant(value: impl ToString) -> Attr {
+    Attr::tokens_static("aria-relevant", value, " ")
+}
+
+aria_bool! {
+    aria_required => "aria-required";
+}
+
+aria_string! {
+    aria_roledescription => "aria-roledescription";
+}
+
+aria_int! {
+    aria_rowcount => "aria-rowcount";
+    aria_rowindex => "aria-rowindex";
+}
+
+aria_string! {
+    aria_rowindextext => "aria-rowindextext";
+}
+
+aria_int! {
+    aria_rowspan => "aria-rowspan";
+}
+
+aria_bool! {
+    aria_selected => "aria-selected";
+}
+
+aria_int! {
+    aria_setsize => "aria-setsize";
+}
+
+aria_enum! {
+    AriaSort as "aria-sort";
+    None => "none",
+    Ascending => "ascending",
+    Descending => "descending",
+    Other => "other",
+}
+
+aria_number! {
+    aria_valuemax => "aria-valuemax";
+    aria_valuemin => "aria-valuemin";
+    aria_valuenow => "aria-valuenow";
+}
+
+aria_string! {
+    aria_valuetext => "aria-valuetext";
+}
+
+from_bool!(
+    AriaChecked,
+    AriaCurrent,
+    AriaHaspopup,
+    AriaInvalid,
+    AriaPressed
+);
@@ -6,9 +6,16 @@
 //! [0]: https://developer.mozilla.org/en-US/docs/Web/HTML/Attributes
 //! [1]: https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes
 
-use std::fmt;
+use std::{fmt, ops::BitOr};
 
-use crate::{Attr, Element, ElementComponent};
+use crate::{
+    csp,
+    css::{self, StyleError},
+    tokens::split_tokens,
+    Attr, Element, ElementComponent, IntoAttrValue, Render,
+};
+
+pub use crate::tokens::TokenList;
 
 macro_rules! url {
     ( global, $name:expr ) => {
@@ -46,7 +53,7 @@ macro_rules! attr_yes {
         #[doc = concat!("Create (or replace) ", stringify!($article), " `", $actual, "` attribute")]
         #[doc = concat!("(", $url, ").")]
         pub fn $name() -> Attr {
-            Attr::yes($actual)
+            Attr::set_static($actual, "")
         }
     };
 }
@@ -59,7 +66,7 @@ macro_rules! attr_set {
         #[doc = concat!("Create (or replace) ", stringify!($article), " `", $actual, "` attribute")]
         #[doc = concat!("(", $url, ").")]
         pub fn $name(value: impl ToString) -> Attr {
-            Attr::set($actual, value)
+            Attr::set_static($actual, value)
         }
     };
 }
@@ -72,7 +79,23 @@ macro_rules! attr_append {
         #[doc = concat!("Create (or append to) ", stringify!($article), " `", $actual, "` attribute")]
         #[doc = concat!("(", $url, ").")]
         pub fn $name(value: impl ToString) -> Attr {
-            Attr::append($actual, value, $separator)
+            Attr::append_static($actual, value, $separator)
+        }
+    };
+}
+
+macro_rules! attr_tokens {
+    (
+        $name:ident as $article:ident $actual:expr, separated by $separator:expr;
+        at $url:expr;
+    ) => {
+        #[doc = concat!("Create (or add tokens to) ", stringify!($article), " `", $actual, "` attribute")]
+        #[doc = concat!("(", $url, ").")]
+        ///
+        /// Tokens already present in the attribute are not added again, see
+        /// [`TokenList`].
+        pub fn $name(value: impl ToString) -> Attr {
+            Attr::tokens_static($actual, value, $separator)
         }
     };
 }
@@ -85,25 +108,81 @@ macro_rules! attr_enum {
     ) => {
         #[doc = concat!("Create (or replace) ", stringify!($article), " `", $actual, "` attribute")]
         #[doc = concat!("(", $url, ").")]
-        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #[derive(Debug, Clone, PartialEq, Eq)]
         pub enum $name {
             $(
                 #[doc = concat!("The value `", stringify!($valstr), "`.")]
                 $valname,
             )*
+            /// Any other value, e.g. a new or vendor-specific one.
+            Other(String),
+        }
+
+        impl $name {
+            /// Construct [`Self::Other`] from an arbitrary value.
+            pub fn other(value: impl ToString) -> Self {
+                Self::Other(value.to_string())
+            }
         }
 
         impl fmt::Display for $name {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 match self {
                     $( Self::$valname => $valstr.fmt(f), )*
+                    Self::Other(value) => value.fmt(f),
                 }
             }
         }
 
         impl ElementComponent for $name {
             fn add_to_element(self, element: &mut Element) {
-                Attr::set($actual, self).add_to_element(element);
+                Attr::set_static($actual, self).add_to_element(element);
+            }
+        }
+    };
+    (
+        $name:ident as $article:ident $actual:expr, removed by $absent:ident;
+        at $url:expr;
+        $( $valname:ident => $valstr:expr, )*
+    ) => {
+        #[doc = concat!("Create, replace or remove ", stringify!($article), " `", $actual, "` attribute")]
+        #[doc = concat!("(", $url, ").")]
+        ///
+        /// Values rendered as an empty string result in a boolean attribute
+        /// without value.
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum $name {
+            $(
+                #[doc = concat!("The value `", stringify!($valstr), "`.")]
+                $valname,
+            )*
+            /// No attribute at all. Removes the attribute if it is already
+            /// present.
+            $absent,
+            /// Any other value, e.g. a new or vendor-specific one.
+            Other(String),
+        }
+
+        impl $name {
+            /// Construct [`Self::Other`] from an arbitrary value.
+            pub fn other(value: impl ToString) -> Self {
+                Self::Other(value.to_string())
+            }
+        }
+
+        impl IntoAttrValue for $name {
+            fn into_attr_value(self) -> Option<String> {
+                match self {
+                    $( Self::$valname => Some($valstr.to_string()), )*
+                    Self::$absent => None,
+                    Self::Other(value) => Some(value),
+                }
+            }
+        }
+
+        impl ElementComponent for $name {
+            fn add_to_element(self, element: &mut Element) {
+                Attr::set_static_value($actual, self).add_to_element(element);
             }
         }
     };
@@ -112,27 +191,141 @@ macro_rules! attr_enum {
         at $url:expr;
         $( $valname:ident => $valstr:expr, )*
     ) => {
-        #[doc = concat!("Create (or append to) ", stringify!($article), " `", $actual, "` attribute")]
+        #[doc = concat!("Create (or add a token to) ", stringify!($article), " `", $actual, "` attribute")]
         #[doc = concat!("(", $url, ").")]
-        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #[derive(Debug, Clone, PartialEq, Eq)]
         pub enum $name {
             $(
                 #[doc = concat!("The value `", stringify!($valstr), "`.")]
                 $valname,
             )*
+            /// Any other value, e.g. a new or vendor-specific one.
+            Other(String),
+        }
+
+        impl $name {
+            /// Construct [`Self::Other`] from an arbitrary value.
+            pub fn other(value: impl ToString) -> Self {
+                Self::Other(value.to_string())
+            }
         }
 
         impl fmt::Display for $name {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 match self {
                     $( Self::$valname => $valstr.fmt(f), )*
+                    Self::Other(value) => value.fmt(f),
                 }
             }
         }
 
         impl ElementComponent for $name {
             fn add_to_element(self, element: &mut Element) {
-                Attr::append($actual, self, $separator).add_to_element(element);
+                Attr::tokens_static($actual, self, $separator).add_to_element(element);
+            }
+        }
+    };
+}
+
+/// Define an ordered set of attribute value tokens, with the same API as
+/// [`RelSet`]. The [`ElementComponent`] impl is left to the caller.
+macro_rules! token_set {
+    (
+        $( #[$meta:meta] )*
+        pub struct $set:ident(Vec<$token:ident>);
+    ) => {
+        $( #[$meta] )*
+        #[derive(Debug, Clone, Default, PartialEq, Eq)]
+        pub struct $set(Vec<$token>);
+
+        impl $set {
+            /// Create a new empty set.
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// Insert a token into the set if it is not already present.
+            pub fn insert(&mut self, token: $token) {
+                if !self.contains(&token) {
+                    self.0.push(token);
+                }
+            }
+
+            /// A more builder-pattern-like version of [`Self::insert`].
+            pub fn with(mut self, token: $token) -> Self {
+                self.insert(token);
+                self
+            }
+
+            /// Whether the set contains a token.
+            pub fn contains(&self, token: &$token) -> bool {
+                self.0.contains(token)
+            }
+
+            /// Whether the set contains no tokens.
+            pub fn is_empty(&self) -> bool {
+                self.0.is_empty()
+            }
+
+            /// Iterate over the tokens in insertion order.
+            pub fn iter(&self) -> impl Iterator<Item = &$token> {
+                self.0.iter()
+            }
+        }
+
+        impl fmt::Display for $set {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                for (i, token) in self.0.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{token}")?;
+                }
+                Ok(())
+            }
+        }
+
+        impl FromIterator<$token> for $set {
+            fn from_iter<T: IntoIterator<Item = $token>>(iter: T) -> Self {
+                let mut set = Self::new();
+                set.extend(iter);
+                set
+            }
+        }
+
+        impl Extend<$token> for $set {
+            fn extend<T: IntoIterator<Item = $token>>(&mut self, iter: T) {
+                for token in iter {
+                    self.insert(token);
+                }
+            }
+        }
+
+        impl From<$token> for $set {
+            fn from(value: $token) -> Self {
+                Self::new().with(value)
+            }
+        }
+
+        impl<const L: usize> From<[$token; L]> for $set {
+            fn from(value: [$token; L]) -> Self {
+                value.into_iter().collect()
+            }
+        }
+
+        impl BitOr for $token {
+            type Output = $set;
+
+            fn bitor(self, rhs: Self) -> Self::Output {
+                $set::from([self, rhs])
+            }
+        }
+
+        impl BitOr<$token> for $set {
+            type Output = Self;
+
+            fn bitor(self, rhs: $token) -> Self::Output {
+                self.with(rhs)
             }
         }
     };
@@ -142,12 +335,151 @@ macro_rules! attr_enum {
 // Attributes //
 ////////////////
 
-attr_append! {
+attr_tokens! {
     accept as an "accept", separated by ", ";
     at url!(normal, "accept");
 }
 
-attr_append! {
+/// A validated list of file types, used to create (or append to) an `accept`
+/// attribute
+/// ([MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Attributes/accept)).
+///
+/// Each file type is either a file extension starting with a `.` (e.g.
+/// `.pdf`), a MIME type without parameters (e.g. `application/pdf`), or one of
+/// the wildcards `audio/*`, `video/*` and `image/*`. File types are compared
+/// case-insensitively and stored in lowercase. Each file type is only included
+/// once, in the order it was first inserted.
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, html::*};
+///
+/// let accept = attr::Accept::images().try_with(".PDF").unwrap();
+/// assert_eq!(
+///     input((attr::TypeInput::File, accept)).render_to_string().unwrap(),
+///     r#"<input accept="image/*, .pdf" type="file">"#,
+/// );
+///
+/// assert!(attr::Accept::parse("image/png, text/*").is_none());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Accept(Vec<String>);
+
+impl Accept {
+    /// Create a new empty list, which accepts all file types.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A list accepting all images (`image/*`).
+    pub fn images() -> Self {
+        Self(vec!["image/*".to_string()])
+    }
+
+    /// A list accepting all audio files (`audio/*`).
+    pub fn audio() -> Self {
+        Self(vec!["audio/*".to_string()])
+    }
+
+    /// A list accepting all videos (`video/*`).
+    pub fn video() -> Self {
+        Self(vec!["video/*".to_string()])
+    }
+
+    /// Parse a comma-separated list of file types.
+    ///
+    /// Returns [`None`] if any of the file types is invalid.
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut result = Self::new();
+        for file_type in split_tokens(value, ",") {
+            result = result.try_with(file_type)?;
+        }
+        Some(result)
+    }
+
+    /// Insert a file type into the list if it is not already present.
+    ///
+    /// Returns whether the file type is valid. Invalid file types are not
+    /// inserted.
+    pub fn insert(&mut self, file_type: &str) -> bool {
+        let file_type = file_type.trim().to_ascii_lowercase();
+        if !is_valid_file_type(&file_type) {
+            return false;
+        }
+        if !self.contains(&file_type) {
+            self.0.push(file_type);
+        }
+        true
+    }
+
+    /// A more builder-pattern-like version of [`Self::insert`].
+    ///
+    /// Returns [`None`] if the file type is invalid.
+    pub fn try_with(mut self, file_type: &str) -> Option<Self> {
+        self.insert(file_type).then_some(self)
+    }
+
+    /// Whether the list contains a file type.
+    pub fn contains(&self, file_type: &str) -> bool {
+        self.0.iter().any(|t| t.eq_ignore_ascii_case(file_type))
+    }
+
+    /// Whether the list contains no file types.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the file types in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|t| &t[..])
+    }
+}
+
+/// Whether a lowercase file type is valid in an `accept` attribute.
+fn is_valid_file_type(file_type: &str) -> bool {
+    fn is_token(s: &str) -> bool {
+        // https://mimesniff.spec.whatwg.org/#http-token-code-point
+        // Without `*`, which is only allowed in the wildcards
+        !s.is_empty()
+            && (s.bytes()).all(|b| b.is_ascii_alphanumeric() || b"!#$%&'+-.^_`|~".contains(&b))
+    }
+
+    if let Some(extension) = file_type.strip_prefix('.') {
+        return !extension.is_empty()
+            && !extension
+                .chars()
+                .any(|c| c == ',' || c == '.' || c.is_whitespace() || c.is_control());
+    }
+    match file_type.split_once('/') {
+        Some(("audio" | "video" | "image", "*")) => true,
+        Some((r#type, subtype)) => is_token(r#type) && is_token(subtype),
+        None => false,
+    }
+}
+
+impl fmt::Display for Accept {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, file_type) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{file_type}")?;
+        }
+        Ok(())
+    }
+}
+
+impl ElementComponent for Accept {
+    fn add_to_element(self, element: &mut Element) {
+        // An empty list should not produce an empty `accept` attribute.
+        if !self.is_empty() {
+            accept(self).add_to_element(element);
+        }
+    }
+}
+
+attr_tokens! {
     accesskey as an "accesskey", separated by " ";
     at url!(global, "accesskey");
 }
@@ -162,6 +494,190 @@ attr_append! {
     at url!(element "iframe", "allow");
 }
 
+/// A permissions policy, used to create (or append to) an `allow` attribute
+/// ([MDN](https://developer.mozilla.org/en-US/docs/Web/HTTP/Permissions_Policy)).
+///
+/// Each directive consists of a feature name and an allowlist of
+/// [`AllowOrigin`]s. Directives are rendered in the order they were first
+/// added, separated by `; `. Adding a directive for a feature that is already
+/// present replaces its allowlist. When added to an element that already has
+/// an `allow` attribute, the allowlists of features present in both are
+/// merged instead. Whitespace, `;`, `,` and `'` are removed from feature names
+/// and origins, since they would change the meaning of the policy.
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, html::*, html::attr::{Allow, AllowOrigin}};
+///
+/// let allow = Allow::new()
+///     .feature("fullscreen")
+///     .feature_for("camera", [AllowOrigin::Self_, AllowOrigin::origin("https://example.com")])
+///     .deny("geolocation");
+///
+/// assert_eq!(
+///     iframe(allow).render_to_string().unwrap(),
+///     r#"<iframe allow="fullscreen; camera 'self' https://example.com; geolocation 'none'"></iframe>"#,
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Allow(Vec<(String, Vec<AllowOrigin>)>);
+
+impl Allow {
+    /// Create a new policy without any directives.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow a feature for the origin of the iframe's `src`.
+    ///
+    /// This is the default allowlist of the `allow` attribute, so only the
+    /// feature name is rendered.
+    pub fn feature(self, feature: impl ToString) -> Self {
+        self.feature_for(feature, [])
+    }
+
+    /// Allow a feature for a list of origins.
+    ///
+    /// An empty list is equivalent to [`Self::feature`].
+    pub fn feature_for(
+        mut self,
+        feature: impl ToString,
+        origins: impl IntoIterator<Item = AllowOrigin>,
+    ) -> Self {
+        let feature = feature.to_string();
+        let origins = origins.into_iter().collect();
+        match self.0.iter_mut().find(|(f, _)| *f == feature) {
+            Some((_, existing)) => *existing = origins,
+            None => self.0.push((feature, origins)),
+        }
+        self
+    }
+
+    /// Disallow a feature entirely.
+    pub fn deny(self, feature: impl ToString) -> Self {
+        self.feature_for(feature, [AllowOrigin::None])
+    }
+
+    /// Whether the policy contains no directives.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for Allow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (feature, origins)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            csp::write_sanitized(f, feature)?;
+            for origin in origins {
+                write!(f, " {origin}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ElementComponent for Allow {
+    fn add_to_element(self, element: &mut Element) {
+        // An empty policy should not produce an empty `allow` attribute.
+        if self.is_empty() {
+            return;
+        }
+        let existing = element.remove_attr_ci("allow").unwrap_or_default();
+        let mut directives = existing
+            .split(';')
+            .filter_map(|directive| {
+                let mut tokens = directive.split_whitespace().map(str::to_string);
+                Some((tokens.next()?, tokens.collect::<Vec<_>>()))
+            })
+            .collect::<Vec<_>>();
+        let policy = self.to_string();
+        for directive in policy.split("; ") {
+            let mut tokens = directive.split_whitespace().map(str::to_string);
+            let Some(feature) = tokens.next() else {
+                continue;
+            };
+            let origins = tokens.collect::<Vec<_>>();
+            match directives.iter_mut().find(|(f, _)| *f == feature) {
+                Some((_, existing)) => merge_allowlists(existing, origins),
+                None => directives.push((feature, origins)),
+            }
+        }
+        let value = (directives.into_iter())
+            .map(|(feature, origins)| [feature].into_iter().chain(origins).collect::<Vec<_>>())
+            .map(|tokens| tokens.join(" "))
+            .collect::<Vec<_>>()
+            .join("; ");
+        Attr::set_static("allow", value).add_to_element(element);
+    }
+}
+
+/// Merge an allowlist into another, keeping the origins of both.
+fn merge_allowlists(existing: &mut Vec<String>, new: Vec<String>) {
+    // An empty allowlist is the same as `'src'`.
+    let src = || vec![AllowOrigin::Src.to_string()];
+    if existing.is_empty() {
+        *existing = src();
+    }
+    for origin in if new.is_empty() { src() } else { new } {
+        if !existing.contains(&origin) {
+            existing.push(origin);
+        }
+    }
+    let none = AllowOrigin::None.to_string();
+    if existing.len() > 1 {
+        existing.retain(|origin| *origin != none);
+    }
+    if *existing == src() {
+        existing.clear();
+    }
+}
+
+/// An entry in the allowlist of an [`Allow`] directive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AllowOrigin {
+    /// All origins (`*`).
+    Any,
+    /// The origin of the embedding document (`'self'`).
+    Self_,
+    /// The origin of the iframe's `src` (`'src'`).
+    Src,
+    /// No origin (`'none'`). Should be the only entry in an allowlist.
+    None,
+    /// A specific origin like `https://example.com`.
+    Origin(String),
+}
+
+impl AllowOrigin {
+    /// Construct [`Self::Origin`] from an origin.
+    ///
+    /// Anything after the origin (e.g. a path) and surrounding whitespace is
+    /// removed, since it is not allowed in an allowlist.
+    pub fn origin(origin: impl ToString) -> Self {
+        let origin = origin.to_string();
+        let origin = origin.trim();
+        let start = origin.find("://").map_or(0, |i| i + 3);
+        let end = (origin[start..].find(['/', '?', '#', ';', ' ', '\'']))
+            .map_or(origin.len(), |i| start + i);
+        Self::Origin(origin[..end].to_string())
+    }
+}
+
+impl fmt::Display for AllowOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => "*".fmt(f),
+            Self::Self_ => "'self'".fmt(f),
+            Self::Src => "'src'".fmt(f),
+            Self::None => "'none'".fmt(f),
+            Self::Origin(origin) => csp::write_sanitized(f, origin),
+        }
+    }
+}
+
 attr_set! {
     alt as an "alt";
     at concat!(
@@ -227,6 +743,11 @@ attr_enum! {
     Environment => "environment",
 }
 
+attr_set! {
+    charset as a "charset";
+    at url!(element "meta", "charset");
+}
+
 attr_yes! {
     checked as a "checked";
     at url!(element "input", "checked");
@@ -242,7 +763,7 @@ attr_set! {
     );
 }
 
-attr_append! {
+attr_tokens! {
     class as a "class", separated by " ";
     at url!(global, "class");
 }
@@ -300,7 +821,10 @@ attr_set! {
 
 /// Create (or replace) a `data-*` attribute
 /// ([MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/data-*)).
-pub fn data_x(name: impl ToString, value: impl ToString) -> Attr {
+///
+/// The value is converted like in [`Attr::set`], so booleans are written as
+/// `"true"` and `"false"` and [`None`] removes the attribute.
+pub fn data_x(name: impl ToString, value: impl IntoAttrValue) -> Attr {
     Attr::set(format!("data-{}", name.to_string()), value)
 }
 
@@ -384,7 +908,7 @@ attr_enum! {
     Send => "send",
 }
 
-attr_append! {
+attr_tokens! {
     exportparts as an "exportparts", separated by ", ";
     at url!(global, "exportparts");
 }
@@ -460,7 +984,7 @@ attr_enum! {
     Top => "_top",
 }
 
-attr_append! {
+attr_tokens! {
     headers as a "headers", separated by " ";
     at concat!(
         url!(element "td", "headers"), ", ",
@@ -482,12 +1006,23 @@ attr_set! {
 }
 
 attr_enum! {
-    Hidden as a "hidden";
+    Hidden as a "hidden", removed by No;
     at url!(global, "hidden");
     Yes => "",
     UntilFound => "until-found",
 }
 
+/// [`Hidden::Yes`] for `true`, [`Hidden::No`] for `false`.
+impl From<bool> for Hidden {
+    fn from(value: bool) -> Self {
+        if value {
+            Self::Yes
+        } else {
+            Self::No
+        }
+    }
+}
+
 attr_set! {
     high as a "high";
     at url!(element "meter", "high");
@@ -639,6 +1174,16 @@ attr_set! {
     at url!(normal, "maxlength");
 }
 
+attr_set! {
+    media as a "media";
+    at concat!(
+        url!(element "link", "media"), ", ",
+        url!(element "meta", "media"), ", ",
+        url!(element "source", "media"), ", ",
+        url!(element "style", "media")
+    );
+}
+
 attr_set! {
     minlength as a "minlength";
     at url!(normal, "minlength");
@@ -710,7 +1255,7 @@ attr_set! {
     at url!(element "meter", "optimum");
 }
 
-attr_append! {
+attr_tokens! {
     part as a "part", separated by " ";
     at url!(global, "part");
 }
@@ -720,7 +1265,7 @@ attr_set! {
     at url!(normal, "pattern");
 }
 
-attr_append! {
+attr_tokens! {
     ping as a "ping", separated by " ";
     at concat!(
         url!(element "a", "ping"), ", ",
@@ -739,10 +1284,22 @@ attr_yes! {
 }
 
 attr_enum! {
-    Popover as a "popover";
+    Popover as a "popover", removed by No;
     at url!(global, "popover");
     Auto => "",
     Manual => "manual",
+    Hint => "hint",
+}
+
+/// [`Popover::Auto`] for `true`, [`Popover::No`] for `false`.
+impl From<bool> for Popover {
+    fn from(value: bool) -> Self {
+        if value {
+            Self::Auto
+        } else {
+            Self::No
+        }
+    }
 }
 
 attr_set! {
@@ -820,11 +1377,47 @@ attr_enum! {
     TermsOfService => "terms-of-service",
 }
 
-attr_append! {
+attr_tokens! {
     rel as a "rel", separated by " ";
     at url!(normal, "rel");
 }
 
+token_set! {
+    /// A set of [`Rel`] tokens, used to create (or append to) a `rel` attribute
+    /// ([MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Attributes/rel)).
+    ///
+    /// Each token is only included once, in the order it was first inserted.
+    /// A set can also be built by combining [`Rel`] values with `|`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Render, html::*};
+    ///
+    /// let set = attr::Rel::Noopener | attr::Rel::Noreferrer | attr::Rel::Noopener;
+    /// assert_eq!(
+    ///     a((set, attr::href("https://example.com"))).render_to_string().unwrap(),
+    ///     r#"<a href="https://example.com" rel="noopener noreferrer"></a>"#,
+    /// );
+    /// ```
+    pub struct RelSet(Vec<Rel>);
+}
+
+impl ElementComponent for RelSet {
+    fn add_to_element(self, element: &mut Element) {
+        // An empty set should not produce an empty `rel` attribute.
+        if !self.is_empty() {
+            TokenList::from(self).add_to_element(element);
+        }
+    }
+}
+
+impl From<RelSet> for TokenList {
+    fn from(value: RelSet) -> Self {
+        Self::new("rel", " ").with(value)
+    }
+}
+
 attr_yes! {
     required as a "required";
     at url!(normal, "required");
@@ -835,6 +1428,92 @@ attr_yes! {
     at url!(element "ol", "reversed");
 }
 
+attr_enum! {
+    Role as a "role";
+    at "[MDN](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Roles)";
+    Alert => "alert",
+    Alertdialog => "alertdialog",
+    Application => "application",
+    Article => "article",
+    Banner => "banner",
+    Blockquote => "blockquote",
+    Button => "button",
+    Caption => "caption",
+    Cell => "cell",
+    Checkbox => "checkbox",
+    Code => "code",
+    Columnheader => "columnheader",
+    Combobox => "combobox",
+    Complementary => "complementary",
+    Contentinfo => "contentinfo",
+    Definition => "definition",
+    Deletion => "deletion",
+    Dialog => "dialog",
+    Document => "document",
+    Emphasis => "emphasis",
+    Feed => "feed",
+    Figure => "figure",
+    Form => "form",
+    Generic => "generic",
+    Grid => "grid",
+    Gridcell => "gridcell",
+    Group => "group",
+    Heading => "heading",
+    Img => "img",
+    Insertion => "insertion",
+    Link => "link",
+    List => "list",
+    Listbox => "listbox",
+    Listitem => "listitem",
+    Log => "log",
+    Main => "main",
+    Marquee => "marquee",
+    Math => "math",
+    Menu => "menu",
+    Menubar => "menubar",
+    Menuitem => "menuitem",
+    Menuitemcheckbox => "menuitemcheckbox",
+    Menuitemradio => "menuitemradio",
+    Meter => "meter",
+    Navigation => "navigation",
+    None => "none",
+    Note => "note",
+    Option => "option",
+    Paragraph => "paragraph",
+    Presentation => "presentation",
+    Progressbar => "progressbar",
+    Radio => "radio",
+    Radiogroup => "radiogroup",
+    Region => "region",
+    Row => "row",
+    Rowgroup => "rowgroup",
+    Rowheader => "rowheader",
+    Scrollbar => "scrollbar",
+    Search => "search",
+    Searchbox => "searchbox",
+    Separator => "separator",
+    Slider => "slider",
+    Spinbutton => "spinbutton",
+    Status => "status",
+    Strong => "strong",
+    Subscript => "subscript",
+    Superscript => "superscript",
+    Switch => "switch",
+    Tab => "tab",
+    Table => "table",
+    Tablist => "tablist",
+    Tabpanel => "tabpanel",
+    Term => "term",
+    Textbox => "textbox",
+    Time => "time",
+    Timer => "timer",
+    Toolbar => "toolbar",
+    Tooltip => "tooltip",
+    Tree => "tree",
+    Treegrid => "treegrid",
+    Treeitem => "treeitem",
+}
+
 attr_set! {
     rows as a "rows";
     at url!(element "textarea", "rows");
@@ -848,11 +1527,69 @@ attr_set! {
     );
 }
 
-attr_append! {
+attr_enum! {
+    Sandbox as a "sandbox", separated by " ";
+    at url!(element "iframe", "sandbox");
+    AllowDownloads => "allow-downloads",
+    AllowForms => "allow-forms",
+    AllowModals => "allow-modals",
+    AllowOrientationLock => "allow-orientation-lock",
+    AllowPointerLock => "allow-pointer-lock",
+    AllowPopups => "allow-popups",
+    AllowPopupsToEscapeSandbox => "allow-popups-to-escape-sandbox",
+    AllowPresentation => "allow-presentation",
+    AllowSameOrigin => "allow-same-origin",
+    AllowScripts => "allow-scripts",
+    AllowStorageAccessByUserActivation => "allow-storage-access-by-user-activation",
+    AllowTopNavigation => "allow-top-navigation",
+    AllowTopNavigationByUserActivation => "allow-top-navigation-by-user-activation",
+    AllowTopNavigationToCustomProtocols => "allow-top-navigation-to-custom-protocols",
+}
+
+attr_tokens! {
     sandbox as a "sandbox", separated by " ";
     at url!(element "iframe", "sandbox");
 }
 
+token_set! {
+    /// A set of [`Sandbox`] tokens, used to create (or add to) a `sandbox`
+    /// attribute
+    /// ([MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/iframe#sandbox)).
+    ///
+    /// Each token is only included once, in the order it was first inserted.
+    /// A set can also be built by combining [`Sandbox`] values with `|`.
+    ///
+    /// Unlike [`RelSet`], an empty set still produces an empty `sandbox`
+    /// attribute, which applies all restrictions. Note that combining
+    /// [`Sandbox::AllowScripts`] and [`Sandbox::AllowSameOrigin`] for
+    /// same-origin content lets the content remove its own `sandbox`
+    /// attribute.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Render, html::*};
+    ///
+    /// let set = attr::Sandbox::AllowScripts | attr::Sandbox::AllowForms;
+    /// assert_eq!(
+    ///     iframe(set).render_to_string().unwrap(),
+    ///     r#"<iframe sandbox="allow-scripts allow-forms"></iframe>"#,
+    /// );
+    ///
+    /// assert_eq!(
+    ///     iframe(attr::SandboxSet::new()).render_to_string().unwrap(),
+    ///     r#"<iframe sandbox></iframe>"#,
+    /// );
+    /// ```
+    pub struct SandboxSet(Vec<Sandbox>);
+}
+
+impl ElementComponent for SandboxSet {
+    fn add_to_element(self, element: &mut Element) {
+        Attr::tokens_static("sandbox", self, " ").add_to_element(element);
+    }
+}
+
 attr_enum! {
     Scope as a "scope";
     at url!(element "th", "scope");
@@ -867,6 +1604,28 @@ attr_yes! {
     at url!(element "option", "selected");
 }
 
+attr_yes! {
+    shadowrootclonable as a "shadowrootclonable";
+    at url!(element "template", "shadowrootclonable");
+}
+
+attr_yes! {
+    shadowrootdelegatesfocus as a "shadowrootdelegatesfocus";
+    at url!(element "template", "shadowrootdelegatesfocus");
+}
+
+attr_enum! {
+    Shadowrootmode as a "shadowrootmode";
+    at url!(element "template", "shadowrootmode");
+    Open => "open",
+    Closed => "closed",
+}
+
+attr_yes! {
+    shadowrootserializable as a "shadowrootserializable";
+    at url!(element "template", "shadowrootserializable");
+}
+
 attr_enum! {
     Shape as a "shape";
     at url!(element "area", "shape");
@@ -893,7 +1652,7 @@ attr_append! {
     );
 }
 
-attr_append! {
+attr_tokens! {
     sizes_link as a "sizes", separated by " ";
     at url!(element "link", "sizes");
 }
@@ -938,6 +1697,39 @@ attr_set! {
     at url!(element "iframe", "srcdoc");
 }
 
+/// Create (or replace) a `srcdoc` attribute containing a rendered element or
+/// document
+/// ([MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/iframe#srcdoc)).
+///
+/// The browser decodes character references in attribute values before
+/// parsing the `srcdoc` as a document. Every `&` of the rendered output is
+/// therefore escaped once more, so that escaped text like `&lt;` inside the
+/// embedded document stays escaped. Quotes are escaped when the attribute is
+/// rendered, like for all attribute values.
+///
+/// Fails if the element or document fails to render.
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, html::*};
+///
+/// let preview = html(body(p("<script> & \"quotes\""))).into_document();
+/// let frame = iframe((attr::srcdoc_element(&preview).unwrap(), attr::sandbox("")));
+/// assert_eq!(
+///     frame.render_to_string().unwrap(),
+///     concat!(
+///         r#"<iframe sandbox srcdoc="<!DOCTYPE html><html><body>"#,
+///         r#"<p>&amp;lt;script&amp;gt; &amp;amp; &quot;quotes&quot;</p>"#,
+///         r#"</body></html>"></iframe>"#,
+///     ),
+/// );
+/// ```
+pub fn srcdoc_element(element: &impl Render) -> crate::Result<Attr> {
+    let html = element.render_to_string()?;
+    Ok(Attr::set_static("srcdoc", html.replace('&', "&amp;")))
+}
+
 attr_set! {
     srclang as a "srclang";
     at url!(element "track", "srclang");
@@ -966,6 +1758,26 @@ attr_append! {
     at url!(global, "style");
 }
 
+/// Create (or append to) a `style` attribute like [`style`], but only if the
+/// value passes [`css::check_style`].
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, css::StyleError, html::*};
+///
+/// let p = p((attr::style_checked("color: red").unwrap(), "Hi"));
+/// assert_eq!(p.render_to_string().unwrap(), r#"<p style="color: red">Hi</p>"#);
+///
+/// let bad = attr::style_checked("background: url(javascript:alert(1))");
+/// assert_eq!(bad.unwrap_err(), StyleError::ScriptUrl);
+/// ```
+pub fn style_checked(value: impl ToString) -> Result<Attr, StyleError> {
+    let value = value.to_string();
+    css::check_style(&value)?;
+    Ok(style(value))
+}
+
 attr_set! {
     tabindex as a "tabindex";
     at url!(global, "tabindex");
@@ -992,12 +1804,23 @@ attr_set! {
 }
 
 attr_enum! {
-    Translate as a "translate";
+    Translate as a "translate", removed by Inherit;
     at url!(global, "translate");
-    Yes => "",
+    Yes => "yes",
     No => "no",
 }
 
+/// [`Translate::Yes`] for `true`, [`Translate::No`] for `false`.
+impl From<bool> for Translate {
+    fn from(value: bool) -> Self {
+        if value {
+            Self::Yes
+        } else {
+            Self::No
+        }
+    }
+}
+
 attr_set! {
     r#type as a "type";
     at concat!(
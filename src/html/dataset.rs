@@ -0,0 +1,168 @@
+//! Managing groups of [`data-*` attributes][mdn].
+//!
+//! A [`Dataset`] collects related data attributes and converts Rust-style
+//! names (`user_id`, `userId`) to the kebab-case names HTML expects
+//! (`data-user-id`), so attribute names don't have to be formatted by hand.
+//! The module is named after the DOM's [`dataset`][dataset] property, which
+//! performs the reverse conversion in JavaScript.
+//!
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/data-*
+//! [dataset]: https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement/dataset
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Render, html::{*, dataset::Dataset}};
+//!
+//! struct User {
+//!     id: u32,
+//!     display_name: String,
+//!     is_admin: bool,
+//! }
+//!
+//! impl From<&User> for Dataset {
+//!     fn from(user: &User) -> Self {
+//!         Dataset::prefixed("user")
+//!             .set("id", user.id)
+//!             .set("display_name", &user.display_name)
+//!             .set("is_admin", user.is_admin)
+//!     }
+//! }
+//!
+//! let user = User { id: 42, display_name: "Alice".to_string(), is_admin: false };
+//! let card = div((Dataset::from(&user), Dataset::new().set("sortKey", "a")));
+//! assert_eq!(
+//!     card.render_to_string().unwrap(),
+//!     concat!(
+//!         r#"<div data-sort-key="a" data-user-display-name="Alice" data-user-id="42" "#,
+//!         r#"data-user-is-admin="false"></div>"#,
+//!     ),
+//! );
+//! ```
+
+use crate::{Attr, Element, ElementComponent, IntoAttrValue};
+
+/// Convert a Rust-style name to kebab-case.
+///
+/// Underscores, spaces and dashes become single dashes, and a dash is inserted
+/// at every transition from a lowercase letter or digit to an uppercase letter.
+/// Acronyms are kept together. The result is in lowercase and never starts or
+/// ends with a dash.
+///
+/// # Example
+///
+/// ```
+/// use el::html::dataset::kebab_case;
+///
+/// assert_eq!(kebab_case("user_id"), "user-id");
+/// assert_eq!(kebab_case("userId"), "user-id");
+/// assert_eq!(kebab_case("HTMLParser"), "html-parser");
+/// assert_eq!(kebab_case("__private__field"), "private-field");
+/// ```
+pub fn kebab_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(name.len());
+    let mut dash = false;
+    for (i, &c) in chars.iter().enumerate() {
+        if matches!(c, '_' | '-' | ' ') {
+            dash = true;
+            continue;
+        }
+
+        if c.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next = chars.get(i + 1).copied();
+            if previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next.is_some_and(char::is_lowercase))
+            {
+                dash = true;
+            }
+        }
+
+        if dash && !result.is_empty() {
+            result.push('-');
+        }
+        dash = false;
+        result.extend(c.to_lowercase());
+    }
+    result
+}
+
+/// A group of `data-*` attributes, see the [module documentation](self).
+///
+/// Values are converted like in [`Attr::set`], so booleans are written as
+/// `"true"` and `"false"` and [`None`] removes the attribute. When the same
+/// name is set multiple times, the last value is used.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Dataset {
+    prefix: String,
+    attributes: Vec<(String, Option<String>)>,
+}
+
+impl Dataset {
+    /// Create a new empty dataset.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new empty dataset whose attribute names all start with a
+    /// prefix, e.g. `user` for `data-user-*`.
+    pub fn prefixed(prefix: &str) -> Self {
+        Self {
+            prefix: kebab_case(prefix),
+            attributes: vec![],
+        }
+    }
+
+    /// Add an attribute in-place. The name is converted using [`kebab_case`].
+    pub fn insert(&mut self, name: &str, value: impl IntoAttrValue) {
+        let name = match (&self.prefix[..], kebab_case(name)) {
+            ("", name) => format!("data-{name}"),
+            (prefix, name) if name.is_empty() => format!("data-{prefix}"),
+            (prefix, name) => format!("data-{prefix}-{name}"),
+        };
+        self.attributes.push((name, value.into_attr_value()));
+    }
+
+    /// A more builder-pattern-like version of [`Self::insert`].
+    pub fn set(mut self, name: &str, value: impl IntoAttrValue) -> Self {
+        self.insert(name, value);
+        self
+    }
+
+    /// The full attribute names and values, in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        (self.attributes.iter()).map(|(name, value)| (&name[..], value.as_deref()))
+    }
+
+    /// Whether the dataset contains no attributes.
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+}
+
+impl<K: AsRef<str>, V: IntoAttrValue> Extend<(K, V)> for Dataset {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (name, value) in iter {
+            self.insert(name.as_ref(), value);
+        }
+    }
+}
+
+/// Collects attributes from a map or list of pairs.
+impl<K: AsRef<str>, V: IntoAttrValue> FromIterator<(K, V)> for Dataset {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut dataset = Self::new();
+        dataset.extend(iter);
+        dataset
+    }
+}
+
+impl ElementComponent for Dataset {
+    fn add_to_element(self, element: &mut Element) {
+        for (name, value) in self.attributes {
+            element.add(Attr::set(name, value));
+        }
+    }
+}
@@ -0,0 +1,981 @@
+//! Convenience constructors for common combinations of elements and
+//! attributes.
+//!
+//! Most documents start with the same handful of elements in their `<head>`,
+//! and some form controls need a bundle of attributes to work well. The
+//! functions in this module construct them in a single call and take care of
+//! attribute combinations that are easy to forget.
+
+use std::fmt;
+
+use crate::{url::url_scheme, Attr, Element, ElementComponent, Encoding};
+
+use super::{attr, datalist, ids::IdRegistry, input, link, meta, noscript, option, script, small};
+
+/// A `<meta charset="utf-8">` element
+/// ([MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meta#charset)).
+///
+/// UTF-8 is the only encoding allowed by the standard.
+pub fn meta_charset_utf8() -> Element {
+    meta(attr::charset("utf-8"))
+}
+
+/// A `<meta charset>` element declaring a specific [`Encoding`]
+/// ([MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meta#charset)).
+///
+/// Use this together with [`crate::RenderOptions::encoding`] when rendering for
+/// legacy systems. Otherwise, prefer [`meta_charset_utf8`].
+pub fn meta_charset(encoding: Encoding) -> Element {
+    meta(attr::charset(encoding.label()))
+}
+
+/// A `<meta http-equiv="content-type">` element declaring the HTML MIME type
+/// and a specific [`Encoding`]
+/// ([MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meta#http-equiv)).
+///
+/// This is equivalent to [`meta_charset`], which should be preferred. A
+/// document must not contain both.
+///
+/// # Example
+///
+/// ```
+/// use el::{Encoding, Render, html::helpers::http_equiv_content_type};
+///
+/// assert_eq!(
+///     http_equiv_content_type(Encoding::Utf8).render_to_string().unwrap(),
+///     r#"<meta content="text/html; charset=utf-8" http-equiv="content-type">"#,
+/// );
+/// ```
+pub fn http_equiv_content_type(encoding: Encoding) -> Element {
+    meta((
+        attr::HttpEquiv::ContentType,
+        attr::content(format!("text/html; charset={}", encoding.label())),
+    ))
+}
+
+/// A `<meta http-equiv="x-ua-compatible" content="IE=edge">` element, telling
+/// legacy versions of Internet Explorer to use their most modern rendering
+/// engine.
+pub fn x_ua_compatible_edge() -> Element {
+    meta((attr::HttpEquiv::XUaCompatible, attr::content("IE=edge")))
+}
+
+/// A `<meta http-equiv="default-style">` element selecting the alternative
+/// style sheet with the given title as the preferred one
+/// ([MDN](https://developer.mozilla.org/en-US/docs/Web/CSS/Alternative_style_sheets)).
+pub fn default_style(title: impl ToString) -> Element {
+    meta((attr::HttpEquiv::DefaultStyle, attr::content(title)))
+}
+
+/// A `<meta name="viewport">` element with the content
+/// `width=device-width, initial-scale=1`.
+///
+/// This is the viewport configuration recommended for most responsive pages.
+/// Use [`meta_viewport`] for a custom configuration.
+pub fn meta_viewport_default() -> Element {
+    meta_viewport(Viewport::new().width("device-width").initial_scale(1.0))
+}
+
+/// A `<meta name="viewport">` element with a custom configuration
+/// ([MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Viewport_meta_tag)).
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, html::helpers::*};
+///
+/// let viewport = Viewport::new()
+///     .width("device-width")
+///     .initial_scale(1.0)
+///     .user_scalable(false);
+///
+/// assert_eq!(
+///     meta_viewport(viewport).render_to_string().unwrap(),
+///     r#"<meta content="width=device-width, initial-scale=1, user-scalable=no" name="viewport">"#,
+/// );
+/// ```
+pub fn meta_viewport(viewport: Viewport) -> Element {
+    meta((attr::name("viewport"), attr::content(viewport)))
+}
+
+/// The configuration of a `<meta name="viewport">` element, see
+/// [`meta_viewport`].
+///
+/// Properties that are not set are omitted from the rendered value.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Viewport {
+    width: Option<String>,
+    height: Option<String>,
+    initial_scale: Option<f64>,
+    minimum_scale: Option<f64>,
+    maximum_scale: Option<f64>,
+    user_scalable: Option<bool>,
+    interactive_widget: Option<String>,
+}
+
+impl Viewport {
+    /// Create a new empty viewport configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the `width` property, usually to `device-width` or a pixel value.
+    pub fn width(mut self, width: impl ToString) -> Self {
+        self.width = Some(width.to_string());
+        self
+    }
+
+    /// Set the `height` property, usually to `device-height` or a pixel value.
+    pub fn height(mut self, height: impl ToString) -> Self {
+        self.height = Some(height.to_string());
+        self
+    }
+
+    /// Set the `initial-scale` property.
+    pub fn initial_scale(mut self, scale: f64) -> Self {
+        self.initial_scale = Some(scale);
+        self
+    }
+
+    /// Set the `minimum-scale` property.
+    pub fn minimum_scale(mut self, scale: f64) -> Self {
+        self.minimum_scale = Some(scale);
+        self
+    }
+
+    /// Set the `maximum-scale` property.
+    pub fn maximum_scale(mut self, scale: f64) -> Self {
+        self.maximum_scale = Some(scale);
+        self
+    }
+
+    /// Set the `user-scalable` property.
+    pub fn user_scalable(mut self, scalable: bool) -> Self {
+        self.user_scalable = Some(scalable);
+        self
+    }
+
+    /// Set the `interactive-widget` property, e.g. to `resizes-content`.
+    pub fn interactive_widget(mut self, behaviour: impl ToString) -> Self {
+        self.interactive_widget = Some(behaviour.to_string());
+        self
+    }
+}
+
+impl fmt::Display for Viewport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let properties = [
+            ("width", self.width.clone()),
+            ("height", self.height.clone()),
+            ("initial-scale", self.initial_scale.map(|s| s.to_string())),
+            ("minimum-scale", self.minimum_scale.map(|s| s.to_string())),
+            ("maximum-scale", self.maximum_scale.map(|s| s.to_string())),
+            (
+                "user-scalable",
+                self.user_scalable
+                    .map(|s| if s { "yes" } else { "no" }.to_string()),
+            ),
+            ("interactive-widget", self.interactive_widget.clone()),
+        ];
+
+        let mut first = true;
+        for (name, value) in properties {
+            let Some(value) = value else { continue };
+            if !first {
+                write!(f, ", ")?;
+            }
+            first = false;
+            write!(f, "{name}={value}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A `<meta name="robots">` element telling search engines how to index the
+/// page
+/// ([Google](https://developers.google.com/search/docs/crawling-indexing/robots-meta-tag)).
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, html::helpers::*};
+///
+/// let robots = Robots::new()
+///     .nofollow()
+///     .max_snippet(50)
+///     .max_image_preview(ImagePreview::Large);
+///
+/// assert_eq!(
+///     meta_robots(robots).render_to_string().unwrap(),
+///     r#"<meta content="nofollow, max-snippet:50, max-image-preview:large" name="robots">"#,
+/// );
+/// ```
+pub fn meta_robots(robots: Robots) -> Element {
+    meta((attr::name("robots"), attr::content(robots)))
+}
+
+/// Like [`meta_robots`], but only for a specific crawler, e.g. `googlebot`.
+///
+/// Crawlers combine the directives for them with the ones in
+/// [`meta_robots`], using the most restrictive ones.
+pub fn meta_robots_for(crawler: impl ToString, robots: Robots) -> Element {
+    meta((attr::name(crawler), attr::content(robots)))
+}
+
+/// A `<meta name="description">` element with a short summary of the page,
+/// which search engines may show in their results.
+pub fn meta_description(description: impl ToString) -> Element {
+    meta((attr::name("description"), attr::content(description)))
+}
+
+/// A `<link rel="canonical">` element declaring the preferred URL of a page
+/// that is reachable under several URLs
+/// ([Google](https://developers.google.com/search/docs/crawling-indexing/consolidate-duplicate-urls)).
+pub fn link_canonical(href: impl ToString) -> Element {
+    link((attr::Rel::Canonical, attr::href(href)))
+}
+
+/// The maximum size of image previews in search results, see
+/// [`Robots::max_image_preview`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImagePreview {
+    /// No image preview.
+    None,
+    /// A default image preview.
+    Standard,
+    /// A larger image preview, up to the width of the viewport.
+    Large,
+}
+
+impl fmt::Display for ImagePreview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::None => "none",
+            Self::Standard => "standard",
+            Self::Large => "large",
+        })
+    }
+}
+
+/// The directives of a `<meta name="robots">` element, see [`meta_robots`].
+///
+/// Directives that are not set are omitted from the rendered value. Without
+/// any directives, the value is `all`, which is also the default behaviour
+/// of search engines.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Robots {
+    noindex: bool,
+    nofollow: bool,
+    noarchive: bool,
+    nosnippet: bool,
+    noimageindex: bool,
+    notranslate: bool,
+    max_snippet: Option<i32>,
+    max_image_preview: Option<ImagePreview>,
+    max_video_preview: Option<i32>,
+    unavailable_after: Option<String>,
+}
+
+impl Robots {
+    /// Create a new configuration without any directives.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Don't show the page in search results, the same as `noindex` and
+    /// `nofollow` together.
+    pub fn none() -> Self {
+        Self::new().noindex().nofollow()
+    }
+
+    /// Add the `noindex` directive: Don't show the page in search results.
+    pub fn noindex(mut self) -> Self {
+        self.noindex = true;
+        self
+    }
+
+    /// Add the `nofollow` directive: Don't follow the links on the page.
+    pub fn nofollow(mut self) -> Self {
+        self.nofollow = true;
+        self
+    }
+
+    /// Add the `noarchive` directive: Don't show a cached copy of the page.
+    pub fn noarchive(mut self) -> Self {
+        self.noarchive = true;
+        self
+    }
+
+    /// Add the `nosnippet` directive: Don't show a text snippet or video
+    /// preview in search results.
+    pub fn nosnippet(mut self) -> Self {
+        self.nosnippet = true;
+        self
+    }
+
+    /// Add the `noimageindex` directive: Don't index the images on the page.
+    pub fn noimageindex(mut self) -> Self {
+        self.noimageindex = true;
+        self
+    }
+
+    /// Add the `notranslate` directive: Don't offer a translation of the page
+    /// in search results.
+    pub fn notranslate(mut self) -> Self {
+        self.notranslate = true;
+        self
+    }
+
+    /// Set the `max-snippet` directive: The maximum number of characters of
+    /// a text snippet. `0` is the same as [`Self::nosnippet`] and `-1` means
+    /// no limit.
+    pub fn max_snippet(mut self, characters: i32) -> Self {
+        self.max_snippet = Some(characters);
+        self
+    }
+
+    /// Set the `max-image-preview` directive.
+    pub fn max_image_preview(mut self, preview: ImagePreview) -> Self {
+        self.max_image_preview = Some(preview);
+        self
+    }
+
+    /// Set the `max-video-preview` directive: The maximum number of seconds
+    /// of a video preview. `-1` means no limit.
+    pub fn max_video_preview(mut self, seconds: i32) -> Self {
+        self.max_video_preview = Some(seconds);
+        self
+    }
+
+    /// Set the `unavailable_after` directive: Don't show the page in search
+    /// results after a date, preferably in ISO 8601 format (e.g.
+    /// `2025-12-31`).
+    pub fn unavailable_after(mut self, date: impl ToString) -> Self {
+        self.unavailable_after = Some(date.to_string());
+        self
+    }
+}
+
+impl fmt::Display for Robots {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flags = [
+            ("noindex", self.noindex),
+            ("nofollow", self.nofollow),
+            ("noarchive", self.noarchive),
+            ("nosnippet", self.nosnippet),
+            ("noimageindex", self.noimageindex),
+            ("notranslate", self.notranslate),
+        ];
+        let directives = (flags.into_iter())
+            .filter(|(_, set)| *set)
+            .map(|(name, _)| name.to_string())
+            .chain(self.max_snippet.map(|n| format!("max-snippet:{n}")))
+            .chain((self.max_image_preview).map(|p| format!("max-image-preview:{p}")))
+            .chain((self.max_video_preview).map(|n| format!("max-video-preview:{n}")))
+            .chain((self.unavailable_after.as_ref()).map(|d| format!("unavailable_after:{d}")))
+            .collect::<Vec<_>>();
+
+        if directives.is_empty() {
+            write!(f, "all")
+        } else {
+            write!(f, "{}", directives.join(", "))
+        }
+    }
+}
+
+/// A `<meta http-equiv="refresh">` element reloading the page after a number of
+/// seconds, or redirecting to another URL
+/// ([MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meta#http-equiv)).
+///
+/// The URL is quoted, and quotes, whitespace and control characters inside it
+/// are percent-encoded, so browsers always parse it the same way.
+///
+/// Returns [`None`] if the URL is empty or uses a scheme that can execute code
+/// (`javascript:`, `vbscript:` or `data:`). Since [`Option`] is an
+/// [`crate::ElementComponent`], the result can be added to an element directly.
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, html::helpers::meta_refresh};
+///
+/// assert_eq!(
+///     meta_refresh(3, Some("/next page")).unwrap().render_to_string().unwrap(),
+///     r#"<meta content="3; url='/next%20page'" http-equiv="refresh">"#,
+/// );
+/// assert!(meta_refresh(0, Some("javascript:alert(1)")).is_none());
+/// ```
+pub fn meta_refresh(seconds: u32, url: Option<&str>) -> Option<Element> {
+    let content = match url {
+        None => seconds.to_string(),
+        Some(url) => {
+            let url = url.trim_matches(|c: char| c.is_ascii_whitespace() || c.is_control());
+            if url.is_empty() || has_unsafe_scheme(url) {
+                return None;
+            }
+
+            let mut encoded = String::with_capacity(url.len());
+            for c in url.chars() {
+                match c {
+                    '\'' | '"' => encoded.push_str(&format!("%{:02X}", c as u32)),
+                    c if c.is_whitespace() || c.is_control() => {
+                        let mut buf = [0; 4];
+                        for byte in c.encode_utf8(&mut buf).bytes() {
+                            encoded.push_str(&format!("%{byte:02X}"));
+                        }
+                    }
+                    c => encoded.push(c),
+                }
+            }
+            format!("{seconds}; url='{encoded}'")
+        }
+    };
+    Some(meta((attr::HttpEquiv::Refresh, attr::content(content))))
+}
+
+/// Whether a URL uses a scheme that can execute code when navigated to.
+fn has_unsafe_scheme(url: &str) -> bool {
+    matches!(
+        url_scheme(url).as_deref(),
+        Some("javascript" | "vbscript" | "data")
+    )
+}
+
+/// Append query parameters to a URL, e.g. for [`attr::href`] or
+/// [`attr::action`].
+///
+/// Keys and values are percent-encoded, so they can contain any characters,
+/// including `&`, `=`, `#` and non-ASCII text. Only the unreserved characters
+/// `A-Z`, `a-z`, `0-9`, `-`, `.`, `_` and `~` are left as they are, and spaces
+/// become `%20`. The base URL is not changed. If it already has a query, the
+/// parameters are appended to it, and if it has a fragment, they are inserted
+/// before it.
+///
+/// Browsers drop the query of the `action` of forms submitted with `GET`, so
+/// use hidden inputs there instead.
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, html::{*, helpers::href_with_query}};
+///
+/// let url = href_with_query("/search", [("q", "fish & chips"), ("page", "2")]);
+/// assert_eq!(url, "/search?q=fish%20%26%20chips&page=2");
+/// assert_eq!(
+///     a((attr::href(url), "Next")).render_to_string().unwrap(),
+///     r#"<a href="/search?q=fish%20%26%20chips&page=2">Next</a>"#,
+/// );
+///
+/// assert_eq!(href_with_query("/a?x=1#top", [("ä", "=")]), "/a?x=1&%C3%A4=%3D#top");
+/// assert_eq!(href_with_query("/a", [] as [(&str, &str); 0]), "/a");
+/// ```
+pub fn href_with_query(
+    base: &str,
+    pairs: impl IntoIterator<Item = (impl AsRef<str>, impl AsRef<str>)>,
+) -> String {
+    let (base, fragment) = match base.find('#') {
+        Some(i) => base.split_at(i),
+        None => (base, ""),
+    };
+
+    let mut url = base.to_string();
+    for (key, value) in pairs {
+        if !url.contains('?') {
+            url.push('?');
+        } else if !url.ends_with(['?', '&']) {
+            url.push('&');
+        }
+        percent_encode(&mut url, key.as_ref());
+        url.push('=');
+        percent_encode(&mut url, value.as_ref());
+    }
+    url.push_str(fragment);
+    url
+}
+
+/// Percent-encode all but the unreserved characters of a URL component.
+fn percent_encode(url: &mut String, component: &str) {
+    for byte in component.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            url.push(byte as char);
+        } else {
+            url.push_str(&format!("%{byte:02X}"));
+        }
+    }
+}
+
+/// A `<link rel="stylesheet">` element loading the stylesheet at `href`.
+pub fn stylesheet(href: impl ToString) -> Element {
+    link((attr::Rel::Stylesheet, attr::href(href)))
+}
+
+/// A `<link rel="preload">` element preloading the resource at `href`
+/// ([MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Attributes/rel/preload)).
+///
+/// Fonts and fetches are always requested in CORS mode by the browser, so a
+/// preload for them must include the `crossorigin` attribute. Otherwise, the
+/// preloaded response won't match the actual request and the resource is
+/// downloaded twice. This function adds the attribute where necessary.
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, html::{attr::As, helpers::preload}};
+///
+/// assert_eq!(
+///     preload("/font.woff2", As::Font).render_to_string().unwrap(),
+///     r#"<link as="font" crossorigin="anonymous" href="/font.woff2" rel="preload">"#,
+/// );
+/// ```
+pub fn preload(href: impl ToString, r#as: attr::As) -> Element {
+    let crossorigin = match &r#as {
+        attr::As::Font | attr::As::Fetch => Some(attr::Crossorigin::Anonymous),
+        _ => None,
+    };
+    link((attr::Rel::Preload, attr::href(href), r#as, crossorigin))
+}
+
+/// A `<link rel="modulepreload">` element preloading the JS module at `href`.
+pub fn modulepreload(href: impl ToString) -> Element {
+    link((attr::Rel::Modulepreload, attr::href(href)))
+}
+
+/// A `<script type="module">` element loading the JS module at `src`.
+///
+/// Module scripts are deferred by default, so no `defer` attribute is added.
+pub fn module_script(src: impl ToString) -> Element {
+    script((attr::TypeScript::Module, attr::src(src)))
+}
+
+/// An `<input type="file">` element with its constraints, followed by a
+/// `<noscript>` note describing them.
+///
+/// The `accept` list restricts the files offered by the browser's file picker.
+/// Browsers have no built-in limit for the file size, so `max_size_hint` (in
+/// bytes) is only added as a `data-max-size` attribute for client-side scripts
+/// to check. Since neither constraint is enforced without scripts, the
+/// constraints are also described in a `<noscript>` element, which is omitted
+/// if there are none. The server must validate uploads regardless.
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, html::{attr::Accept, form, helpers::file_input}};
+///
+/// let accept = Accept::parse("image/png, image/jpeg").unwrap();
+/// let input = file_input("photos", accept, true, Some(5 * 1024 * 1024));
+/// assert_eq!(
+///     form(input).render_to_string().unwrap(),
+///     concat!(
+///         r#"<form><input accept="image/png, image/jpeg" data-max-size="5242880" "#,
+///         r#"multiple name="photos" type="file">"#,
+///         "<noscript><small>Accepted file types: image/png, image/jpeg. ",
+///         "Maximum size per file: 5 MiB.</small></noscript></form>",
+///     ),
+/// );
+/// ```
+pub fn file_input(
+    name: impl ToString,
+    accept: attr::Accept,
+    multiple: bool,
+    max_size_hint: Option<u64>,
+) -> Vec<Element> {
+    let mut note = vec![];
+    if !accept.is_empty() {
+        note.push(format!("Accepted file types: {accept}."));
+    }
+    if let Some(max_size) = max_size_hint {
+        let size = file_size(max_size);
+        if multiple {
+            note.push(format!("Maximum size per file: {size}."));
+        } else {
+            note.push(format!("Maximum file size: {size}."));
+        }
+    }
+
+    let input = input((
+        attr::TypeInput::File,
+        attr::name(name),
+        accept,
+        multiple.then(attr::multiple),
+        max_size_hint.map(|size| attr::data_x("max-size", size)),
+    ));
+    if note.is_empty() {
+        vec![input]
+    } else {
+        vec![input, noscript(small(note.join(" ")))]
+    }
+}
+
+/// An `<input>` element offering a list of suggestions, followed by the
+/// `<datalist>` element containing them
+/// ([MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/datalist)).
+///
+/// The input's `list` attribute is set to the id of the datalist, which is
+/// generated from the input's `id` or `name` (falling back to `list`) using
+/// the registry, so it is unique in the document. Duplicate suggestions are
+/// only included once. Any kind of input works, but suggestions are most
+/// useful for [search](attr::TypeInput::Search), text, email and URL inputs.
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, html::{*, helpers::datalist_input, ids::IdRegistry}};
+///
+/// let mut ids = IdRegistry::new();
+/// let search = input((attr::TypeInput::Search, attr::name("q")));
+/// let form = form(datalist_input(&mut ids, search, ["rust", "html", "rust"]));
+/// assert_eq!(
+///     form.render_to_string().unwrap(),
+///     concat!(
+///         r#"<form><input list="q-list" name="q" type="search">"#,
+///         r#"<datalist id="q-list"><option value="rust"></option><option value="html"></option></datalist>"#,
+///         "</form>",
+///     ),
+/// );
+/// ```
+pub fn datalist_input(
+    ids: &mut IdRegistry,
+    input: Element,
+    suggestions: impl IntoIterator<Item = impl ToString>,
+) -> Vec<Element> {
+    let base = match input.attr_ci("id").or(input.attr_ci("name")) {
+        Some(base) if !base.is_empty() => format!("{base}-list"),
+        _ => "list".to_string(),
+    };
+    let id = ids.unique(base);
+
+    let mut values = vec![];
+    for suggestion in suggestions {
+        let suggestion = suggestion.to_string();
+        if !values.contains(&suggestion) {
+            values.push(suggestion);
+        }
+    }
+    let options = values.into_iter().map(|value| option(attr::value(value)));
+
+    vec![
+        input.with(attr::list(&id)),
+        datalist((attr::id(id), options.collect::<Vec<_>>())),
+    ]
+}
+
+/// The attribute marking an element that only works with scripts enabled, see
+/// [`with_noscript`].
+pub const REQUIRES_SCRIPT: &str = "data-requires-script";
+
+/// An attribute marking an element that only works with scripts enabled, see
+/// [`with_noscript`].
+pub fn requires_script() -> Attr {
+    Attr::yes(REQUIRES_SCRIPT)
+}
+
+/// A widget that requires scripts, followed by a `<noscript>` element
+/// containing a fallback for visitors without scripts.
+///
+/// The widget is marked with the [`REQUIRES_SCRIPT`] attribute, which can be
+/// used to hide it using CSS while scripts are unavailable. The
+/// [`NoscriptFallback`](crate::lint::NoscriptFallback) lint checks that every
+/// marked widget has a fallback.
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, html::{*, helpers::with_noscript}};
+///
+/// let map = with_noscript(
+///     div(attr::id("map")),
+///     a((attr::href("/locations"), "List of locations")),
+/// );
+/// assert_eq!(
+///     main(map).render_to_string().unwrap(),
+///     concat!(
+///         r#"<main><div data-requires-script id="map"></div>"#,
+///         r#"<noscript><a href="/locations">List of locations</a></noscript></main>"#,
+///     ),
+/// );
+/// ```
+pub fn with_noscript(widget: Element, fallback: impl ElementComponent) -> Vec<Element> {
+    vec![widget.with(requires_script()), noscript(fallback)]
+}
+
+/// Format a number of bytes for humans, using binary units.
+fn file_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["bytes", "KiB", "MiB", "GiB", "TiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    let size = format!("{size:.1}");
+    let size = size.strip_suffix(".0").unwrap_or(&size);
+    format!("{size} {}", UNITS[unit])
+}
+
+/// The `<link>` and `<meta>` elements for a set of icons, see [`Icons`].
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, html::{head, helpers::*}};
+///
+/// let icons = Icons::new("/static")
+///     .favicon_ico()
+///     .svg()
+///     .png_sizes([32])
+///     .apple_touch_icon()
+///     .manifest()
+///     .theme_color("#336699");
+///
+/// assert_eq!(
+///     head(icons_set(icons)).render_to_string().unwrap(),
+///     concat!(
+///         "<head>",
+///         r#"<link href="/static/favicon.ico" rel="icon" sizes="32x32">"#,
+///         r#"<link href="/static/icon.svg" rel="icon" type="image/svg+xml">"#,
+///         r#"<link href="/static/favicon-32x32.png" rel="icon" sizes="32x32" type="image/png">"#,
+///         r#"<link href="/static/apple-touch-icon.png" rel="apple-touch-icon">"#,
+///         r#"<link href="/static/site.webmanifest" rel="manifest">"#,
+///         r##"<meta content="#336699" name="theme-color">"##,
+///         "</head>",
+///     ),
+/// );
+/// ```
+pub fn icons_set(icons: Icons) -> Vec<Element> {
+    let base = icons.base.trim_end_matches('/');
+    let mut elements = vec![];
+
+    if icons.favicon_ico {
+        elements.push(link((
+            attr::Rel::Icon,
+            attr::href(format!("{base}/favicon.ico")),
+            attr::sizes_link("32x32"),
+        )));
+    }
+    if icons.svg {
+        elements.push(link((
+            attr::Rel::Icon,
+            attr::href(format!("{base}/icon.svg")),
+            attr::r#type("image/svg+xml"),
+        )));
+    }
+    for size in icons.png_sizes {
+        elements.push(link((
+            attr::Rel::Icon,
+            attr::href(format!("{base}/favicon-{size}x{size}.png")),
+            attr::sizes_link(format!("{size}x{size}")),
+            attr::r#type("image/png"),
+        )));
+    }
+    if icons.apple_touch_icon {
+        elements.push(link((
+            attr::Rel::other("apple-touch-icon"),
+            attr::href(format!("{base}/apple-touch-icon.png")),
+        )));
+    }
+    if let Some(color) = icons.mask_icon {
+        elements.push(link((
+            attr::Rel::other("mask-icon"),
+            attr::href(format!("{base}/safari-pinned-tab.svg")),
+            Attr::set("color", color),
+        )));
+    }
+    if icons.manifest {
+        elements.push(link((
+            attr::Rel::Manifest,
+            attr::href(format!("{base}/site.webmanifest")),
+        )));
+    }
+    for (color, media) in icons.theme_colors {
+        elements.push(meta((
+            attr::name("theme-color"),
+            attr::content(color),
+            media.map(attr::media),
+        )));
+    }
+
+    elements
+}
+
+/// The configuration of a set of icons, see [`icons_set`].
+///
+/// All files are expected in the same directory, using the names most icon
+/// generators produce:
+///
+/// | Method                     | File                    |
+/// |----------------------------|-------------------------|
+/// | [`Self::favicon_ico`]      | `favicon.ico`           |
+/// | [`Self::svg`]              | `icon.svg`              |
+/// | [`Self::png_sizes`]        | `favicon-NxN.png`       |
+/// | [`Self::apple_touch_icon`] | `apple-touch-icon.png`  |
+/// | [`Self::mask_icon`]        | `safari-pinned-tab.svg` |
+/// | [`Self::manifest`]         | `site.webmanifest`      |
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Icons {
+    base: String,
+    favicon_ico: bool,
+    svg: bool,
+    png_sizes: Vec<u32>,
+    apple_touch_icon: bool,
+    mask_icon: Option<String>,
+    manifest: bool,
+    theme_colors: Vec<(String, Option<String>)>,
+}
+
+impl Icons {
+    /// Create a new configuration without any icons, for files in the
+    /// directory at `base` (e.g. `/static` or `https://cdn.example.com`).
+    pub fn new(base: impl ToString) -> Self {
+        Self {
+            base: base.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Include a `favicon.ico` file, the fallback for older browsers.
+    pub fn favicon_ico(mut self) -> Self {
+        self.favicon_ico = true;
+        self
+    }
+
+    /// Include an `icon.svg` file, preferred by modern browsers.
+    pub fn svg(mut self) -> Self {
+        self.svg = true;
+        self
+    }
+
+    /// Include square PNG icons in the given sizes (in pixels).
+    pub fn png_sizes(mut self, sizes: impl IntoIterator<Item = u32>) -> Self {
+        self.png_sizes.extend(sizes);
+        self
+    }
+
+    /// Include an `apple-touch-icon.png` file, usually 180×180 pixels.
+    pub fn apple_touch_icon(mut self) -> Self {
+        self.apple_touch_icon = true;
+        self
+    }
+
+    /// Include a monochrome `safari-pinned-tab.svg` file, displayed in the
+    /// given color.
+    pub fn mask_icon(mut self, color: impl ToString) -> Self {
+        self.mask_icon = Some(color.to_string());
+        self
+    }
+
+    /// Include a `site.webmanifest` file
+    /// ([MDN](https://developer.mozilla.org/en-US/docs/Web/Manifest)).
+    pub fn manifest(mut self) -> Self {
+        self.manifest = true;
+        self
+    }
+
+    /// Set the theme color used by browsers to style the surrounding UI.
+    pub fn theme_color(mut self, color: impl ToString) -> Self {
+        self.theme_colors.push((color.to_string(), None));
+        self
+    }
+
+    /// Set the theme color for a media query, e.g.
+    /// `(prefers-color-scheme: dark)`.
+    pub fn theme_color_for(mut self, color: impl ToString, media: impl ToString) -> Self {
+        self.theme_colors
+            .push((color.to_string(), Some(media.to_string())));
+        self
+    }
+}
+
+/// The `<link rel="alternate" hreflang="...">` elements for a page that is
+/// available in several languages
+/// ([Google](https://developers.google.com/search/docs/specialty/international/localized-versions)).
+///
+/// Every version of the page should contain the same set of links, including
+/// one to itself. The `x_default` URL is linked as `hreflang="x-default"`
+/// and is used for all other languages, e.g. a language selection page.
+///
+/// Language codes consist of a language (e.g. `en`), optionally followed by a
+/// script (e.g. `zh-Hant`) and a region (e.g. `en-GB` or `es-419`). Their case
+/// is normalized, and `_` is accepted instead of `-`. Returns [`None`] if a
+/// code is invalid or appears more than once, which search engines would
+/// otherwise silently ignore.
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, html::{head, helpers::hreflang_alternates}};
+///
+/// let links = hreflang_alternates(
+///     [("en", "https://example.com/en/"), ("de-de", "https://example.com/de/")],
+///     "https://example.com/",
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     head(links).render_to_string().unwrap(),
+///     concat!(
+///         "<head>",
+///         r#"<link href="https://example.com/en/" hreflang="en" rel="alternate">"#,
+///         r#"<link href="https://example.com/de/" hreflang="de-DE" rel="alternate">"#,
+///         r#"<link href="https://example.com/" hreflang="x-default" rel="alternate">"#,
+///         "</head>",
+///     ),
+/// );
+///
+/// assert!(hreflang_alternates([("english", "/en/")], "/").is_none());
+/// assert!(hreflang_alternates([("en", "/en/"), ("EN", "/en-2/")], "/").is_none());
+/// ```
+pub fn hreflang_alternates(
+    alternates: impl IntoIterator<Item = (impl AsRef<str>, impl ToString)>,
+    x_default: impl ToString,
+) -> Option<Vec<Element>> {
+    let mut codes = vec![];
+    let mut elements = vec![];
+    for (code, url) in alternates {
+        let code = normalize_hreflang(code.as_ref())?;
+        if codes.contains(&code) {
+            return None;
+        }
+        codes.push(code.clone());
+        elements.push(alternate(code, url));
+    }
+    elements.push(alternate("x-default", x_default));
+    Some(elements)
+}
+
+fn alternate(code: impl ToString, url: impl ToString) -> Element {
+    link((attr::Rel::Alternate, attr::hreflang(code), attr::href(url)))
+}
+
+/// Validate a language code for [`hreflang_alternates`] and normalize its
+/// case, e.g. `zh-hant-tw` to `zh-Hant-TW`.
+fn normalize_hreflang(code: &str) -> Option<String> {
+    let mut subtags = code.split(['-', '_']);
+    let language = subtags.next()?;
+    if !(2..=3).contains(&language.len()) || !language.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return None;
+    }
+    let mut result = language.to_ascii_lowercase();
+
+    let mut subtag = subtags.next();
+    if let Some(script) = subtag.filter(|s| s.len() == 4) {
+        if !script.bytes().all(|b| b.is_ascii_alphabetic()) {
+            return None;
+        }
+        result.push('-');
+        result.push_str(&script[..1].to_ascii_uppercase());
+        result.push_str(&script[1..].to_ascii_lowercase());
+        subtag = subtags.next();
+    }
+    if let Some(region) = subtag {
+        let alpha = region.len() == 2 && region.bytes().all(|b| b.is_ascii_alphabetic());
+        let numeric = region.len() == 3 && region.bytes().all(|b| b.is_ascii_digit());
+        if !alpha && !numeric {
+            return None;
+        }
+        result.push('-');
+        result.push_str(&region.to_ascii_uppercase());
+    }
+
+    subtags.next().is_none().then_some(result)
+}
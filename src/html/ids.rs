@@ -0,0 +1,95 @@
+//! Generating unique `id` attributes.
+//!
+//! Many elements are wired together by ids, e.g. a `<label for>` and its
+//! control, or an `<input list>` and its `<datalist>`. If the same component
+//! is used twice on a page, hard-coded ids collide and the second copy is
+//! silently wired to the first. An [`IdRegistry`] remembers the ids that are
+//! already in use and hands out new ones that don't collide.
+//!
+//! # Example
+//!
+//! ```
+//! use el::html::{*, ids::IdRegistry};
+//!
+//! let existing = main(section(attr::id("search")));
+//! let mut ids = IdRegistry::from_element(&existing);
+//! assert_eq!(ids.unique("search"), "search-2");
+//! assert_eq!(ids.unique("search"), "search-3");
+//! assert_eq!(ids.unique("results"), "results");
+//! ```
+
+use std::collections::HashSet;
+
+use crate::{Content, Element};
+
+/// The ids in use in a document, see the [module documentation](self).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IdRegistry {
+    used: HashSet<String>,
+}
+
+impl IdRegistry {
+    /// Create a new registry without any ids.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry containing the ids of an element and all its
+    /// descendants, including those in [`Content::Trailer`]s.
+    pub fn from_element(element: &Element) -> Self {
+        let mut registry = Self::new();
+        registry.add_element(element);
+        registry
+    }
+
+    fn add_element(&mut self, element: &Element) {
+        if let Some(id) = element.attr_ci("id") {
+            self.reserve(id);
+        }
+        for child in &element.children {
+            let mut child = child;
+            while let Content::Trailer(content) = child {
+                child = content;
+            }
+            if let Content::Element(child) = child {
+                self.add_element(child);
+            }
+        }
+    }
+
+    /// Mark an id as used.
+    ///
+    /// Returns whether the id was unused before.
+    pub fn reserve(&mut self, id: impl ToString) -> bool {
+        self.used.insert(id.to_string())
+    }
+
+    /// Whether an id is used.
+    pub fn contains(&self, id: &str) -> bool {
+        self.used.contains(id)
+    }
+
+    /// Generate and reserve an unused id based on `base`.
+    ///
+    /// The id is `base` itself if it is unused, or `base` followed by the
+    /// smallest suffix `-2`, `-3`, ... that makes it unique otherwise. Since
+    /// ids must not contain whitespace, whitespace in `base` is replaced by
+    /// `-`. An empty `base` is replaced by `id`.
+    pub fn unique(&mut self, base: impl AsRef<str>) -> String {
+        let base = base
+            .as_ref()
+            .replace(|c: char| c.is_ascii_whitespace(), "-");
+        let base = if base.is_empty() {
+            "id".to_string()
+        } else {
+            base
+        };
+        let mut id = base.clone();
+        let mut n = 1;
+        while !self.reserve(&id) {
+            n += 1;
+            id = format!("{base}-{n}");
+        }
+        id
+    }
+}
@@ -0,0 +1,119 @@
+//! Builders for [microdata] items.
+//!
+//! Microdata annotates elements with machine-readable properties using the
+//! `itemscope`, `itemtype` and `itemprop` attributes. An [`Item`] keeps these
+//! attributes consistent: properties are always bound to an element inside
+//! the item they belong to.
+//!
+//! [microdata]: https://html.spec.whatwg.org/multipage/microdata.html
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Render, html::{*, microdata::Item}};
+//!
+//! let person = div(
+//!     Item::new("https://schema.org/Person")
+//!         .prop("name", h2("Alice"))
+//!         .content(" works at ")
+//!         .prop(
+//!             "worksFor",
+//!             span(Item::new("https://schema.org/Organization").prop("name", span("ACME"))),
+//!         )
+//!         .meta("birthDate", "1970-01-01"),
+//! );
+//!
+//! assert_eq!(
+//!     person.render_to_string().unwrap(),
+//!     concat!(
+//!         r#"<div itemscope itemtype="https://schema.org/Person">"#,
+//!         r#"<h2 itemprop="name">Alice</h2> works at "#,
+//!         r#"<span itemprop="worksFor" itemscope itemtype="https://schema.org/Organization">"#,
+//!         r#"<span itemprop="name">ACME</span></span>"#,
+//!         r#"<meta content="1970-01-01" itemprop="birthDate"></div>"#,
+//!     ),
+//! );
+//! ```
+
+use crate::{Attr, Content, Element, ElementComponent};
+
+use super::{attr, link, meta};
+
+/// A microdata item with its properties, see the [module
+/// documentation](self).
+///
+/// When added to an element, the element becomes the item's scope and the
+/// properties are appended as its children.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Item {
+    types: Vec<String>,
+    id: Option<String>,
+    children: Vec<Content>,
+}
+
+impl Item {
+    /// Create a new item of a type, e.g. `https://schema.org/Person`.
+    pub fn new(itemtype: impl ToString) -> Self {
+        Self::untyped().with_type(itemtype)
+    }
+
+    /// Create a new item without a type.
+    pub fn untyped() -> Self {
+        Self::default()
+    }
+
+    /// Add another type to the item.
+    ///
+    /// All types of an item must come from the same vocabulary.
+    pub fn with_type(mut self, itemtype: impl ToString) -> Self {
+        self.types.push(itemtype.to_string());
+        self
+    }
+
+    /// Set the global identifier of the item (`itemid`).
+    pub fn id(mut self, id: impl ToString) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    /// Add a property whose value is the element.
+    ///
+    /// To add an item as the value of a property, add the nested [`Item`] to
+    /// the element.
+    pub fn prop(mut self, name: impl ToString, element: Element) -> Self {
+        let element = element.with(Attr::append_static("itemprop", name, " "));
+        self.children.push(element.into());
+        self
+    }
+
+    /// Add a property with a value that isn't displayed, using a `<meta>`
+    /// element.
+    pub fn meta(self, name: impl ToString, content: impl ToString) -> Self {
+        self.prop(name, meta(attr::content(content)))
+    }
+
+    /// Add a property with a URL value that isn't displayed, using a `<link>`
+    /// element.
+    pub fn link(self, name: impl ToString, href: impl ToString) -> Self {
+        self.prop(name, link(attr::href(href)))
+    }
+
+    /// Add content that isn't a property of the item.
+    pub fn content(mut self, c: impl Into<Content>) -> Self {
+        self.children.push(c.into());
+        self
+    }
+}
+
+impl ElementComponent for Item {
+    fn add_to_element(self, element: &mut Element) {
+        element.add(attr::itemscope());
+        if !self.types.is_empty() {
+            element.add(attr::itemtype(self.types.join(" ")));
+        }
+        if let Some(id) = self.id {
+            element.add(attr::itemid(id));
+        }
+        element.children.extend(self.children);
+    }
+}
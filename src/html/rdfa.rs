@@ -0,0 +1,147 @@
+//! Builders for [RDFa Lite][rdfa] resources.
+//!
+//! RDFa annotates elements with machine-readable properties using the
+//! `vocab`, `typeof`, `resource`, `prefix` and `property` attributes. Like
+//! [`microdata::Item`](super::microdata::Item), a [`Resource`] keeps these
+//! attributes consistent: properties are always bound to an element inside
+//! the resource they belong to.
+//!
+//! [rdfa]: https://www.w3.org/TR/rdfa-lite/
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Render, html::{*, rdfa::Resource}};
+//!
+//! let person = div(
+//!     Resource::new("Person")
+//!         .vocab("https://schema.org/")
+//!         .prop("name", h2("Alice"))
+//!         .content(" works at ")
+//!         .prop("worksFor", span(Resource::new("Organization").prop("name", span("ACME"))))
+//!         .meta("birthDate", "1970-01-01"),
+//! );
+//!
+//! assert_eq!(
+//!     person.render_to_string().unwrap(),
+//!     concat!(
+//!         r#"<div typeof="Person" vocab="https://schema.org/">"#,
+//!         r#"<h2 property="name">Alice</h2> works at "#,
+//!         r#"<span property="worksFor" typeof="Organization">"#,
+//!         r#"<span property="name">ACME</span></span>"#,
+//!         r#"<meta content="1970-01-01" property="birthDate"></div>"#,
+//!     ),
+//! );
+//! ```
+
+use crate::{Attr, Content, Element, ElementComponent};
+
+use super::{attr, link, meta};
+
+/// An RDFa resource with its properties, see the [module
+/// documentation](self).
+///
+/// When added to an element, the element becomes the resource's scope and the
+/// properties are appended as its children.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Resource {
+    types: Vec<String>,
+    vocab: Option<String>,
+    prefixes: Vec<(String, String)>,
+    resource: Option<String>,
+    children: Vec<Content>,
+}
+
+impl Resource {
+    /// Create a new resource of a type, e.g. `Person` in the
+    /// `https://schema.org/` [vocabulary](Self::vocab).
+    pub fn new(r#typeof: impl ToString) -> Self {
+        Self::untyped().with_type(r#typeof)
+    }
+
+    /// Create a new resource without a type.
+    pub fn untyped() -> Self {
+        Self::default()
+    }
+
+    /// Add another type to the resource.
+    pub fn with_type(mut self, r#typeof: impl ToString) -> Self {
+        self.types.push(r#typeof.to_string());
+        self
+    }
+
+    /// Set the vocabulary that types and property names without a prefix
+    /// refer to (`vocab`), for this resource and everything inside it.
+    pub fn vocab(mut self, vocab: impl ToString) -> Self {
+        self.vocab = Some(vocab.to_string());
+        self
+    }
+
+    /// Define a prefix for types and property names like `dc:title`
+    /// (`prefix`), for this resource and everything inside it.
+    pub fn prefix(mut self, prefix: impl ToString, iri: impl ToString) -> Self {
+        self.prefixes.push((prefix.to_string(), iri.to_string()));
+        self
+    }
+
+    /// Set the IRI identifying the resource (`resource`).
+    pub fn resource(mut self, iri: impl ToString) -> Self {
+        self.resource = Some(iri.to_string());
+        self
+    }
+
+    /// Add a property whose value is the element.
+    ///
+    /// To add a resource as the value of a property, add the nested
+    /// [`Resource`] to the element.
+    pub fn prop(mut self, name: impl ToString, element: Element) -> Self {
+        let element = element.with(Attr::append_static("property", name, " "));
+        self.children.push(element.into());
+        self
+    }
+
+    /// Add a property with a value that isn't displayed, using a `<meta>`
+    /// element.
+    pub fn meta(self, name: impl ToString, content: impl ToString) -> Self {
+        self.prop(name, meta(attr::content(content)))
+    }
+
+    /// Add a property with a URL value that isn't displayed, using a `<link>`
+    /// element.
+    pub fn link(self, name: impl ToString, href: impl ToString) -> Self {
+        self.prop(name, link(attr::href(href)))
+    }
+
+    /// Add content that isn't a property of the resource.
+    pub fn content(mut self, c: impl Into<Content>) -> Self {
+        self.children.push(c.into());
+        self
+    }
+}
+
+impl ElementComponent for Resource {
+    fn add_to_element(self, element: &mut Element) {
+        if let Some(vocab) = self.vocab {
+            element.add(Attr::set_static("vocab", vocab));
+        }
+        for (prefix, iri) in self.prefixes {
+            element.add(Attr::append_static(
+                "prefix",
+                format!("{prefix}: {iri}"),
+                " ",
+            ));
+        }
+        if self.types.is_empty() {
+            // Without a type, an empty `typeof` still starts a new resource.
+            if self.resource.is_none() {
+                element.add(Attr::set_static("typeof", ""));
+            }
+        } else {
+            element.add(Attr::append_static("typeof", self.types.join(" "), " "));
+        }
+        if let Some(resource) = self.resource {
+            element.add(Attr::set_static("resource", resource));
+        }
+        element.children.extend(self.children);
+    }
+}
@@ -0,0 +1,376 @@
+//! Typed attribute structs for common HTML elements.
+//!
+//! Each struct only contains the element-specific attributes of its element,
+//! so using an attribute on an element it doesn't apply to is a compile-time
+//! error. Global attributes like `id` or `class` are not included and can be
+//! added as additional components.
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Render, html::{attr, typed::ImgAttrs}};
+//!
+//! let img = ImgAttrs::new()
+//!     .src("/cat.png")
+//!     .alt("A cat")
+//!     .width(640)
+//!     .loading(attr::Loading::Lazy)
+//!     .build(attr::class("photo"));
+//!
+//! assert_eq!(
+//!     img.render_to_string().unwrap(),
+//!     r#"<img alt="A cat" class="photo" loading="lazy" src="/cat.png" width="640">"#,
+//! );
+//! ```
+
+use crate::{Element, ElementComponent};
+
+use super::attr;
+
+macro_rules! typed_attrs {
+    (
+        $name:ident for $element:ident;
+        values { $( $value:ident, )* }
+        numbers { $( $number:ident, )* }
+        typed { $( $typed:ident: $typedty:ty, )* }
+        flags { $( $flag:ident, )* }
+    ) => {
+        #[doc = concat!("Typed attributes for the `<", stringify!($element), ">` element")]
+        #[doc = concat!("([MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/", stringify!($element), "#attributes)).")]
+        #[derive(Debug, Clone, Default, PartialEq, Eq)]
+        pub struct $name {
+            $(
+                #[doc = concat!("See `attr::", stringify!($value), "`.")]
+                pub $value: Option<String>,
+            )*
+            $(
+                #[doc = concat!("See `attr::", stringify!($number), "`.")]
+                pub $number: Option<u32>,
+            )*
+            $(
+                #[doc = concat!("See [`", stringify!($typedty), "`].")]
+                pub $typed: Option<$typedty>,
+            )*
+            $(
+                #[doc = concat!("See `attr::", stringify!($flag), "`.")]
+                pub $flag: bool,
+            )*
+        }
+
+        impl $name {
+            /// Create a new set of attributes where no attribute is set.
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            $(
+                #[doc = concat!("Set the `attr::", stringify!($value), "` attribute.")]
+                pub fn $value(mut self, value: impl ToString) -> Self {
+                    self.$value = Some(value.to_string());
+                    self
+                }
+            )*
+
+            $(
+                #[doc = concat!("Set the `attr::", stringify!($number), "` attribute.")]
+                pub fn $number(mut self, value: u32) -> Self {
+                    self.$number = Some(value);
+                    self
+                }
+            )*
+
+            $(
+                #[doc = concat!("Set the [`", stringify!($typedty), "`] attribute.")]
+                pub fn $typed(mut self, value: impl Into<$typedty>) -> Self {
+                    self.$typed = Some(value.into());
+                    self
+                }
+            )*
+
+            $(
+                #[doc = concat!("Set the `attr::", stringify!($flag), "` attribute.")]
+                pub fn $flag(mut self, value: bool) -> Self {
+                    self.$flag = value;
+                    self
+                }
+            )*
+
+            #[doc = concat!("Construct a `<", stringify!($element), ">` element with these attributes and")]
+            /// additional components.
+            pub fn build(self, c: impl ElementComponent) -> Element {
+                super::$element((self, c))
+            }
+        }
+
+        impl ElementComponent for $name {
+            fn add_to_element(self, element: &mut Element) {
+                $(
+                    if let Some(value) = self.$value {
+                        element.add(attr::$value(value));
+                    }
+                )*
+                $(
+                    if let Some(value) = self.$number {
+                        element.add(attr::$number(value));
+                    }
+                )*
+                $( element.add(self.$typed); )*
+                $(
+                    if self.$flag {
+                        element.add(attr::$flag());
+                    }
+                )*
+            }
+        }
+    };
+}
+
+typed_attrs! {
+    AAttrs for a;
+    values {
+        download,
+        href,
+        hreflang,
+        r#type,
+    }
+    numbers {}
+    typed {
+        referrerpolicy: attr::Referrerpolicy,
+        rel: attr::RelSet,
+        target: attr::Target,
+    }
+    flags {}
+}
+
+typed_attrs! {
+    ButtonAttrs for button;
+    values {
+        form,
+        formaction,
+        name,
+        value,
+    }
+    numbers {}
+    typed {
+        formenctype: attr::Formenctype,
+        formmethod: attr::Formmethod,
+        formtarget: attr::Formtarget,
+        r#type: attr::TypeButton,
+    }
+    flags {
+        disabled,
+        formnovalidate,
+    }
+}
+
+typed_attrs! {
+    FormAttrs for form;
+    values {
+        action,
+        autocomplete,
+        name,
+    }
+    numbers {}
+    typed {
+        enctype: attr::Enctype,
+        method: attr::Method,
+        target: attr::Target,
+    }
+    flags {
+        novalidate,
+    }
+}
+
+typed_attrs! {
+    IframeAttrs for iframe;
+    values {
+        allow,
+        name,
+        src,
+        srcdoc,
+    }
+    numbers {
+        height,
+        width,
+    }
+    typed {
+        loading: attr::Loading,
+        referrerpolicy: attr::Referrerpolicy,
+        sandbox: attr::SandboxSet,
+    }
+    flags {}
+}
+
+typed_attrs! {
+    ImgAttrs for img;
+    values {
+        alt,
+        sizes,
+        src,
+        srcset,
+        usemap,
+    }
+    numbers {
+        height,
+        width,
+    }
+    typed {
+        crossorigin: attr::Crossorigin,
+        decoding: attr::Decoding,
+        loading: attr::Loading,
+        referrerpolicy: attr::Referrerpolicy,
+    }
+    flags {
+        ismap,
+    }
+}
+
+typed_attrs! {
+    InputAttrs for input;
+    values {
+        accept,
+        alt,
+        autocomplete,
+        dirname,
+        form,
+        list,
+        max,
+        min,
+        name,
+        pattern,
+        placeholder,
+        src,
+        step,
+        value,
+    }
+    numbers {
+        maxlength,
+        minlength,
+        size,
+    }
+    typed {
+        capture: attr::Capture,
+        r#type: attr::TypeInput,
+    }
+    flags {
+        checked,
+        disabled,
+        multiple,
+        readonly,
+        required,
+    }
+}
+
+typed_attrs! {
+    LabelAttrs for label;
+    values {
+        r#for,
+        form,
+    }
+    numbers {}
+    typed {}
+    flags {}
+}
+
+typed_attrs! {
+    LinkAttrs for link;
+    values {
+        href,
+        hreflang,
+        integrity,
+        r#type,
+    }
+    numbers {}
+    typed {
+        r#as: attr::As,
+        crossorigin: attr::Crossorigin,
+        referrerpolicy: attr::Referrerpolicy,
+        rel: attr::RelSet,
+    }
+    flags {}
+}
+
+typed_attrs! {
+    MetaAttrs for meta;
+    values {
+        charset,
+        content,
+        name,
+    }
+    numbers {}
+    typed {
+        http_equiv: attr::HttpEquiv,
+    }
+    flags {}
+}
+
+typed_attrs! {
+    ScriptAttrs for script;
+    values {
+        integrity,
+        src,
+    }
+    numbers {}
+    typed {
+        crossorigin: attr::Crossorigin,
+        referrerpolicy: attr::Referrerpolicy,
+        r#type: attr::TypeScript,
+    }
+    flags {
+        r#async,
+        defer,
+    }
+}
+
+typed_attrs! {
+    TdAttrs for td;
+    values {
+        headers,
+    }
+    numbers {
+        colspan,
+        rowspan,
+    }
+    typed {}
+    flags {}
+}
+
+typed_attrs! {
+    TextareaAttrs for textarea;
+    values {
+        autocomplete,
+        dirname,
+        form,
+        name,
+        placeholder,
+    }
+    numbers {
+        cols,
+        maxlength,
+        minlength,
+        rows,
+    }
+    typed {
+        wrap: attr::Wrap,
+    }
+    flags {
+        disabled,
+        readonly,
+        required,
+    }
+}
+
+typed_attrs! {
+    ThAttrs for th;
+    values {
+        headers,
+    }
+    numbers {
+        colspan,
+        rowspan,
+    }
+    typed {
+        scope: attr::Scope,
+    }
+    flags {}
+}
@@ -0,0 +1,158 @@
+//! Helpers for [htmx] partial responses.
+//!
+//! [htmx]: https://htmx.org/
+
+use std::fmt;
+
+use crate::{html, Attr, Content, Element, Error, Placeholder, Render, RenderOptions, Result};
+
+/// How an out-of-band element is swapped into the page
+/// ([htmx docs](https://htmx.org/attributes/hx-swap/)).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Swap {
+    /// Replace the entire target element.
+    #[default]
+    OuterHtml,
+    /// Replace the contents of the target element.
+    InnerHtml,
+    /// Insert before the target element.
+    BeforeBegin,
+    /// Insert before the first child of the target element.
+    AfterBegin,
+    /// Insert after the last child of the target element.
+    BeforeEnd,
+    /// Insert after the target element.
+    AfterEnd,
+    /// Delete the target element.
+    Delete,
+    /// Don't swap anything.
+    None,
+}
+
+impl fmt::Display for Swap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OuterHtml => "outerHTML",
+            Self::InnerHtml => "innerHTML",
+            Self::BeforeBegin => "beforebegin",
+            Self::AfterBegin => "afterbegin",
+            Self::BeforeEnd => "beforeend",
+            Self::AfterEnd => "afterend",
+            Self::Delete => "delete",
+            Self::None => "none",
+        }
+        .fmt(f)
+    }
+}
+
+/// A partial response consisting of main content and any number of
+/// out-of-band swaps
+/// ([htmx docs](https://htmx.org/attributes/hx-swap-oob/)).
+///
+/// The main content is rendered first, followed by the out-of-band elements
+/// in the order they were added. Each out-of-band element receives an
+/// `hx-swap-oob` attribute with an explicit target selector, so it doesn't
+/// need a matching `id`. Elements that can only appear inside tables (e.g.
+/// `<tr>`) are wrapped in a `<template>` so they survive parsing.
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, html::*, htmx::OobResponse};
+///
+/// let response = OobResponse::new()
+///     .main(p("Saved!"))
+///     .swap("#flash", div("1 item saved"))
+///     .swap("#row-3", tr(td("updated")));
+///
+/// assert_eq!(
+///     response.render_to_string().unwrap(),
+///     concat!(
+///         "<p>Saved!</p>",
+///         r##"<div hx-swap-oob="outerHTML:#flash">1 item saved</div>"##,
+///         r##"<template><tr hx-swap-oob="outerHTML:#row-3"><td>updated</td></tr></template>"##,
+///     ),
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OobResponse {
+    main: Vec<Content>,
+    oob: Vec<(Swap, String, Element)>,
+}
+
+impl OobResponse {
+    /// Create a new empty response.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append to the main content of the response.
+    ///
+    /// The main content is swapped into the page according to the `hx-target`
+    /// and `hx-swap` attributes of the requesting element.
+    pub fn main(mut self, content: impl Into<Content>) -> Self {
+        self.main.push(content.into());
+        self
+    }
+
+    /// Replace the element matching `selector` with `element`.
+    ///
+    /// This is equivalent to calling [`Self::swap_with`] with
+    /// [`Swap::OuterHtml`].
+    pub fn swap(self, selector: impl ToString, element: Element) -> Self {
+        self.swap_with(Swap::OuterHtml, selector, element)
+    }
+
+    /// Swap `element` into the element matching `selector` using a specific
+    /// swap strategy.
+    ///
+    /// For [`Swap::InnerHtml`] and the insertion strategies, only the
+    /// children of `element` are swapped in.
+    pub fn swap_with(mut self, swap: Swap, selector: impl ToString, element: Element) -> Self {
+        self.oob.push((swap, selector.to_string(), element));
+        self
+    }
+
+    /// Convert the response into a flat list of content, ready for rendering.
+    pub fn into_contents(self) -> Vec<Content> {
+        let mut contents = self.main;
+        for (swap, selector, element) in self.oob {
+            let needs_template = needs_template(&element.name);
+            let element = element.with(Attr::set("hx-swap-oob", format!("{swap}:{selector}")));
+            contents.push(if needs_template {
+                html::template(element).into()
+            } else {
+                element.into()
+            });
+        }
+        contents
+    }
+}
+
+/// Whether an element is dropped by the HTML parser unless it appears inside a
+/// table.
+fn needs_template(name: &str) -> bool {
+    matches!(
+        name,
+        "caption" | "col" | "colgroup" | "tbody" | "td" | "tfoot" | "th" | "thead" | "tr"
+    )
+}
+
+impl Render for OobResponse {
+    fn render<W: fmt::Write>(&self, w: &mut W) -> Result<()> {
+        self.render_with(w, &RenderOptions::default())
+    }
+
+    fn render_with<W: fmt::Write>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        self.clone().into_contents().render_with(w, options)
+    }
+
+    fn render_lenient<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        options: &RenderOptions,
+        placeholder: &Placeholder,
+    ) -> Result<Vec<Error>> {
+        (self.clone().into_contents()).render_lenient(w, options, placeholder)
+    }
+}
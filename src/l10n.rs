@@ -0,0 +1,215 @@
+//! Localized text for numbers and dates.
+//!
+//! The functions in this module format a value according to the conventions
+//! of a [`Locale`] (e.g. digit grouping, decimal separator, month names) using
+//! [ICU4X] and its compiled locale data. The result is ordinary text content,
+//! so it is escaped like any other text.
+//!
+//! Locales without data fall back to a more general locale and ultimately to
+//! the root locale, so formatting never fails. Every call creates a new
+//! formatter, which is cheap compared to rendering a page, but not free.
+//!
+//! This module requires the `l10n` feature flag.
+//!
+//! [ICU4X]: https://github.com/unicode-org/icu4x
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Render, html::*, l10n::{self, locale, Date, DateStyle}};
+//!
+//! let de = locale!("de-DE");
+//! let date = Date::try_new_iso(2024, 3, 9).unwrap();
+//! let html = p((
+//!     l10n::text_num(1234567.5, &de),
+//!     " Besucher seit ",
+//!     l10n::text_date(date, &de, DateStyle::Long),
+//! ));
+//! assert_eq!(
+//!     html.render_to_string().unwrap(),
+//!     "<p>1.234.567,5 Besucher seit 9. März 2024</p>",
+//! );
+//! ```
+
+use fixed_decimal::FloatPrecision;
+use icu_datetime::{fieldsets, DateTimeFormatter};
+use icu_decimal::DecimalFormatter;
+
+pub use fixed_decimal::Decimal;
+pub use icu_calendar::{Date, Iso};
+pub use icu_locale_core::{locale, Locale};
+pub use icu_time::{DateTime, Time};
+
+use crate::Content;
+
+/// A number that can be formatted by [`text_num`].
+///
+/// Floats are formatted with as many digits as necessary to round-trip, e.g.
+/// `0.1 + 0.2` as `0.30000000000000004`. Round them first or use a
+/// [`Decimal`] to control the number of digits. Values that are not finite
+/// are formatted as `NaN`, `∞` and `-∞` respectively.
+pub trait Number {
+    /// The number as a decimal, or a replacement text if it is not finite.
+    fn to_decimal(self) -> Result<Decimal, &'static str>;
+}
+
+impl Number for Decimal {
+    fn to_decimal(self) -> Result<Decimal, &'static str> {
+        Ok(self)
+    }
+}
+
+macro_rules! number_int {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl Number for $t {
+                fn to_decimal(self) -> Result<Decimal, &'static str> {
+                    Ok(Decimal::from(self))
+                }
+            }
+        )*
+    };
+}
+
+number_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl Number for f64 {
+    fn to_decimal(self) -> Result<Decimal, &'static str> {
+        if self.is_nan() {
+            Err("NaN")
+        } else if self == Self::INFINITY {
+            Err("∞")
+        } else if self == Self::NEG_INFINITY {
+            Err("-∞")
+        } else {
+            Decimal::try_from_f64(self, FloatPrecision::RoundTrip).map_err(|_| "NaN")
+        }
+    }
+}
+
+impl Number for f32 {
+    fn to_decimal(self) -> Result<Decimal, &'static str> {
+        f64::from(self).to_decimal()
+    }
+}
+
+/// Format a number for a locale.
+///
+/// # Example
+///
+/// ```
+/// use el::{Content, l10n::{locale, text_num, Decimal}};
+///
+/// assert_eq!(text_num(-1234, &locale!("en-US")), Content::text("-1,234"));
+/// assert_eq!(text_num(0.5, &locale!("fr")), Content::text("0,5"));
+///
+/// let mut price = Decimal::from(1999);
+/// price.multiply_pow10(-2);
+/// assert_eq!(text_num(price, &locale!("de")), Content::text("19,99"));
+/// ```
+pub fn text_num(n: impl Number, locale: &Locale) -> Content {
+    let n = match n.to_decimal() {
+        Ok(n) => n,
+        Err(text) => return Content::text(text),
+    };
+    let text = match DecimalFormatter::try_new(locale.into(), Default::default()) {
+        Ok(formatter) => formatter.format(&n).to_string(),
+        Err(_) => n.to_string(),
+    };
+    Content::text(text)
+}
+
+/// How much detail to include when formatting dates, see [`text_date`].
+///
+/// The exact format depends on the locale.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DateStyle {
+    /// Only numbers, e.g. `3/9/24` in English.
+    Short,
+    /// An abbreviated month name, e.g. `Mar 9, 2024` in English.
+    #[default]
+    Medium,
+    /// The full month name, e.g. `March 9, 2024` in English.
+    Long,
+}
+
+impl DateStyle {
+    fn date(self) -> fieldsets::YMD {
+        match self {
+            Self::Short => fieldsets::YMD::short(),
+            Self::Medium => fieldsets::YMD::medium(),
+            Self::Long => fieldsets::YMD::long(),
+        }
+    }
+
+    fn date_time(self) -> fieldsets::YMDT {
+        match self {
+            Self::Short => fieldsets::YMDT::short(),
+            Self::Medium => fieldsets::YMDT::medium(),
+            Self::Long => fieldsets::YMDT::long(),
+        }
+    }
+}
+
+/// Format a date for a locale.
+///
+/// # Example
+///
+/// ```
+/// use el::{Content, l10n::{locale, text_date, Date, DateStyle}};
+///
+/// let date = Date::try_new_iso(2024, 3, 9).unwrap();
+/// assert_eq!(
+///     text_date(date, &locale!("en-US"), DateStyle::Medium),
+///     Content::text("Mar 9, 2024"),
+/// );
+/// ```
+pub fn text_date(date: Date<Iso>, locale: &Locale, style: DateStyle) -> Content {
+    let text = match DateTimeFormatter::try_new(locale.into(), style.date()) {
+        Ok(formatter) => formatter.format(&date).to_string(),
+        Err(_) => iso_date(&date),
+    };
+    Content::text(text)
+}
+
+/// Format a date and time of day for a locale.
+///
+/// # Example
+///
+/// ```
+/// use el::{Content, l10n::{locale, text_date_time, Date, DateStyle, DateTime, Time}};
+///
+/// let date_time = DateTime {
+///     date: Date::try_new_iso(2024, 3, 9).unwrap(),
+///     time: Time::try_new(14, 5, 0, 0).unwrap(),
+/// };
+/// assert_eq!(
+///     text_date_time(date_time, &locale!("de"), DateStyle::Short),
+///     Content::text("09.03.24, 14:05:00"),
+/// );
+/// ```
+pub fn text_date_time(date_time: DateTime<Iso>, locale: &Locale, style: DateStyle) -> Content {
+    let text = match DateTimeFormatter::try_new(locale.into(), style.date_time()) {
+        Ok(formatter) => formatter.format(&date_time).to_string(),
+        Err(_) => {
+            let time = &date_time.time;
+            let (hour, minute) = (time.hour.number(), time.minute.number());
+            format!(
+                "{} {hour:02}:{minute:02}:{:02}",
+                iso_date(&date_time.date),
+                time.second.number()
+            )
+        }
+    };
+    Content::text(text)
+}
+
+/// Format a date as `YYYY-MM-DD`, in case there is no data for the locale.
+fn iso_date(date: &Date<Iso>) -> String {
+    let (year, month, day) = (
+        date.year().extended_year(),
+        date.month().ordinal,
+        date.day_of_month().0,
+    );
+    format!("{year:04}-{month:02}-{day:02}")
+}
@@ -0,0 +1,249 @@
+//! Layouts with named blocks, similar to template inheritance in Jinja.
+//!
+//! A [`Layout`] turns a set of [`Blocks`] into a [`Document`]. Pages fill in
+//! the blocks they care about (usually via the [`Page`] trait), and the layout
+//! decides where each block ends up and what to use for blocks that were not
+//! filled. Besides the page title, the standard blocks are [`HEAD_EXTRA`],
+//! [`CONTENT`] and [`SCRIPTS`], but layouts may use any block names.
+//!
+//! Layouts are layered by wrapping: a more specific layout modifies the blocks
+//! (e.g. wrapping the content in a sidebar layout) and passes them on to a more
+//! general one.
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Document, Render, html::*, layout::{self, BaseLayout, Blocks, Layout, Page}};
+//!
+//! /// Adds a navigation bar to every page and uses the base layout.
+//! struct SiteLayout;
+//!
+//! impl Layout for SiteLayout {
+//!     fn apply(&self, blocks: Blocks) -> Document {
+//!         let blocks = blocks
+//!             .wrap(layout::CONTENT, main)
+//!             .prepend(layout::CONTENT, nav(a((attr::href("/"), "Home"))));
+//!         BaseLayout::new().lang("en").title_suffix(" | Example").apply(blocks)
+//!     }
+//! }
+//!
+//! struct About;
+//!
+//! impl Page for About {
+//!     type Layout = SiteLayout;
+//!
+//!     fn layout(&self) -> SiteLayout {
+//!         SiteLayout
+//!     }
+//!
+//!     fn blocks(&self) -> Blocks {
+//!         Blocks::new().title("About").content(p("Hello world"))
+//!     }
+//! }
+//!
+//! assert_eq!(
+//!     About.to_document().render_to_string().unwrap(),
+//!     concat!(
+//!         r#"<!DOCTYPE html><html lang="en"><head><meta charset="utf-8">"#,
+//!         r#"<meta content="width=device-width, initial-scale=1" name="viewport">"#,
+//!         "<title>About | Example</title></head><body>",
+//!         r#"<nav><a href="/">Home</a></nav><main><p>Hello world</p></main>"#,
+//!         "</body></html>",
+//!     ),
+//! );
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::{
+    html::{self, attr, helpers},
+    Content, Document,
+};
+
+/// The name of the block containing additional elements for the `<head>`.
+pub const HEAD_EXTRA: &str = "head_extra";
+/// The name of the block containing the main content of the page.
+pub const CONTENT: &str = "content";
+/// The name of the block containing scripts at the end of the `<body>`.
+pub const SCRIPTS: &str = "scripts";
+
+/// Content for the named blocks of a [`Layout`], see the [module
+/// documentation](self).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Blocks {
+    title: Option<String>,
+    blocks: BTreeMap<String, Vec<Content>>,
+}
+
+impl Blocks {
+    /// Create a new set of blocks where no block is filled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append content to the block with the given name.
+    pub fn block(mut self, name: impl ToString, content: impl Into<Content>) -> Self {
+        (self.blocks.entry(name.to_string()).or_default()).push(content.into());
+        self
+    }
+
+    /// Insert content at the start of the block with the given name.
+    pub fn prepend(mut self, name: impl ToString, content: impl Into<Content>) -> Self {
+        (self.blocks.entry(name.to_string()).or_default()).insert(0, content.into());
+        self
+    }
+
+    /// Replace the contents of a block with the result of a function.
+    ///
+    /// The function is called with the current contents, which are empty if
+    /// the block was not filled. This is useful for wrapping a block in an
+    /// element, e.g. the [`CONTENT`] in a `<main>`.
+    pub fn wrap<C: Into<Content>>(
+        mut self,
+        name: impl ToString,
+        f: impl FnOnce(Vec<Content>) -> C,
+    ) -> Self {
+        let name = name.to_string();
+        let content = self.take(&name);
+        self.blocks.insert(name, vec![f(content).into()]);
+        self
+    }
+
+    /// Set the page title, replacing any previous title.
+    pub fn title(mut self, title: impl ToString) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Append content to the [`HEAD_EXTRA`] block.
+    pub fn head_extra(self, content: impl Into<Content>) -> Self {
+        self.block(HEAD_EXTRA, content)
+    }
+
+    /// Append content to the [`CONTENT`] block.
+    pub fn content(self, content: impl Into<Content>) -> Self {
+        self.block(CONTENT, content)
+    }
+
+    /// Append content to the [`SCRIPTS`] block.
+    pub fn scripts(self, content: impl Into<Content>) -> Self {
+        self.block(SCRIPTS, content)
+    }
+
+    /// Whether the block with the given name was filled.
+    pub fn is_filled(&self, name: &str) -> bool {
+        self.blocks.contains_key(name)
+    }
+
+    /// The contents of the block with the given name.
+    pub fn get(&self, name: &str) -> Option<&[Content]> {
+        self.blocks.get(name).map(|c| &c[..])
+    }
+
+    /// The page title, if it was set.
+    pub fn get_title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Remove a block, returning its contents.
+    ///
+    /// Returns an empty list if the block was not filled.
+    pub fn take(&mut self, name: &str) -> Vec<Content> {
+        self.blocks.remove(name).unwrap_or_default()
+    }
+
+    /// Remove a block, returning its contents or a fallback if the block was
+    /// not filled.
+    pub fn take_or(&mut self, name: &str, fallback: impl Into<Content>) -> Vec<Content> {
+        self.blocks
+            .remove(name)
+            .unwrap_or_else(|| vec![fallback.into()])
+    }
+}
+
+/// A layout that places [`Blocks`] in a [`Document`], see the [module
+/// documentation](self).
+pub trait Layout {
+    /// Create a document from the blocks of a page.
+    fn apply(&self, blocks: Blocks) -> Document;
+}
+
+impl<F: Fn(Blocks) -> Document> Layout for F {
+    fn apply(&self, blocks: Blocks) -> Document {
+        self(blocks)
+    }
+}
+
+/// A page that fills in the blocks of a [`Layout`], see the [module
+/// documentation](self).
+pub trait Page {
+    /// The layout this page uses.
+    type Layout: Layout;
+
+    /// The layout this page uses.
+    fn layout(&self) -> Self::Layout;
+
+    /// The blocks provided by this page.
+    fn blocks(&self) -> Blocks;
+
+    /// Resolve the page into a document using its layout.
+    fn to_document(&self) -> Document {
+        self.layout().apply(self.blocks())
+    }
+}
+
+/// A general purpose layout for HTML pages.
+///
+/// The `<head>` contains a UTF-8 charset declaration, the default viewport,
+/// the title and the [`HEAD_EXTRA`] block. The `<body>` contains the
+/// [`CONTENT`] followed by the [`SCRIPTS`] block. Other blocks are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BaseLayout {
+    lang: Option<String>,
+    default_title: String,
+    title_suffix: String,
+}
+
+impl BaseLayout {
+    /// Create a new base layout.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the `lang` attribute of the `<html>` element.
+    pub fn lang(mut self, lang: impl ToString) -> Self {
+        self.lang = Some(lang.to_string());
+        self
+    }
+
+    /// Set the title used if the page doesn't set a title.
+    pub fn default_title(mut self, title: impl ToString) -> Self {
+        self.default_title = title.to_string();
+        self
+    }
+
+    /// Set a suffix appended to titles set by the page, e.g. `" | Site Name"`.
+    pub fn title_suffix(mut self, suffix: impl ToString) -> Self {
+        self.title_suffix = suffix.to_string();
+        self
+    }
+}
+
+impl Layout for BaseLayout {
+    fn apply(&self, mut blocks: Blocks) -> Document {
+        let title = match blocks.get_title() {
+            Some(title) => format!("{title}{}", self.title_suffix),
+            None => self.default_title.clone(),
+        };
+        Document(html::html((
+            self.lang.as_ref().map(attr::lang),
+            html::head((
+                helpers::meta_charset_utf8(),
+                helpers::meta_viewport_default(),
+                html::title(title),
+                blocks.take(HEAD_EXTRA),
+            )),
+            html::body((blocks.take(CONTENT), blocks.take(SCRIPTS))),
+        )))
+    }
+}
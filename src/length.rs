@@ -0,0 +1,115 @@
+use std::fmt;
+
+use crate::IntoAttrValue;
+
+/// A length or coordinate used as an attribute value, e.g. for `width`,
+/// `height` or SVG coordinates.
+///
+/// Numbers are formatted the same way regardless of the platform or locale:
+/// with a `.` as decimal point, rounded to at most four decimal places, and
+/// without trailing zeroes or exponent. Values that are not finite (NaN or
+/// infinite) are formatted as `0`, since they are never valid.
+///
+/// # Example
+///
+/// ```
+/// use el::{Attr, Length, Render, html::*, svg};
+///
+/// let img = img((attr::width(Length::Number(640.0)), attr::height(480)));
+/// assert_eq!(img.render_to_string().unwrap(), r#"<img height="480" width="640">"#);
+///
+/// let rect = svg::rect((
+///     Attr::set("x", Length::from(1.0 / 3.0)),
+///     Attr::set("width", Length::Percent(50.0)),
+///     Attr::set("height", Length::Em(1.5)),
+/// ));
+/// assert_eq!(
+///     rect.render_to_string().unwrap(),
+///     r#"<rect height="1.5em" width="50%" x="0.3333" />"#,
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Length {
+    /// A number without unit, e.g. SVG user units or HTML pixel dimensions.
+    Number(f64),
+    /// A number of CSS pixels (`px`).
+    Px(f64),
+    /// A percentage (`%`).
+    Percent(f64),
+    /// A multiple of the element's font size (`em`).
+    Em(f64),
+    /// A multiple of the root element's font size (`rem`).
+    Rem(f64),
+}
+
+impl Length {
+    /// The number of the length, without unit.
+    pub fn value(self) -> f64 {
+        match self {
+            Self::Number(n) | Self::Px(n) | Self::Percent(n) | Self::Em(n) | Self::Rem(n) => n,
+        }
+    }
+
+    /// The unit of the length, or the empty string for [`Self::Number`].
+    pub fn unit(self) -> &'static str {
+        match self {
+            Self::Number(_) => "",
+            Self::Px(_) => "px",
+            Self::Percent(_) => "%",
+            Self::Em(_) => "em",
+            Self::Rem(_) => "rem",
+        }
+    }
+}
+
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", Number(self.value(), 4), self.unit())
+    }
+}
+
+impl IntoAttrValue for Length {
+    fn into_attr_value(self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+macro_rules! length_from {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl From<$t> for Length {
+                fn from(value: $t) -> Self {
+                    Self::Number(value.into())
+                }
+            }
+        )*
+    };
+}
+
+length_from!(i8, i16, i32, u8, u16, u32, f32, f64);
+
+/// Formats a number with at most the given number of decimal places, see
+/// [`Length`].
+pub(crate) struct Number(pub(crate) f64, pub(crate) i32);
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(n, decimals) = *self;
+        if !n.is_finite() {
+            return write!(f, "0");
+        }
+        // Large numbers have no decimal places to round anyway, and scaling
+        // them would introduce errors
+        let scale = 10_f64.powi(decimals);
+        let n = if n.abs() * scale < 2_f64.powi(52) {
+            (n * scale).round() / scale
+        } else {
+            n
+        };
+        // Avoid "-0", which is valid but looks odd
+        let n = if n == 0.0 { 0.0 } else { n };
+        // Unlike `{:e}`, the default formatting never uses an exponent
+        write!(f, "{n}")
+    }
+}
@@ -26,10 +26,21 @@
 //! content components is preserved. To set attributes, include [`Attr`] values
 //! as components.
 //!
+//! Strings are included as text, which is escaped when rendered. Other values
+//! like numbers can be included as text using [`text()`] or [`texts`].
+//! Unescaped content can be included using [`raw()`], though this is rarely
+//! necessary.
+//!
+//! To return several sibling elements without a wrapping element, e.g. from a
+//! helper function, collect them into a [`Fragment`].
+//!
 //! If you want to render an entire web page, wrap an [`html::html`] element in
 //! a [`Document`]. When rendered, documents include the `<!DOCTYPE html>`
 //! annotation required by the standard.
 //!
+//! Rendering fails if the output would be invalid or unsafe. Mistakes that
+//! still produce valid HTML can optionally be found using the [`lint`] module.
+//!
 //! ## Usage example
 //!
 //! ```
@@ -59,6 +70,9 @@
 //! is enabled, [`Document`] implements axum's `IntoResponse` trait and can be
 //! returned directly from handlers. In order to prevent accidentally returning
 //! incomplete HTML documents, [`Element`] does not implement `IntoResponse`.
+//! Partial responses for [htmx] can be returned deliberately using a
+//! [`Fragment`], an `axum::Partial` or [`htmx::OobResponse`]. To also set the
+//! status code, headers or cache policy, return an `axum::Page` instead.
 //!
 //! ```toml
 //! [dependencies]
@@ -66,25 +80,138 @@
 //! ```
 //!
 //! [axum]: https://crates.io/crates/axum
+//! [htmx]: https://htmx.org/
+//!
+//! ## Compression
+//!
+//! The optional `gzip` and `brotli` feature flags enable the `compress`
+//! module, which contains writers that compress the output while it is being
+//! rendered.
+//!
+//! ## Charts
+//!
+//! The optional `charts` feature flag enables the `charts` module, which draws
+//! small sparklines, bar charts and donut charts as inline SVG.
+//!
+//! ## Localization
+//!
+//! The optional `l10n` feature flag enables the `l10n` module, which formats
+//! numbers and dates for a locale using [ICU4X] and turns them into text
+//! content.
+//!
+//! [ICU4X]: https://github.com/unicode-org/icu4x
+//!
+//! ## Diagnostics
+//!
+//! With the optional `miette` feature flag, render [`Error`]s implement
+//! [miette]'s `Diagnostic` trait. Together with the `track-caller` feature
+//! flag, reports point to the place in the source code where the element that
+//! caused the error was constructed, which is useful for static site
+//! generators run from the command line.
+//!
+//! [miette]: https://crates.io/crates/miette
+//!
+//! ## Serialization
+//!
+//! With the optional `serde` feature flag, [`Element`], [`Content`],
+//! [`ElementKind`] and [`Document`] implement serde's `Serialize` and
+//! `Deserialize` traits. Trees can then be cached, sent over the network or
+//! stored in a database and rendered later. The source location recorded by
+//! the `track-caller` feature is not serialized.
+//!
+//! Deserialized trees are rendered like any other, including their raw and
+//! trusted content, so only deserialize data from trusted sources.
+//!
+//! ## Logging
+//!
+//! With the optional `tracing` feature flag, rendering a [`Document`] creates
+//! a [tracing] span, and render errors are logged as events. Events include
+//! the [`Error::code`], [`Error::path`], [`Error::location`] and
+//! [`Error::label`], so problems
+//! in production can be diagnosed from the logs. Errors recovered from by
+//! [`Render::render_lenient`] and warnings found by the [`lint`] module are
+//! logged at the `WARN` level.
+//!
+//! [tracing]: https://crates.io/crates/tracing
+//!
+//! ## Forbidding raw content
+//!
+//! [`Content::Raw`] is an escape hatch that bypasses all checks. Projects that
+//! want to ban it entirely can enable the optional `deprecate-raw` feature
+//! flag, which marks [`Content::raw`] and the raw variants of [`Content`],
+//! [`ContentRef`] and [`StaticContent`] as deprecated. Every use then results
+//! in a warning, which can be turned into a compile error using
+//! `#![deny(deprecated)]`. Internally, el still uses raw content (e.g. for
+//! [`Content::doctype`]). Unescaped HTML that is actually needed can still be
+//! included as [`TrustedHtml`], which is easier to audit.
 //!
 //! ## But what about that small helper function?
 //!
 //! See the readme for more details.
 
+pub mod audit;
 #[cfg(feature = "axum")]
-mod axum;
+pub mod axum;
+pub mod bench_support;
+mod borrowed;
+#[cfg(feature = "charts")]
+pub mod charts;
 mod check;
+pub mod chunked;
+#[cfg(any(feature = "gzip", feature = "brotli"))]
+pub mod compress;
+pub mod csp;
+pub mod css;
+#[cfg(feature = "miette")]
+mod diagnostic;
 mod element;
+pub mod flash;
 pub mod html;
+pub mod htmx;
+#[cfg(feature = "l10n")]
+pub mod l10n;
+pub mod layout;
+mod length;
+pub mod lint;
 pub mod mathml;
+pub mod outline;
+pub mod pages;
+pub mod query;
 mod render;
+pub mod site;
+pub mod sse;
+mod static_element;
+#[cfg(feature = "proptest")]
+pub mod strategy;
 pub mod svg;
+pub mod tabular;
+pub mod template;
+pub mod testing;
+pub mod text;
+mod tokens;
+mod trusted;
+mod url;
+
+pub use self::{borrowed::*, element::*, length::*, render::*, static_element::*, trusted::*};
+
+// Lets the derive macros refer to `::el` in tests
+#[cfg(all(test, feature = "derive"))]
+extern crate self as el;
+
+// Only used by the benchmarks
+#[cfg(test)]
+use criterion as _;
 
-pub use self::{element::*, render::*};
+// Only used by the serde tests
+#[cfg(test)]
+use serde_json as _;
 
 #[cfg(test)]
 mod tests {
-    use crate::{html::*, Attr, Content, Element, Render};
+    use crate::{
+        html::*, sse, svg, Attr, Content, ContentRef, Document, Element, Placeholder, Render,
+        RenderOptions,
+    };
 
     #[test]
     fn simple_website() {
@@ -133,6 +260,10 @@ mod tests {
         assert!(script("hello </ScRiPt ... world")
             .render_to_string()
             .is_err());
+
+        assert!(script("</scriptx </scrip\u{e9}t> </script")
+            .render_to_string()
+            .is_ok());
     }
 
     #[test]
@@ -176,7 +307,14 @@ mod tests {
             .render_to_string()
             .unwrap(),
             r#"<p class="foo bar" id="bar"></p>"#,
-        )
+        );
+
+        assert_eq!(
+            input(attr::TypeInput::other("x-vendor"))
+                .render_to_string()
+                .unwrap(),
+            r#"<input type="x-vendor">"#,
+        );
     }
 
     #[test]
@@ -211,4 +349,3366 @@ mod tests {
             r#"<html><!-- -><!- --></html>"#,
         );
     }
+
+    #[test]
+    fn foreign_xml() {
+        let options = RenderOptions {
+            foreign_xml: true,
+            ..RenderOptions::default()
+        };
+        let render = |element: Element| {
+            let mut result = String::new();
+            element.render_with(&mut result, &options).unwrap();
+            result
+        };
+
+        assert_eq!(
+            render(svg::svg((
+                Attr::yes("data-empty"),
+                Attr::set("data-text", "a & b < c"),
+                Content::comment("a -- b ---"),
+            ))),
+            r#"<svg data-empty="" data-text="a &amp; b &lt; c"><!--a -= b -=- --></svg>"#,
+        );
+
+        // HTML elements are not affected
+        assert_eq!(render(input(Attr::yes("checked"))), r#"<input checked>"#,);
+    }
+
+    #[test]
+    fn sse_chunking() {
+        let event = |html: &str| sse::Event::from_html(html).max_line_length(10).to_string();
+
+        // Only spaces between attributes are replaced
+        assert_eq!(
+            event(r#"<ul><li id="a" class="x y">a b c d e f</li></ul>"#),
+            "data: <ul><li\ndata: id=\"a\"\ndata: class=\"x y\">a b c d e f</li></ul>\n\n",
+        );
+        assert_eq!(
+            event("<p title='a b' lang=en>x</p>"),
+            "data: <p\ndata: title='a b'\ndata: lang=en>x</p>\n\n",
+        );
+        assert_eq!(
+            event("<p>1 < 2 and 3 > 2</p>"),
+            "data: <p>1 < 2 and 3 > 2</p>\n\n"
+        );
+
+        // Contents that aren't parsed as HTML are never split
+        assert_eq!(
+            event("<script>if (a <b c>) {}</script><i a b>"),
+            "data: <script>if (a <b c>) {}</script><i\ndata: a b>\n\n",
+        );
+        assert_eq!(
+            event("<TEXTAREA>x <b c></TextArea ><!-- <b c> --><i a>"),
+            "data: <TEXTAREA>x <b c></TextArea ><!-- <b c> --><i\ndata: a>\n\n",
+        );
+        assert_eq!(
+            event("<pre id=\"abc\" lang=\"en\">\n<i class=\"abc\">"),
+            concat!(
+                "data: <pre\ndata: id=\"abc\"\ndata: lang=\"en\">\n",
+                "data: <i\ndata: class=\"abc\">\n\n",
+            ),
+        );
+        assert_eq!(event("<i a b c d e f"), "data: <i a b c d e f\n\n");
+
+        let list = ul((li((attr::class("first"), "a")), li("b")));
+        let event = sse::Event::new(&list).unwrap().max_line_length(12);
+        assert_eq!(
+            event.to_string(),
+            "data: <ul><li\ndata: class=\"first\">a</li><li>b</li></ul>\n\n",
+        );
+
+        let event = sse::Event::from_html("a\r\nb\rc").id("1\n2");
+        assert_eq!(event.to_string(), "id: 12\ndata: a\ndata: b\ndata: c\n\n");
+    }
+
+    #[test]
+    #[cfg(feature = "track-caller")]
+    fn track_caller() {
+        let line = line!() + 1;
+        let element = ul((li("a"), li(input("b"))));
+
+        let location = element.location.unwrap();
+        assert_eq!(location.file(), file!());
+        assert_eq!(location.line(), line);
+
+        let error = element.render_to_string().unwrap_err();
+        let location = error.location().unwrap();
+        assert_eq!(location.line(), line);
+        assert!(error.to_string().contains(&format!("{}:{line}:", file!())));
+    }
+
+    #[test]
+    fn lenient() {
+        fn render(element: Element) -> (String, Vec<String>) {
+            let mut result = String::new();
+            let errors = element
+                .into_document()
+                .render_lenient(
+                    &mut result,
+                    &RenderOptions::default(),
+                    &Placeholder::Content(Content::comment("error")),
+                )
+                .unwrap();
+            let errors = errors.into_iter().map(|e| e.path()).collect();
+            (result, errors)
+        }
+
+        assert_eq!(
+            render(html(body((p("a"), br("b"), p(i(br("c"))))))),
+            (
+                concat!(
+                    "<!DOCTYPE html><html><body><p>a</p>",
+                    "<!--error-->",
+                    "<p><i><!--error--></i></p>",
+                    "</body></html>",
+                )
+                .to_string(),
+                vec![
+                    "/0(body)/1(br)/0".to_string(),
+                    "/0(body)/2(p)/0(i)/0(br)/0".to_string(),
+                ],
+            ),
+        );
+
+        // Parents are responsible for children in invalid places
+        assert_eq!(
+            render(html(body(script(p("a"))))),
+            (
+                concat!(
+                    "<!DOCTYPE html><html><body>",
+                    "<!--error-->",
+                    "</body></html>",
+                )
+                .to_string(),
+                vec!["/0(body)/0(script)/0(p)".to_string()],
+            ),
+        );
+
+        // Trailers are still moved
+        assert_eq!(
+            render(html(body((Content::trailer(br("a")), p("b"))))).0,
+            "<!DOCTYPE html><html><body><p>b</p><!--error--></body></html>",
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn arbitrary_trees() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        use crate::Document;
+
+        // Same checks as the fuzz target, but with a simple deterministic
+        // xorshift generator instead of a fuzzer.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        for _ in 0..1000 {
+            let bytes = (0..256)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    (state >> 24) as u8
+                })
+                .collect::<Vec<_>>();
+            let mut u = Unstructured::new(&bytes);
+            let Ok((document, options, placeholder)) =
+                <(Document, RenderOptions, Placeholder)>::arbitrary(&mut u)
+            else {
+                continue;
+            };
+
+            let mut strict = String::new();
+            let result = document.render_with(&mut strict, &options);
+            let mut lenient = String::new();
+            let errors = document
+                .render_lenient(&mut lenient, &options, &placeholder)
+                .unwrap();
+            if result.is_ok() {
+                assert!(errors.is_empty());
+                assert_eq!(strict, lenient);
+            } else {
+                assert!(!errors.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "proptest")]
+    fn proptest_strategies() {
+        use proptest::prelude::*;
+
+        use crate::strategy::{element, valid_element};
+
+        proptest!(|(element in valid_element())| {
+            let document = html(body(element)).into_document();
+            prop_assert!(document.render_to_string().is_ok());
+        });
+
+        proptest!(|(element in element())| {
+            let strict = element.render_to_string();
+            let mut lenient = String::new();
+            let errors = element
+                .render_lenient(&mut lenient, &RenderOptions::default(), &Placeholder::Empty)
+                .unwrap();
+            prop_assert_eq!(strict.is_ok(), errors.is_empty());
+        });
+    }
+
+    #[test]
+    fn template_instantiation() {
+        use crate::template::Slots;
+
+        let row = template(tr((
+            td(slot((attr::name("name"), "Anonymous"))),
+            td(slot(())),
+            td(template(slot(()))),
+        )));
+        assert_eq!(
+            row.instantiate(&Slots::new().fill_default(em("42")))
+                .unwrap()
+                .render_to_string()
+                .unwrap(),
+            "<tr><td>Anonymous</td><td><em>42</em></td><td><template><slot></slot></template></td></tr>",
+        );
+
+        assert_eq!(div(()).instantiate(&Slots::new()), None);
+    }
+
+    #[test]
+    fn attribute_interning() {
+        use std::borrow::Cow;
+
+        use crate::element::KNOWN_ATTRIBUTE_NAMES;
+
+        assert!(KNOWN_ATTRIBUTE_NAMES.windows(2).all(|w| w[0] < w[1]));
+
+        let element = p((
+            attr::class("a"),
+            Attr::set("ID", "b"),
+            Attr::set("x-custom", "c"),
+        ));
+        let borrowed = element
+            .attributes
+            .keys()
+            .map(|name| (&name[..], matches!(name, Cow::Borrowed(_))))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            borrowed,
+            [("class", true), ("id", true), ("x-custom", false)]
+        );
+    }
+
+    #[test]
+    fn outline() {
+        use crate::outline::Outline;
+
+        let page = html(body((
+            header(h1("Title")),
+            main((
+                article((header(h4("Skipped")), p("..."))),
+                section((Attr::set("aria-label", "Settings"), h2("Settings"))),
+                form((
+                    Attr::set("aria-labelledby", "search-heading"),
+                    h2((attr::id("search-heading"), "Search")),
+                    label(("Query ", input(attr::name("q")))),
+                    input((attr::r#type(attr::TypeInput::Hidden), attr::name("page"))),
+                    button("Go"),
+                )),
+            )),
+            footer(a((attr::href("/imprint"), img(attr::alt("Imprint"))))),
+        )));
+
+        let outline = Outline::new(&page);
+        assert_eq!(
+            outline.to_string(),
+            concat!(
+                "Landmarks:\n",
+                "  banner\n",
+                "  main\n",
+                "  region \"Settings\"\n",
+                "  form \"Search\"\n",
+                "  contentinfo\n",
+                "Headings:\n",
+                "  h1 \"Title\"\n",
+                "        h4 \"Skipped\"\n",
+                "    h2 \"Settings\"\n",
+                "    h2 \"Search\"\n",
+                "Controls:\n",
+                "  input \"Query\"\n",
+                "  button \"Go\"\n",
+                "Links:\n",
+                "  \"Imprint\" -> /imprint\n",
+            ),
+        );
+        assert_eq!(outline.skipped_heading_levels()[0].text, "Skipped");
+        assert!(outline.unlabelled_controls().is_empty());
+    }
+
+    #[test]
+    fn role_lint() {
+        use crate::lint::{Linter, RoleUsage};
+
+        let tree = div((
+            button(attr::Role::Button),
+            div(attr::Role::Button),
+            div((attr::Role::Button, attr::tabindex(0))),
+            a(attr::Role::Button),
+            a((attr::href("#"), attr::Role::Button)),
+            span(attr::Role::other("widget")),
+            span(attr::Role::other("switch-ish tab")),
+            script(attr::Role::Presentation),
+        ));
+
+        let warnings = Linter::new().with(RoleUsage).lint(&tree);
+        let warnings = warnings
+            .iter()
+            .map(|w| (w.path(), w.message()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            [
+                ("/0(button)", "Role \"button\" is redundant on <button>"),
+                (
+                    "/1(div)",
+                    "Role \"button\" requires <div> to be focusable, e.g. using tabindex"
+                ),
+                (
+                    "/3(a)",
+                    "Role \"button\" requires <a> to be focusable, e.g. using tabindex"
+                ),
+                ("/5(span)", "Abstract role \"widget\" must not be used"),
+                ("/6(span)", "Unknown role \"switch-ish\""),
+                ("/7(script)", "<script> must not have a role"),
+            ],
+        );
+    }
+
+    #[test]
+    fn microdata() {
+        use crate::{
+            html::microdata::Item,
+            lint::{Linter, OrphanedItemprop},
+        };
+
+        let item = div(Item::new("https://schema.org/Book")
+            .with_type("https://schema.org/CreativeWork")
+            .id("urn:isbn:0-330-34032-8")
+            .prop("name", span("Title"))
+            .prop("author", span(Item::new("https://schema.org/Person")))
+            .link("url", "https://example.com/"));
+        assert_eq!(
+            item.render_to_string().unwrap(),
+            concat!(
+                r#"<div itemid="urn:isbn:0-330-34032-8" itemscope "#,
+                r#"itemtype="https://schema.org/Book https://schema.org/CreativeWork">"#,
+                r#"<span itemprop="name">Title</span>"#,
+                r#"<span itemprop="author" itemscope itemtype="https://schema.org/Person"></span>"#,
+                r#"<link href="https://example.com/" itemprop="url"></div>"#,
+            ),
+        );
+
+        let tree = div((
+            item,
+            span(attr::itemprop("orphan")),
+            div((attr::id("extra"), p(attr::itemprop("referenced")))),
+            div((attr::itemscope(), attr::itemref("extra"))),
+        ));
+        let warnings = Linter::new().with(OrphanedItemprop::default()).lint(&tree);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].path(), "/1(span)");
+    }
+
+    #[test]
+    fn rdfa() {
+        use crate::html::rdfa::Resource;
+
+        let book = div(Resource::new("Book")
+            .with_type("dc:BibliographicResource")
+            .vocab("https://schema.org/")
+            .prefix("dc", "http://purl.org/dc/terms/")
+            .resource("urn:isbn:0-330-34032-8")
+            .prop("name", span("Title"))
+            .prop("author", span(Resource::new("Person")))
+            .link("url", "https://example.com/"));
+        assert_eq!(
+            book.render_to_string().unwrap(),
+            concat!(
+                r#"<div prefix="dc: http://purl.org/dc/terms/" resource="urn:isbn:0-330-34032-8" "#,
+                r#"typeof="Book dc:BibliographicResource" vocab="https://schema.org/">"#,
+                r#"<span property="name">Title</span>"#,
+                r#"<span property="author" typeof="Person"></span>"#,
+                r#"<link href="https://example.com/" property="url"></div>"#,
+            ),
+        );
+
+        assert_eq!(
+            p(Resource::untyped()).render_to_string().unwrap(),
+            "<p typeof></p>",
+        );
+        assert_eq!(
+            p(Resource::untyped().resource("#me"))
+                .render_to_string()
+                .unwrap(),
+            r##"<p resource="#me"></p>"##,
+        );
+    }
+
+    #[test]
+    fn token_lists() {
+        use crate::html::attr::{Rel, TokenList};
+
+        let element = div((
+            attr::class("a b"),
+            attr::class("b  c"),
+            Attr::append("style", "x", "; "),
+            Attr::append("style", "x", "; "),
+            attr::accept("image/png,image/gif"),
+            attr::accept("image/gif, text/plain"),
+            attr::rel("noopener"),
+            Rel::Noopener | Rel::Noreferrer,
+        ));
+        assert_eq!(element.attributes["class"], "a b c");
+        assert_eq!(element.attributes["style"], "x; x");
+        assert_eq!(
+            element.attributes["accept"],
+            "image/png,image/gif, text/plain"
+        );
+        assert_eq!(element.attributes["rel"], "noopener noreferrer");
+
+        let list = element.token_list("accept", ", ");
+        assert_eq!(
+            list.iter().collect::<Vec<_>>(),
+            ["image/png", "image/gif", "text/plain"]
+        );
+        assert_eq!(
+            list.without("image/gif").to_string(),
+            "image/png, text/plain"
+        );
+
+        let mut list = TokenList::new("class", " ");
+        list.extend(["x y", " ", "y", "z"]);
+        assert!(list.remove("y"));
+        assert!(!list.remove("y"));
+        assert_eq!(list.len(), 2);
+        let element = element.with(list.replacing());
+        assert_eq!(element.attributes["class"], "x z");
+        let element = element.with(TokenList::new("class", " "));
+        assert_eq!(element.attributes["class"], "x z");
+    }
+
+    #[test]
+    fn sandbox_tokens() {
+        use crate::html::{
+            attr::{Sandbox, SandboxSet},
+            typed::IframeAttrs,
+        };
+
+        let element = iframe((
+            Sandbox::AllowScripts,
+            Sandbox::AllowForms | Sandbox::AllowScripts,
+            Sandbox::other("allow-something-new"),
+        ));
+        assert_eq!(
+            element.attributes["sandbox"],
+            "allow-scripts allow-forms allow-something-new"
+        );
+
+        let element = IframeAttrs::new()
+            .src("/embed")
+            .sandbox(SandboxSet::new())
+            .build(());
+        assert_eq!(
+            element.render_to_string().unwrap(),
+            r#"<iframe sandbox src="/embed"></iframe>"#,
+        );
+
+        let set = SandboxSet::from([Sandbox::AllowPopups, Sandbox::AllowPopupsToEscapeSandbox]);
+        assert!(set.contains(&Sandbox::AllowPopups));
+        assert_eq!(
+            set.to_string(),
+            "allow-popups allow-popups-to-escape-sandbox"
+        );
+    }
+
+    #[test]
+    fn allow_policy() {
+        use crate::html::attr::{Allow, AllowOrigin};
+
+        let allow = Allow::new()
+            .feature_for("camera", [AllowOrigin::Any])
+            .feature("autoplay")
+            .feature_for("camera", [AllowOrigin::Src, AllowOrigin::Self_]);
+        let element = iframe((attr::allow("payment"), allow, Allow::new()));
+        assert_eq!(
+            element.attributes["allow"],
+            "payment; camera 'src' 'self'; autoplay"
+        );
+
+        assert_eq!(
+            AllowOrigin::origin(" https://example.com/path?q#x "),
+            AllowOrigin::Origin("https://example.com".to_string()),
+        );
+        assert_eq!(
+            AllowOrigin::origin("https://a.com'; b").to_string(),
+            "https://a.com"
+        );
+
+        let evil = Allow::new().feature_for(
+            "camera; microphone",
+            [AllowOrigin::Origin("a.com 'self'".to_string())],
+        );
+        assert_eq!(evil.to_string(), "cameramicrophone a.comself");
+
+        let element = iframe((
+            Attr::set("ALLOW", "camera 'self'; geolocation 'none'; autoplay"),
+            Allow::new()
+                .feature_for("camera", [AllowOrigin::origin("https://a.com")])
+                .feature_for("geolocation", [AllowOrigin::Self_])
+                .feature_for("autoplay", [AllowOrigin::Src])
+                .feature("fullscreen"),
+        ));
+        assert_eq!(
+            element.attributes["allow"],
+            "camera 'self' https://a.com; geolocation 'self'; autoplay; fullscreen"
+        );
+        let element = element.with(Allow::new().feature_for("autoplay", [AllowOrigin::Any]));
+        assert_eq!(
+            element.attributes["allow"],
+            "camera 'self' https://a.com; geolocation 'self'; autoplay 'src' *; fullscreen"
+        );
+    }
+
+    #[test]
+    fn csp() {
+        use crate::csp::{Csp, Source};
+
+        let csp = Csp::new()
+            .script_src(Source::Self_)
+            .style_src([Source::Self_, Source::Sha256("abc=".to_string())])
+            .script_src([Source::Self_, Source::nonce("n'; x"), Source::ReportSample])
+            .connect_src([
+                Source::host("wss://example.com"),
+                Source::Scheme("https:".to_string()),
+            ])
+            .upgrade_insecure_requests()
+            .report_to("csp endpoint")
+            .directive("sandbox", [Source::Any]);
+        assert_eq!(
+            csp.to_string(),
+            "script-src 'self' 'nonce-nx' 'report-sample'; style-src 'self' 'sha256-abc='; \
+             connect-src wss://example.com https:; upgrade-insecure-requests; \
+             report-to cspendpoint; sandbox *",
+        );
+        assert_eq!(
+            csp.meta().attributes["content"],
+            "script-src 'self' 'nonce-nx' 'report-sample'; style-src 'self' 'sha256-abc='; \
+             connect-src wss://example.com https:; upgrade-insecure-requests",
+        );
+        assert!(Csp::new().is_empty());
+
+        let head = head((
+            link((
+                attr::Rel::Modulepreload,
+                attr::href("/a.js"),
+                attr::nonce("m"),
+            )),
+            link((attr::rel("PRELOAD"), attr::As::Script, attr::nonce("p"))),
+            link((
+                attr::Rel::Stylesheet,
+                attr::href("/a.css"),
+                attr::nonce("s"),
+            )),
+            link((attr::rel("preload"), attr::As::Image, attr::nonce("i"))),
+        ));
+        assert_eq!(
+            Csp::new().nonces_from(&head).to_string(),
+            "script-src 'nonce-m' 'nonce-p'; style-src 'nonce-s'",
+        );
+    }
+
+    #[test]
+    fn meta_refresh() {
+        use crate::html::helpers::meta_refresh;
+
+        let content =
+            |seconds, url| meta_refresh(seconds, url).map(|m| m.attributes["content"].clone());
+        assert_eq!(content(5, None).unwrap(), "5");
+        assert_eq!(
+            content(0, Some(" /a'b\"c ")).unwrap(),
+            "0; url='/a%27b%22c'"
+        );
+        assert_eq!(content(0, Some("/\u{a0}")).unwrap(), "0; url='/%C2%A0'");
+        assert_eq!(
+            content(1, Some("https://example.com/?q=1;x")).unwrap(),
+            "1; url='https://example.com/?q=1;x'"
+        );
+        assert_eq!(content(0, Some("")), None);
+        assert_eq!(content(0, Some("JavaScript:alert(1)")), None);
+        assert_eq!(content(0, Some("java\tscript:alert(1)")), None);
+        assert_eq!(content(0, Some("data:text/html,hi")), None);
+        assert_eq!(content(0, Some("\u{1} VBScript:x")), None);
+        assert!(content(0, Some("/javascript:")).is_some());
+        assert!(content(0, Some("?x=javascript:")).is_some());
+    }
+
+    #[test]
+    fn icons() {
+        use crate::html::helpers::{icons_set, Icons};
+
+        assert!(icons_set(Icons::new("/")).is_empty());
+
+        let icons = Icons::new("/icons/")
+            .png_sizes([16, 32])
+            .mask_icon("#000")
+            .theme_color("white")
+            .theme_color_for("black", "(prefers-color-scheme: dark)");
+        let hrefs = icons_set(icons)
+            .iter()
+            .map(|e| e.render_to_string().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            hrefs,
+            [
+                r#"<link href="/icons/favicon-16x16.png" rel="icon" sizes="16x16" type="image/png">"#,
+                r#"<link href="/icons/favicon-32x32.png" rel="icon" sizes="32x32" type="image/png">"#,
+                r##"<link color="#000" href="/icons/safari-pinned-tab.svg" rel="mask-icon">"##,
+                r#"<meta content="white" name="theme-color">"#,
+                r#"<meta content="black" media="(prefers-color-scheme: dark)" name="theme-color">"#,
+            ],
+        );
+    }
+
+    #[test]
+    fn base_lint() {
+        use crate::lint::{BaseElement, Linter};
+
+        let page = html((
+            head((base(attr::href("https://example.com/")), base(()))),
+            body(base(attr::href("/relative/"))),
+        ));
+        let mut linter = Linter::new().with(BaseElement::default());
+        let warnings = linter.lint(&page);
+        let warnings = warnings
+            .iter()
+            .map(|w| (w.path(), w.message()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            [
+                ("/0(head)/1(base)", "Only the first <base> element is used"),
+                ("/1(body)/0(base)", "Only the first <base> element is used"),
+                ("/1(body)/0(base)", "<base> must be a child of <head>"),
+                (
+                    "/1(body)/0(base)",
+                    "<base> href \"/relative/\" is not an absolute URL"
+                ),
+            ],
+        );
+
+        let page = html(head(base(attr::href("https://example.com/"))));
+        assert!(linter.lint(&page).is_empty());
+    }
+
+    #[test]
+    fn http_equiv_helpers() {
+        use crate::{html::helpers::*, Encoding};
+
+        let head = head((
+            http_equiv_content_type(Encoding::Latin1),
+            x_ua_compatible_edge(),
+            default_style("Dark"),
+        ));
+        assert_eq!(
+            head.render_to_string().unwrap(),
+            concat!(
+                "<head>",
+                r#"<meta content="text/html; charset=iso-8859-1" http-equiv="content-type">"#,
+                r#"<meta content="IE=edge" http-equiv="x-ua-compatible">"#,
+                r#"<meta content="Dark" http-equiv="default-style">"#,
+                "</head>",
+            ),
+        );
+    }
+
+    #[test]
+    fn typed_getters() {
+        let element = input((
+            attr::id("amount"),
+            attr::class("a b"),
+            attr::class("c"),
+            attr::min("-5"),
+            attr::max("+12e3"),
+            attr::step("0.25"),
+            attr::required(),
+            attr::accept("image/png, image/gif"),
+            Attr::set("data-n", "NaN"),
+        ));
+
+        assert_eq!(element.get_id(), Some("amount"));
+        assert_eq!(element.get_attr("class"), Some("a b c"));
+        assert_eq!(element.get_classes().collect::<Vec<_>>(), ["a", "b", "c"]);
+        assert_eq!(
+            element.get_tokens("accept", ", ").collect::<Vec<_>>(),
+            ["image/png", "image/gif"]
+        );
+        assert_eq!(element.get_tokens("rel", " ").count(), 0);
+        assert_eq!(element.get_int("min"), Some(-5));
+        assert_eq!(element.get_int("max"), Some(12));
+        assert_eq!(element.get_int("step"), Some(0));
+        assert_eq!(element.get_float("max"), Some(12000.0));
+        assert_eq!(element.get_float("step"), Some(0.25));
+        assert_eq!(element.get_float("data-n"), None);
+        assert!(element.get_flag("required"));
+        assert!(!element.get_flag("disabled"));
+    }
+
+    #[test]
+    fn case_insensitive_attributes() {
+        use crate::lint::{AttributeApplicability, Linter, RoleUsage};
+
+        let mut element = div(attr::id("x"));
+        element
+            .attributes
+            .insert("ROLE".into(), "button".to_string());
+        element.attributes.insert("Role".into(), "link".to_string());
+        element.attributes.insert("ColSpan".into(), "2".to_string());
+
+        assert_eq!(element.attr_ci("ID"), Some("x"));
+        assert!(element.has_attr_ci("colspan"));
+        assert!(!element.has_attr_ci("rowspan"));
+        element.attr_ci_mut("colspan").unwrap().push('0');
+        assert_eq!(element.attributes["ColSpan"], "20");
+
+        let warnings = Linter::new()
+            .with(AttributeApplicability)
+            .with(RoleUsage)
+            .lint(&element);
+        assert_eq!(warnings.len(), 2);
+
+        assert_eq!(element.remove_attr_ci("role"), Some("button".to_string()));
+        assert!(!element.has_attr_ci("role"));
+        element.attributes.insert("role".into(), "none".to_string());
+        element.attributes.insert("ROLE".into(), "link".to_string());
+        assert_eq!(element.remove_attr_ci("role"), Some("none".to_string()));
+        assert_eq!(element.attributes.len(), 2);
+    }
+
+    #[test]
+    fn attr_values() {
+        let some = Some("hello".to_string());
+        let element = div((
+            Attr::set("hidden", true),
+            Attr::set("title", some.as_deref()),
+            Attr::set("tabindex", -1),
+            Attr::set("data-ratio", 0.5),
+            Attr::set("accesskey", 'k'),
+            attr::data_x("open", false),
+            Attr::set("aria-expanded", true),
+            Attr::set("draggable", false),
+        ));
+        assert_eq!(
+            element.render_to_string().unwrap(),
+            concat!(
+                r#"<div accesskey="k" aria-expanded="true" data-open="false" data-ratio="0.5" "#,
+                r#"draggable="false" hidden tabindex="-1" title="hello"></div>"#,
+            ),
+        );
+
+        // Only boolean attributes are removed by `false`
+        let none = None::<String>;
+        let element = element.with((
+            Attr::set("HIDDEN", false),
+            Attr::set("title", none),
+            Attr::set("aria-expanded", Some(false)),
+            Attr::flag("data-open", false),
+            Attr::flag("x-custom", true),
+        ));
+        assert!(!element.has_attr_ci("hidden"));
+        assert!(!element.has_attr_ci("title"));
+        assert!(!element.has_attr_ci("data-open"));
+        assert_eq!(element.attributes["aria-expanded"], "false");
+        assert_eq!(element.attributes["x-custom"], "");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn inner_text() {
+        let tree = div((
+            "  leading ",
+            span(" and  trailing "),
+            p(("para", br(()), "graph")),
+            pre("  keep\n  this "),
+            table((
+                tr((th("A"), th("B"))),
+                tr((
+                    td("1"),
+                    td(("2", Content::comment("x"), Content::raw("<b>"))),
+                )),
+            )),
+            div((Attr::yes("hidden"), "invisible")),
+            style("p {}"),
+            "end",
+        ));
+        assert_eq!(
+            tree.inner_text(),
+            "leading and trailing\n\npara\ngraph\n\n  keep\n  this\nA\tB\n1\t2\nend",
+        );
+        assert_eq!(pre(" a  b ").inner_text(), " a  b");
+        assert_eq!(div(()).inner_text(), "");
+    }
+
+    #[test]
+    fn render_plaintext() {
+        let tree = body((
+            h2("Items"),
+            ul((
+                li(("First", ul((li("Nested"), li(p(("Two", br(()), "lines"))))))),
+                li(a((attr::href("mailto:a@example.com"), "a@example.com"))),
+                li(a((attr::href("#top"), "Top"))),
+                li(a((attr::href(" JAVA\tSCRIPT:void(0)"), "Click"))),
+            )),
+            ol((attr::start(3), li("Three"), li("Four"))),
+            blockquote((p("Quoted"), p("twice"))),
+            hr(()),
+            img(attr::alt("Logo")),
+        ));
+        assert_eq!(
+            tree.render_plaintext(),
+            concat!(
+                "Items\n-----\n\n",
+                "- First\n  - Nested\n\n  - Two\n    lines\n\n",
+                "- a@example.com\n- Top\n- Click\n",
+                "3. Three\n4. Four\n\n",
+                "> Quoted\n>\n> twice\n\n",
+                "----------\n",
+                "Logo",
+            ),
+        );
+        assert_eq!(
+            tree.clone().into_document().render_plaintext(),
+            tree.render_plaintext()
+        );
+        let max = i64::MAX;
+        let list = ol((Attr::set("start", max.to_string()), li("a"), li("b")));
+        assert_eq!(list.render_plaintext(), format!("{max}. a\n{max}. b"));
+    }
+
+    #[test]
+    fn strict_profile() {
+        use crate::lint::{Linter, StrictProfile};
+
+        let page = html((
+            head((meta(attr::charset("utf-8")), style("a".repeat(70_000)))),
+            body((
+                script((attr::r#type("application/ld+json"), "{}")),
+                script(attr::src("https://evil.example.com/x.js")),
+                embed(attr::src("/plugin")),
+                a((attr::href(" Java\tScript:alert(1)"), "Click")),
+                button(Attr::set("ONCLICK", "go()")),
+                div(attr::style("b".repeat(6_000))),
+            )),
+        ));
+
+        let warnings = Linter::new().with(StrictProfile::amp_like()).lint(&page);
+        let warnings = warnings
+            .iter()
+            .map(|w| (w.path(), w.message()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            [
+                ("/0(head)", "<head> must contain <meta name=\"viewport\">"),
+                ("/0(head)", "<head> must contain <link rel=\"canonical\">"),
+                ("/1(body)/1(script)", "Custom scripts are not allowed"),
+                ("/1(body)/2(embed)", "<embed> is not allowed"),
+                (
+                    "/1(body)/3(a)",
+                    "javascript: URL in \"href\" is not allowed"
+                ),
+                (
+                    "/1(body)/4(button)",
+                    "Event handler attribute \"onclick\" is not allowed"
+                ),
+                (
+                    "/1(body)/5(div)",
+                    "style attribute is 6000 bytes long, at most 1000 are allowed"
+                ),
+                (
+                    "/",
+                    "CSS is 76000 bytes long in total, at most 75000 are allowed"
+                ),
+            ],
+        );
+
+        let warnings = Linter::new().with(StrictProfile::new()).lint(&page);
+        assert_eq!(warnings.len(), 3);
+
+        // Non-ASCII attribute names must not panic
+        let odd = p((
+            Attr::set("aé", "x"),
+            Attr::set("éa", "x"),
+            Attr::set("oné", "x"),
+        ));
+        let warnings = Linter::new().with(StrictProfile::new()).lint(&odd);
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn bench_support() {
+        use crate::bench_support::*;
+
+        let tree = deep_tree(3).render_to_string().unwrap();
+        assert_eq!(
+            tree,
+            concat!(
+                r#"<div class="level-0">Level 0<div class="level-1">Level 1"#,
+                r#"<div class="level-2">Level 2<span>Leaf</span></div></div></div>"#,
+            ),
+        );
+
+        let element = attribute_heavy(1, 2).render_to_string().unwrap();
+        assert_eq!(
+            element,
+            concat!(
+                r#"<div><span class="item even" data-attribute-0="&quot;0-0&quot;" "#,
+                r#"data-attribute-1="&quot;0-1&quot;" hidden id="element-0"></span></div>"#,
+            ),
+        );
+
+        for size in [0, 1, 10] {
+            assert_eq!(
+                page(size).render_to_string().unwrap(),
+                page(size).render_to_string().unwrap(),
+            );
+            assert!(text_heavy(size).render_to_string().is_ok());
+        }
+        let len = page(10).render_to_string().unwrap().len();
+        assert!((20_000..40_000).contains(&len), "{len}");
+    }
+
+    #[test]
+    fn outlined_constructors() {
+        assert_eq!(
+            p((attr::id("a"), "Hi", em("there"))),
+            Element::normal("p").with((attr::id("a"), "Hi", em("there"))),
+        );
+        assert_eq!(br(()), Element::new("br", crate::ElementKind::Void));
+        assert_eq!(
+            svg::linear_gradient(()),
+            Element::new("linearGradient", crate::ElementKind::Foreign),
+        );
+        assert_eq!(
+            crate::mathml::mrow(crate::mathml::mi("x")),
+            Element::new("mrow", crate::ElementKind::Foreign).with(crate::mathml::mi("x")),
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn static_elements() {
+        use crate::{ElementKind, StaticContent, StaticElement};
+
+        const HEAD: StaticElement = StaticElement::normal("head").with_children(&[
+            StaticContent::Element(
+                StaticElement::new("meta", ElementKind::Void)
+                    .with_attributes(&[("charset", "utf-8")]),
+            ),
+            StaticContent::Comment("Static"),
+            StaticContent::Element(
+                StaticElement::new("script", ElementKind::RawText)
+                    .with_attributes(&[("defer", ""), ("src", "/a.js")])
+                    .with_children(&[StaticContent::Raw("")]),
+            ),
+        ]);
+
+        assert_eq!(
+            Element::from(HEAD),
+            head((
+                meta(attr::charset("utf-8")),
+                Content::comment("Static"),
+                script((attr::defer(), attr::src("/a.js"), Content::raw(""))),
+            )),
+        );
+        assert_eq!(
+            HEAD.render_to_string().unwrap(),
+            r#"<head><meta charset="utf-8"><!--Static--><script defer src="/a.js"></script></head>"#,
+        );
+
+        const INVALID: StaticElement =
+            StaticElement::new("br", ElementKind::Void).with_children(&[StaticContent::Text("x")]);
+        assert!(INVALID.render_to_string().is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn borrowed_elements() {
+        use crate::{ContentRef, ElementKind, ElementRef};
+
+        let text = String::from("a < b & \"c\"");
+        let shared = p(("Shared ", em("fragment")));
+        let tree = ElementRef::normal("div")
+            .attr("title", &text)
+            .attr("hidden", "")
+            .child(&text)
+            .child(ContentRef::Comment("note"))
+            .child(ElementRef::new("script", ElementKind::RawText).child("if (a < b) {}"))
+            .child(ElementRef::new("br", ElementKind::Void))
+            .child(&shared);
+
+        let owned = tree.to_element();
+        assert_eq!(
+            owned,
+            div((
+                attr::title(&text),
+                Attr::yes("hidden"),
+                &text,
+                Content::comment("note"),
+                script("if (a < b) {}"),
+                br(()),
+                shared.clone(),
+            )),
+        );
+        assert_eq!(
+            tree.render_to_string().unwrap(),
+            owned.render_to_string().unwrap(),
+        );
+
+        let invalid = ElementRef::normal("p")
+            .child(ElementRef::new("script", ElementKind::RawText).child("</script><b>"));
+        assert_eq!(
+            invalid.render_to_string().unwrap_err().path(),
+            "/0(script)/0"
+        );
+        let invalid = ElementRef::normal("p")
+            .child(ElementRef::new("br", ElementKind::Void).child(ContentRef::Raw("x")));
+        assert_eq!(invalid.render_to_string().unwrap_err().path(), "/0(br)/0");
+        let invalid = ElementRef::normal("p").child(ElementRef::normal("b c"));
+        assert!(invalid.render_to_string().is_err());
+
+        let options = RenderOptions {
+            foreign_xml: true,
+            ..RenderOptions::default()
+        };
+        let math = ElementRef::new("math", ElementKind::Foreign)
+            .attr("display", "")
+            .child(ContentRef::Comment("a--b"));
+        let mut result = String::new();
+        math.render_with(&mut result, &options).unwrap();
+        assert_eq!(result, r#"<math display=""><!--a-=b--></math>"#);
+    }
+
+    #[test]
+    fn dyn_render() {
+        use crate::DynRender;
+
+        let parts: Vec<Box<dyn DynRender>> = vec![
+            Box::new(html((head(()), Content::Flush, body(p("Hi")))).into_document()),
+            Box::new(div(input("invalid"))),
+            Box::new(Content::text("a & b")),
+        ];
+
+        assert_eq!(
+            parts[0].render_chunks().unwrap(),
+            [
+                "<!DOCTYPE html><html><head></head>",
+                "<body><p>Hi</p></body></html>"
+            ],
+        );
+        assert!(parts[1].render_to_string().is_err());
+        assert_eq!(parts[2].render_to_string().unwrap(), "a &amp; b");
+
+        let mut result = String::new();
+        let errors = parts[1]
+            .render_lenient(&mut result, &RenderOptions::default(), &Placeholder::Empty)
+            .unwrap();
+        assert_eq!(result, "<div></div>");
+        assert_eq!(errors.len(), 1);
+
+        let mut result = String::new();
+        let writer: &mut dyn std::fmt::Write = &mut result;
+        parts[2]
+            .render_dyn(writer, &RenderOptions::default())
+            .unwrap();
+        assert_eq!(result, "a &amp; b");
+    }
+
+    #[test]
+    fn streaming() {
+        use crate::FlushWrite;
+
+        #[derive(Default)]
+        struct Recorder {
+            current: String,
+            sent: Vec<String>,
+        }
+
+        impl std::fmt::Write for Recorder {
+            fn write_str(&mut self, s: &str) -> std::fmt::Result {
+                self.current.push_str(s);
+                Ok(())
+            }
+        }
+
+        impl FlushWrite for Recorder {
+            fn flush_chunk(&mut self) -> std::fmt::Result {
+                self.sent.push(std::mem::take(&mut self.current));
+                Ok(())
+            }
+        }
+
+        // The head is passed on before the body fails to render
+        let page = html((
+            head(title("Hi")),
+            Content::Flush,
+            body((p("Hello"), Content::Flush, br("invalid"))),
+        ))
+        .into_document();
+        let mut recorder = Recorder::default();
+        assert!(page
+            .render_streaming(&mut recorder, &RenderOptions::default())
+            .is_err());
+        assert_eq!(
+            recorder.sent,
+            [
+                "<!DOCTYPE html><html><head><title>Hi</title></head>",
+                "<body><p>Hello</p>",
+            ],
+        );
+        assert!(page.render_chunks().is_err());
+
+        // Trait objects stream as well
+        let boxed: Box<dyn crate::DynRender> = Box::new(div((p("a"), Content::Flush, p("b"))));
+        let mut recorder = Recorder::default();
+        boxed
+            .render_streaming(&mut recorder, &RenderOptions::default())
+            .unwrap();
+        assert_eq!(recorder.sent, ["<div><p>a</p>"]);
+        assert_eq!(recorder.current, "<p>b</p></div>");
+    }
+
+    #[cfg(all(feature = "gzip", feature = "brotli"))]
+    #[test]
+    fn compression() {
+        use std::io::Read;
+
+        use crate::compress::{BrotliWriter, Compression, GzipWriter};
+
+        let page = html((
+            head(title("Hi")),
+            Content::Flush,
+            body(p("Hello ".repeat(100))),
+        ));
+        let expected = page.render_to_string().unwrap();
+
+        let mut writer = GzipWriter::new(vec![]);
+        page.render(&mut writer).unwrap();
+        let compressed = writer.finish().unwrap();
+        assert!(compressed.len() < expected.len());
+        let mut decompressed = String::new();
+        flate2::read::GzDecoder::new(&compressed[..])
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, expected);
+
+        let mut writer = BrotliWriter::new(vec![]);
+        page.render(&mut writer).unwrap();
+        let compressed = writer.finish().unwrap();
+        let mut decompressed = String::new();
+        brotli::Decompressor::new(&compressed[..], 4096)
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, expected);
+
+        // Every prefix of the chunks can be decompressed on its own
+        let chunks = page.render_chunks().unwrap();
+        let options = RenderOptions::default();
+        let compressed = Compression::Gzip.render_chunks(&page, &options).unwrap();
+        assert_eq!(compressed.len(), 2);
+        let mut decompressed = vec![];
+        let result =
+            flate2::read::GzDecoder::new(&compressed[0][..]).read_to_end(&mut decompressed);
+        assert!(result.is_err()); // The stream is incomplete
+        assert_eq!(String::from_utf8(decompressed).unwrap(), chunks[0]);
+        let mut decompressed = String::new();
+        flate2::read::GzDecoder::new(&compressed.concat()[..])
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, expected);
+
+        let compressed = Compression::Brotli.render_chunks(&page, &options).unwrap();
+        let mut decompressed = vec![];
+        let result =
+            brotli::Decompressor::new(&compressed[0][..], 4096).read_to_end(&mut decompressed);
+        assert!(result.is_err());
+        assert_eq!(String::from_utf8(decompressed).unwrap(), chunks[0]);
+        let mut decompressed = String::new();
+        brotli::Decompressor::new(&compressed.concat()[..], 4096)
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, expected);
+
+        // Streaming directly to a writer produces the same stream
+        let written = Compression::Brotli
+            .render_to_writer(&page, &options, vec![])
+            .unwrap();
+        assert_eq!(written, compressed.concat());
+        let error = Compression::Gzip
+            .render_to_writer(&br("invalid"), &options, vec![])
+            .unwrap_err();
+        assert!(error.get_ref().unwrap().is::<crate::Error>());
+
+        assert_eq!(
+            Compression::negotiate("gzip, deflate, br;q=0.9"),
+            Some(Compression::Brotli),
+        );
+        assert_eq!(
+            Compression::negotiate("br;q=0, gzip"),
+            Some(Compression::Gzip)
+        );
+        assert_eq!(Compression::negotiate("identity"), None);
+        assert_eq!(Compression::negotiate("*"), Some(Compression::Brotli));
+        assert_eq!(Compression::negotiate("br;q=0, *"), Some(Compression::Gzip));
+        assert_eq!(
+            Compression::negotiate("gzip;q=0, *"),
+            Some(Compression::Brotli)
+        );
+        assert_eq!(Compression::negotiate("br;q=0, gzip;q=0, *"), None);
+        assert_eq!(
+            Compression::negotiate("*, br, br;q=0"),
+            Some(Compression::Gzip)
+        );
+        assert_eq!(Compression::Brotli.content_encoding(), "br");
+    }
+
+    #[test]
+    fn removable_enum_attributes() {
+        let element = div((
+            attr::Hidden::Yes,
+            attr::Popover::Manual,
+            attr::Translate::No,
+        ));
+        assert_eq!(
+            element.render_to_string().unwrap(),
+            r#"<div hidden popover="manual" translate="no"></div>"#,
+        );
+
+        let element = element.with((
+            attr::Hidden::from(false),
+            attr::Popover::No,
+            attr::Translate::Inherit,
+        ));
+        assert_eq!(element, div(()));
+
+        let element = div((
+            attr::Hidden::from(true),
+            attr::Popover::from(true),
+            attr::Translate::from(true),
+        ));
+        assert_eq!(
+            element.render_to_string().unwrap(),
+            r#"<div hidden popover translate="yes"></div>"#,
+        );
+        assert!(element.get_flag("hidden"));
+        assert_eq!(
+            div(attr::Hidden::UntilFound).get_attr("hidden"),
+            Some("until-found"),
+        );
+    }
+
+    #[test]
+    fn datasets() {
+        use std::collections::BTreeMap;
+
+        use crate::html::dataset::{kebab_case, Dataset};
+
+        assert_eq!(kebab_case("id"), "id");
+        assert_eq!(kebab_case("user_id"), "user-id");
+        assert_eq!(kebab_case("userID"), "user-id");
+        assert_eq!(kebab_case("XMLHttpRequest"), "xml-http-request");
+        assert_eq!(kebab_case("item2Name"), "item2-name");
+        assert_eq!(kebab_case("a  b--c"), "a-b-c");
+        assert_eq!(kebab_case("_"), "");
+
+        let map = BTreeMap::from([("sort_key", "b"), ("groupName", "x")]);
+        let element = div((
+            attr::data_x("sort-key", "a"),
+            map.into_iter().collect::<Dataset>(),
+            Dataset::prefixed("chart")
+                .set("", "bar")
+                .set("max_value", 10)
+                .set("stacked", true)
+                .set("legend", false)
+                .set("title", None::<&str>),
+        ));
+        assert_eq!(
+            element.render_to_string().unwrap(),
+            concat!(
+                r#"<div data-chart="bar" data-chart-legend="false" data-chart-max-value="10" "#,
+                r#"data-chart-stacked="true" data-group-name="x" data-sort-key="b"></div>"#,
+            ),
+        );
+
+        let dataset = Dataset::new()
+            .set("a", 1)
+            .set("b", false)
+            .set("c", None::<u8>);
+        assert_eq!(
+            dataset.iter().collect::<Vec<_>>(),
+            [
+                ("data-a", Some("1")),
+                ("data-b", Some("false")),
+                ("data-c", None)
+            ],
+        );
+        assert!(Dataset::prefixed("x").is_empty());
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    #[allow(deprecated)]
+    fn tabular_derive() {
+        use crate::tabular::{html_table, ToDl, ToHtmlTable};
+
+        fn link(url: &str) -> Element {
+            a((attr::href(url), url))
+        }
+
+        #[derive(ToHtmlTable, ToDl)]
+        struct Row<'a, T: crate::tabular::ToCell> {
+            r#type: &'a str,
+            value: T,
+            missing: Option<f64>,
+            #[el(with = "link")]
+            url: &'a str,
+            #[el(rename = "<Raw>")]
+            raw: Content,
+        }
+
+        let row = Row {
+            r#type: "a & b",
+            value: 'x',
+            missing: None,
+            url: "/x",
+            raw: Content::raw("<b>hi</b>"),
+        };
+        assert_eq!(
+            row.to_html_table().render_to_string().unwrap(),
+            concat!(
+                "<table><thead><tr>",
+                r#"<th scope="col">Type</th><th scope="col">Value</th><th scope="col">Missing</th>"#,
+                r#"<th scope="col">Url</th><th scope="col">&lt;Raw&gt;</th>"#,
+                "</tr></thead><tbody><tr>",
+                r#"<td>a &amp; b</td><td>x</td><td></td><td><a href="/x">/x</a></td><td><b>hi</b></td>"#,
+                "</tr></tbody></table>",
+            ),
+        );
+        assert_eq!(row.dl_entries().len(), 5);
+        assert_eq!(
+            html_table::<Row<'_, char>>([]),
+            table((thead(tr(Row::<char>::table_header())), tbody(()))),
+        );
+    }
+
+    #[test]
+    fn error_page() {
+        use crate::pages::{error_page, internal_error_page};
+
+        let page = error_page(418, "I'm a <teapot>", p("Short & stout"));
+        assert_eq!(page.status, 418);
+        let rendered = page.render_to_string().unwrap();
+        assert!(rendered.starts_with("<!DOCTYPE html><html lang=\"en\"><head>"));
+        assert!(rendered.contains("<title>418 I'm a &lt;teapot&gt;</title>"));
+        assert!(rendered.contains(concat!(
+            "<main><div class=\"status\">418</div><h1>I'm a &lt;teapot&gt;</h1>",
+            "<p>Short &amp; stout</p></main>",
+        )));
+        assert_eq!(
+            Document::from(page.clone()).render_to_string().unwrap(),
+            rendered,
+        );
+
+        let page = internal_error_page();
+        assert_eq!(page.status, 500);
+        assert!(page
+            .render_to_string()
+            .unwrap()
+            .contains("<h1>Internal Server Error</h1>"));
+    }
+
+    #[test]
+    fn document_redirect() {
+        let html = Document::redirect("/a?b=\"c\"&d")
+            .unwrap()
+            .render_to_string()
+            .unwrap();
+        assert!(html.contains("url='/a?b=%22c%22&d'"));
+        assert!(html.contains(r#"<a href="/a?b=&quot;c&quot;&d">/a?b="c"&amp;d</a>"#));
+        assert!(Document::redirect("").is_none());
+        assert!(Document::redirect(" data:text/html,hi").is_none());
+    }
+
+    #[test]
+    fn layout_blocks() {
+        use crate::layout::{self, BaseLayout, Blocks, Layout};
+
+        let sidebar_layout = |mut blocks: Blocks| {
+            let sidebar = blocks.take_or("sidebar", p("No sidebar"));
+            let blocks = blocks.wrap(layout::CONTENT, |content| {
+                div((attr::class("grid"), aside(sidebar), main(content)))
+            });
+            BaseLayout::new().default_title("Untitled").apply(blocks)
+        };
+
+        let document = sidebar_layout.apply(
+            Blocks::new()
+                .content(p("Main"))
+                .scripts(script("run()"))
+                .head_extra(meta(attr::name("robots")))
+                .block("unused", p("Ignored")),
+        );
+        assert_eq!(
+            document.render_to_string().unwrap(),
+            concat!(
+                r#"<!DOCTYPE html><html><head><meta charset="utf-8">"#,
+                r#"<meta content="width=device-width, initial-scale=1" name="viewport">"#,
+                r#"<title>Untitled</title><meta name="robots"></head><body>"#,
+                r#"<div class="grid"><aside><p>No sidebar</p></aside><main><p>Main</p></main></div>"#,
+                "<script>run()</script></body></html>",
+            ),
+        );
+
+        let blocks = Blocks::new().title("A").title("B").block("sidebar", "S");
+        assert_eq!(blocks.get_title(), Some("B"));
+        assert!(blocks.is_filled("sidebar"));
+        assert!(!blocks.is_filled(layout::CONTENT));
+        let rendered = sidebar_layout.apply(blocks).render_to_string().unwrap();
+        assert!(rendered.contains("<title>B</title>"));
+        assert!(rendered.contains("<aside>S</aside><main></main>"));
+    }
+
+    #[test]
+    fn deterministic_hash_map_attributes() {
+        use std::collections::HashMap;
+
+        use crate::assert_deterministic;
+
+        let expected = div(attr::id("second")).render_to_string().unwrap();
+        for _ in 0..32 {
+            let attrs = HashMap::from([
+                ("ID".to_string(), "first".to_string()),
+                ("id".to_string(), "second".to_string()),
+            ]);
+            assert_eq!(assert_deterministic(&div(attrs)).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic = "nondeterministic rendering: second output differs at byte 0"]
+    fn deterministic_check_fails() {
+        use std::{cell::Cell, fmt};
+
+        use crate::assert_deterministic;
+
+        #[derive(Clone)]
+        struct Counter(Cell<u32>);
+
+        impl Render for Counter {
+            fn render<W: fmt::Write>(&self, w: &mut W) -> crate::Result<()> {
+                self.0.set(self.0.get() + 1);
+                let text = Content::text(self.0.get());
+                text.render(w)
+            }
+        }
+
+        assert_deterministic(&Counter(Cell::new(0))).unwrap();
+    }
+
+    #[test]
+    fn attr_list() {
+        use crate::AttrList;
+
+        let pairs: &[(&str, &str)] = &[("CLASS", "a"), ("id", "x"), ("class", "b a"), ("ID", "y")];
+        assert_eq!(
+            div((attr::class("z"), pairs)).render_to_string().unwrap(),
+            r#"<div class="z a b" id="y"></div>"#,
+        );
+
+        let list = [("hidden", true), ("hidden", false), ("open", true)]
+            .into_iter()
+            .collect::<AttrList<_, _>>();
+        assert_eq!(
+            details(list).render_to_string().unwrap(),
+            "<details open></details>",
+        );
+
+        let list = AttrList(vec![("required", false), ("spellcheck", false)]);
+        assert_eq!(
+            input(list).render_to_string().unwrap(),
+            r#"<input spellcheck="false">"#,
+        );
+
+        let list = AttrList::from(vec![(String::from("title"), None::<String>)]);
+        assert_eq!(
+            p((attr::title("t"), list)).render_to_string().unwrap(),
+            "<p></p>",
+        );
+    }
+
+    #[test]
+    fn trusted_html() {
+        use crate::TrustedHtml;
+
+        let fragment = ul((li("a < b"), li(em("c"))));
+        let trusted = TrustedHtml::from_render(&fragment).unwrap();
+        assert_eq!(
+            trusted.as_str(),
+            "<ul><li>a &lt; b</li><li><em>c</em></li></ul>"
+        );
+        assert_eq!(TrustedHtml::try_from(&fragment).unwrap(), trusted);
+        assert_eq!(
+            div((trusted.clone(), Content::from(trusted)))
+                .render_to_string()
+                .unwrap(),
+            "<div><ul><li>a &lt; b</li><li><em>c</em></li></ul><ul><li>a &lt; b</li><li><em>c</em></li></ul></div>",
+        );
+
+        let closing = TrustedHtml::from_static("</script><script>alert(1)");
+        assert!(br(closing.clone()).render_to_string().is_err());
+        let options = RenderOptions {
+            check_raw: true,
+            ..RenderOptions::default()
+        };
+        let mut out = String::new();
+        assert!(script(closing).render_with(&mut out, &options).is_err());
+    }
+
+    #[test]
+    fn error_label() {
+        let widget = div(br("oops")).labelled("widget");
+        let page = main(section(widget).labelled("section"));
+        let error = page.render_to_string().unwrap_err();
+        assert_eq!(error.label(), Some("widget"));
+        assert_eq!(error.path(), "/0(section)/0(div)/0(br)/0");
+
+        let error = div(br("oops")).render_to_string().unwrap_err();
+        assert_eq!(error.label(), None);
+    }
+
+    #[test]
+    fn render_budget() {
+        use std::time::Duration;
+
+        use crate::{ElementRef, ErrorCause, Placeholder};
+
+        let list = ul(vec![li("item"); 10]);
+        let options = |max_nodes| RenderOptions {
+            max_nodes: Some(max_nodes),
+            ..RenderOptions::default()
+        };
+
+        // 10 <li> and 10 texts
+        assert!(list.render_with(&mut String::new(), &options(20)).is_ok());
+        let error = (list.render_with(&mut String::new(), &options(19))).unwrap_err();
+        assert!(matches!(error.cause(), ErrorCause::BudgetExceeded));
+        assert_eq!(error.path(), "/9(li)/0");
+
+        let result = list.render_lenient(&mut String::new(), &options(5), &Placeholder::Empty);
+        assert!(matches!(
+            result.unwrap_err().cause(),
+            ErrorCause::BudgetExceeded
+        ));
+
+        let contents = vec![Content::text("a"); 3];
+        assert!(contents
+            .render_with(&mut String::new(), &options(2))
+            .is_err());
+
+        let mut borrowed = ElementRef::normal("ul");
+        for _ in 0..3 {
+            borrowed.add_child("item");
+        }
+        assert!(borrowed
+            .render_with(&mut String::new(), &options(2))
+            .is_err());
+        assert!(borrowed
+            .render_with(&mut String::new(), &options(3))
+            .is_ok());
+
+        let options = RenderOptions {
+            time_limit: Some(Duration::ZERO),
+            ..RenderOptions::default()
+        };
+        let large = ul(vec![li("item"); 100]);
+        let error = (large.render_with(&mut String::new(), &options)).unwrap_err();
+        assert!(matches!(error.cause(), ErrorCause::BudgetExceeded));
+        let small = ul(li("item"));
+        assert!(small.render_with(&mut String::new(), &options).is_ok());
+    }
+
+    #[test]
+    fn site_render() {
+        use crate::site::SiteRender;
+
+        let mut site = SiteRender::new();
+        let footer = site
+            .partial(&footer((
+                script(attr::src("/footer.js")),
+                img(attr::src("data:image/png;base64,AAAA")),
+                script(attr::src("HTTPS://cdn.example.com/a.js")),
+                script(attr::src("//cdn.example.com/b.js")),
+            )))
+            .unwrap();
+        site.register_asset("/fonts/a.woff2");
+        site.insert(
+            "/a.html",
+            html((
+                head((
+                    link((attr::Rel::Canonical, attr::href("https://example.com/a"))),
+                    link((attr::rel("ICON"), attr::href("/favicon.ico"))),
+                )),
+                body((
+                    a((attr::href("/b"), "B")),
+                    footer.clone(),
+                    Content::Trailer(Box::new(script(attr::src("/late.js")).into())),
+                )),
+            )),
+        );
+        site.insert("/b/", html(body((br("invalid"), footer))));
+
+        let pages = site.render_all();
+        assert_eq!(site.paths().collect::<Vec<_>>(), ["/a.html", "/b/"]);
+        assert!(pages["/a.html"].as_ref().unwrap().contains(
+            r#"<footer><script src="/footer.js"></script><img src="data:image/png;base64,AAAA">"#
+        ));
+        assert!(pages["/b/"].is_err());
+        assert!(site.render("/c").is_none());
+        assert_eq!(
+            site.assets().into_iter().collect::<Vec<_>>(),
+            ["/favicon.ico", "/fonts/a.woff2", "/footer.js", "/late.js"],
+        );
+
+        assert_eq!(SiteRender::file_path("/").unwrap(), "index.html");
+        assert_eq!(SiteRender::file_path("").unwrap(), "index.html");
+        assert_eq!(SiteRender::file_path("/a.html").unwrap(), "a.html");
+        assert_eq!(SiteRender::file_path("/b/c/").unwrap(), "b/c/index.html");
+        assert_eq!(SiteRender::file_path("/b..c/").unwrap(), "b..c/index.html");
+        for invalid in [
+            "/../etc",
+            "/a/./b",
+            "..",
+            "/a/..",
+            "/a\\..\\b",
+            "/a\0",
+            "C:/x",
+            "/c:",
+        ] {
+            let error = SiteRender::file_path(invalid).unwrap_err();
+            assert_eq!(error.0, invalid);
+        }
+    }
+
+    #[test]
+    fn srcdoc_element() {
+        let inner = a((attr::href("/?a=1&b=\"2\""), "&amp;"));
+        assert_eq!(
+            iframe(attr::srcdoc_element(&inner).unwrap())
+                .render_to_string()
+                .unwrap(),
+            r#"<iframe srcdoc="<a href=&quot;/?a=1&amp;b=&amp;quot;2&amp;quot;&quot;>&amp;amp;amp;</a>"></iframe>"#,
+        );
+        assert!(attr::srcdoc_element(&br("invalid")).is_err());
+    }
+
+    #[test]
+    fn svg_sprite_sheet() {
+        use crate::svg::sprite::SpriteSheet;
+
+        let mut icons = SpriteSheet::with_prefix("i-");
+        icons.insert_svg(
+            "dot",
+            svg::svg((
+                Attr::set("viewBox", "0 0 8 8"),
+                svg::circle(Attr::set("r", "4")),
+            )),
+        );
+        assert!(icons.contains("dot"));
+        assert_eq!(icons.id("dot"), "i-dot");
+        assert!(icons.icon("missing").is_none());
+
+        // Without references, nothing is injected
+        let mut empty = html(body(p("hi"))).into_document();
+        icons.inject(&mut empty);
+        assert_eq!(
+            empty.render_to_string().unwrap(),
+            "<!DOCTYPE html><html><body><p>hi</p></body></html>",
+        );
+
+        // Without a body, the sprite goes to the start of the root
+        let mut page =
+            html(icons.icon("dot").unwrap().with(Attr::set("width", "8"))).into_document();
+        icons.inject(&mut page);
+        assert_eq!(
+            page.render_to_string().unwrap(),
+            concat!(
+                r#"<!DOCTYPE html><html><svg aria-hidden="true" style="display: none">"#,
+                r#"<symbol id="i-dot" viewBox="0 0 8 8"><circle r="4" /></symbol></svg>"#,
+                r##"<svg aria-hidden="true" class="icon" width="8"><use href="#i-dot" /></svg></html>"##,
+            ),
+        );
+
+        assert_eq!(
+            icons.sprite(["missing"]).render_to_string().unwrap(),
+            r#"<svg aria-hidden="true" style="display: none" />"#,
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "charts")]
+    fn charts() {
+        use crate::charts::Chart;
+
+        let chart = Chart::new("Sales").size(40.0, 20.0).color("red");
+        let bars = (chart.clone().labels(["a", "b"]))
+            .bar(&[10.0, -10.0, f64::NAN])
+            .render_to_string()
+            .unwrap();
+        assert_eq!(
+            bars,
+            concat!(
+                r#"<svg height="20" role="img" viewBox="0 0 40 20" width="40"><title>Sales</title>"#,
+                r#"<rect fill="red" height="10" width="18" x="1" y="0"><title>a: 10</title></rect>"#,
+                r#"<rect fill="red" height="10" width="18" x="21" y="10"><title>b: -10</title></rect>"#,
+                "</svg>",
+            ),
+        );
+
+        // Charts without values only contain the accessible text
+        let empty = chart.clone().description("Nothing sold yet");
+        for svg in [
+            empty.sparkline(&[]),
+            empty.bar(&[f64::INFINITY]),
+            empty.donut(&[0.0, -1.0]),
+        ] {
+            assert_eq!(
+                svg.render_to_string().unwrap(),
+                concat!(
+                    r#"<svg height="20" role="img" viewBox="0 0 40 20" width="40">"#,
+                    "<title>Sales</title><desc>Nothing sold yet</desc></svg>",
+                ),
+            );
+        }
+
+        // Constant values are drawn in the middle
+        let flat = chart.sparkline(&[2.0, 2.0]).render_to_string().unwrap();
+        assert!(flat.contains(r#"points="1,10 39,10""#));
+
+        let donut = (Chart::new("Share").size(20.0, 20.0))
+            .colors(["red", "blue"])
+            .donut(&[1.0, 3.0])
+            .render_to_string()
+            .unwrap();
+        assert_eq!(
+            donut,
+            concat!(
+                r#"<svg height="20" role="img" viewBox="0 0 20 20" width="20"><title>Share</title>"#,
+                r#"<circle cx="10" cy="10" fill="none" r="8" stroke="red" stroke-dasharray="12.57 37.7" "#,
+                r#"stroke-dashoffset="0" stroke-width="4" transform="rotate(-90 10 10)"><title>1</title></circle>"#,
+                r#"<circle cx="10" cy="10" fill="none" r="8" stroke="blue" stroke-dasharray="37.7 12.57" "#,
+                r#"stroke-dashoffset="-12.57" stroke-width="4" transform="rotate(-90 10 10)"><title>3</title></circle>"#,
+                "</svg>",
+            ),
+        );
+    }
+
+    #[test]
+    fn mathml_latex() {
+        use crate::mathml::latex;
+
+        let render = |input| latex::to_mathml(input).unwrap().render_to_string().unwrap();
+
+        assert_eq!(
+            render(r"\sqrt[3]{x_1^2} - \alpha\Omega"),
+            concat!(
+                "<math><mroot><msubsup><mi>x</mi><mn>1</mn><mn>2</mn></msubsup><mn>3</mn></mroot>",
+                r#"<mo>−</mo><mi>α</mi><mi mathvariant="normal">Ω</mi></math>"#,
+            ),
+        );
+        assert_eq!(
+            render(r"\sum_{i=1}^n i^{23} \le \sin\left(\frac12\right."),
+            concat!(
+                "<math><munderover><mo>∑</mo><mrow><mi>i</mi><mo>=</mo><mn>1</mn></mrow><mi>n</mi></munderover>",
+                "<msup><mi>i</mi><mn>23</mn></msup><mo>≤</mo><mi>sin</mi>",
+                "<mrow><mo>(</mo><mfrac><mn>1</mn><mn>2</mn></mfrac></mrow></math>",
+            ),
+        );
+        assert_eq!(
+            render(r"\text{if } x\to\infty\,"),
+            concat!(
+                "<math><mtext>if </mtext><mi>x</mi><mo>→</mo><mi>∞</mi>",
+                r#"<mspace width="0.1667em" /></math>"#,
+            ),
+        );
+        assert_eq!(
+            latex::to_mathml_block("x^23")
+                .unwrap()
+                .render_to_string()
+                .unwrap(),
+            r#"<math display="block"><msup><mi>x</mi><mn>2</mn></msup><mn>3</mn></math>"#,
+        );
+
+        let error = |input| latex::parse(input).unwrap_err();
+        assert_eq!(error(r"\foo").message(), r"Unknown command \foo");
+        assert_eq!(error(r"x^2^3").position(), 3);
+        assert_eq!(error("{x").message(), "Expected '}'");
+        assert_eq!(error("x}").to_string(), "Unexpected '}' at position 1");
+        assert_eq!(error(r"x \right)").message(), r"Unmatched \right");
+        assert_eq!(error(r"\left( x").message(), r"Missing \right");
+        assert_eq!(error("a & b").message(), "Unsupported '&'");
+
+        // Deeply nested input is rejected instead of overflowing the stack
+        let nested = |depth| format!("{}x{}", "{".repeat(depth), "}".repeat(depth));
+        assert!(latex::to_mathml(&nested(latex::MAX_DEPTH - 1)).is_ok());
+        let too_deep = latex::parse(&nested(20_000)).unwrap_err();
+        assert_eq!(
+            too_deep.message(),
+            format!("Nested more than {} levels deep", latex::MAX_DEPTH),
+        );
+        assert_eq!(too_deep.position(), latex::MAX_DEPTH);
+        assert!(latex::parse(&format!("{}x", r"\sqrt".repeat(20_000))).is_err());
+        assert!(latex::parse(&format!("x{}", "^{".repeat(20_000))).is_err());
+    }
+
+    #[test]
+    fn foreign_descendants() {
+        use crate::{ContentRef, ElementKind, ElementRef};
+
+        let image = svg::svg((
+            a(svg::circle(())),
+            br(()),
+            style("a > b { fill: red; }"),
+            svg::title("1 < 2"),
+        ));
+        assert_eq!(
+            div((image.clone(), p(()), br(())))
+                .render_to_string()
+                .unwrap(),
+            concat!(
+                "<div><svg><a><circle /></a><br /><style>a &gt; b { fill: red; }</style>",
+                "<title>1 &lt; 2</title></svg><p></p><br></div>",
+            ),
+        );
+
+        // Raw text checks don't apply to foreign descendants
+        let image = svg::svg(script("</script><img>"));
+        assert_eq!(
+            image.render_to_string().unwrap(),
+            "<svg><script>&lt;/script&gt;&lt;img&gt;</script></svg>",
+        );
+        let image = ElementRef::new("svg", ElementKind::Foreign)
+            .child(ElementRef::new("script", ElementKind::RawText).child(ContentRef::Text("<b>")));
+        assert_eq!(
+            image.render_to_string().unwrap(),
+            "<svg><script>&lt;b&gt;</script></svg>",
+        );
+    }
+
+    #[test]
+    fn integration_points() {
+        use crate::mathml;
+
+        let image = svg::svg((
+            svg::foreign_object((style("a > b {}"), br(()), svg::svg(style("a > b {}")))),
+            svg::desc(div(())),
+            svg::g(div(())),
+        ));
+        assert_eq!(
+            image.render_to_string().unwrap(),
+            concat!(
+                "<svg><foreignObject><style>a > b {}</style><br>",
+                "<svg><style>a &gt; b {}</style></svg></foreignObject>",
+                "<desc><div></div></desc><g><div /></g></svg>",
+            ),
+        );
+
+        let formula = mathml::math((
+            mathml::mi(b(())),
+            mathml::annotation_xml((Attr::set("encoding", "Text/HTML"), div(()))),
+            mathml::annotation_xml((Attr::set("encoding", "MathML-Content"), div(()))),
+        ));
+        assert_eq!(
+            formula.render_to_string().unwrap(),
+            concat!(
+                "<math><mi><b></b></mi>",
+                r#"<annotation-xml encoding="Text/HTML"><div></div></annotation-xml>"#,
+                r#"<annotation-xml encoding="MathML-Content"><div /></annotation-xml></math>"#,
+            ),
+        );
+    }
+
+    #[test]
+    fn chunked_children() {
+        use crate::chunked::ChunkedChildren;
+
+        let mut children = ChunkedChildren::new().chunk_size(10);
+        assert!(children.is_empty());
+        children.push(b("bold")).unwrap();
+        children.push("a < b").unwrap();
+        assert!(children.push(br("invalid")).is_err());
+        children.push(i(())).unwrap();
+        assert_eq!(children.len(), 3);
+
+        let element = p(children);
+        assert_eq!(element.children.len(), 2);
+        assert_eq!(
+            element.render_to_string().unwrap(),
+            "<p><b>bold</b>a &lt; b<i></i></p>",
+        );
+
+        let options = RenderOptions {
+            foreign_xml: true,
+            ..RenderOptions::default()
+        };
+        let mut children = ChunkedChildren::with_options(options);
+        children.push(svg::svg(Attr::yes("hidden"))).unwrap();
+        assert_eq!(
+            div(children).render_to_string().unwrap(),
+            r#"<div><svg hidden="" /></div>"#,
+        );
+    }
+
+    #[test]
+    fn from_tag_name() {
+        use crate::{ElementKind, Namespace};
+
+        let cases = [
+            (area(()), "area"),
+            (base(()), "base"),
+            (br(()), "br"),
+            (col(()), "col"),
+            (embed(()), "embed"),
+            (hr(()), "hr"),
+            (img(()), "img"),
+            (input(()), "input"),
+            (link(()), "link"),
+            (meta(()), "meta"),
+            (source(()), "source"),
+            (track(()), "track"),
+            (wbr(()), "wbr"),
+            (script(()), "script"),
+            (style(()), "style"),
+            (textarea(()), "textarea"),
+            (title(()), "title"),
+            (template(()), "template"),
+            (div(()), "div"),
+            (iframe(()), "iframe"),
+            (noscript(()), "noscript"),
+        ];
+        for (element, name) in cases {
+            assert_eq!(Element::from_tag_name(name, Namespace::Html), element);
+        }
+
+        assert_eq!(ElementKind::for_html_tag("XMP"), ElementKind::RawText);
+        assert_eq!(ElementKind::for_html_tag("param"), ElementKind::Void);
+        assert_eq!(
+            Element::from_tag_name("linearGradient", Namespace::Svg),
+            svg::linear_gradient(()),
+        );
+        assert_eq!(
+            Element::from_tag_name("mi", Namespace::MathMl).kind,
+            ElementKind::Foreign,
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn security_report() {
+        use crate::audit::FindingKind;
+
+        assert!(p(("Safe", a(attr::href("/")))).security_report().is_empty());
+
+        let element = div((
+            style("p {}"),
+            Content::raw("<b>"),
+            a(Attr::set("HREF", " JavaScript:alert(1)")),
+            svg::a(Attr::set("xlink:href", "javascript:void(0)")),
+            Content::trailer(script("init()")),
+            img((attr::src("javascript:x"), Attr::set("onerror", "x()"))),
+        ));
+        let report = element.security_report();
+        let kinds = (report.findings().iter())
+            .map(|f| (f.path(), f.kind().clone()))
+            .collect::<Vec<_>>();
+        let attribute = |a: &str| a.to_string();
+        assert_eq!(
+            kinds,
+            [
+                ("/0(style)", FindingKind::InlineStyle),
+                ("/1", FindingKind::Raw),
+                (
+                    "/2(a)",
+                    FindingKind::JavascriptUrl {
+                        attribute: attribute("href")
+                    }
+                ),
+                (
+                    "/3(a)",
+                    FindingKind::JavascriptUrl {
+                        attribute: attribute("xlink:href")
+                    }
+                ),
+                ("/4(script)", FindingKind::InlineScript),
+                (
+                    "/5(img)",
+                    FindingKind::EventHandler {
+                        attribute: attribute("onerror")
+                    }
+                ),
+                (
+                    "/5(img)",
+                    FindingKind::JavascriptUrl {
+                        attribute: attribute("src")
+                    }
+                ),
+            ],
+        );
+
+        // Non-ASCII attribute names must not panic
+        let odd = p((Attr::set("aé", "x"), Attr::set("oné", "x")));
+        assert_eq!(odd.security_report().findings().len(), 1);
+    }
+
+    #[test]
+    fn render_options_builder() {
+        let options = RenderOptions::new()
+            .escape_end_tags(true)
+            .check_raw(true)
+            .check_styles(true)
+            .max_nodes(10)
+            .time_limit(std::time::Duration::from_secs(1));
+        assert_eq!(
+            options,
+            RenderOptions {
+                escape_end_tags: true,
+                check_raw: true,
+                check_styles: true,
+                max_nodes: Some(10),
+                time_limit: Some(std::time::Duration::from_secs(1)),
+                ..RenderOptions::default()
+            },
+        );
+        assert_eq!(
+            RenderOptions::strict(),
+            options.max_nodes(None).time_limit(None)
+        );
+
+        let element = p(("Grüße", textarea("</textarea>")));
+        let options = RenderOptions::strict();
+        let string = element.render_to_string_with(&options).unwrap();
+        assert_eq!(
+            string,
+            "<p>Grüße<textarea>&lt;&#x2F;textarea&gt;</textarea></p>"
+        );
+
+        let mut bytes = vec![];
+        element.render_to_writer(&mut bytes).unwrap();
+        assert_eq!(bytes, element.render_to_string().unwrap().as_bytes());
+
+        // Text is escaped, but trusted HTML can't be encoded
+        let options = RenderOptions::new().encoding(crate::Encoding::Ascii);
+        let mut bytes = vec![];
+        element
+            .render_to_writer_with_options(&mut bytes, &options)
+            .unwrap();
+        assert!(bytes.starts_with(b"<p>Gr&#xFC;&#xDF;e"));
+        let error = p(crate::TrustedHtml::from_static("Grüße"))
+            .render_to_writer_with_options(&mut vec![], &options)
+            .unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert!(error.get_ref().unwrap().is::<crate::Error>());
+
+        // Errors of the writer itself are passed through
+        struct Full;
+        impl std::io::Write for Full {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::WriteZero.into())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+        let error = element.render_to_writer(Full).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::WriteZero);
+    }
+
+    #[test]
+    fn display_attributes() {
+        let address = std::net::Ipv4Addr::LOCALHOST;
+        let element = p((
+            attr::title("a"),
+            Attr::display("title", address),
+            Attr::display("tabindex", -1),
+            Attr::display("CLASS", "b"),
+        ));
+        assert_eq!(
+            element.render_to_string().unwrap(),
+            r#"<p class="b" tabindex="-1" title="127.0.0.1"></p>"#,
+        );
+    }
+
+    #[cfg(feature = "axum")]
+    #[test]
+    fn axum_page() {
+        use std::time::Duration;
+
+        use ::axum_core::response::IntoResponse;
+        use http::{header, HeaderValue, StatusCode};
+
+        use crate::axum::{CachePolicy, Page};
+
+        let page = Page::new(Document::minimal("Hi", p("Hello")))
+            .status(StatusCode::NOT_FOUND)
+            .cache(CachePolicy::Public(Duration::from_secs(3600)))
+            .header(header::SET_COOKIE, HeaderValue::from_static("a=1"))
+            .header(header::SET_COOKIE, HeaderValue::from_static("b=2"))
+            .header(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+        let response = page.into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let headers = response.headers();
+        assert_eq!(headers[header::CONTENT_TYPE], "text/html; charset=utf-8");
+        assert_eq!(headers.get_all(header::SET_COOKIE).iter().count(), 2);
+        // Explicit headers replace the cache policy
+        assert_eq!(
+            headers
+                .get_all(header::CACHE_CONTROL)
+                .iter()
+                .collect::<Vec<_>>(),
+            ["no-cache"],
+        );
+
+        let response = Page::new(Document::minimal("Hi", ()))
+            .cache(CachePolicy::Private(Duration::from_secs(60)))
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            "private, max-age=60"
+        );
+
+        // Render errors take precedence
+        let response = Page::new(html(br("x")))
+            .status(StatusCode::CREATED)
+            .into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(CachePolicy::NoStore.to_string(), "no-store");
+    }
+
+    #[test]
+    fn flash_messages() {
+        use crate::flash::{self, Level};
+
+        assert!(flash::messages(Vec::<(Level, String)>::new()).is_empty());
+
+        let messages = flash::messages([
+            (Level::Info, "a"),
+            (Level::Warning, "<b>"),
+            (Level::Info, "c"),
+        ]);
+        assert_eq!(
+            div(messages).render_to_string().unwrap(),
+            concat!(
+                r#"<div><div aria-live="polite" class="flash flash-warning" role="status">"#,
+                "<ul><li>&lt;b&gt;</li></ul></div>",
+                r#"<div aria-live="polite" class="flash flash-info" role="status">"#,
+                "<ul><li>a</li><li>c</li></ul></div></div>",
+            ),
+        );
+
+        // Empty lists are still rendered when requested explicitly
+        assert_eq!(
+            flash::list(Level::Error, Vec::<String>::new())
+                .render_to_string()
+                .unwrap(),
+            r#"<div aria-live="assertive" class="flash flash-error" role="alert"><ul></ul></div>"#,
+        );
+    }
+
+    #[test]
+    fn file_input() {
+        use crate::html::{attr::Accept, helpers::file_input};
+
+        assert_eq!(
+            Accept::new()
+                .try_with(".PDF")
+                .and_then(|a| a.try_with(" Image/* "))
+                .and_then(|a| a.try_with("application/vnd.ms-excel"))
+                .and_then(|a| a.try_with(".pdf"))
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            [".pdf", "image/*", "application/vnd.ms-excel"],
+        );
+        for invalid in [
+            "",
+            ".",
+            "pdf",
+            ".a,b",
+            "text/*",
+            "*/*",
+            "image/",
+            "a b/c",
+            "text/plain;q=1",
+        ] {
+            assert!(!Accept::new().insert(invalid), "{invalid:?}");
+        }
+        assert_eq!(Accept::parse(""), Some(Accept::new()));
+        assert_eq!(
+            Accept::parse("audio/*,video/*").unwrap().to_string(),
+            "audio/*, video/*"
+        );
+
+        assert_eq!(
+            div(file_input("f", Accept::new(), false, None))
+                .render_to_string()
+                .unwrap(),
+            r#"<div><input name="f" type="file"></div>"#,
+        );
+        assert_eq!(
+            div(file_input("f", Accept::new(), true, Some(1536)))
+                .render_to_string()
+                .unwrap(),
+            concat!(
+                r#"<div><input data-max-size="1536" multiple name="f" type="file">"#,
+                "<noscript><small>Maximum size per file: 1.5 KiB.</small></noscript></div>",
+            ),
+        );
+        assert_eq!(
+            div(file_input("f", Accept::images(), false, Some(100)))
+                .render_to_string()
+                .unwrap(),
+            concat!(
+                r#"<div><input accept="image/*" data-max-size="100" name="f" type="file">"#,
+                "<noscript><small>Accepted file types: image/*. ",
+                "Maximum file size: 100 bytes.</small></noscript></div>",
+            ),
+        );
+    }
+
+    #[test]
+    fn noscript_fallback() {
+        use crate::{
+            html::helpers::{requires_script, with_noscript},
+            lint::{Linter, NoscriptFallback},
+        };
+
+        let tree = div((
+            with_noscript(div(Element::normal("x-nested")), p("Fallback")),
+            Element::normal("x-map").with(noscript("Fallback")),
+            div(requires_script()),
+            "\n  ",
+            Content::comment("The fallback"),
+            noscript(()),
+            Element::normal("x-chart"),
+            button(requires_script()),
+            p(noscript(())),
+            svg::svg(Element::new("font-face", crate::ElementKind::Foreign)),
+        ));
+
+        let warnings = Linter::new().with(NoscriptFallback).lint(&tree);
+        let warnings = warnings
+            .iter()
+            .map(|w| (w.path(), w.message()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            [
+                (
+                    "/7(x-chart)",
+                    "<x-chart> requires scripts but has no <noscript> fallback"
+                ),
+                (
+                    "/8(button)",
+                    "<button> requires scripts but has no <noscript> fallback"
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn sorted_classes() {
+        let element = div((
+            Attr::set("class", "b\ta  c b"),
+            Element::normal("x-widget").with(Attr::set("CLASS", "z y")),
+            svg::svg(Attr::set("class", "icon big")),
+        ));
+
+        assert_eq!(
+            element.render_to_string().unwrap(),
+            concat!(
+                "<div class=\"b\ta  c b\"><x-widget class=\"z y\"></x-widget>",
+                r#"<svg class="icon big" /></div>"#,
+            ),
+        );
+
+        let options = RenderOptions::new().sort_classes(true);
+        assert_eq!(
+            element.render_to_string_with(&options).unwrap(),
+            concat!(
+                r#"<div class="a b c"><x-widget class="y z"></x-widget>"#,
+                r#"<svg class="big icon" /></div>"#,
+            ),
+        );
+        assert_eq!(
+            ContentRef::from(&element)
+                .render_to_string_with(&options)
+                .unwrap(),
+            element.render_to_string_with(&options).unwrap(),
+        );
+    }
+
+    #[test]
+    fn element_parts() {
+        let element = div((attr::id("a"), "Text", br(())))
+            .labelled("Component")
+            .with(Attr::set("hidden", true));
+        let (name, kind, attributes, children) = element.clone().into_parts();
+        assert_eq!(name, "div");
+        assert_eq!(kind, crate::ElementKind::Normal);
+        assert_eq!(attributes.len(), 2);
+        assert_eq!(children.len(), 2);
+
+        let rebuilt = Element::from_parts(name, kind, attributes, children);
+        assert_eq!(rebuilt.label, None);
+        assert_eq!(rebuilt.labelled("Component"), element);
+
+        // Names are kept as they are
+        let svg = Element::from_parts(
+            "foreignObject".to_string(),
+            crate::ElementKind::Foreign,
+            Default::default(),
+            vec![],
+        );
+        assert_eq!(svg, svg::foreign_object(()));
+    }
+
+    #[test]
+    fn lengths() {
+        use crate::Length;
+
+        let cases = [
+            (Length::Number(1.0), "1"),
+            (Length::Number(-0.00001), "0"),
+            (Length::Number(0.1 + 0.2), "0.3"),
+            (Length::Number(1e21), "1000000000000000000000"),
+            (Length::Number(f64::NAN), "0"),
+            (Length::Number(f64::NEG_INFINITY), "0"),
+            (Length::Px(12.5), "12.5px"),
+            (Length::Percent(100.0), "100%"),
+            (Length::Em(-1.25), "-1.25em"),
+            (Length::Rem(0.33333), "0.3333rem"),
+            (Length::from(3_u8), "3"),
+            (Length::from(2.5_f32), "2.5"),
+        ];
+        for (length, expected) in cases {
+            assert_eq!(length.to_string(), expected);
+        }
+
+        assert_eq!(Length::Em(2.0).value(), 2.0);
+        assert_eq!(Length::Em(2.0).unit(), "em");
+        assert_eq!(
+            div(Attr::set("width", Length::Px(0.5))).attributes["width"],
+            "0.5px"
+        );
+    }
+
+    #[test]
+    fn view_box() {
+        use crate::svg::attr::{Align, MeetOrSlice, PreserveAspectRatio, ViewBox};
+
+        assert_eq!(
+            ViewBox::new(-0.5, 1.0 / 3.0, 1e3, f64::NAN).to_string(),
+            "-0.5 0.3333 1000 0"
+        );
+        assert_eq!(PreserveAspectRatio::default().to_string(), "xMidYMid");
+        assert_eq!(
+            PreserveAspectRatio::new(Align::XMaxYMax, MeetOrSlice::Meet).to_string(),
+            "xMaxYMax"
+        );
+        assert_eq!(
+            PreserveAspectRatio::new(Align::None, MeetOrSlice::Slice).to_string(),
+            "none"
+        );
+
+        // Names keep their case on foreign elements only
+        let element = svg::symbol((ViewBox::size(1.0, 2.0), PreserveAspectRatio::none()));
+        assert_eq!(element.attributes["viewBox"], "0 0 1 2");
+        assert_eq!(element.attributes["preserveAspectRatio"], "none");
+        let element = div(ViewBox::size(1.0, 2.0));
+        assert_eq!(element.attributes["viewbox"], "0 0 1 2");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn text_functions() {
+        use crate::{raw, text, texts};
+
+        assert_eq!(text(42), Content::text("42"));
+        assert_eq!(text('<'), Content::text("<"));
+        assert_eq!(raw("<hr>"), Content::raw("<hr>"));
+        assert_eq!(texts(["a", "b"]), [Content::text("a"), Content::text("b")]);
+        assert!(texts(Vec::<u8>::new()).is_empty());
+
+        let element = div((text(1.5), raw("<hr>"), script(text("a < b"))));
+        assert_eq!(
+            element.render_to_string().unwrap(),
+            "<div>1.5<hr><script>a < b</script></div>",
+        );
+        assert!(script(text("</script>")).render_to_string().is_err());
+    }
+
+    #[test]
+    fn pretty_printing() {
+        fn pretty(content: &impl Render) -> String {
+            let options = RenderOptions::new().pretty(true);
+            content.render_to_string_with(&options).unwrap()
+        }
+
+        // Blocks are indented
+        let list = div(ul((li("a"), "\n  ", li(b("b")))));
+        assert_eq!(
+            pretty(&list),
+            "<div>\n  <ul>\n    <li>a</li>\n    <li><b>b</b></li>\n  </ul>\n</div>",
+        );
+
+        // Mixed content is left alone
+        let mixed = div((p(("Hello ", em("world"))), "text", p("!")));
+        assert_eq!(pretty(&mixed), mixed.render_to_string().unwrap());
+        let nested = section(p(("Hello ", em("world"))));
+        assert_eq!(
+            pretty(&nested),
+            "<section>\n  <p>Hello <em>world</em></p>\n</section>"
+        );
+
+        // Markers
+        let kept = div((crate::PreserveWhitespace, div(p("a")), div(p("b"))));
+        assert_eq!(pretty(&kept), kept.render_to_string().unwrap());
+        let flat = ul((crate::NoIndent, li("a"), li(ol(li("b")))));
+        assert_eq!(
+            pretty(&flat),
+            "<ul>\n<li>a</li>\n<li>\n  <ol>\n    <li>b</li>\n  </ol>\n</li>\n</ul>",
+        );
+        assert_eq!(ul(crate::NoIndent).whitespace, crate::Whitespace::NoIndent);
+
+        // Whitespace-sensitive and special elements
+        let code = div((pre(div("  x\n")), script("let x;\n")));
+        assert_eq!(
+            pretty(&code),
+            "<div>\n  <pre><div>  x\n</div></pre>\n  <script>let x;\n</script>\n</div>",
+        );
+        let form = div((p("a"), textarea(" y ")));
+        assert_eq!(pretty(&form), form.render_to_string().unwrap());
+        let icon = div(svg::svg(svg::g(svg::circle(()))));
+        assert_eq!(pretty(&icon), "<div><svg><g><circle /></g></svg></div>");
+        let comment = div((Content::comment("c"), p("a")));
+        assert_eq!(pretty(&comment), "<div>\n  <!--c-->\n  <p>a</p>\n</div>");
+
+        // Documents
+        let document = Document::from(html((
+            crate::NoIndent,
+            head(title("Hi")),
+            body((div(Content::trailer(script(()))), footer(()))),
+        )));
+        assert_eq!(
+            pretty(&document),
+            "<!DOCTYPE html>\n<html>\n<head>\n  <title>Hi</title>\n</head>\n<body>\n  <div></div>\n  <footer></footer>\n  <script></script>\n</body>\n</html>",
+        );
+        assert!(!document.render_to_string().unwrap().contains('\n'));
+    }
+
+    #[test]
+    fn render_fragment() {
+        let page = Document::from(html(body((
+            header(nav((attr::id("nav"), a("Home")))),
+            main((
+                attr::id("main"),
+                div((attr::id("widget"), "Hello <world>")),
+                Content::trailer(script(attr::id("late"))),
+            )),
+        ))));
+
+        let widget = page.0.find_by_id("widget").unwrap();
+        assert_eq!(widget.name, "div");
+        assert_eq!(page.0.find_by_id("late").unwrap().name, "script");
+        assert!(page.0.find_by_id("").is_none());
+
+        assert_eq!(
+            page.render_fragment("widget").unwrap().unwrap(),
+            r#"<div id="widget">Hello &lt;world&gt;</div>"#,
+        );
+        assert_eq!(
+            page.render_fragment("main").unwrap().unwrap(),
+            r#"<main id="main"><div id="widget">Hello &lt;world&gt;</div><script id="late"></script></main>"#,
+        );
+        assert!(page.render_fragment("missing").is_none());
+
+        let options = RenderOptions::new().pretty(true);
+        assert_eq!(
+            page.render_fragment_with("nav", &options).unwrap().unwrap(),
+            r#"<nav id="nav"><a>Home</a></nav>"#,
+        );
+
+        let broken = Document::from(div(Element::normal("bad name").with(attr::id("x"))));
+        assert!(broken.render_fragment("x").unwrap().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "l10n")]
+    fn localized_text() {
+        use crate::l10n::{self, locale, Date, DateStyle, DateTime, Decimal, Time};
+
+        let (en, de, ar) = (locale!("en-US"), locale!("de-DE"), locale!("ar-EG"));
+        let render = |content: Content| p(content).render_to_string().unwrap();
+
+        assert_eq!(render(l10n::text_num(1234567, &en)), "<p>1,234,567</p>");
+        assert_eq!(render(l10n::text_num(-0.25, &de)), "<p>-0,25</p>");
+        assert_eq!(render(l10n::text_num(1234u16, &ar)), "<p>١٬٢٣٤</p>");
+        assert_eq!(
+            render(l10n::text_num(u128::MAX, &en)),
+            "<p>340,282,366,920,938,463,463,374,607,431,768,211,455</p>",
+        );
+        assert_eq!(render(l10n::text_num(Decimal::from(5), &en)), "<p>5</p>");
+        assert_eq!(render(l10n::text_num(f64::NAN, &en)), "<p>NaN</p>");
+        assert_eq!(render(l10n::text_num(f32::NEG_INFINITY, &de)), "<p>-∞</p>");
+
+        let date = Date::try_new_iso(2024, 3, 9).unwrap();
+        assert_eq!(
+            render(l10n::text_date(date, &en, DateStyle::Short)),
+            "<p>3/9/24</p>"
+        );
+        assert_eq!(
+            render(l10n::text_date(date, &en, DateStyle::default())),
+            "<p>Mar 9, 2024</p>"
+        );
+        assert_eq!(
+            render(l10n::text_date(date, &de, DateStyle::Long)),
+            "<p>9. März 2024</p>"
+        );
+        assert_eq!(
+            render(l10n::text_date(date, &locale!("xx"), DateStyle::Short)),
+            render(l10n::text_date(date, &locale!("und"), DateStyle::Short)),
+        );
+
+        let date_time = DateTime {
+            date,
+            time: Time::try_new(14, 5, 0, 0).unwrap(),
+        };
+        assert_eq!(
+            render(l10n::text_date_time(date_time, &en, DateStyle::Medium)),
+            "<p>Mar 9, 2024, 2:05:00\u{202f}PM</p>",
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "axum")]
+    fn axum_security_headers() {
+        use ::axum_core::response::IntoResponse;
+        use http::{header, HeaderValue};
+
+        use crate::{
+            axum::{Page, SecurityHeaders},
+            csp::{Csp, Source},
+        };
+
+        let document = Document::minimal(
+            "Hi",
+            (
+                script((attr::nonce("n1"), "go()")),
+                script(attr::src("/a.js")),
+                style((attr::nonce(""), "p {}")),
+                Content::trailer(script(attr::nonce("n2"))),
+            ),
+        );
+
+        let headers = SecurityHeaders::new().headers(&document);
+        assert_eq!(
+            headers["content-security-policy"],
+            "object-src 'none'; base-uri 'self'; script-src 'nonce-n1' 'nonce-n2'",
+        );
+        assert_eq!(headers[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+        assert_eq!(
+            headers[header::REFERRER_POLICY],
+            "strict-origin-when-cross-origin"
+        );
+        assert_eq!(SecurityHeaders::default(), SecurityHeaders::new());
+
+        let security = SecurityHeaders::new()
+            .csp(Csp::new().default_src(Source::Self_))
+            .report_only(true)
+            .nosniff(false)
+            .referrer_policy(attr::Referrerpolicy::NoReferrer);
+        let headers = security.headers(&document);
+        assert_eq!(headers.len(), 2);
+        assert_eq!(
+            headers["content-security-policy-report-only"],
+            "default-src 'self'; script-src 'nonce-n1' 'nonce-n2'",
+        );
+        assert_eq!(headers[header::REFERRER_POLICY], "no-referrer");
+
+        let none = SecurityHeaders::new()
+            .csp(None)
+            .nosniff(false)
+            .referrer_policy(None);
+        assert!(none.headers(&document).is_empty());
+        let empty = SecurityHeaders::new().csp(Csp::new());
+        assert!(!empty
+            .headers(&Document::minimal("Hi", ()))
+            .contains_key("content-security-policy"));
+
+        // Explicit headers take precedence
+        let response = Page::new(document)
+            .security(SecurityHeaders::new())
+            .header(
+                header::REFERRER_POLICY,
+                HeaderValue::from_static("no-referrer"),
+            )
+            .into_response();
+        let headers = response.headers();
+        assert_eq!(headers[header::CONTENT_TYPE], "text/html; charset=utf-8");
+        assert!(headers.contains_key(header::CONTENT_SECURITY_POLICY));
+        assert_eq!(headers[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+        assert_eq!(headers.get_all(header::REFERRER_POLICY).iter().count(), 1);
+        assert_eq!(headers[header::REFERRER_POLICY], "no-referrer");
+    }
+
+    #[test]
+    fn hreflang_alternates() {
+        use crate::html::helpers::hreflang_alternates;
+
+        let codes = |links: Vec<Element>| {
+            (links.iter())
+                .map(|link| link.get_attr("hreflang").unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let links = hreflang_alternates(
+            [
+                ("EN", "/en/"),
+                ("en_gb", "/en-gb/"),
+                ("es-419", "/es/"),
+                ("zh-hant-tw", "/zh/"),
+                ("sr-latn", "/sr/"),
+                ("fil", "/fil/"),
+            ],
+            "/",
+        )
+        .unwrap();
+        assert_eq!(
+            codes(links),
+            [
+                "en",
+                "en-GB",
+                "es-419",
+                "zh-Hant-TW",
+                "sr-Latn",
+                "fil",
+                "x-default"
+            ],
+        );
+
+        let links = hreflang_alternates(Vec::<(&str, &str)>::new(), "/").unwrap();
+        assert_eq!(
+            head(links).render_to_string().unwrap(),
+            r#"<head><link href="/" hreflang="x-default" rel="alternate"></head>"#,
+        );
+
+        for invalid in [
+            "",
+            "e",
+            "engl",
+            "x-default",
+            "en-",
+            "en-G",
+            "en-1234",
+            "en-US-x",
+            "d3",
+        ] {
+            assert!(
+                hreflang_alternates([(invalid, "/")], "/").is_none(),
+                "{invalid}"
+            );
+        }
+        assert!(hreflang_alternates([("de-DE", "/a"), ("de_de", "/b")], "/").is_none());
+    }
+
+    #[test]
+    fn robots_meta() {
+        use crate::html::helpers::*;
+
+        assert_eq!(Robots::new().to_string(), "all");
+        assert_eq!(Robots::none().to_string(), "noindex, nofollow");
+        assert_eq!(
+            Robots::new()
+                .unavailable_after("2025-12-31")
+                .max_video_preview(-1)
+                .max_image_preview(ImagePreview::None)
+                .max_snippet(0)
+                .notranslate()
+                .noimageindex()
+                .nosnippet()
+                .noarchive()
+                .to_string(),
+            "noarchive, nosnippet, noimageindex, notranslate, max-snippet:0, \
+             max-image-preview:none, max-video-preview:-1, unavailable_after:2025-12-31",
+        );
+        assert_eq!(ImagePreview::Standard.to_string(), "standard");
+
+        let head = head((
+            meta_robots(Robots::new().noindex()),
+            meta_robots_for("googlebot", Robots::new().max_snippet(-1)),
+            meta_description("A \"quoted\" page"),
+            link_canonical("https://example.com/"),
+        ));
+        assert_eq!(
+            head.render_to_string().unwrap(),
+            concat!(
+                "<head>",
+                r#"<meta content="noindex" name="robots">"#,
+                r#"<meta content="max-snippet:-1" name="googlebot">"#,
+                r#"<meta content="A &quot;quoted&quot; page" name="description">"#,
+                r#"<link href="https://example.com/" rel="canonical">"#,
+                "</head>",
+            ),
+        );
+    }
+
+    #[test]
+    fn datalist_inputs() {
+        use crate::html::{helpers::datalist_input, ids::IdRegistry};
+
+        let page = body((
+            div(attr::id("city-list")),
+            Content::trailer(script(attr::id("list"))),
+        ));
+        let mut ids = IdRegistry::from_element(&page);
+        assert!(ids.contains("city-list"));
+        assert!(ids.contains("list"));
+        assert!(!ids.reserve("list"));
+        assert!(ids.reserve("other"));
+        assert_eq!(ids.unique(""), "id");
+        assert_eq!(ids.unique("a b\tc"), "a-b-c");
+
+        let city = || input((attr::TypeInput::Text, attr::name("city")));
+        let first = datalist_input(&mut ids, city(), ["Berlin", "Paris"]);
+        let second = datalist_input(&mut ids, city(), Vec::<String>::new());
+        assert_eq!(first[0].get_attr("list"), Some("city-list-2"));
+        assert_eq!(first[1].get_attr("id"), Some("city-list-2"));
+        assert_eq!(first[1].children.len(), 2);
+        assert_eq!(second[0].get_attr("list"), Some("city-list-3"));
+        assert!(second[1].children.is_empty());
+
+        // The id takes precedence over the name
+        let both = input((attr::id("origin"), attr::name("from")));
+        let elements = datalist_input(&mut ids, both, [1, 2, 1]);
+        assert_eq!(elements[0].get_attr("list"), Some("origin-list"));
+        assert_eq!(
+            elements[1].render_to_string().unwrap(),
+            r#"<datalist id="origin-list"><option value="1"></option><option value="2"></option></datalist>"#,
+        );
+
+        let anonymous = datalist_input(&mut ids, input(()), ["x"]);
+        assert_eq!(anonymous[0].get_attr("list"), Some("list-2"));
+    }
+
+    #[test]
+    fn error_codes() {
+        use crate::ErrorCause;
+
+        let code = |element: Element| element.render_to_string().unwrap_err().code();
+        assert_eq!(code(Element::normal("a b")), "EL0002");
+        assert_eq!(code(div(Attr::set("a=b", ""))), "EL0003");
+        assert_eq!(code(br("x")), "EL0004");
+        assert_eq!(code(script("</script>")), "EL0005");
+        let options = RenderOptions::new().max_nodes(1);
+        let error = div((p(()), p(())))
+            .render_to_string_with(&options)
+            .unwrap_err();
+        assert_eq!(error.code(), "EL0007");
+        assert_eq!(error.code(), error.cause().code());
+
+        let causes = [
+            ErrorCause::Format(std::fmt::Error),
+            ErrorCause::InvalidTagName {
+                name: String::new(),
+            },
+            ErrorCause::InvalidAttrName {
+                name: String::new(),
+            },
+            ErrorCause::InvalidChild,
+            ErrorCause::InvalidRawText {
+                text: String::new(),
+            },
+            ErrorCause::Unencodable {
+                text: String::new(),
+            },
+            ErrorCause::BudgetExceeded,
+            ErrorCause::UnsafeStyle {
+                style: String::new(),
+                error: crate::css::StyleError::ScriptUrl,
+            },
+        ];
+        let codes = causes.iter().map(|c| c.code()).collect::<Vec<_>>();
+        assert_eq!(
+            codes,
+            ["EL0001", "EL0002", "EL0003", "EL0004", "EL0005", "EL0006", "EL0007", "EL0008"],
+        );
+    }
+
+    #[test]
+    fn tree_queries() {
+        use crate::query::Selector;
+
+        let mut page = html((
+            head((meta(attr::charset("utf-8")), title("Hi"))),
+            body((
+                Content::comment("nav"),
+                nav((
+                    attr::id("nav"),
+                    a((attr::class("ext link"), attr::href("https://x.org"))),
+                )),
+                main((
+                    section((attr::class("card"), p("a"))),
+                    section((attr::class("card wide"), attr::id("second"), p("b"))),
+                    svg::svg(svg::foreign_object(())),
+                )),
+                Content::trailer(script(attr::src("/late.js"))),
+            )),
+        ));
+
+        let names = |elements: Vec<&Element>| {
+            elements
+                .into_iter()
+                .map(|e| e.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(page.children_elements().collect()), ["head", "body"]);
+        assert_eq!(
+            names(page.descendants().collect()),
+            [
+                "head",
+                "meta",
+                "title",
+                "body",
+                "nav",
+                "a",
+                "main",
+                "section",
+                "p",
+                "section",
+                "p",
+                "svg",
+                "foreignObject",
+                "script",
+            ],
+        );
+        assert_eq!(p("x").descendants().count(), 0);
+
+        assert_eq!(
+            page.find(|e| e.name == "p").unwrap().children,
+            [Content::text("a")]
+        );
+        assert!(page.find(|e| e.name == "html").is_none());
+
+        assert_eq!(page.query("section").unwrap().count(), 2);
+        assert_eq!(page.query("SECTION.card").unwrap().count(), 2);
+        assert_eq!(page.query(".card.wide").unwrap().count(), 1);
+        assert_eq!(
+            page.query("#second").unwrap().next().unwrap().name,
+            "section"
+        );
+        assert_eq!(page.query("section#second.card").unwrap().count(), 1);
+        assert_eq!(page.query(".link").unwrap().count(), 1);
+        assert_eq!(page.query("[href]").unwrap().count(), 1);
+        assert_eq!(page.query("a[HREF='https://x.org']").unwrap().count(), 1);
+        assert_eq!(page.query(r#"[src="/late.js"]"#).unwrap().count(), 1);
+        assert_eq!(page.query("*").unwrap().count(), 14);
+        assert_eq!(page.query("foreignObject").unwrap().count(), 1);
+        assert_eq!(page.query("foreignobject").unwrap().count(), 0);
+
+        for invalid in [
+            "", "nav a", "a,b", "#", ".", "[x", "[x=]", "a:hover", "#a#b", "**",
+        ] {
+            assert!(Selector::parse(invalid).is_none(), "{invalid}");
+        }
+        for selector in [
+            "a",
+            "*",
+            "#a",
+            "a.b.c",
+            "[x]",
+            "input[type=\"a'b\"]",
+            "x[y='a\"b']",
+        ] {
+            let parsed = Selector::parse(selector).unwrap();
+            assert_eq!(Selector::parse(&parsed.to_string()), Some(parsed));
+        }
+        assert_eq!(Selector::parse("#a#a").unwrap().to_string(), "#a");
+
+        let head = page.find_mut(|e| e.name == "head").unwrap();
+        head.add(script(attr::src("/app.js")));
+        let late = page
+            .find_mut(|e| e.get_attr("src") == Some("/late.js"))
+            .unwrap();
+        late.name = "noscript".to_string();
+        let head = page.find(|e| e.name == "head").unwrap();
+        assert_eq!(head.query("script[src]").unwrap().count(), 1);
+        assert_eq!(page.query("noscript").unwrap().count(), 1);
+        assert_eq!(page.query("script").unwrap().count(), 1);
+    }
+
+    #[test]
+    fn invalid_query() {
+        use crate::query::{InvalidSelector, Selector};
+
+        let error = div(()).query("nav a").err().unwrap();
+        assert_eq!(error, InvalidSelector("nav a".to_string()));
+        assert_eq!(
+            error.to_string(),
+            r#"invalid or unsupported selector "nav a""#
+        );
+        assert_eq!("a".parse::<Selector>(), Ok(Selector::parse("a").unwrap()));
+    }
+
+    #[test]
+    #[cfg(all(feature = "miette", feature = "track-caller"))]
+    fn miette_diagnostics() {
+        use miette::Diagnostic;
+
+        let line = line!() + 1;
+        let page = main(section(br("x")).labelled("Card"));
+        let error = page.render_to_string().unwrap_err();
+        assert_eq!(error.code(), "EL0004");
+        assert_eq!(Diagnostic::code(&error).unwrap().to_string(), "EL0004");
+        assert!(error.help().unwrap().to_string().contains("void elements"));
+
+        let source = error.source_code().unwrap();
+        let label = error.labels().unwrap().next().unwrap();
+        assert_eq!(label.label(), Some("element constructed here (in Card)"));
+        let contents = source.read_span(label.inner(), 0, 0).unwrap();
+        assert_eq!(std::str::from_utf8(contents.data()).unwrap(), "br");
+        assert_eq!(contents.line() + 1, line as usize);
+        assert_eq!(contents.name(), Some(file!()));
+
+        // Without a location, there is nothing to point to
+        let mut element = Element::normal("x y");
+        element.location = None;
+        let error = element.render_to_string().unwrap_err();
+        assert!(error.source_code().is_none());
+        assert!(error.labels().is_none());
+        assert!(error.help().is_some());
+    }
+
+    #[test]
+    fn legacy_output() {
+        let page = Document::from(html(body(p("a\nb"))));
+
+        let options = RenderOptions::new().uppercase_doctype(true);
+        let string = page.render_to_string_with(&options).unwrap();
+        assert_eq!(
+            string,
+            "<!DOCTYPE HTML><html><body><p>a\nb</p></body></html>"
+        );
+
+        let options = RenderOptions::new().trailing_newline(true).crlf(true);
+        let string = page.render_to_string_with(&options).unwrap();
+        assert_eq!(
+            string,
+            "<!DOCTYPE html><html><body><p>a\nb</p></body></html>\r\n"
+        );
+
+        // Elements never get a trailing newline, only documents do.
+        let options = RenderOptions::new().trailing_newline(true);
+        let string = p("a").render_to_string_with(&options).unwrap();
+        assert_eq!(string, "<p>a</p>");
+
+        let options = RenderOptions::new().pretty(true).crlf(true);
+        let string = page.render_to_string_with(&options).unwrap();
+        assert_eq!(
+            string,
+            "<!DOCTYPE html>\r\n<html>\r\n  <body>\r\n    <p>a\nb</p>\r\n  </body>\r\n</html>"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_roundtrip() {
+        let page = Document::from(html((
+            head(title("Hi")),
+            body((
+                crate::NoIndent,
+                Content::comment("c"),
+                div((attr::class("a"), p("x < y"), Content::trailer(script("1")))),
+                svg::svg(svg::circle(())),
+                crate::TrustedHtml::from_static("<b>b</b>"),
+            )),
+        )));
+
+        let json = serde_json::to_string(&page).unwrap();
+        let back: Document = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.0, page.0);
+        assert_eq!(back.0.location, None);
+        assert_eq!(
+            back.render_to_string().unwrap(),
+            page.render_to_string().unwrap()
+        );
+
+        // Documents serialize like their root element.
+        let root = serde_json::to_value(&page.0).unwrap();
+        assert_eq!(serde_json::to_value(&page).unwrap(), root);
+
+        // Fields added later are optional.
+        let element: Element = serde_json::from_str(
+            r#"{"name":"p","kind":"Normal","attributes":{},"children":[{"Text":"hi"}],"label":null}"#,
+        )
+        .unwrap();
+        assert_eq!(element, p("hi"));
+    }
+
+    #[test]
+    fn fragments() {
+        let items = ["a", "b"].into_iter().map(li).collect::<crate::Fragment>();
+        assert_eq!(items.0.len(), 2);
+        assert_eq!(ul(items.clone()), ul((li("a"), li("b"))));
+        assert_eq!(items.render_to_string().unwrap(), "<li>a</li><li>b</li>");
+
+        let mut fragment = crate::Fragment::new()
+            .with("x < y")
+            .with(Content::comment("c"));
+        fragment.push(br(()));
+        fragment.extend([Content::Flush]);
+        fragment.extend(items);
+        assert_eq!(
+            fragment.render_to_string().unwrap(),
+            "x &lt; y<!--c--><br><li>a</li><li>b</li>"
+        );
+        assert_eq!(fragment.render_chunks().unwrap().len(), 2);
+
+        let empty = crate::Fragment::new();
+        assert_eq!(empty.render_to_string().unwrap(), "");
+        assert_eq!(div(empty), div(()));
+
+        // Errors are reported like for any other content.
+        let fragment = crate::Fragment::from(vec![Content::element(br("x"))]);
+        assert!(fragment.render_to_string().is_err());
+    }
+
+    #[test]
+    fn golden_files() {
+        use crate::testing::{diff, Golden, GoldenError};
+
+        let dir = std::env::temp_dir().join(format!("el-golden-test-{}", std::process::id()));
+        let golden = Golden::new(&dir).update(false);
+        let page = Document::from(html(body((h1("Title"), p("One"), p("Two")))));
+
+        match golden.check("pages/index", &page) {
+            Err(GoldenError::Missing(path)) => assert_eq!(path, dir.join("pages/index.html")),
+            other => panic!("unexpected result {other:?}"),
+        }
+
+        golden
+            .clone()
+            .update(true)
+            .check("pages/index", &page)
+            .unwrap();
+        golden.assert("pages/index", &page);
+
+        // Line endings and trailing whitespace are ignored.
+        let legacy = "<!DOCTYPE html>\r\n<html>  \r\n  <body>\r\n    <h1>Title</h1>\r\n    <p>One</p>\r\n    <p>Two</p>\r\n  </body>\r\n</html>";
+        std::fs::write(golden.path("legacy"), legacy).unwrap();
+        golden.assert("legacy", &page);
+
+        let changed = Document::from(html(body((h1("Title"), p("One"), p("2")))));
+        match golden.check("legacy", &changed) {
+            Err(GoldenError::Mismatch { diff, .. }) => assert_eq!(
+                diff,
+                "...\n <h1>Title</h1>\n <p>One</p>\n-<p>Two</p>\n+<p>2</p>\n </body>\n </html>\n"
+            ),
+            other => panic!("unexpected result {other:?}"),
+        }
+
+        // So are attribute order and whitespace between tags.
+        let page = Document::from(html(body(a((attr::href("/"), attr::id("home"), "Home")))));
+        let compact = "<!DOCTYPE html><html><body><a id=home  href=\"/\">Home</a></body></html>";
+        std::fs::write(golden.path("compact"), compact).unwrap();
+        golden.assert("compact", &page);
+
+        let broken = Document::from(html(br("x")));
+        assert!(matches!(
+            golden.check("broken", &broken),
+            Err(GoldenError::Render(_))
+        ));
+
+        assert_eq!(diff("a\nb\n", "b\nc\n"), "-a\n b\n+c\n");
+        assert_eq!(diff("", "a\n"), "+a\n");
+        assert_eq!(
+            diff("1\n2\n3\n4\n5\n6\n7\n8\n", "1\nx\n3\n4\n5\n6\n7\ny\n"),
+            " 1\n-2\n+x\n 3\n 4\n...\n 6\n 7\n-8\n+y\n"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "axum")]
+    fn axum_partial() {
+        use ::axum_core::response::IntoResponse;
+        use http::{header, StatusCode};
+
+        use crate::{axum::Partial, Fragment};
+
+        let response = Partial(tr(td("1"))).into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/html; charset=utf-8"
+        );
+
+        let partial = Partial([li("a"), li("b")].into_iter().collect::<Fragment>());
+        assert_eq!(partial.render_to_string().unwrap(), "<li>a</li><li>b</li>");
+
+        let response = Fragment::new().with(li("a")).into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/html; charset=utf-8"
+        );
+
+        let response = Partial(br("x")).into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn into_element_derive() {
+        use crate::{ElementComponent, IntoElement};
+
+        fn pair<T: ToString>(a: T, b: &'static str) -> Element {
+            span((a.to_string(), b))
+        }
+
+        #[derive(IntoElement)]
+        enum View<T: ToString> {
+            Empty,
+            #[el(with = "pair")]
+            Pair(T, &'static str),
+            #[el(with = "pair")]
+            Named {
+                value: T,
+                suffix: &'static str,
+            },
+            Children(Element, Attr),
+        }
+
+        #[derive(IntoElement)]
+        enum Never {}
+
+        fn never(never: Never) -> impl ElementComponent {
+            never
+        }
+
+        assert_eq!(div(View::<u8>::Empty), div(()));
+        assert_eq!(div(View::Pair(1, "a")), div(span(("1", "a"))));
+        assert_eq!(
+            div(View::Named {
+                value: 'x',
+                suffix: "b"
+            }),
+            div(span(("x", "b")))
+        );
+        assert_eq!(
+            div(View::<u8>::Children(br(()), attr::id("c"))),
+            div((br(()), attr::id("c")))
+        );
+        let _ = never;
+    }
+
+    #[test]
+    fn style_checks() {
+        use crate::css::{check_style, quote, StyleError};
+
+        for style in [
+            "",
+            "color: red; margin: 0 auto",
+            "background: url(/a.png) no-repeat, url('data:image/png;base64,AAAA')",
+            "content: 'url(javascript:x)'; font-family: \"expression(\"",
+            "font-family: 'a\\'b'",
+            "width: calc(100% - (2 * var(--gap)))",
+            "--behavior-x: 1; grid-area: a / b",
+            "color: /* comment */ red",
+        ] {
+            assert_eq!(check_style(style), Ok(()), "{style:?}");
+        }
+
+        for (style, error) in [
+            (
+                "background: url(javascript:alert(1))",
+                StyleError::ScriptUrl,
+            ),
+            (
+                "background: URL( \"  JavaScript:x\" )",
+                StyleError::ScriptUrl,
+            ),
+            ("background: url(java\tscript:x)", StyleError::ScriptUrl),
+            ("background: url(java\\9script:x)", StyleError::ScriptUrl),
+            ("background: url('\\6a avascript:x')", StyleError::ScriptUrl),
+            ("background: url(vbscript:x)", StyleError::ScriptUrl),
+            ("background: url(VBScript:x)", StyleError::ScriptUrl),
+            ("width: expression(alert(1))", StyleError::Expression),
+            ("width: e\\78pression(alert(1))", StyleError::Expression),
+            ("behavior: url(x.htc)", StyleError::Binding),
+            ("-moz-binding : url(x.xml#x)", StyleError::Binding),
+            ("color: red\u{7f}", StyleError::ControlCharacter('\u{7f}')),
+            ("font-family: 'a", StyleError::UnterminatedString),
+            ("font-family: 'a\nb'", StyleError::UnterminatedString),
+            ("color: red /* x", StyleError::UnterminatedComment),
+            ("width: calc(1px", StyleError::UnbalancedParentheses),
+            ("width: 1px)", StyleError::UnbalancedParentheses),
+        ] {
+            assert_eq!(check_style(style), Err(error), "{style:?}");
+        }
+
+        let evil = "x'; background: url(javascript:alert(1)); a: '\n\u{0}";
+        assert_eq!(
+            check_style(&format!("font-family: {}", quote(evil))),
+            Ok(())
+        );
+
+        assert!(attr::style_checked("color: red").is_ok());
+        assert_eq!(
+            attr::style_checked("width: expression(1)").unwrap_err(),
+            StyleError::Expression
+        );
+        assert_eq!(
+            p((
+                attr::style("color: red"),
+                attr::style_checked("margin: 0").ok()
+            )),
+            p(attr::style("color: red; margin: 0"))
+        );
+
+        let element = div(p(Attr::set("STYLE", "x: url(javascript:y)")));
+        assert!(element.render_to_string().is_ok());
+        let error = element
+            .render_to_string_with(&RenderOptions::strict())
+            .unwrap_err();
+        assert_eq!(error.path(), "/0(p)");
+        assert!(matches!(
+            error.cause(),
+            crate::ErrorCause::UnsafeStyle {
+                error: StyleError::ScriptUrl,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn svg_attributes() {
+        use crate::{svg::attr, Length};
+
+        let rect = svg::rect((
+            attr::x(1.0 / 3.0),
+            attr::y(-0.0),
+            attr::width(Length::Percent(50.0)),
+            attr::height(f64::NAN),
+            attr::rx(2_u8),
+            attr::transform("rotate(45)"),
+            attr::opacity(0.123_456),
+        ));
+        assert_eq!(
+            rect.render_to_string().unwrap(),
+            r#"<rect height="0" opacity="0.1235" rx="2" transform="rotate(45)" width="50%" x="0.3333" y="0" />"#,
+        );
+
+        let root = svg::svg((attr::xmlns(), attr::view_box(0.0, 0.0, 8.5, 4.0)));
+        assert_eq!(root.attributes["viewBox"], "0 0 8.5 4");
+        assert_eq!(root.attributes["xmlns"], attr::NAMESPACE);
+        assert_eq!(
+            svg::line((attr::x1(0), attr::y1(0), attr::x2(1), attr::y2(1)))
+                .render_to_string()
+                .unwrap(),
+            r#"<line x1="0" x2="1" y1="0" y2="1" />"#,
+        );
+    }
+
+    #[test]
+    fn query_strings() {
+        use crate::html::helpers::href_with_query;
+
+        assert_eq!(
+            href_with_query("https://example.com/", [("a b", "c+d"), ("e", "")]),
+            "https://example.com/?a%20b=c%2Bd&e="
+        );
+        assert_eq!(href_with_query("/x?", [("k", "v")]), "/x?k=v");
+        assert_eq!(href_with_query("/x?a=1&", [("k", "v")]), "/x?a=1&k=v");
+        assert_eq!(
+            href_with_query("/x#y?z", [("k", "#?&=/")]),
+            "/x?k=%23%3F%26%3D%2F#y?z"
+        );
+        assert_eq!(
+            href_with_query("", vec![("emoji".to_string(), "🐟".to_string())]),
+            "?emoji=%F0%9F%90%9F"
+        );
+        assert_eq!(href_with_query("/", [("-._~", "AZaz09")]), "/?-._~=AZaz09");
+
+        let form = form((
+            attr::Method::Post,
+            attr::action(href_with_query("/cart", [("item", "<1>")])),
+        ));
+        assert_eq!(
+            form.render_to_string().unwrap(),
+            r#"<form action="/cart?item=%3C1%3E" method="post"></form>"#
+        );
+    }
+
+    #[test]
+    fn aria_attributes() {
+        use crate::html::aria::*;
+
+        let slider = div((
+            Role::Slider,
+            aria_valuemin(0.0),
+            aria_valuemax(1.0),
+            aria_valuenow(1.0 / 3.0),
+            aria_valuetext("a third"),
+            AriaOrientation::Horizontal,
+            aria_label("Volume"),
+        ));
+        assert_eq!(
+            slider.render_to_string().unwrap(),
+            concat!(
+                r#"<div aria-label="Volume" aria-orientation="horizontal" aria-valuemax="1" "#,
+                r#"aria-valuemin="0" aria-valuenow="0.3333" aria-valuetext="a third" role="slider"></div>"#,
+            ),
+        );
+
+        let cell = td((
+            aria_colindex(2),
+            aria_rowspan(-1),
+            AriaSort::Other,
+            AriaChecked::from(true),
+            AriaCurrent::from(false),
+            AriaCurrent::Page,
+            aria_relevant("additions text"),
+            aria_relevant("text all"),
+        ));
+        assert_eq!(cell.attributes["aria-colindex"], "2");
+        assert_eq!(cell.attributes["aria-rowspan"], "-1");
+        assert_eq!(cell.attributes["aria-sort"], "other");
+        assert_eq!(cell.attributes["aria-checked"], "true");
+        assert_eq!(cell.attributes["aria-current"], "page");
+        assert_eq!(cell.attributes["aria-relevant"], "additions text all");
+
+        assert_eq!(AriaPressed::Mixed.to_string(), "mixed");
+        assert_eq!(AriaInvalid::from(false).to_string(), "false");
+        assert_eq!(AriaLive::Polite.to_string(), "polite");
+    }
 }
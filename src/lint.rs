@@ -0,0 +1,875 @@
+//! Optional checks for mistakes that don't prevent rendering.
+//!
+//! Rendering only fails if the output would be invalid or unsafe. Many other
+//! mistakes, like attributes on elements they don't apply to, produce valid
+//! HTML that browsers silently ignore. A [`Linter`] finds such mistakes by
+//! running a set of [`Lint`]s over an element tree.
+//!
+//! Linting is entirely opt-in and separate from rendering.
+//!
+//! # Example
+//!
+//! ```
+//! use el::{html::*, lint::{AttributeApplicability, Linter}};
+//!
+//! let table = table(tr((td(attr::colspan(2)), div(attr::colspan(2)))));
+//!
+//! let warnings = Linter::new().with(AttributeApplicability).lint(&table);
+//! assert_eq!(warnings.len(), 1);
+//! assert_eq!(warnings[0].path(), "/0(tr)/1(div)");
+//! ```
+
+use std::{collections::HashSet, fmt};
+
+use crate::{
+    html::helpers::REQUIRES_SCRIPT,
+    render::format_path,
+    url::{has_javascript_scheme, URL_ATTRIBUTES},
+    Content, Document, Element, ElementKind,
+};
+
+/// A mistake found by a [`Lint`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    lint: &'static str,
+    path: String,
+    message: String,
+}
+
+impl Warning {
+    /// The name of the lint that produced this warning.
+    pub fn lint(&self) -> &'static str {
+        self.lint
+    }
+
+    /// A human-readable path from the topmost element to the element that
+    /// caused the warning.
+    ///
+    /// The path has the same format as [`crate::Error::path`].
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// A human-readable description of the mistake.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Warning at {}: {} ({})",
+            self.path, self.message, self.lint
+        )
+    }
+}
+
+/// Information about the element currently being checked by a [`Lint`].
+pub struct LintContext<'a> {
+    lint: &'static str,
+    path: &'a [(usize, Option<String>)],
+    ancestors: &'a [&'a Element],
+    warnings: &'a mut Vec<Warning>,
+}
+
+impl LintContext<'_> {
+    /// The path to the current element, see [`Warning::path`].
+    pub fn path(&self) -> String {
+        format_path(self.path)
+    }
+
+    /// The ancestors of the current element, starting with the topmost
+    /// element.
+    pub fn ancestors(&self) -> &[&Element] {
+        self.ancestors
+    }
+
+    /// The direct parent of the current element, if any.
+    pub fn parent(&self) -> Option<&Element> {
+        self.ancestors.last().copied()
+    }
+
+    /// Report a mistake at the current element.
+    pub fn warn(&mut self, message: impl ToString) {
+        self.warnings.push(Warning {
+            lint: self.lint,
+            path: self.path(),
+            message: message.to_string(),
+        });
+    }
+}
+
+/// A single check run by a [`Linter`].
+pub trait Lint {
+    /// A short name identifying the lint in [`Warning`]s.
+    fn name(&self) -> &'static str;
+
+    /// Check a single element.
+    ///
+    /// Called once for every element in the tree, in document order.
+    fn check_element(&mut self, element: &Element, cx: &mut LintContext<'_>);
+
+    /// Called once after all elements have been checked, with the topmost
+    /// element as current element.
+    ///
+    /// Lints that keep state between elements should report any remaining
+    /// mistakes and reset their state here.
+    fn finish(&mut self, cx: &mut LintContext<'_>) {
+        let _ = cx;
+    }
+}
+
+/// Runs a set of [`Lint`]s over element trees.
+#[derive(Default)]
+pub struct Linter {
+    lints: Vec<Box<dyn Lint>>,
+}
+
+impl Linter {
+    /// Create a new linter without any lints.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a lint to the linter.
+    pub fn add(&mut self, lint: impl Lint + 'static) {
+        self.lints.push(Box::new(lint));
+    }
+
+    /// A more builder-pattern-like version of [`Self::add`].
+    pub fn with(mut self, lint: impl Lint + 'static) -> Self {
+        self.add(lint);
+        self
+    }
+
+    /// Run all lints over an element and its descendants.
+    pub fn lint(&mut self, element: &Element) -> Vec<Warning> {
+        let mut warnings = vec![];
+        for lint in &mut self.lints {
+            let lint = &mut **lint;
+            walk(lint, element, &mut vec![], &mut vec![], &mut warnings);
+            lint.finish(&mut LintContext {
+                lint: lint.name(),
+                path: &[],
+                ancestors: &[],
+                warnings: &mut warnings,
+            });
+        }
+        #[cfg(feature = "tracing")]
+        for warning in &warnings {
+            tracing::warn!(
+                lint = warning.lint,
+                path = warning.path,
+                "{}",
+                warning.message
+            );
+        }
+        warnings
+    }
+
+    /// Run all lints over a document.
+    pub fn lint_document(&mut self, document: &Document) -> Vec<Warning> {
+        self.lint(&document.0)
+    }
+}
+
+fn walk<'a>(
+    lint: &mut dyn Lint,
+    element: &'a Element,
+    path: &mut Vec<(usize, Option<String>)>,
+    ancestors: &mut Vec<&'a Element>,
+    warnings: &mut Vec<Warning>,
+) {
+    lint.check_element(
+        element,
+        &mut LintContext {
+            lint: lint.name(),
+            path,
+            ancestors,
+            warnings,
+        },
+    );
+
+    ancestors.push(element);
+    for (i, child) in element.children.iter().enumerate() {
+        if let Content::Element(child) = child {
+            path.push((i, Some(child.name.clone())));
+            walk(lint, child, path, ancestors, warnings);
+            path.pop();
+        }
+    }
+    ancestors.pop();
+}
+
+/// Flags known HTML attributes on elements they don't apply to.
+///
+/// For example, `colspan` only applies to `<td>` and `<th>` elements. Global
+/// attributes and unknown attributes are never flagged. Foreign elements and
+/// custom elements are not checked.
+///
+/// The list of attributes is based on [MDN][mdn] and covers the same
+/// attributes as [`crate::html::attr`].
+///
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/HTML/Attributes
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AttributeApplicability;
+
+impl Lint for AttributeApplicability {
+    fn name(&self) -> &'static str {
+        "attribute_applicability"
+    }
+
+    fn check_element(&mut self, element: &Element, cx: &mut LintContext<'_>) {
+        if element.kind == ElementKind::Foreign || element.name.contains('-') {
+            return;
+        }
+
+        for name in element.attributes.keys() {
+            let Some((_, elements)) = ATTRIBUTE_ELEMENTS
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            else {
+                continue;
+            };
+            if !elements.contains(&element.name.as_str()) {
+                cx.warn(format!(
+                    "Attribute {name:?} does not apply to <{}>",
+                    element.name
+                ));
+            }
+        }
+    }
+}
+
+/// Flags questionable uses of the `role` attribute.
+///
+/// This includes abstract and unknown roles, roles on elements that must not
+/// have one (e.g. `<script>`), roles that repeat the element's implicit role
+/// (e.g. `<nav role="navigation">`), and interactive roles on elements that
+/// can't receive keyboard focus (e.g. `<div role="button">` or
+/// `<a role="button">` without `href` or `tabindex`).
+///
+/// The rules are based on [ARIA in HTML][aria-html].
+///
+/// [aria-html]: https://www.w3.org/TR/html-aria/
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RoleUsage;
+
+impl Lint for RoleUsage {
+    fn name(&self) -> &'static str {
+        "role_usage"
+    }
+
+    fn check_element(&mut self, element: &Element, cx: &mut LintContext<'_>) {
+        let Some(roles) = element.attr_ci("role") else {
+            return;
+        };
+        let name = element.name.as_str();
+
+        if NO_ROLE_ELEMENTS.contains(&name) {
+            cx.warn(format!("<{name}> must not have a role"));
+            return;
+        }
+
+        // Browsers use the first role they recognize, the rest are fallbacks.
+        let Some(role) = roles.split_whitespace().next() else {
+            cx.warn("Empty role");
+            return;
+        };
+        for role in roles.split_whitespace() {
+            if ABSTRACT_ROLES.contains(&role) {
+                cx.warn(format!("Abstract role {role:?} must not be used"));
+            } else if !KNOWN_ROLES.contains(&role) {
+                cx.warn(format!("Unknown role {role:?}"));
+            }
+        }
+
+        if implicit_role(element) == Some(role) {
+            cx.warn(format!("Role {role:?} is redundant on <{name}>"));
+        }
+
+        if FOCUSABLE_ROLES.contains(&role) && !is_focusable(element) {
+            cx.warn(format!(
+                "Role {role:?} requires <{name}> to be focusable, e.g. using tabindex"
+            ));
+        }
+    }
+}
+
+/// The implicit ARIA role of an element, for elements where it doesn't depend
+/// on the context.
+fn implicit_role(element: &Element) -> Option<&'static str> {
+    let has = |name: &str| element.has_attr_ci(name);
+    Some(match element.name.as_str() {
+        "a" | "area" if has("href") => "link",
+        "article" => "article",
+        "aside" => "complementary",
+        "blockquote" => "blockquote",
+        "button" => "button",
+        "code" => "code",
+        "datalist" => "listbox",
+        "del" => "deletion",
+        "details" => "group",
+        "dialog" => "dialog",
+        "em" => "emphasis",
+        "fieldset" => "group",
+        "figure" => "figure",
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+        "hr" => "separator",
+        "ins" => "insertion",
+        "li" => "listitem",
+        "main" => "main",
+        "math" => "math",
+        "menu" | "ol" | "ul" => "list",
+        "meter" => "meter",
+        "nav" => "navigation",
+        "optgroup" => "group",
+        "option" => "option",
+        "output" => "status",
+        "p" => "paragraph",
+        "progress" => "progressbar",
+        "search" => "search",
+        "strong" => "strong",
+        "sub" => "subscript",
+        "sup" => "superscript",
+        "table" => "table",
+        "tbody" | "tfoot" | "thead" => "rowgroup",
+        "textarea" => "textbox",
+        "time" => "time",
+        "tr" => "row",
+        _ => return None,
+    })
+}
+
+/// Whether an element can receive keyboard focus without scripts.
+fn is_focusable(element: &Element) -> bool {
+    let has = |name: &str| element.has_attr_ci(name);
+    if has("tabindex") || has("contenteditable") {
+        return true;
+    }
+    match element.name.as_str() {
+        "a" | "area" => has("href"),
+        "input" => element.attr_ci("type") != Some("hidden"),
+        "button" | "iframe" | "select" | "summary" | "textarea" => true,
+        _ => false,
+    }
+}
+
+/// Flags `itemprop` attributes that don't belong to any microdata item.
+///
+/// A property belongs to an item if one of its ancestors has the `itemscope`
+/// attribute, or if it or one of its ancestors has an `id` referenced by the
+/// `itemref` attribute of an item anywhere in the tree. Properties that don't
+/// belong to an item are ignored by microdata consumers.
+///
+/// Use [`crate::html::microdata::Item`] to avoid this mistake.
+#[derive(Debug, Clone, Default)]
+pub struct OrphanedItemprop {
+    /// Paths and `id`s (own and ancestors') of properties without an
+    /// `itemscope` ancestor.
+    candidates: Vec<(String, Vec<String>)>,
+    /// All `id`s referenced by `itemref` attributes.
+    referenced: HashSet<String>,
+}
+
+impl Lint for OrphanedItemprop {
+    fn name(&self) -> &'static str {
+        "orphaned_itemprop"
+    }
+
+    fn check_element(&mut self, element: &Element, cx: &mut LintContext<'_>) {
+        if element.has_attr_ci("itemscope") {
+            if let Some(itemref) = element.attr_ci("itemref") {
+                (self.referenced).extend(itemref.split_whitespace().map(|id| id.to_string()));
+            }
+        }
+
+        if !element.has_attr_ci("itemprop") {
+            return;
+        }
+        let ancestors = cx.ancestors();
+        if ancestors.iter().any(|a| a.has_attr_ci("itemscope")) {
+            return;
+        }
+        let ids = (ancestors.iter().copied().chain([element]))
+            .filter_map(|e| e.attr_ci("id").map(str::to_string))
+            .collect();
+        self.candidates.push((cx.path(), ids));
+    }
+
+    fn finish(&mut self, cx: &mut LintContext<'_>) {
+        let referenced = std::mem::take(&mut self.referenced);
+        for (path, ids) in self.candidates.drain(..) {
+            if !ids.iter().any(|id| referenced.contains(id)) {
+                cx.warnings.push(Warning {
+                    lint: cx.lint,
+                    path,
+                    message: "Property does not belong to any item".to_string(),
+                });
+            }
+        }
+    }
+}
+
+/// Flags misplaced or ambiguous `<base>` elements.
+///
+/// A document should have at most one `<base>` element, since browsers only
+/// use the first one. It must be a child of `<head>`, and its `href` should be
+/// an absolute URL, since a relative one is itself resolved against the
+/// document's URL and makes relative links depend on where the page is served.
+#[derive(Debug, Clone, Default)]
+pub struct BaseElement {
+    seen: bool,
+}
+
+impl Lint for BaseElement {
+    fn name(&self) -> &'static str {
+        "base_element"
+    }
+
+    fn check_element(&mut self, element: &Element, cx: &mut LintContext<'_>) {
+        if element.name != "base" || element.kind == ElementKind::Foreign {
+            return;
+        }
+
+        if self.seen {
+            cx.warn("Only the first <base> element is used");
+        }
+        self.seen = true;
+
+        if cx.parent().is_some_and(|p| p.name != "head") {
+            cx.warn("<base> must be a child of <head>");
+        }
+
+        if let Some(href) = element.attr_ci("href") {
+            if !is_absolute_url(href) {
+                cx.warn(format!("<base> href {href:?} is not an absolute URL"));
+            }
+        }
+    }
+
+    fn finish(&mut self, _cx: &mut LintContext<'_>) {
+        self.seen = false;
+    }
+}
+
+/// Flags widgets that require scripts but have no `<noscript>` fallback.
+///
+/// An element requires scripts if it has the
+/// [`REQUIRES_SCRIPT`] attribute (see
+/// [`with_noscript`](crate::html::helpers::with_noscript)) or if it is an
+/// autonomous custom element, i.e. its name contains a `-`. Its fallback is a
+/// `<noscript>` element that is either its next element sibling or one of its
+/// children. Descendants of an element requiring scripts are not checked
+/// again.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoscriptFallback;
+
+impl NoscriptFallback {
+    fn requires_script(element: &Element) -> bool {
+        element.kind != ElementKind::Foreign
+            && (element.name.contains('-') || element.has_attr_ci(REQUIRES_SCRIPT))
+    }
+
+    fn is_noscript(content: &Content) -> bool {
+        matches!(content, Content::Element(e) if e.name == "noscript" && e.kind != ElementKind::Foreign)
+    }
+}
+
+impl Lint for NoscriptFallback {
+    fn name(&self) -> &'static str {
+        "noscript_fallback"
+    }
+
+    fn check_element(&mut self, element: &Element, cx: &mut LintContext<'_>) {
+        if !Self::requires_script(element)
+            || cx.ancestors().iter().any(|a| Self::requires_script(a))
+            || element.children.iter().any(Self::is_noscript)
+        {
+            return;
+        }
+
+        let next_sibling = cx
+            .parent()
+            .zip(cx.path.last())
+            .and_then(|(parent, (i, _))| {
+                (parent.children[i + 1..].iter()).find(|c| match c {
+                    Content::Text(text) => !text.trim().is_empty(),
+                    Content::Comment(_) | Content::Flush => false,
+                    _ => true,
+                })
+            });
+        if !next_sibling.is_some_and(Self::is_noscript) {
+            cx.warn(format!(
+                "<{}> requires scripts but has no <noscript> fallback",
+                element.name
+            ));
+        }
+    }
+}
+
+/// Whether a URL starts with a scheme.
+fn is_absolute_url(url: &str) -> bool {
+    let url = url.trim();
+    let Some((scheme, _)) = url.split_once(':') else {
+        return false;
+    };
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+/// Enforces the rules of a constrained HTML subset, like the one required by
+/// [AMP][amp] or some email clients.
+///
+/// The profile flags
+///
+/// - `<script>` elements, unless they contain JSON data or load a script from
+///   an allowed source,
+/// - inline event handler attributes (e.g. `onclick`) and `javascript:` URLs,
+/// - forbidden elements,
+/// - `style` attributes over a size limit,
+/// - documents whose CSS (`<style>` elements and `style` attributes) exceeds a
+///   size limit in total, and
+/// - documents missing required boilerplate in their `<head>`.
+///
+/// Use [`Self::amp_like`] for a preset similar to AMP's rules, and the other
+/// methods to adjust it.
+///
+/// [amp]: https://amp.dev/documentation/guides-and-tutorials/learn/spec/amphtml
+///
+/// # Example
+///
+/// ```
+/// use el::{html::*, lint::{Linter, StrictProfile}};
+///
+/// let profile = StrictProfile::amp_like().max_style_attribute_bytes(10);
+/// let page = body((
+///     script("alert(1)"),
+///     script((attr::src("https://cdn.ampproject.org/v0.js"), attr::r#async())),
+///     p((attr::style("color: rebeccapurple"), "Hi")),
+/// ));
+///
+/// let warnings = Linter::new().with(profile).lint(&page);
+/// assert_eq!(warnings.len(), 2);
+/// assert_eq!(warnings[0].path(), "/0(script)");
+/// assert_eq!(warnings[1].path(), "/2(p)");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StrictProfile {
+    allowed_script_sources: Vec<String>,
+    forbidden_elements: Vec<String>,
+    max_style_attribute_bytes: Option<usize>,
+    max_css_bytes: Option<usize>,
+    require_canonical: bool,
+    css_bytes: usize,
+}
+
+impl StrictProfile {
+    /// Create a new profile that only forbids scripts, event handler
+    /// attributes and `javascript:` URLs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A profile similar to AMP's rules.
+    ///
+    /// Scripts may only be loaded from `https://cdn.ampproject.org/`, plugin
+    /// and frame elements are forbidden, CSS is limited to 75,000 bytes in
+    /// total and 1,000 bytes per `style` attribute, and the `<head>` must
+    /// contain `<meta charset>`, `<meta name="viewport">` and
+    /// `<link rel="canonical">`.
+    pub fn amp_like() -> Self {
+        let mut profile = Self::new()
+            .allow_script_source("https://cdn.ampproject.org/")
+            .max_css_bytes(75_000)
+            .max_style_attribute_bytes(1_000)
+            .require_canonical();
+        for name in ["applet", "embed", "frame", "frameset", "object", "param"] {
+            profile = profile.forbid_element(name);
+        }
+        profile
+    }
+
+    /// Allow `<script>` elements whose `src` starts with a prefix.
+    pub fn allow_script_source(mut self, prefix: impl ToString) -> Self {
+        self.allowed_script_sources.push(prefix.to_string());
+        self
+    }
+
+    /// Forbid all elements with a tag name.
+    pub fn forbid_element(mut self, name: impl ToString) -> Self {
+        self.forbidden_elements.push(name.to_string());
+        self
+    }
+
+    /// Limit the size of each `style` attribute.
+    pub fn max_style_attribute_bytes(mut self, bytes: usize) -> Self {
+        self.max_style_attribute_bytes = Some(bytes);
+        self
+    }
+
+    /// Limit the total size of all `<style>` elements and `style` attributes.
+    pub fn max_css_bytes(mut self, bytes: usize) -> Self {
+        self.max_css_bytes = Some(bytes);
+        self
+    }
+
+    /// Require the `<head>` to contain `<meta charset>`,
+    /// `<meta name="viewport">` and `<link rel="canonical">`.
+    pub fn require_canonical(mut self) -> Self {
+        self.require_canonical = true;
+        self
+    }
+
+    fn check_script(&self, element: &Element, cx: &mut LintContext<'_>) {
+        let r#type = element.attr_ci("type").unwrap_or("").trim();
+        if r#type.eq_ignore_ascii_case("application/json")
+            || r#type.eq_ignore_ascii_case("application/ld+json")
+        {
+            return;
+        }
+        let allowed = element.attr_ci("src").is_some_and(|src| {
+            (self.allowed_script_sources.iter()).any(|prefix| src.starts_with(&prefix[..]))
+        });
+        if !allowed {
+            cx.warn("Custom scripts are not allowed");
+        }
+    }
+
+    fn check_head(&self, element: &Element, cx: &mut LintContext<'_>) {
+        let children = || {
+            (element.children.iter()).filter_map(|c| match c {
+                Content::Element(e) => Some(e),
+                _ => None,
+            })
+        };
+        let has = |name: &str, attr: &str, value: Option<&str>| {
+            children().any(|e| {
+                e.name == name
+                    && match value {
+                        Some(value) => e
+                            .get_tokens(attr, " ")
+                            .any(|t| t.eq_ignore_ascii_case(value)),
+                        None => e.has_attr_ci(attr),
+                    }
+            })
+        };
+        if !has("meta", "charset", None) {
+            cx.warn("<head> must contain <meta charset>");
+        }
+        if !has("meta", "name", Some("viewport")) {
+            cx.warn("<head> must contain <meta name=\"viewport\">");
+        }
+        if !has("link", "rel", Some("canonical")) {
+            cx.warn("<head> must contain <link rel=\"canonical\">");
+        }
+    }
+}
+
+impl Lint for StrictProfile {
+    fn name(&self) -> &'static str {
+        "strict_profile"
+    }
+
+    #[allow(deprecated)]
+    fn check_element(&mut self, element: &Element, cx: &mut LintContext<'_>) {
+        let name = element.name.as_str();
+        let foreign = element.kind == ElementKind::Foreign;
+
+        if !foreign && self.forbidden_elements.iter().any(|n| *n == name) {
+            cx.warn(format!("<{name}> is not allowed"));
+            return;
+        }
+        if name == "script" {
+            self.check_script(element, cx);
+        }
+        if name == "head" && !foreign && self.require_canonical {
+            self.check_head(element, cx);
+        }
+
+        for (attr, value) in &element.attributes {
+            if is_event_handler(attr) {
+                cx.warn(format!("Event handler attribute {attr:?} is not allowed"));
+            } else if has_javascript_scheme(value) && URL_ATTRIBUTES.contains(&&attr[..]) {
+                cx.warn(format!("javascript: URL in {attr:?} is not allowed"));
+            }
+        }
+
+        if let Some(style) = element.attr_ci("style") {
+            self.css_bytes += style.len();
+            if let Some(max) = self.max_style_attribute_bytes {
+                if style.len() > max {
+                    cx.warn(format!(
+                        "style attribute is {} bytes long, at most {max} are allowed",
+                        style.len()
+                    ));
+                }
+            }
+        }
+        if name == "style" && !foreign {
+            for child in &element.children {
+                match child {
+                    Content::Text(text) | Content::Raw(text) => self.css_bytes += text.len(),
+                    Content::Trusted(html) => self.css_bytes += html.as_str().len(),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn finish(&mut self, cx: &mut LintContext<'_>) {
+        let bytes = std::mem::take(&mut self.css_bytes);
+        if let Some(max) = self.max_css_bytes {
+            if bytes > max {
+                cx.warn(format!(
+                    "CSS is {bytes} bytes long in total, at most {max} are allowed"
+                ));
+            }
+        }
+    }
+}
+
+/// Whether an attribute name looks like an event handler (e.g. `onclick`),
+/// ignoring case.
+pub(crate) fn is_event_handler(name: &str) -> bool {
+    name.len() > 2 && name.as_bytes()[..2].eq_ignore_ascii_case(b"on")
+}
+
+/// Elements that must not have a `role` attribute.
+#[rustfmt::skip]
+const NO_ROLE_ELEMENTS: &[&str] = &[
+    "base", "col", "colgroup", "datalist", "head", "html", "link", "map", "meta", "noscript",
+    "picture", "script", "slot", "source", "style", "template", "title", "track",
+];
+
+/// Abstract roles, see <https://www.w3.org/TR/wai-aria-1.2/#abstract_roles>.
+#[rustfmt::skip]
+const ABSTRACT_ROLES: &[&str] = &[
+    "command", "composite", "input", "landmark", "range", "roletype", "section", "sectionhead",
+    "select", "structure", "widget", "window",
+];
+
+/// All non-abstract roles, same as [`crate::html::attr::Role`].
+#[rustfmt::skip]
+const KNOWN_ROLES: &[&str] = &[
+    "alert", "alertdialog", "application", "article", "banner", "blockquote", "button", "caption",
+    "cell", "checkbox", "code", "columnheader", "combobox", "complementary", "contentinfo",
+    "definition", "deletion", "dialog", "document", "emphasis", "feed", "figure", "form", "generic",
+    "grid", "gridcell", "group", "heading", "img", "insertion", "link", "list", "listbox",
+    "listitem", "log", "main", "marquee", "math", "menu", "menubar", "menuitem", "menuitemcheckbox",
+    "menuitemradio", "meter", "navigation", "none", "note", "option", "paragraph", "presentation",
+    "progressbar", "radio", "radiogroup", "region", "row", "rowgroup", "rowheader", "scrollbar",
+    "search", "searchbox", "separator", "slider", "spinbutton", "status", "strong", "subscript",
+    "superscript", "switch", "tab", "table", "tablist", "tabpanel", "term", "textbox", "time",
+    "timer", "toolbar", "tooltip", "tree", "treegrid", "treeitem",
+];
+
+/// Roles of interactive widgets that must be focusable.
+#[rustfmt::skip]
+const FOCUSABLE_ROLES: &[&str] = &[
+    "button", "checkbox", "combobox", "link", "menuitem", "menuitemcheckbox", "menuitemradio",
+    "radio", "searchbox", "slider", "spinbutton", "switch", "tab", "textbox",
+];
+
+/// Non-global attributes and the elements they apply to.
+#[rustfmt::skip]
+const ATTRIBUTE_ELEMENTS: &[(&str, &[&str])] = &[
+    ("accept", &["input"]),
+    ("action", &["form"]),
+    ("allow", &["iframe"]),
+    ("alt", &["area", "img", "input"]),
+    ("as", &["link"]),
+    ("async", &["script"]),
+    ("autocomplete", &["form", "input", "select", "textarea"]),
+    ("autoplay", &["audio", "video"]),
+    ("capture", &["input"]),
+    ("charset", &["meta"]),
+    ("checked", &["input"]),
+    ("cite", &["blockquote", "del", "ins", "q"]),
+    ("cols", &["textarea"]),
+    ("colspan", &["td", "th"]),
+    ("content", &["meta"]),
+    ("controls", &["audio", "video"]),
+    ("coords", &["area"]),
+    ("crossorigin", &["audio", "img", "link", "script", "video"]),
+    ("data", &["object"]),
+    ("datetime", &["del", "ins", "time"]),
+    ("decoding", &["img"]),
+    ("default", &["track"]),
+    ("defer", &["script"]),
+    ("dirname", &["input", "textarea"]),
+    ("disabled", &["button", "fieldset", "input", "link", "optgroup", "option", "select", "textarea"]),
+    ("download", &["a", "area"]),
+    ("enctype", &["form"]),
+    ("for", &["label", "output"]),
+    ("form", &["button", "fieldset", "input", "label", "meter", "object", "output", "progress", "select", "textarea"]),
+    ("formaction", &["button", "input"]),
+    ("formenctype", &["button", "input"]),
+    ("formmethod", &["button", "input"]),
+    ("formnovalidate", &["button", "input"]),
+    ("formtarget", &["button", "input"]),
+    ("headers", &["td", "th"]),
+    ("height", &["canvas", "embed", "iframe", "img", "input", "object", "source", "video"]),
+    ("high", &["meter"]),
+    ("href", &["a", "area", "base", "link"]),
+    ("hreflang", &["a", "link"]),
+    ("http-equiv", &["meta"]),
+    ("integrity", &["link", "script"]),
+    ("ismap", &["img"]),
+    ("kind", &["track"]),
+    ("list", &["input"]),
+    ("loading", &["iframe", "img"]),
+    ("loop", &["audio", "video"]),
+    ("low", &["meter"]),
+    ("max", &["input", "meter", "progress"]),
+    ("maxlength", &["input", "textarea"]),
+    ("media", &["link", "meta", "source", "style"]),
+    ("method", &["form"]),
+    ("min", &["input", "meter"]),
+    ("minlength", &["input", "textarea"]),
+    ("multiple", &["input", "select"]),
+    ("muted", &["audio", "video"]),
+    ("name", &["button", "details", "fieldset", "form", "iframe", "input", "map", "meta", "object", "output", "select", "slot", "textarea"]),
+    ("novalidate", &["form"]),
+    ("open", &["details", "dialog"]),
+    ("optimum", &["meter"]),
+    ("pattern", &["input"]),
+    ("ping", &["a", "area"]),
+    ("placeholder", &["input", "textarea"]),
+    ("playsinline", &["video"]),
+    ("poster", &["video"]),
+    ("preload", &["audio", "video"]),
+    ("readonly", &["input", "textarea"]),
+    ("referrerpolicy", &["a", "area", "iframe", "img", "link", "script"]),
+    ("rel", &["a", "area", "form", "link"]),
+    ("required", &["input", "select", "textarea"]),
+    ("reversed", &["ol"]),
+    ("rows", &["textarea"]),
+    ("rowspan", &["td", "th"]),
+    ("sandbox", &["iframe"]),
+    ("scope", &["th"]),
+    ("selected", &["option"]),
+    ("shadowrootclonable", &["template"]),
+    ("shadowrootdelegatesfocus", &["template"]),
+    ("shadowrootmode", &["template"]),
+    ("shadowrootserializable", &["template"]),
+    ("shape", &["area"]),
+    ("size", &["input", "select"]),
+    ("sizes", &["img", "link", "source"]),
+    ("span", &["col", "colgroup"]),
+    ("src", &["audio", "embed", "iframe", "img", "input", "script", "source", "track", "video"]),
+    ("srcdoc", &["iframe"]),
+    ("srclang", &["track"]),
+    ("srcset", &["img", "source"]),
+    ("start", &["ol"]),
+    ("step", &["input"]),
+    ("target", &["a", "area", "base", "form"]),
+    ("type", &["a", "button", "embed", "input", "link", "object", "ol", "script", "source"]),
+    ("usemap", &["img"]),
+    ("value", &["button", "data", "input", "li", "meter", "option", "progress"]),
+    ("width", &["canvas", "embed", "iframe", "img", "input", "object", "source", "video"]),
+    ("wrap", &["textarea"]),
+];
@@ -1,6 +1,8 @@
 //! Definitions for all non-deprecated MathML elements
 //! ([MDN](https://developer.mozilla.org/en-US/docs/Web/MathML/Element)).
 
+pub mod latex;
+
 use crate::{Element, ElementComponent, ElementKind};
 
 macro_rules! element {
@@ -10,8 +12,14 @@ macro_rules! element {
     ( $name:ident, $tag:expr ) => {
         #[doc = concat!("The `<", $tag, ">` tag")]
         #[doc = concat!("([MDN](https://developer.mozilla.org/en-US/docs/Web/MathML/Element/", $tag, ")).")]
+        #[cfg_attr(feature = "track-caller", track_caller)]
         pub fn $name(c: impl ElementComponent) -> Element {
-            Element::new($tag, ElementKind::Foreign).with(c)
+            let mut c = Some(c);
+            Element::new_with($tag, ElementKind::Foreign, &mut |element| {
+                if let Some(c) = c.take() {
+                    c.add_to_element(element);
+                }
+            })
         }
     };
 }
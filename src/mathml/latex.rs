@@ -0,0 +1,570 @@
+//! Converting LaTeX-like math syntax to MathML.
+//!
+//! Building MathML by hand quickly becomes tedious for real formulas. This
+//! module parses a commonly used subset of LaTeX math syntax into an
+//! [`Element`] tree built from the constructors in the [`mathml`](super)
+//! module. The output only uses elements from [MathML Core], which is
+//! supported by all major browsers.
+//!
+//! [MathML Core]: https://www.w3.org/TR/mathml-core/
+//!
+//! # Supported syntax
+//!
+//! - Letters, numbers and operators like `+`, `=` or `(`
+//! - Groups using `{...}`
+//! - Superscripts and subscripts using `^` and `_`
+//! - `\frac{a}{b}`, `\sqrt{x}` and `\sqrt[n]{x}`
+//! - `\left(...\right)` with any delimiter, including `.` for no delimiter
+//! - `\text{...}` and `\operatorname{...}`
+//! - Greek letters like `\alpha` or `\Omega`
+//! - Common symbols like `\cdot`, `\leq`, `\infty`, `\to` or `\in`
+//! - Large operators like `\sum`, `\prod` and `\int`
+//! - Functions like `\sin`, `\log` or `\lim`
+//! - Spacing using `\,`, `\:`, `\;`, `\quad`, `\qquad` and `~`
+//!
+//! Anything else (e.g. environments or alignment using `&`) results in a
+//! [`ParseError`], and so does input nested more than [`MAX_DEPTH`] levels
+//! deep.
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Render, mathml::latex};
+//!
+//! let formula = latex::to_mathml(r"x^2 + \frac{1}{2}").unwrap();
+//! assert_eq!(
+//!     formula.render_to_string().unwrap(),
+//!     concat!(
+//!         "<math><msup><mi>x</mi><mn>2</mn></msup><mo>+</mo>",
+//!         "<mfrac><mn>1</mn><mn>2</mn></mfrac></math>",
+//!     ),
+//! );
+//!
+//! assert!(latex::to_mathml(r"\frac{1}").is_err());
+//! ```
+
+use std::{error, fmt};
+
+use crate::{
+    mathml::{
+        math, mfrac, mi, mn, mo, mover, mroot, mrow, mspace, msqrt, msub, msubsup, msup, mtext,
+        munder, munderover,
+    },
+    Attr, Element,
+};
+
+/// Commands that produce an identifier.
+const IDENTIFIERS: &[(&str, &str)] = &[
+    ("alpha", "α"),
+    ("beta", "β"),
+    ("gamma", "γ"),
+    ("delta", "δ"),
+    ("epsilon", "ϵ"),
+    ("varepsilon", "ε"),
+    ("zeta", "ζ"),
+    ("eta", "η"),
+    ("theta", "θ"),
+    ("vartheta", "ϑ"),
+    ("iota", "ι"),
+    ("kappa", "κ"),
+    ("lambda", "λ"),
+    ("mu", "μ"),
+    ("nu", "ν"),
+    ("xi", "ξ"),
+    ("pi", "π"),
+    ("varpi", "ϖ"),
+    ("rho", "ρ"),
+    ("varrho", "ϱ"),
+    ("sigma", "σ"),
+    ("varsigma", "ς"),
+    ("tau", "τ"),
+    ("upsilon", "υ"),
+    ("phi", "ϕ"),
+    ("varphi", "φ"),
+    ("chi", "χ"),
+    ("psi", "ψ"),
+    ("omega", "ω"),
+    ("infty", "∞"),
+    ("partial", "∂"),
+    ("nabla", "∇"),
+    ("emptyset", "∅"),
+    ("ell", "ℓ"),
+    ("hbar", "ℏ"),
+];
+
+/// Commands that produce an upright identifier.
+const UPRIGHT_IDENTIFIERS: &[(&str, &str)] = &[
+    ("Gamma", "Γ"),
+    ("Delta", "Δ"),
+    ("Theta", "Θ"),
+    ("Lambda", "Λ"),
+    ("Xi", "Ξ"),
+    ("Pi", "Π"),
+    ("Sigma", "Σ"),
+    ("Upsilon", "Υ"),
+    ("Phi", "Φ"),
+    ("Psi", "Ψ"),
+    ("Omega", "Ω"),
+];
+
+/// Commands that produce an operator.
+const OPERATORS: &[(&str, &str)] = &[
+    ("cdot", "⋅"),
+    ("times", "×"),
+    ("div", "÷"),
+    ("pm", "±"),
+    ("mp", "∓"),
+    ("ast", "∗"),
+    ("circ", "∘"),
+    ("leq", "≤"),
+    ("le", "≤"),
+    ("geq", "≥"),
+    ("ge", "≥"),
+    ("neq", "≠"),
+    ("ne", "≠"),
+    ("approx", "≈"),
+    ("equiv", "≡"),
+    ("sim", "∼"),
+    ("propto", "∝"),
+    ("ll", "≪"),
+    ("gg", "≫"),
+    ("to", "→"),
+    ("rightarrow", "→"),
+    ("leftarrow", "←"),
+    ("gets", "←"),
+    ("Rightarrow", "⇒"),
+    ("Leftarrow", "⇐"),
+    ("leftrightarrow", "↔"),
+    ("Leftrightarrow", "⇔"),
+    ("mapsto", "↦"),
+    ("implies", "⟹"),
+    ("iff", "⟺"),
+    ("in", "∈"),
+    ("notin", "∉"),
+    ("ni", "∋"),
+    ("subset", "⊂"),
+    ("subseteq", "⊆"),
+    ("supset", "⊃"),
+    ("supseteq", "⊇"),
+    ("cup", "∪"),
+    ("cap", "∩"),
+    ("setminus", "∖"),
+    ("forall", "∀"),
+    ("exists", "∃"),
+    ("neg", "¬"),
+    ("land", "∧"),
+    ("wedge", "∧"),
+    ("lor", "∨"),
+    ("vee", "∨"),
+    ("oplus", "⊕"),
+    ("otimes", "⊗"),
+    ("mid", "∣"),
+    ("parallel", "∥"),
+    ("perp", "⊥"),
+    ("ldots", "…"),
+    ("cdots", "⋯"),
+    ("vdots", "⋮"),
+    ("ddots", "⋱"),
+    ("langle", "⟨"),
+    ("rangle", "⟩"),
+    ("lfloor", "⌊"),
+    ("rfloor", "⌋"),
+    ("lceil", "⌈"),
+    ("rceil", "⌉"),
+    ("{", "{"),
+    ("}", "}"),
+    ("|", "‖"),
+    ("int", "∫"),
+    ("iint", "∬"),
+    ("oint", "∮"),
+];
+
+/// Commands that produce an operator whose scripts are placed below and above
+/// it when the formula is displayed as a block.
+const LARGE_OPERATORS: &[(&str, &str)] = &[
+    ("sum", "∑"),
+    ("prod", "∏"),
+    ("coprod", "∐"),
+    ("bigcup", "⋃"),
+    ("bigcap", "⋂"),
+    ("bigoplus", "⨁"),
+    ("bigotimes", "⨂"),
+];
+
+/// Commands that produce a function name.
+const FUNCTIONS: &[&str] = &[
+    "arccos", "arcsin", "arctan", "arg", "cos", "cosh", "cot", "coth", "csc", "deg", "det", "dim",
+    "exp", "gcd", "hom", "inf", "ker", "lg", "lim", "liminf", "limsup", "ln", "log", "max", "min",
+    "Pr", "sec", "sin", "sinh", "sup", "tan", "tanh",
+];
+
+/// Commands that produce horizontal space, with their width.
+const SPACES: &[(&str, &str)] = &[
+    (",", "0.1667em"),
+    (":", "0.2222em"),
+    (">", "0.2222em"),
+    (";", "0.2778em"),
+    (" ", "0.25em"),
+    ("quad", "1em"),
+    ("qquad", "2em"),
+];
+
+/// How deeply groups, commands and scripts may be nested.
+///
+/// The parser is recursive, so without a limit, deeply nested input like
+/// thousands of `{` would overflow the stack. The limit is low enough for
+/// unoptimized builds on threads with small stacks, e.g. tokio's workers.
+pub const MAX_DEPTH: usize = 128;
+
+/// An error that can occur while parsing LaTeX syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    position: usize,
+    message: String,
+}
+
+impl ParseError {
+    fn new(position: usize, message: impl ToString) -> Self {
+        Self {
+            position,
+            message: message.to_string(),
+        }
+    }
+
+    /// The byte offset in the input where the error occurred.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// A human-readable description of the error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Convert LaTeX-like syntax into an inline `<math>` element.
+pub fn to_mathml(input: &str) -> Result<Element, ParseError> {
+    Ok(math(parse(input)?))
+}
+
+/// Convert LaTeX-like syntax into a `<math display="block">` element, e.g.
+/// for a formula in its own paragraph.
+pub fn to_mathml_block(input: &str) -> Result<Element, ParseError> {
+    Ok(math((Attr::set("display", "block"), parse(input)?)))
+}
+
+/// Convert LaTeX-like syntax into a list of MathML elements without a
+/// surrounding `<math>` element, e.g. to include it in a larger formula.
+pub fn parse(input: &str) -> Result<Vec<Element>, ParseError> {
+    let mut parser = Parser {
+        input,
+        pos: 0,
+        depth: 0,
+    };
+    let row = parser.row(None)?;
+    match parser.peek() {
+        None => Ok(row),
+        Some(_) if parser.at_right() => Err(parser.error("Unmatched \\right")),
+        Some(c) => Err(parser.error(format!("Unexpected {c:?}"))),
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: impl ToString) -> ParseError {
+        ParseError::new(self.pos, message)
+    }
+
+    fn rest(&self) -> &str {
+        &self.input[self.pos..]
+    }
+
+    /// Whether the next command is `\right`, which ends a row.
+    fn at_right(&self) -> bool {
+        let rest = self.rest();
+        rest.starts_with("\\right") && !rest[6..].starts_with(|c: char| c.is_ascii_alphabetic())
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(ParseError::new(
+                self.pos - c.len_utf8(),
+                format!("Expected {expected:?}, found {c:?}"),
+            )),
+            None => Err(self.error(format!("Expected {expected:?}"))),
+        }
+    }
+
+    /// Parse elements until the end of the input, a closing brace, the given
+    /// end character or a `\right`.
+    fn row(&mut self, end: Option<char>) -> Result<Vec<Element>, ParseError> {
+        let mut row = vec![];
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None | Some('}') => break,
+                Some(c) if Some(c) == end => break,
+                Some('\\') if self.at_right() => break,
+                Some('^' | '_') => {
+                    // Scripts without a base
+                    let scripted = self.scripts(mrow(()), false)?;
+                    row.push(scripted);
+                }
+                Some(_) => {
+                    let (atom, large) = self.atom()?;
+                    let scripted = self.scripts(atom, large)?;
+                    row.push(scripted);
+                }
+            }
+        }
+        Ok(row)
+    }
+
+    /// Parse the superscript and subscript of a base, if any.
+    fn scripts(&mut self, base: Element, large: bool) -> Result<Element, ParseError> {
+        let mut sub = None;
+        let mut sup = None;
+        loop {
+            self.skip_whitespace();
+            let script = match self.peek() {
+                Some('_') => &mut sub,
+                Some('^') => &mut sup,
+                _ => break,
+            };
+            let start = self.pos;
+            self.next();
+            if script.is_some() {
+                return Err(ParseError::new(start, "Double script"));
+            }
+            *script = Some(self.argument()?);
+        }
+
+        Ok(match (sub, sup, large) {
+            (None, None, _) => base,
+            (Some(sub), None, false) => msub((base, sub)),
+            (None, Some(sup), false) => msup((base, sup)),
+            (Some(sub), Some(sup), false) => msubsup((base, sub, sup)),
+            (Some(sub), None, true) => munder((base, sub)),
+            (None, Some(sup), true) => mover((base, sup)),
+            (Some(sub), Some(sup), true) => munderover((base, sub, sup)),
+        })
+    }
+
+    /// Parse a single element, e.g. the argument of a command or script.
+    fn argument(&mut self) -> Result<Element, ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            None => Err(self.error("Missing argument")),
+            Some('}' | '^' | '_') => Err(self.error("Missing argument")),
+            // Only a single digit, so that `x^23` means `x^{2}3` like in LaTeX
+            Some(c) if c.is_ascii_digit() => {
+                self.next();
+                Ok(mn(c.to_string()))
+            }
+            Some(_) => Ok(self.atom()?.0),
+        }
+    }
+
+    /// Parse a group's contents after its opening brace.
+    fn group(&mut self) -> Result<Element, ParseError> {
+        let row = self.row(None)?;
+        if self.at_right() {
+            return Err(self.error("Unmatched \\right"));
+        }
+        self.expect('}')?;
+        Ok(wrap(row))
+    }
+
+    /// Parse a single element and whether it is a large operator.
+    ///
+    /// All recursion passes through here, so this is where the nesting depth
+    /// is limited.
+    fn atom(&mut self) -> Result<(Element, bool), ParseError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error(format!("Nested more than {MAX_DEPTH} levels deep")));
+        }
+        self.depth += 1;
+        let result = self.atom_inner();
+        self.depth -= 1;
+        result
+    }
+
+    fn atom_inner(&mut self) -> Result<(Element, bool), ParseError> {
+        let start = self.pos;
+        let c = self.next().ok_or_else(|| self.error("Missing argument"))?;
+        let atom = match c {
+            '{' => self.group()?,
+            '\\' => return self.command(start),
+            '0'..='9' | '.' => {
+                while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+                    self.next();
+                }
+                let number = &self.input[start..self.pos];
+                if number == "." {
+                    mo(".")
+                } else {
+                    mn(number)
+                }
+            }
+            '-' => mo("−"),
+            '*' => mo("∗"),
+            '\'' => mo("′"),
+            '~' => mspace(Attr::set("width", "0.25em")),
+            '+' | '=' | '<' | '>' | ',' | ';' | ':' | '!' | '?' | '(' | ')' | '[' | ']' | '|'
+            | '/' => mo(c.to_string()),
+            '&' | '#' | '%' | '$' => {
+                return Err(ParseError::new(start, format!("Unsupported {c:?}")))
+            }
+            c if c.is_alphabetic() => mi(c.to_string()),
+            c => mo(c.to_string()),
+        };
+        Ok((atom, false))
+    }
+
+    /// Parse a command after its backslash.
+    fn command(&mut self, start: usize) -> Result<(Element, bool), ParseError> {
+        let name_start = self.pos;
+        match self.peek() {
+            Some(c) if c.is_ascii_alphabetic() => {
+                while self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+                    self.next();
+                }
+            }
+            Some(_) => {
+                self.next();
+            }
+            None => return Err(ParseError::new(start, "Missing command name")),
+        }
+        let name = &self.input[name_start..self.pos];
+
+        let lookup = |table: &[(&str, &'static str)]| {
+            table.iter().find(|(n, _)| *n == name).map(|(_, s)| *s)
+        };
+        if let Some(symbol) = lookup(IDENTIFIERS) {
+            return Ok((mi(symbol), false));
+        }
+        if let Some(symbol) = lookup(UPRIGHT_IDENTIFIERS) {
+            return Ok((mi((Attr::set("mathvariant", "normal"), symbol)), false));
+        }
+        if let Some(symbol) = lookup(OPERATORS) {
+            return Ok((mo(symbol), false));
+        }
+        if let Some(symbol) = lookup(LARGE_OPERATORS) {
+            return Ok((mo(symbol), true));
+        }
+        if let Some(width) = lookup(SPACES) {
+            return Ok((mspace(Attr::set("width", width)), false));
+        }
+        if FUNCTIONS.contains(&name) {
+            return Ok((mi(name), false));
+        }
+
+        let element = match name {
+            "frac" => {
+                let numerator = self.argument()?;
+                let denominator = self.argument()?;
+                mfrac((numerator, denominator))
+            }
+            "sqrt" => {
+                self.skip_whitespace();
+                if self.peek() == Some('[') {
+                    self.next();
+                    let index = wrap(self.row(Some(']'))?);
+                    self.expect(']')?;
+                    mroot((self.argument()?, index))
+                } else {
+                    msqrt(self.argument()?)
+                }
+            }
+            "text" => mtext(self.text()?),
+            "operatorname" => mi(self.text()?),
+            "left" => {
+                let open = self.delimiter()?;
+                let inner = self.row(None)?;
+                if !self.at_right() {
+                    return Err(self.error("Missing \\right"));
+                }
+                self.pos += "\\right".len();
+                let close = self.delimiter()?;
+                mrow((open, inner, close))
+            }
+            "right" => return Err(ParseError::new(start, "Unmatched \\right")),
+            _ => return Err(ParseError::new(start, format!("Unknown command \\{name}"))),
+        };
+        Ok((element, false))
+    }
+
+    /// Parse the delimiter after `\left` or `\right`.
+    fn delimiter(&mut self) -> Result<Option<Element>, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.peek() {
+            Some('.') => {
+                self.next();
+                Ok(None)
+            }
+            Some('(' | ')' | '[' | ']' | '|' | '/' | '\\') => match self.atom()?.0 {
+                delimiter if delimiter.name == "mo" => Ok(Some(delimiter)),
+                _ => Err(ParseError::new(start, "Invalid delimiter")),
+            },
+            _ => Err(self.error("Missing delimiter")),
+        }
+    }
+
+    /// Parse a brace-delimited argument as text.
+    fn text(&mut self) -> Result<String, ParseError> {
+        self.expect('{')?;
+        let start = self.pos;
+        let mut depth = 0_usize;
+        loop {
+            match self.next() {
+                None => return Err(self.error("Expected '}'")),
+                Some('{') => depth += 1,
+                Some('}') if depth == 0 => break,
+                Some('}') => depth -= 1,
+                Some(_) => {}
+            }
+        }
+        Ok(self.input[start..self.pos - 1].to_string())
+    }
+}
+
+/// Combine a list of elements into a single element.
+fn wrap(mut row: Vec<Element>) -> Element {
+    if row.len() == 1 {
+        row.remove(0)
+    } else {
+        mrow(row)
+    }
+}
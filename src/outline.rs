@@ -0,0 +1,423 @@
+//! A structured summary of a document, similar to what assistive technology
+//! presents to its users.
+//!
+//! An [`Outline`] lists the landmarks, headings, form controls and links of
+//! an element tree, along with their accessible names. It is useful for
+//! automated accessibility smoke tests, e.g. checking that every form control
+//! has a label or that the heading hierarchy doesn't skip levels.
+//!
+//! The accessible names are a simplified version of the
+//! [accessible name computation][accname]. Only static information from the
+//! element tree is used, so the result may differ from what browsers compute
+//! once scripts and styles are involved.
+//!
+//! [accname]: https://www.w3.org/TR/accname-1.2/
+//!
+//! # Example
+//!
+//! ```
+//! use el::{html::*, outline::Outline};
+//!
+//! let page = html(body((
+//!     nav(a((attr::href("/"), "Home"))),
+//!     main((
+//!         h1("Sign up"),
+//!         form((
+//!             label((attr::r#for("email"), "Email")),
+//!             input((attr::id("email"), attr::name("email"))),
+//!             input(attr::name("nickname")),
+//!         )),
+//!     )),
+//! )));
+//!
+//! let outline = Outline::new(&page);
+//! assert_eq!(outline.landmarks[0].role, "navigation");
+//! assert_eq!(outline.headings[0].text, "Sign up");
+//! assert_eq!(outline.controls[0].label.as_deref(), Some("Email"));
+//! assert_eq!(outline.controls[1].label, None);
+//! assert_eq!(outline.links[0].text, "Home");
+//! ```
+
+use std::{collections::HashMap, fmt};
+
+use crate::{render::format_path, Content, Document, Element};
+
+/// A landmark region of a page, e.g. `<nav>` or `<main>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Landmark {
+    /// The landmark role, e.g. `navigation` or `main`.
+    pub role: String,
+    /// The accessible name of the landmark, if any.
+    pub label: Option<String>,
+    /// The path to the element, see [`crate::Error::path`].
+    pub path: String,
+}
+
+/// A heading, e.g. `<h2>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    /// The heading level from 1 to 6.
+    pub level: u8,
+    /// The text of the heading.
+    pub text: String,
+    /// The path to the element, see [`crate::Error::path`].
+    pub path: String,
+}
+
+/// A form control, e.g. `<input>` or `<button>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Control {
+    /// The tag name of the element.
+    pub element: String,
+    /// The `type` attribute of the element, if any.
+    pub r#type: Option<String>,
+    /// The `name` attribute of the element, if any.
+    pub name: Option<String>,
+    /// The accessible name of the control, if any.
+    pub label: Option<String>,
+    /// The path to the element, see [`crate::Error::path`].
+    pub path: String,
+}
+
+/// A link, i.e. an `<a>` element with an `href` attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    /// The target of the link.
+    pub href: String,
+    /// The accessible name of the link.
+    pub text: String,
+    /// The path to the element, see [`crate::Error::path`].
+    pub path: String,
+}
+
+/// A structured summary of an element tree, see the [module
+/// documentation](self).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Outline {
+    /// All landmarks in document order.
+    pub landmarks: Vec<Landmark>,
+    /// All headings in document order.
+    pub headings: Vec<Heading>,
+    /// All form controls in document order.
+    pub controls: Vec<Control>,
+    /// All links in document order.
+    pub links: Vec<Link>,
+}
+
+impl Outline {
+    /// Summarize an element and its descendants.
+    pub fn new(element: &Element) -> Self {
+        let mut ids = HashMap::new();
+        collect_ids(element, &mut ids);
+
+        let mut walker = Walker {
+            ids,
+            labels: HashMap::new(),
+            outline: Self::default(),
+        };
+        walker.collect_labels(element, None);
+        walker.walk(element, &mut vec![], &mut vec![]);
+        walker.outline
+    }
+
+    /// Summarize a document.
+    pub fn of_document(document: &Document) -> Self {
+        Self::new(&document.0)
+    }
+
+    /// Headings whose level is more than one greater than the level of the
+    /// preceding heading, e.g. an `<h4>` directly following an `<h2>`.
+    ///
+    /// The first heading is compared against a virtual heading of level 0.
+    pub fn skipped_heading_levels(&self) -> Vec<&Heading> {
+        let mut previous = 0;
+        let mut result = vec![];
+        for heading in &self.headings {
+            if heading.level > previous + 1 {
+                result.push(heading);
+            }
+            previous = heading.level;
+        }
+        result
+    }
+
+    /// Form controls without an accessible name.
+    pub fn unlabelled_controls(&self) -> Vec<&Control> {
+        self.controls.iter().filter(|c| c.label.is_none()).collect()
+    }
+}
+
+impl fmt::Display for Outline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Landmarks:")?;
+        for landmark in &self.landmarks {
+            write!(f, "  {}", landmark.role)?;
+            if let Some(label) = &landmark.label {
+                write!(f, " {label:?}")?;
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(f, "Headings:")?;
+        for heading in &self.headings {
+            let indent = "  ".repeat(heading.level.into());
+            writeln!(f, "{indent}h{} {:?}", heading.level, heading.text)?;
+        }
+
+        writeln!(f, "Controls:")?;
+        for control in &self.controls {
+            write!(f, "  {}", control.element)?;
+            if let Some(r#type) = &control.r#type {
+                write!(f, "[type={type}]")?;
+            }
+            match &control.label {
+                Some(label) => writeln!(f, " {label:?}")?,
+                None => writeln!(f, " (no label)")?,
+            }
+        }
+
+        writeln!(f, "Links:")?;
+        for link in &self.links {
+            writeln!(f, "  {:?} -> {}", link.text, link.href)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Landmark roles, see <https://www.w3.org/TR/wai-aria-1.2/#landmark_roles>.
+const LANDMARK_ROLES: &[&str] = &[
+    "banner",
+    "complementary",
+    "contentinfo",
+    "form",
+    "main",
+    "navigation",
+    "region",
+    "search",
+];
+
+/// Elements that scope `<header>` and `<footer>` so they are no longer
+/// landmarks.
+const SECTIONING: &[&str] = &["article", "aside", "main", "nav", "section"];
+
+fn collect_ids<'a>(element: &'a Element, ids: &mut HashMap<&'a str, &'a Element>) {
+    if let Some(id) = element.attr_ci("id") {
+        ids.entry(id).or_insert(element);
+    }
+    for child in &element.children {
+        if let Content::Element(child) = child {
+            collect_ids(child, ids);
+        }
+    }
+}
+
+/// The text content of an element, with whitespace collapsed.
+///
+/// Images contribute their alt text.
+fn text_content(element: &Element) -> String {
+    fn collect(element: &Element, result: &mut String) {
+        if element.name == "img" {
+            if let Some(alt) = element.attr_ci("alt") {
+                result.push_str(alt);
+            }
+        }
+        for child in &element.children {
+            match child {
+                Content::Text(text) => result.push_str(text),
+                Content::Element(child) => collect(child, result),
+                _ => {}
+            }
+        }
+    }
+
+    let mut result = String::new();
+    collect(element, &mut result);
+    result.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn non_empty(text: String) -> Option<String> {
+    (!text.is_empty()).then_some(text)
+}
+
+struct Walker<'a> {
+    ids: HashMap<&'a str, &'a Element>,
+    /// Text of `<label>` elements by the element they label.
+    labels: HashMap<*const Element, String>,
+    outline: Outline,
+}
+
+impl<'a> Walker<'a> {
+    /// Find all `<label>`s and the controls they label, either via their
+    /// `for` attribute or by wrapping the control.
+    fn collect_labels(&mut self, element: &'a Element, label: Option<&'a Element>) {
+        let label = match element.name.as_str() {
+            "label" => {
+                let target = element.attr_ci("for");
+                if let Some(target) = target.and_then(|id| self.ids.get(id)) {
+                    self.add_label(target, element);
+                }
+                target.is_none().then_some(element)
+            }
+            "button" | "input" | "meter" | "output" | "progress" | "select" | "textarea" => {
+                if let Some(label) = label {
+                    self.add_label(element, label);
+                }
+                label
+            }
+            _ => label,
+        };
+
+        for child in &element.children {
+            if let Content::Element(child) = child {
+                self.collect_labels(child, label);
+            }
+        }
+    }
+
+    fn add_label(&mut self, control: &Element, label: &Element) {
+        let text = text_content(label);
+        let entry = self.labels.entry(control).or_default();
+        if !entry.is_empty() && !text.is_empty() {
+            entry.push(' ');
+        }
+        entry.push_str(&text);
+    }
+
+    /// The accessible name of an element from ARIA attributes.
+    fn aria_label(&self, element: &Element) -> Option<String> {
+        if let Some(ids) = element.attr_ci("aria-labelledby") {
+            let text = (ids.split_whitespace())
+                .filter_map(|id| self.ids.get(id))
+                .map(|el| text_content(el))
+                .collect::<Vec<_>>()
+                .join(" ");
+            if let Some(text) = non_empty(text) {
+                return Some(text);
+            }
+        }
+        (element.attr_ci("aria-label")).and_then(|l| non_empty(l.trim().to_string()))
+    }
+
+    fn walk(
+        &mut self,
+        element: &'a Element,
+        path: &mut Vec<(usize, Option<String>)>,
+        ancestors: &mut Vec<&'a Element>,
+    ) {
+        self.check(element, &format_path(path), ancestors);
+
+        ancestors.push(element);
+        for (i, child) in element.children.iter().enumerate() {
+            if let Content::Element(child) = child {
+                path.push((i, Some(child.name.clone())));
+                self.walk(child, path, ancestors);
+                path.pop();
+            }
+        }
+        ancestors.pop();
+    }
+
+    fn check(&mut self, element: &Element, path: &str, ancestors: &[&Element]) {
+        let attr = |name: &str| element.attr_ci(name).map(str::to_string);
+        let role = attr("role");
+
+        // Landmarks
+        let implicit_role = match element.name.as_str() {
+            "header" | "footer" if ancestors.iter().any(|a| SECTIONING.contains(&&a.name[..])) => {
+                None
+            }
+            "header" => Some("banner"),
+            "footer" => Some("contentinfo"),
+            "main" => Some("main"),
+            "nav" => Some("navigation"),
+            "aside" => Some("complementary"),
+            "search" => Some("search"),
+            // Forms and sections are only landmarks if they have a name.
+            "form" | "section" if self.aria_label(element).is_some() => {
+                Some(if element.name == "form" {
+                    "form"
+                } else {
+                    "region"
+                })
+            }
+            _ => None,
+        };
+        let landmark = match &role {
+            Some(role) => LANDMARK_ROLES.contains(&&role[..]).then_some(&role[..]),
+            None => implicit_role,
+        };
+        if let Some(role) = landmark {
+            self.outline.landmarks.push(Landmark {
+                role: role.to_string(),
+                label: self.aria_label(element),
+                path: path.to_string(),
+            });
+        }
+
+        // Headings
+        let level = match (element.name.as_str(), role.as_deref()) {
+            (_, Some("heading")) => {
+                Some(attr("aria-level").and_then(|l| l.parse().ok()).unwrap_or(2))
+            }
+            (_, Some(_)) => None,
+            ("h1", _) => Some(1),
+            ("h2", _) => Some(2),
+            ("h3", _) => Some(3),
+            ("h4", _) => Some(4),
+            ("h5", _) => Some(5),
+            ("h6", _) => Some(6),
+            _ => None,
+        };
+        if let Some(level) = level {
+            self.outline.headings.push(Heading {
+                level: level.clamp(1, 6),
+                text: self
+                    .aria_label(element)
+                    .unwrap_or_else(|| text_content(element)),
+                path: path.to_string(),
+            });
+        }
+
+        // Form controls
+        let r#type = attr("type");
+        let is_control = match element.name.as_str() {
+            "input" => r#type.as_deref() != Some("hidden"),
+            "button" | "select" | "textarea" => true,
+            _ => false,
+        };
+        if is_control {
+            let own_text = match (element.name.as_str(), r#type.as_deref()) {
+                ("button", _) => non_empty(text_content(element)),
+                ("input", Some("button" | "reset" | "submit")) => attr("value"),
+                ("input", Some("image")) => attr("alt"),
+                _ => None,
+            };
+            let label = (self.aria_label(element))
+                .or_else(|| non_empty(self.labels.get(&(element as *const _)).cloned()?))
+                .or(own_text)
+                .or_else(|| attr("title"))
+                .or_else(|| attr("placeholder"));
+            self.outline.controls.push(Control {
+                element: element.name.clone(),
+                r#type,
+                name: attr("name"),
+                label,
+                path: path.to_string(),
+            });
+        }
+
+        // Links
+        if element.name == "a" {
+            if let Some(href) = attr("href") {
+                self.outline.links.push(Link {
+                    href,
+                    text: self
+                        .aria_label(element)
+                        .unwrap_or_else(|| text_content(element)),
+                    path: path.to_string(),
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Presentable fallback pages for errors.
+//!
+//! [`error_page`] creates a small, self-contained page with inline styles, so
+//! it works even when the service's regular assets or layout are unavailable.
+//! With the `axum` feature, an [`ErrorPage`] can be returned directly from a
+//! handler and responds with its status code.
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Render, html::*, pages::error_page};
+//!
+//! let page = error_page(404, "Not Found", p("There is nothing at this address."));
+//! let html = page.render_to_string().unwrap();
+//! assert!(html.contains("<title>404 Not Found</title>"));
+//! assert!(html.contains("<p>There is nothing at this address.</p>"));
+//! ```
+
+use std::fmt;
+
+use crate::{
+    html::{
+        attr, body, div, h1, head, helpers::meta_charset_utf8, helpers::meta_viewport_default,
+        html, main, p, style, title,
+    },
+    Document, ElementComponent, Render, RenderOptions, Result,
+};
+
+const STYLE: &str = "\
+body{margin:0;font-family:system-ui,sans-serif;line-height:1.5;\
+color:#222;background:#f4f4f5}\
+main{max-width:40rem;margin:15vh auto 0;padding:0 1.5rem}\
+.status{font-size:4rem;font-weight:700;color:#a1a1aa;line-height:1}\
+h1{margin:.25rem 0 1rem;font-size:1.5rem}\
+pre{overflow:auto;padding:1rem;background:#fff;border:1px solid #e4e4e7}\
+@media(prefers-color-scheme:dark){body{color:#eee;background:#18181b}\
+.status{color:#52525b}pre{background:#27272a;border-color:#3f3f46}}";
+
+/// A styled error page, see the [module documentation](self).
+///
+/// Renders like its [`Document`]. With the `axum` feature, it also implements
+/// `IntoResponse`, responding with [`Self::status`].
+#[derive(Debug, Clone)]
+pub struct ErrorPage {
+    /// The HTTP status code of the page.
+    pub status: u16,
+    /// The page itself.
+    pub document: Document,
+}
+
+impl Render for ErrorPage {
+    fn render<W: fmt::Write>(&self, w: &mut W) -> Result<()> {
+        self.document.render(w)
+    }
+
+    fn render_with<W: fmt::Write>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        self.document.render_with(w, options)
+    }
+}
+
+impl From<ErrorPage> for Document {
+    fn from(value: ErrorPage) -> Self {
+        value.document
+    }
+}
+
+/// Create a styled page for an HTTP status code.
+///
+/// The title is shown below the status code and used in the `<title>`. The
+/// details are added below the heading and may be any element component, e.g.
+/// a paragraph of text or a `<pre>` containing a backtrace.
+///
+/// # Warning
+///
+/// Don't include internal details like error messages or backtraces in pages
+/// shown to untrusted users.
+pub fn error_page(status: u16, title_text: &str, details: impl ElementComponent) -> ErrorPage {
+    let document = Document(html((
+        attr::lang("en"),
+        head((
+            meta_charset_utf8(),
+            meta_viewport_default(),
+            title(format!("{status} {title_text}")),
+            style(STYLE),
+        )),
+        body(main((
+            div((attr::class("status"), status.to_string())),
+            h1(title_text),
+            details,
+        ))),
+    )));
+    ErrorPage { status, document }
+}
+
+/// Create a generic `500 Internal Server Error` page.
+///
+/// It doesn't reveal anything about the error, so it is safe to show to any
+/// user.
+pub fn internal_error_page() -> ErrorPage {
+    error_page(
+        500,
+        "Internal Server Error",
+        p("Something went wrong on our end. Please try again later."),
+    )
+}
+
+/// Respond to a panic with [`internal_error_page`].
+///
+/// The signature matches the panic handlers of `tower-http`'s
+/// `CatchPanicLayer::custom`. The panic payload is dropped without being
+/// shown to the user.
+///
+/// Requires the `axum` feature.
+#[cfg(feature = "axum")]
+pub fn panic_response(
+    payload: Box<dyn std::any::Any + Send + 'static>,
+) -> axum_core::response::Response {
+    drop(payload);
+    axum_core::response::IntoResponse::into_response(internal_error_page())
+}
@@ -0,0 +1,337 @@
+//! Traversing and searching element trees.
+//!
+//! Pages are often assembled from components and then modified as a whole,
+//! e.g. to inject a script into the `<head>` or to add a class to every
+//! external link. The methods in this module find the elements to modify
+//! without having to walk [`Element::children`] by hand:
+//!
+//! - [`Element::children_elements`] iterates over the child elements.
+//! - [`Element::descendants`] iterates over all elements below an element.
+//! - [`Element::find`] and [`Element::find_mut`] return the first descendant
+//!   matching a predicate.
+//! - [`Element::query`] and [`Element::select`] iterate over the descendants
+//!   matching a simple CSS-like [`Selector`].
+//!
+//! Elements inside [`Content::Trailer`]s are included, since they are part of
+//! the tree until rendered.
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Render, html::*};
+//!
+//! let mut page = html((
+//!     head(title("Shop")),
+//!     body((a((attr::href("/"), "Home")), a((attr::class("ext"), attr::href("https://x.org"))))),
+//! ));
+//!
+//! assert_eq!(page.descendants().count(), 5);
+//! assert_eq!(page.query("a.ext").unwrap().count(), 1);
+//!
+//! let head = page.find_mut(|e| e.name == "head").unwrap();
+//! head.add(script(attr::src("/app.js")));
+//! assert!(page.render_to_string().unwrap().contains(r#"<script src="/app.js"></script></head>"#));
+//! ```
+
+use std::{error, fmt, slice, str::FromStr};
+
+use crate::{tokens::split_tokens, Content, Element, ElementKind};
+
+/// The element inside some content, looking through [`Content::Trailer`]s.
+fn content_element(mut content: &Content) -> Option<&Element> {
+    loop {
+        match content {
+            Content::Element(element) => return Some(element),
+            Content::Trailer(inner) => content = inner,
+            _ => return None,
+        }
+    }
+}
+
+/// An iterator over the descendants of an element in document order, see
+/// [`Element::descendants`].
+#[derive(Debug, Clone)]
+pub struct Descendants<'a> {
+    stack: Vec<slice::Iter<'a, Content>>,
+}
+
+impl<'a> Iterator for Descendants<'a> {
+    type Item = &'a Element;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let children = self.stack.last_mut()?;
+            let Some(child) = children.next() else {
+                self.stack.pop();
+                continue;
+            };
+            if let Some(element) = content_element(child) {
+                self.stack.push(element.children.iter());
+                return Some(element);
+            }
+        }
+    }
+}
+
+/// A simple CSS-like selector, see [`Element::query`].
+///
+/// A selector consists of an optional tag name (or `*`) followed by any
+/// number of `#id`, `.class`, `[attribute]` and `[attribute=value]` parts,
+/// e.g. `a`, `.card`, `#main` or `input[type=search].wide`. An element
+/// matches if it matches every part. Combinators (like the space in
+/// `nav a`) and pseudo-classes are not supported.
+///
+/// Like in HTML, tag and attribute names are matched ignoring ASCII case
+/// (except for the tag names of [foreign](ElementKind::Foreign) elements),
+/// while ids, classes and attribute values are matched exactly. Attribute
+/// values may be quoted using `"` or `'`.
+///
+/// # Example
+///
+/// ```
+/// use el::{html::*, query::Selector};
+///
+/// let selector = Selector::parse("input[type=search]").unwrap();
+/// assert!(selector.matches(&input((attr::TypeInput::Search, attr::name("q")))));
+/// assert!(!selector.matches(&input(attr::TypeInput::Text)));
+///
+/// assert!(Selector::parse("nav a").is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    tag: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+    attributes: Vec<(String, Option<String>)>,
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii()
+}
+
+/// Split a non-empty name off the start of a string.
+fn name(s: &str) -> Option<(&str, &str)> {
+    let end = s.find(|c| !is_name_char(c)).unwrap_or(s.len());
+    (end > 0).then(|| s.split_at(end))
+}
+
+impl Selector {
+    /// Parse a selector.
+    ///
+    /// Returns [`None`] if the selector is empty or uses unsupported syntax.
+    pub fn parse(selector: &str) -> Option<Self> {
+        let mut result = Self {
+            tag: None,
+            id: None,
+            classes: vec![],
+            attributes: vec![],
+        };
+
+        let mut rest = selector;
+        if let Some(after) = rest.strip_prefix('*') {
+            rest = after;
+        } else if let Some((tag, after)) = name(rest) {
+            result.tag = Some(tag.to_string());
+            rest = after;
+        } else if rest.is_empty() {
+            return None;
+        }
+
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix('#') {
+                let (id, after) = name(after)?;
+                if result.id.as_ref().is_some_and(|other| other != id) {
+                    return None;
+                }
+                result.id = Some(id.to_string());
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix('.') {
+                let (class, after) = name(after)?;
+                result.classes.push(class.to_string());
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix('[') {
+                let (attribute, after) = name(after)?;
+                let (value, after) = if let Some(after) = after.strip_prefix('=') {
+                    let (value, after) = match after.chars().next()? {
+                        quote @ ('"' | '\'') => {
+                            let after = &after[1..];
+                            let end = after.find(quote)?;
+                            (&after[..end], &after[end + 1..])
+                        }
+                        _ => name(after)?,
+                    };
+                    (Some(value.to_string()), after)
+                } else {
+                    (None, after)
+                };
+                rest = after.strip_prefix(']')?;
+                result.attributes.push((attribute.to_string(), value));
+            } else {
+                return None;
+            }
+        }
+
+        Some(result)
+    }
+
+    /// Whether an element matches the selector.
+    pub fn matches(&self, element: &Element) -> bool {
+        if let Some(tag) = &self.tag {
+            let matches = if element.kind == ElementKind::Foreign {
+                element.name == *tag
+            } else {
+                element.name.eq_ignore_ascii_case(tag)
+            };
+            if !matches {
+                return false;
+            }
+        }
+        if let Some(id) = &self.id {
+            if element.attr_ci("id") != Some(id) {
+                return false;
+            }
+        }
+        if !self.classes.is_empty() {
+            let classes = element.attr_ci("class").unwrap_or("");
+            let classes = split_tokens(classes, " ").collect::<Vec<_>>();
+            if !self.classes.iter().all(|c| classes.contains(&&c[..])) {
+                return false;
+            }
+        }
+        self.attributes
+            .iter()
+            .all(|(name, value)| match (element.attr_ci(name), value) {
+                (Some(actual), Some(value)) => actual == value,
+                (Some(_), None) => true,
+                (None, _) => false,
+            })
+    }
+}
+
+impl FromStr for Selector {
+    type Err = InvalidSelector;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| InvalidSelector(s.to_string()))
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.tag {
+            Some(tag) => write!(f, "{tag}")?,
+            None if self.id.is_none() && self.classes.is_empty() && self.attributes.is_empty() => {
+                write!(f, "*")?
+            }
+            None => {}
+        }
+        if let Some(id) = &self.id {
+            write!(f, "#{id}")?;
+        }
+        for class in &self.classes {
+            write!(f, ".{class}")?;
+        }
+        for (name, value) in &self.attributes {
+            match value {
+                Some(value) if !value.contains('"') => write!(f, "[{name}=\"{value}\"]")?,
+                Some(value) => write!(f, "[{name}='{value}']")?,
+                None => write!(f, "[{name}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The error returned by [`Element::query`] and by parsing a [`Selector`]
+/// with [`str::parse`] if the selector is empty or uses unsupported syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidSelector(pub String);
+
+impl fmt::Display for InvalidSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid or unsupported selector {:?}", self.0)
+    }
+}
+
+impl error::Error for InvalidSelector {}
+
+impl Element {
+    /// Iterate over the children that are elements.
+    pub fn children_elements(&self) -> impl Iterator<Item = &Self> {
+        self.children.iter().filter_map(content_element)
+    }
+
+    /// Iterate over all elements below this one (but not the element itself)
+    /// in document order, i.e. every element before its children.
+    pub fn descendants(&self) -> Descendants<'_> {
+        Descendants {
+            stack: vec![self.children.iter()],
+        }
+    }
+
+    /// The first descendant in document order for which the predicate
+    /// returns `true`.
+    pub fn find(&self, mut predicate: impl FnMut(&Self) -> bool) -> Option<&Self> {
+        self.descendants().find(|element| predicate(element))
+    }
+
+    /// A mutable reference to the first descendant in document order for
+    /// which the predicate returns `true`.
+    ///
+    /// See the [module documentation](crate::query) for an example.
+    pub fn find_mut(&mut self, mut predicate: impl FnMut(&Self) -> bool) -> Option<&mut Self> {
+        fn find_mut<'a>(
+            children: &'a mut [Content],
+            predicate: &mut impl FnMut(&Element) -> bool,
+        ) -> Option<&'a mut Element> {
+            for child in children {
+                let mut child = child;
+                while let Content::Trailer(inner) = child {
+                    child = inner;
+                }
+                if let Content::Element(element) = child {
+                    if predicate(element) {
+                        return Some(element);
+                    }
+                    if let Some(found) = find_mut(&mut element.children, predicate) {
+                        return Some(found);
+                    }
+                }
+            }
+            None
+        }
+
+        find_mut(&mut self.children, &mut predicate)
+    }
+
+    /// Iterate over all descendants matching a [`Selector`] in document
+    /// order.
+    pub fn select<'a>(&'a self, selector: &'a Selector) -> impl Iterator<Item = &'a Self> {
+        self.descendants()
+            .filter(|element| selector.matches(element))
+    }
+
+    /// Iterate over all descendants matching a selector like `#id`,
+    /// `.class` or `tag` in document order, see [`Selector`].
+    ///
+    /// Returns an error if the selector is invalid or uses unsupported syntax
+    /// like combinators.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::html::*;
+    ///
+    /// let list = ul((li((attr::class("done"), "a")), li("b"), li((attr::class("done"), "c"))));
+    /// assert_eq!(list.query("li").unwrap().count(), 3);
+    /// assert_eq!(list.query("li.done").unwrap().count(), 2);
+    /// assert_eq!(list.query(".missing").unwrap().count(), 0);
+    /// assert!(list.query("ul li").is_err());
+    /// ```
+    pub fn query(&self, selector: &str) -> Result<impl Iterator<Item = &Self>, InvalidSelector> {
+        let selector = selector.parse::<Selector>()?;
+        Ok(self
+            .descendants()
+            .filter(move |element| selector.matches(element)))
+    }
+}
@@ -1,12 +1,22 @@
-use std::{error, fmt};
+use std::{
+    error, fmt, io, mem,
+    panic::Location,
+    time::{Duration, Instant},
+};
 
 use crate::{
+    borrowed::{ContentRef, ElementRef},
     check,
-    element::{Content, Element, ElementKind},
+    css::{self, StyleError},
+    element::{Content, Element, ElementKind, Fragment, Whitespace},
+    text,
+    tokens::TokenList,
     Document,
 };
 
 /// The cause of an [`Error`].
+///
+/// Every cause has a stable [code](Self::code), see [`Error::code`].
 #[derive(Debug)]
 pub enum ErrorCause {
     /// An error occurred while formatting a value.
@@ -21,28 +31,73 @@ pub enum ErrorCause {
     /// Text inside a [`ElementKind::RawText`] element contains forbidden
     /// structures.
     InvalidRawText { text: String },
+    /// Text that can't be escaped contains characters not representable in
+    /// the [`Encoding`] of the output.
+    Unencodable { text: String },
+    /// Rendering took more nodes or time than allowed by
+    /// [`RenderOptions::max_nodes`] or [`RenderOptions::time_limit`].
+    BudgetExceeded,
+    /// The value of a `style` attribute failed [`css::check_style`] while
+    /// [`RenderOptions::check_styles`] was enabled.
+    UnsafeStyle { style: String, error: StyleError },
+}
+
+impl ErrorCause {
+    /// A stable code identifying the kind of cause.
+    ///
+    /// | Code     | Cause                     |
+    /// |----------|---------------------------|
+    /// | `EL0001` | [`Self::Format`]          |
+    /// | `EL0002` | [`Self::InvalidTagName`]  |
+    /// | `EL0003` | [`Self::InvalidAttrName`] |
+    /// | `EL0004` | [`Self::InvalidChild`]    |
+    /// | `EL0005` | [`Self::InvalidRawText`]  |
+    /// | `EL0006` | [`Self::Unencodable`]     |
+    /// | `EL0007` | [`Self::BudgetExceeded`]  |
+    /// | `EL0008` | [`Self::UnsafeStyle`]     |
+    ///
+    /// Codes are never reused or changed, even if the corresponding variant
+    /// is renamed or removed. New causes get new codes.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Format(_) => "EL0001",
+            Self::InvalidTagName { .. } => "EL0002",
+            Self::InvalidAttrName { .. } => "EL0003",
+            Self::InvalidChild => "EL0004",
+            Self::InvalidRawText { .. } => "EL0005",
+            Self::Unencodable { .. } => "EL0006",
+            Self::BudgetExceeded => "EL0007",
+            Self::UnsafeStyle { .. } => "EL0008",
+        }
+    }
 }
 
 /// An error that can occur during element rendering.
 #[derive(Debug)]
 pub struct Error {
     reverse_path: Vec<(usize, Option<String>)>,
+    location: Option<&'static Location<'static>>,
+    label: Option<String>,
     cause: ErrorCause,
+    /// The source file containing [`Self::location`], read on demand.
+    #[cfg(feature = "miette")]
+    pub(crate) source_code: std::sync::OnceLock<Option<Box<miette::NamedSource<String>>>>,
 }
 
 impl Error {
     pub(crate) fn new(cause: ErrorCause) -> Self {
         Self {
             reverse_path: vec![],
+            location: None,
+            label: None,
             cause,
+            #[cfg(feature = "miette")]
+            source_code: std::sync::OnceLock::new(),
         }
     }
 
     pub(crate) fn at(mut self, index: usize, child: &Content) -> Self {
-        self.reverse_path.push(match child {
-            Content::Element(el) => (index, Some(el.name.clone())),
-            _ => (index, None),
-        });
+        self.reverse_path.push(path_segment(index, child));
         self
     }
 
@@ -62,29 +117,62 @@ impl Error {
     /// assert_eq!(result.unwrap_err().path(), "/1(input)/0");
     /// ```
     pub fn path(&self) -> String {
-        if self.reverse_path.is_empty() {
-            return "/".to_string();
-        }
+        let mut path = self.reverse_path.clone();
+        path.reverse();
+        format_path(&path)
+    }
 
-        self.reverse_path
-            .iter()
-            .rev()
-            .map(|(index, name)| match name {
-                Some(name) => format!("/{index}({name})"),
-                None => format!("/{index}"),
-            })
-            .collect::<String>()
+    /// The source location where the element that caused the error was
+    /// constructed.
+    ///
+    /// This is the [`Element::location`] of the innermost element along the
+    /// [`Self::path`]. It is only available if the `track-caller` feature is
+    /// enabled.
+    pub fn location(&self) -> Option<&'static Location<'static>> {
+        self.location
+    }
+
+    /// The label of the innermost element with a [`Element::label`] along
+    /// the [`Self::path`], if any.
+    ///
+    /// Labels describe where an element comes from, e.g. the component that
+    /// created it, see [`Element::labelled`].
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
     }
 
     /// The cause of the error.
     pub fn cause(&self) -> &ErrorCause {
         &self.cause
     }
+
+    /// A stable code identifying the kind of error, e.g. `EL0002` for an
+    /// invalid tag name.
+    ///
+    /// Unlike the [`Display`](fmt::Display) text, codes don't change between
+    /// versions, so they can be used to match or aggregate errors. See
+    /// [`ErrorCause::code`] for a list of all codes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Element, Render};
+    ///
+    /// let error = Element::normal("not a tag").render_to_string().unwrap_err();
+    /// assert_eq!(error.code(), "EL0002");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        self.cause.code()
+    }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Render error at {}: ", self.path())?;
+        write!(f, "Render error at {}", self.path())?;
+        if let Some(location) = self.location {
+            write!(f, " (element constructed at {location})")?;
+        }
+        write!(f, ": ")?;
 
         match &self.cause {
             ErrorCause::Format(error) => write!(f, "{error}")?,
@@ -92,6 +180,11 @@ impl fmt::Display for Error {
             ErrorCause::InvalidAttrName { name } => write!(f, "Invalid attribute name {name:?}")?,
             ErrorCause::InvalidChild => write!(f, "Invalid child")?,
             ErrorCause::InvalidRawText { text } => write!(f, "Invalid raw text {text:?}")?,
+            ErrorCause::Unencodable { text } => write!(f, "Unencodable text {text:?}")?,
+            ErrorCause::BudgetExceeded => write!(f, "Render budget exceeded")?,
+            ErrorCause::UnsafeStyle { style, error } => {
+                write!(f, "Unsafe style {style:?} ({error})")?
+            }
         }
 
         Ok(())
@@ -106,168 +199,1821 @@ impl From<fmt::Error> for Error {
     }
 }
 
+/// Emit an event for an error if the `tracing` feature is enabled.
+///
+/// Errors that were recovered from by lenient rendering are less severe.
+fn trace_error(error: &Error, recovered: bool) {
+    #[cfg(feature = "tracing")]
+    {
+        let path = error.path();
+        let location = error.location.map(|l| l.to_string());
+        let label = error.label();
+        let code = error.code();
+        if recovered {
+            tracing::warn!(
+                code,
+                path,
+                location,
+                label,
+                "recovered from render error: {error}"
+            );
+        } else {
+            tracing::error!(code, path, location, label, "{error}");
+        }
+    }
+    #[cfg(not(feature = "tracing"))]
+    let _ = (error, recovered);
+}
+
+fn path_segment(index: usize, child: &Content) -> (usize, Option<String>) {
+    match child {
+        Content::Element(el) => (index, Some(el.name.clone())),
+        _ => (index, None),
+    }
+}
+
+/// Format a path from the topmost element, see [`Error::path`].
+pub(crate) fn format_path(path: &[(usize, Option<String>)]) -> String {
+    let path = path
+        .iter()
+        .map(|(index, name)| match name {
+            Some(name) => format!("/{index}({name})"),
+            None => format!("/{index}"),
+        })
+        .collect::<String>();
+
+    if path.is_empty() {
+        return "/".to_string();
+    }
+    path
+}
+
 /// A wrapper around [`std::result::Result`] with the error [`Error`].
 pub type Result<T> = std::result::Result<T, Error>;
 
-/// Render an [`Element`] or a [`Document`] to a [`fmt::Write`]; usually a
-/// [`String`].
+/// Options that affect how elements are rendered.
 ///
-/// To implement this trait, only [`Self::render`] needs to be implemented.
-pub trait Render {
-    /// Render to a writer.
-    fn render<W: fmt::Write>(&self, w: &mut W) -> Result<()>;
+/// The default options are used by [`Render::render`] and
+/// [`Render::render_to_string`]. Custom options are accepted by the `_with`
+/// methods of [`Render`], e.g. [`Render::render_with`] and
+/// [`Render::render_to_writer_with_options`].
+///
+/// Options can be set either using struct update syntax or using the builder
+/// methods of the same name.
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, RenderOptions, html::*};
+///
+/// let options = RenderOptions {
+///     escape_end_tags: true,
+///     ..RenderOptions::default()
+/// };
+///
+/// let mut result = String::new();
+/// textarea("</textarea>").render_with(&mut result, &options).unwrap();
+/// assert_eq!(result, "<textarea>&lt;&#x2F;textarea&gt;</textarea>");
+///
+/// // The same options, using the builder methods
+/// assert_eq!(options, RenderOptions::new().escape_end_tags(true));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RenderOptions {
+    /// Also escape the `/` of every `</` in text inside
+    /// [`ElementKind::EscapableRawText`] elements.
+    ///
+    /// The `<` is always escaped, so text can never end the element early.
+    /// Some legacy consumers decode entities before looking for the end tag
+    /// though, which this option prevents from matching literal end tags like
+    /// `</textarea>` in the text.
+    pub escape_end_tags: bool,
 
-    /// Render directly to a [`String`].
+    /// Render [`ElementKind::Foreign`] elements using XML-compatible syntax.
     ///
-    /// This method is implemented by default and uses [`Self::render`].
-    fn render_to_string(&self) -> Result<String> {
-        let mut result = String::new();
-        self.render(&mut result)?;
-        Ok(result)
-    }
+    /// SVG and MathML embedded in HTML follow the HTML syntax rules. When
+    /// rendering standalone SVG or MathML documents, or when the output is
+    /// embedded in an XML context, the stricter XML rules apply instead:
+    ///
+    /// - Attributes with an empty value are rendered as `name=""`.
+    /// - Attribute values also escape `&` and `<`.
+    /// - Comments never contain `--` and never end with `-`.
+    ///
+    /// Text is escaped the same way in both cases, which also prevents `]]>`
+    /// from appearing in the output.
+    pub foreign_xml: bool,
+
+    /// The encoding the output will be converted to.
+    ///
+    /// The output is always a Rust string, but characters not representable in
+    /// the target encoding are replaced by character references where
+    /// possible. Where they aren't possible (e.g. in comments, in
+    /// [`ElementKind::RawText`] elements, or in [`Content::Raw`]), rendering
+    /// fails instead. The output can then be converted using
+    /// [`Encoding::encode`].
+    ///
+    /// Remember to declare the encoding in the document, e.g. using
+    /// [`crate::html::helpers::meta_charset`].
+    pub encoding: Encoding,
+
+    /// Surround elements with a [`Element::label`] by comments containing the
+    /// label, see [`Element::labelled`].
+    ///
+    /// This is intended for use during development.
+    pub debug_labels: bool,
+
+    /// Check [`Content::Raw`] inside [`ElementKind::RawText`] and
+    /// [`ElementKind::EscapableRawText`] elements the same way as
+    /// [`Content::Text`] inside [`ElementKind::RawText`] elements.
+    ///
+    /// Raw content is normally never checked. With this option, raw content
+    /// that would end its element early results in an
+    /// [`ErrorCause::InvalidRawText`] error, e.g. a `</script>` inside an
+    /// inline script.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Content, Render, RenderOptions, html::*};
+    ///
+    /// let options = RenderOptions {
+    ///     check_raw: true,
+    ///     ..RenderOptions::default()
+    /// };
+    ///
+    /// let script = script(Content::raw("alert('</script>')"));
+    /// assert!(script.render_to_string().is_ok());
+    /// assert!(script.render_with(&mut String::new(), &options).is_err());
+    /// ```
+    pub check_raw: bool,
+
+    /// Check the values of `style` attributes using [`css::check_style`].
+    ///
+    /// Styles that could execute code in some browsers or that are malformed
+    /// result in an [`ErrorCause::UnsafeStyle`] error. See [`css`] for more
+    /// details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Render, RenderOptions, html::*};
+    ///
+    /// let options = RenderOptions::new().check_styles(true);
+    ///
+    /// let ok = p(attr::style("color: red"));
+    /// assert!(ok.render_to_string_with(&options).is_ok());
+    ///
+    /// let bad = p(attr::style("background: url(javascript:alert(1))"));
+    /// assert!(bad.render_to_string().is_ok());
+    /// assert_eq!(bad.render_to_string_with(&options).unwrap_err().code(), "EL0008");
+    /// ```
+    pub check_styles: bool,
+
+    /// The maximum number of nodes (elements, text, comments, ...) to render
+    /// before failing with [`ErrorCause::BudgetExceeded`].
+    ///
+    /// This protects request handlers from pathologically large trees, e.g.
+    /// ones built from untrusted input. Lenient rendering doesn't recover from
+    /// this error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{ErrorCause, Render, RenderOptions, html::*};
+    ///
+    /// let options = RenderOptions {
+    ///     max_nodes: Some(100),
+    ///     ..RenderOptions::default()
+    /// };
+    ///
+    /// let list = ul(vec![li("item"); 1000]);
+    /// let error = list.render_with(&mut String::new(), &options).unwrap_err();
+    /// assert!(matches!(error.cause(), ErrorCause::BudgetExceeded));
+    /// ```
+    pub max_nodes: Option<usize>,
+
+    /// The maximum wall-clock time to spend rendering before failing with
+    /// [`ErrorCause::BudgetExceeded`].
+    ///
+    /// The time is only checked every few nodes, so rendering may take
+    /// slightly longer. Writing to a slow writer counts towards the limit.
+    /// Lenient rendering doesn't recover from this error.
+    pub time_limit: Option<Duration>,
+
+    /// Render the tokens of every `class` attribute sorted and without
+    /// duplicates.
+    ///
+    /// Classes are normally rendered in the order they were added, which
+    /// depends on the order components are composed in. Sorting them makes
+    /// the output stable, e.g. for snapshot tests or cache keys. The element
+    /// itself is not modified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Render, RenderOptions, html::*};
+    ///
+    /// let options = RenderOptions::new().sort_classes(true);
+    /// let a = p((attr::class("card"), attr::class("active")));
+    /// let b = p((attr::class("active"), attr::class("card active")));
+    /// assert_eq!(
+    ///     a.render_to_string_with(&options).unwrap(),
+    ///     b.render_to_string_with(&options).unwrap(),
+    /// );
+    /// ```
+    pub sort_classes: bool,
+
+    /// Put block-level elements on their own lines and indent them according
+    /// to their depth.
+    ///
+    /// Whitespace is only added where it doesn't change how the document is
+    /// displayed with the default styles: The children of an element are only
+    /// reformatted if they are all block-level elements (e.g. `<div>`, `<li>`
+    /// or `<p>`), comments or whitespace. Anything with text or inline
+    /// elements (e.g. `<p>Hello <em>world</em></p>`) is rendered as-is, as
+    /// are `<pre>` and `<textarea>` elements, raw text elements, foreign
+    /// elements and borrowed elements. The added whitespace is still part of
+    /// the DOM, and stylesheets can make it visible, e.g. with `white-space:
+    /// pre` or `display: inline-block`. Use [`crate::NoIndent`] and
+    /// [`crate::PreserveWhitespace`] for such elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Render, RenderOptions, html::*};
+    ///
+    /// let nav = nav(ul((li(a("Home")), li(("Read the ", a("docs"), "!")))));
+    /// let options = RenderOptions::new().pretty(true);
+    /// assert_eq!(
+    ///     nav.render_to_string_with(&options).unwrap(),
+    ///     "<nav>\n  <ul>\n    <li><a>Home</a></li>\n    <li>Read the <a>docs</a>!</li>\n  </ul>\n</nav>",
+    /// );
+    /// ```
+    pub pretty: bool,
+
+    /// Render the doctype of a [`Document`] as `<!DOCTYPE HTML>` instead of
+    /// `<!DOCTYPE html>`.
+    ///
+    /// Browsers treat both the same. This and the following options only
+    /// exist to produce output identical to that of other tools, e.g. to
+    /// compare it byte by byte with existing golden files during a migration.
+    pub uppercase_doctype: bool,
+
+    /// End a rendered [`Document`] with a line break.
+    pub trailing_newline: bool,
+
+    /// Use CRLF (`\r\n`) instead of LF (`\n`) for the line breaks added by
+    /// [`Self::pretty`](#structfield.pretty) and
+    /// [`Self::trailing_newline`](#structfield.trailing_newline).
+    ///
+    /// Line breaks in text and attribute values are never changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Document, Render, RenderOptions, html::*};
+    ///
+    /// let options = RenderOptions::new()
+    ///     .pretty(true)
+    ///     .uppercase_doctype(true)
+    ///     .trailing_newline(true)
+    ///     .crlf(true);
+    /// let page = Document::from(html(body(p("Hi"))));
+    /// assert_eq!(
+    ///     page.render_to_string_with(&options).unwrap(),
+    ///     "<!DOCTYPE HTML>\r\n<html>\r\n  <body>\r\n    <p>Hi</p>\r\n  </body>\r\n</html>\r\n",
+    /// );
+    /// ```
+    pub crlf: bool,
 }
 
-impl Render for Document {
-    fn render<W: fmt::Write>(&self, w: &mut W) -> Result<()> {
-        Content::doctype().render(w)?;
-        self.0.render(w)?;
-        Ok(())
+impl RenderOptions {
+    /// The default options.
+    pub fn new() -> Self {
+        Self::default()
     }
-}
 
-impl Render for [Content] {
-    fn render<W: fmt::Write>(&self, w: &mut W) -> Result<()> {
-        for content in self {
-            content.render(w)?;
-        }
-        Ok(())
+    /// The strictest options, which enable all optional checks and escaping.
+    ///
+    /// Currently, these are [`Self::check_raw`], [`Self::check_styles`] and
+    /// [`Self::escape_end_tags`]. Future checks will be added here as well.
+    pub fn strict() -> Self {
+        Self::new()
+            .check_raw(true)
+            .check_styles(true)
+            .escape_end_tags(true)
     }
-}
 
-impl Render for Content {
-    fn render<W: fmt::Write>(&self, w: &mut W) -> Result<()> {
-        match self {
-            Self::Raw(text) => write!(w, "{text}")?,
-            Self::Text(text) => render_text(w, text)?,
-            Self::Comment(text) => render_comment(w, text)?,
-            Self::Element(element) => element.render(w)?,
+    /// Set [`Self::escape_end_tags`](#structfield.escape_end_tags).
+    pub fn escape_end_tags(mut self, escape_end_tags: bool) -> Self {
+        self.escape_end_tags = escape_end_tags;
+        self
+    }
+
+    /// Set [`Self::foreign_xml`](#structfield.foreign_xml).
+    pub fn foreign_xml(mut self, foreign_xml: bool) -> Self {
+        self.foreign_xml = foreign_xml;
+        self
+    }
+
+    /// Set [`Self::encoding`](#structfield.encoding).
+    pub fn encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Set [`Self::debug_labels`](#structfield.debug_labels).
+    pub fn debug_labels(mut self, debug_labels: bool) -> Self {
+        self.debug_labels = debug_labels;
+        self
+    }
+
+    /// Set [`Self::check_raw`](#structfield.check_raw).
+    pub fn check_raw(mut self, check_raw: bool) -> Self {
+        self.check_raw = check_raw;
+        self
+    }
+
+    /// Set [`Self::check_styles`](#structfield.check_styles).
+    pub fn check_styles(mut self, check_styles: bool) -> Self {
+        self.check_styles = check_styles;
+        self
+    }
+
+    /// Set [`Self::max_nodes`](#structfield.max_nodes).
+    pub fn max_nodes(mut self, max_nodes: impl Into<Option<usize>>) -> Self {
+        self.max_nodes = max_nodes.into();
+        self
+    }
+
+    /// Set [`Self::time_limit`](#structfield.time_limit).
+    pub fn time_limit(mut self, time_limit: impl Into<Option<Duration>>) -> Self {
+        self.time_limit = time_limit.into();
+        self
+    }
+
+    /// Set [`Self::sort_classes`](#structfield.sort_classes).
+    pub fn sort_classes(mut self, sort_classes: bool) -> Self {
+        self.sort_classes = sort_classes;
+        self
+    }
+
+    /// Set [`Self::pretty`](#structfield.pretty).
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Set [`Self::uppercase_doctype`](#structfield.uppercase_doctype).
+    pub fn uppercase_doctype(mut self, uppercase_doctype: bool) -> Self {
+        self.uppercase_doctype = uppercase_doctype;
+        self
+    }
+
+    /// Set [`Self::trailing_newline`](#structfield.trailing_newline).
+    pub fn trailing_newline(mut self, trailing_newline: bool) -> Self {
+        self.trailing_newline = trailing_newline;
+        self
+    }
+
+    /// Set [`Self::crlf`](#structfield.crlf).
+    pub fn crlf(mut self, crlf: bool) -> Self {
+        self.crlf = crlf;
+        self
+    }
+
+    /// The line break added by the renderer, see [`Self::crlf`](#structfield.crlf).
+    fn newline(&self) -> &'static str {
+        if self.crlf {
+            "\r\n"
+        } else {
+            "\n"
         }
-        Ok(())
     }
 }
 
-impl Render for Element {
-    fn render<W: fmt::Write>(&self, w: &mut W) -> Result<()> {
-        // Checks
-        if !check::is_valid_tag_name(&self.name) {
-            return Err(Error::new(ErrorCause::InvalidTagName {
-                name: self.name.clone(),
-            }));
-        }
-        for name in self.attributes.keys() {
-            if !check::is_valid_attribute_name(name) {
-                return Err(Error::new(ErrorCause::InvalidAttrName {
-                    name: name.clone(),
-                }));
-            }
-        }
+/// A character encoding for the rendered output, see
+/// [`RenderOptions::encoding`].
+///
+/// The HTML standard only allows UTF-8, but some legacy systems require other
+/// encodings.
+///
+/// # Example
+///
+/// ```
+/// use el::{Encoding, Render, RenderOptions, html::*};
+///
+/// let options = RenderOptions {
+///     encoding: Encoding::Latin1,
+///     ..RenderOptions::default()
+/// };
+///
+/// let mut result = String::new();
+/// p("Grüße → Welt").render_with(&mut result, &options).unwrap();
+/// assert_eq!(result, "<p>Grüße &#x2192; Welt</p>");
+///
+/// let bytes = Encoding::Latin1.encode(&result).unwrap();
+/// assert_eq!(bytes[5], 0xFC); // ü
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Encoding {
+    /// UTF-8, the only encoding allowed by the HTML standard.
+    #[default]
+    Utf8,
+    /// US-ASCII.
+    Ascii,
+    /// ISO-8859-1, also known as Latin-1.
+    ///
+    /// Browsers decode documents labelled as ISO-8859-1 as windows-1252, which
+    /// assigns different characters to the bytes `0x80` to `0x9F`. To avoid
+    /// this ambiguity, the characters `U+0080` to `U+009F` are considered
+    /// unrepresentable.
+    Latin1,
+}
 
-        // Opening tag
-        write!(w, "<{}", self.name)?;
-        for (name, value) in &self.attributes {
-            write!(w, " {name}")?;
-            if !value.is_empty() {
-                write!(w, "=")?;
-                render_attribute_value(w, value)?;
-            }
-        }
-        if self.children.is_empty() {
-            // Closing early
-            match self.kind {
-                ElementKind::Void => write!(w, ">")?,
-                ElementKind::Foreign => write!(w, " />")?,
-                _ => write!(w, "></{}>", self.name)?,
-            }
-            return Ok(());
+impl Encoding {
+    /// The label used to declare this encoding, e.g. in a `<meta charset>`
+    /// element.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Utf8 => "utf-8",
+            Self::Ascii => "us-ascii",
+            Self::Latin1 => "iso-8859-1",
         }
-        write!(w, ">")?;
+    }
 
-        // Children
-        for (i, child) in self.children.iter().enumerate() {
-            match self.kind {
-                ElementKind::Void => Err(Error::new(ErrorCause::InvalidChild)),
-                ElementKind::RawText => match child {
-                    c @ Content::Raw(_) => c.render(w),
-                    Content::Text(text) if check::is_valid_raw_text(&self.name, text) => {
-                        write!(w, "{text}").map_err(|e| e.into())
-                    }
-                    Content::Text(text) => Err(Error::new(ErrorCause::InvalidRawText {
-                        text: text.clone(),
-                    })),
-                    _ => Err(Error::new(ErrorCause::InvalidChild)),
-                },
-                ElementKind::EscapableRawText => match child {
-                    c @ (Content::Raw(_) | Content::Text(_)) => c.render(w),
-                    _ => Err(Error::new(ErrorCause::InvalidChild)),
-                },
-                _ => child.render(w),
-            }
-            .map_err(|e| e.at(i, child))?;
+    /// Whether a character can be represented in this encoding.
+    pub fn can_encode(self, c: char) -> bool {
+        match self {
+            Self::Utf8 => true,
+            Self::Ascii => c.is_ascii(),
+            Self::Latin1 => c.is_ascii() || ('\u{A0}'..='\u{FF}').contains(&c),
         }
+    }
 
-        // Closing tag
-        if self.kind != ElementKind::Void {
-            write!(w, "</{}>", self.name)?;
+    /// Convert a string to bytes in this encoding.
+    ///
+    /// Returns [`None`] if the string contains characters that can't be
+    /// represented in this encoding.
+    pub fn encode(self, text: &str) -> Option<Vec<u8>> {
+        match self {
+            Self::Utf8 => Some(text.as_bytes().to_vec()),
+            // Both encodings map their characters directly to bytes.
+            Self::Ascii | Self::Latin1 => text
+                .chars()
+                .map(|c| self.can_encode(c).then_some(c as u8))
+                .collect(),
         }
+    }
 
-        Ok(())
+    fn check(self, text: &str) -> Result<()> {
+        if text.chars().all(|c| self.can_encode(c)) {
+            Ok(())
+        } else {
+            Err(Error::new(ErrorCause::Unencodable {
+                text: text.to_string(),
+            }))
+        }
     }
 }
 
-fn render_text<W: fmt::Write>(w: &mut W, text: &str) -> Result<()> {
-    // As far as I can tell, it should be sufficient to escape `&` and `<`.
-    // `>` is escaped too for symmetry, not for any real reason.
-    //
-    // Reasoning: Whenever we're inside tags, we're in one of these states,
-    // https://html.spec.whatwg.org/multipage/parsing.html#data-state
-    // https://html.spec.whatwg.org/multipage/parsing.html#rawtext-state
-    // https://html.spec.whatwg.org/multipage/parsing.html#rcdata-state
+/// What a failing element is replaced with by [`Render::render_lenient`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Placeholder {
+    /// A comment containing the error message.
+    ///
+    /// The message may reveal details about the page's structure. Consider
+    /// using a different placeholder in production.
+    #[default]
+    Comment,
+    /// Nothing at all.
+    Empty,
+    /// Custom content.
+    ///
+    /// If the content itself fails to render, nothing is rendered instead.
+    Content(Content),
+}
 
-    for c in text.chars() {
-        match c {
-            '&' => write!(w, "&amp;")?,
-            '<' => write!(w, "&lt;")?,
-            '>' => write!(w, "&gt;")?,
-            c => write!(w, "{c}")?,
-        }
-    }
+/// A [`fmt::Write`] that can pass on the output written so far, e.g. by
+/// sending it to the client, see [`Render::render_streaming`].
+pub trait FlushWrite: fmt::Write {
+    /// Pass on everything written so far.
+    ///
+    /// Called at every [`Content::Flush`] marker while rendering.
+    fn flush_chunk(&mut self) -> fmt::Result;
+}
 
-    Ok(())
+impl<W: FlushWrite + ?Sized> FlushWrite for &mut W {
+    fn flush_chunk(&mut self) -> fmt::Result {
+        (**self).flush_chunk()
+    }
 }
 
-fn render_comment<W: fmt::Write>(w: &mut W, text: &str) -> Result<()> {
-    write!(w, "<!--")?;
+/// Render an [`Element`] or a [`Document`] to a [`fmt::Write`]; usually a
+/// [`String`].
+///
+/// To implement this trait, only [`Self::render`] needs to be implemented.
+///
+/// Rendering never panics, no matter how the elements were constructed.
+/// Anything that can't be rendered results in an [`Error`] instead.
+///
+/// Rendering is deterministic: equal trees rendered with the same
+/// [`RenderOptions`] always produce the same output, byte for byte, so the
+/// output can be cached or hashed for an `ETag`. Attributes are always
+/// rendered sorted by name, and tokens within an attribute value keep the
+/// order they were added in. Use [`assert_deterministic`] to check this for
+/// custom implementations.
+///
+/// This trait is not object-safe because its methods are generic over the
+/// writer. Use [`DynRender`] to store different renderable types together.
+pub trait Render {
+    /// Render to a writer.
+    fn render<W: fmt::Write>(&self, w: &mut W) -> Result<()>;
 
-    // A comment...
-    // - must not start with the string ">"
-    // - must not start with the string "->"
-    // - must not contain the strings "<!--", "-->", or "--!>"
-    // - must not end with the string "<!-"
-    //
-    // https://html.spec.whatwg.org/multipage/syntax.html#comments
+    /// Render to a writer using custom [`RenderOptions`].
+    ///
+    /// This method is implemented by default and uses [`Self::render`],
+    /// ignoring the options.
+    fn render_with<W: fmt::Write>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        let _ = options;
+        self.render(w)
+    }
 
-    let text = text
+    /// Render directly to a [`String`].
+    ///
+    /// This method is implemented by default and uses [`Self::render`].
+    fn render_to_string(&self) -> Result<String> {
+        let mut result = String::new();
+        self.render(&mut result)?;
+        Ok(result)
+    }
+
+    /// Render directly to a [`String`] using custom [`RenderOptions`].
+    ///
+    /// This method is implemented by default and uses [`Self::render_with`].
+    fn render_to_string_with(&self, options: &RenderOptions) -> Result<String> {
+        let mut result = String::new();
+        self.render_with(&mut result, options)?;
+        Ok(result)
+    }
+
+    /// Render to an [`io::Write`] using custom [`RenderOptions`], e.g. to a
+    /// file or socket.
+    ///
+    /// The output is converted to bytes in the [`RenderOptions::encoding`].
+    /// Render errors are returned as [`io::Error`]s of kind
+    /// [`io::ErrorKind::InvalidData`] wrapping the [`Error`]. Output is
+    /// written while rendering, so some of it may already have been written
+    /// when an error occurs.
+    ///
+    /// This method is implemented by default and uses [`Self::render_with`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Encoding, Render, RenderOptions, html::*};
+    ///
+    /// let options = RenderOptions::new().encoding(Encoding::Latin1);
+    /// let mut bytes = vec![];
+    /// p("Grüße").render_to_writer_with_options(&mut bytes, &options).unwrap();
+    /// assert_eq!(bytes, b"<p>Gr\xFC\xDFe</p>");
+    ///
+    /// let error = br("x").render_to_writer_with_options(&mut bytes, &options).unwrap_err();
+    /// assert!(error.get_ref().unwrap().is::<el::Error>());
+    /// ```
+    fn render_to_writer_with_options<W: io::Write>(
+        &self,
+        w: W,
+        options: &RenderOptions,
+    ) -> io::Result<()> {
+        let mut writer = IoWriter {
+            w,
+            encoding: options.encoding,
+            error: None,
+        };
+        match self.render_with(&mut writer, options) {
+            Ok(()) => Ok(()),
+            Err(error) => Err(match writer.error {
+                Some(error) => error,
+                None => io::Error::new(io::ErrorKind::InvalidData, error),
+            }),
+        }
+    }
+
+    /// Render to an [`io::Write`] using the default options, see
+    /// [`Self::render_to_writer_with_options`].
+    fn render_to_writer<W: io::Write>(&self, w: W) -> io::Result<()> {
+        self.render_to_writer_with_options(w, &RenderOptions::default())
+    }
+
+    /// Render to a [`FlushWrite`], flushing it at every [`Content::Flush`]
+    /// marker while rendering.
+    ///
+    /// Everything before a marker can be sent to the client as soon as the
+    /// writer is flushed, for example to let the browser start loading
+    /// resources referenced in the `<head>` early while the rest of the page
+    /// is still being rendered. Since output is written while rendering, some
+    /// of it may already have been sent when an error occurs.
+    ///
+    /// The writer is not flushed at the end, so it may still contain output
+    /// after the last marker.
+    ///
+    /// This method is implemented by default and uses [`Self::render_with`],
+    /// never flushing the writer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::fmt;
+    /// use el::{Content, FlushWrite, Render, RenderOptions, html::*};
+    ///
+    /// /// Prints every chunk, e.g. to send it over a socket instead.
+    /// #[derive(Default)]
+    /// struct Printer(String);
+    ///
+    /// impl fmt::Write for Printer {
+    ///     fn write_str(&mut self, s: &str) -> fmt::Result {
+    ///         self.0.push_str(s);
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// impl FlushWrite for Printer {
+    ///     fn flush_chunk(&mut self) -> fmt::Result {
+    ///         println!("{}", self.0);
+    ///         self.0.clear();
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let page = html((head(title("Hi")), Content::Flush, body("Hello")));
+    /// let mut printer = Printer::default();
+    /// page.render_streaming(&mut printer, &RenderOptions::default()).unwrap();
+    /// printer.flush_chunk().unwrap();
+    /// ```
+    fn render_streaming<W: FlushWrite>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        self.render_with(w, options)
+    }
+
+    /// Render to a list of chunks, split at every [`Content::Flush`] marker.
+    ///
+    /// Each chunk can be sent to the client as soon as it is available, for
+    /// example to let the browser start loading resources referenced in the
+    /// `<head>` early. Empty chunks are omitted. To send chunks while the rest
+    /// is still being rendered, use [`Self::render_streaming`] instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Content, Render, html::*};
+    ///
+    /// let page = html((head(title("Hi")), Content::Flush, body("Hello")));
+    /// assert_eq!(
+    ///     page.render_chunks().unwrap(),
+    ///     ["<html><head><title>Hi</title></head>", "<body>Hello</body></html>"],
+    /// );
+    /// ```
+    fn render_chunks(&self) -> Result<Vec<String>> {
+        self.render_chunks_with(&RenderOptions::default())
+    }
+
+    /// Render to a list of chunks using custom [`RenderOptions`], see
+    /// [`Self::render_chunks`].
+    ///
+    /// This method is implemented by default and uses
+    /// [`Self::render_streaming`].
+    fn render_chunks_with(&self, options: &RenderOptions) -> Result<Vec<String>> {
+        let mut chunks = Chunks::default();
+        self.render_streaming(&mut chunks, options)?;
+        chunks.flush_chunk()?;
+        Ok(chunks.chunks)
+    }
+
+    /// Render to a writer, replacing elements that fail to render with a
+    /// [`Placeholder`] instead of failing entirely.
+    ///
+    /// Only the innermost element responsible for an error is replaced, and
+    /// rendering continues after it. All errors encountered this way are
+    /// returned. An [`Err`] is only returned if writing to the writer fails.
+    ///
+    /// This method is implemented by default and uses [`Self::render_with`],
+    /// replacing the entire output if rendering fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{Placeholder, Render, RenderOptions, html::*};
+    ///
+    /// let dashboard = div((
+    ///     section("Widget 1"),
+    ///     section(input("Widget 2")), // <input> is a void element
+    ///     section("Widget 3"),
+    /// ));
+    ///
+    /// let mut result = String::new();
+    /// let placeholder = Placeholder::Content(p("Failed to load widget").into());
+    /// let errors = dashboard
+    ///     .render_lenient(&mut result, &RenderOptions::default(), &placeholder)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     result,
+    ///     concat!(
+    ///         "<div><section>Widget 1</section>",
+    ///         "<section><p>Failed to load widget</p></section>",
+    ///         "<section>Widget 3</section></div>",
+    ///     ),
+    /// );
+    /// assert_eq!(errors.len(), 1);
+    /// assert_eq!(errors[0].path(), "/1(section)/0(input)/0");
+    /// ```
+    fn render_lenient<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        options: &RenderOptions,
+        placeholder: &Placeholder,
+    ) -> Result<Vec<Error>> {
+        let mut result = String::new();
+        match self.render_with(&mut result, options) {
+            Ok(()) => {
+                w.write_str(&result)?;
+                Ok(vec![])
+            }
+            Err(error) => {
+                Renderer::new(w, options).placeholder(placeholder, &error)?;
+                Ok(vec![error])
+            }
+        }
+    }
+}
+
+/// An object-safe version of [`Render`], for storing different renderable
+/// types behind `dyn DynRender`.
+///
+/// This trait is implemented for every type that implements [`Render`], so it
+/// never needs to be implemented manually. Boxed trait objects in turn
+/// implement [`Render`] again. Prefer [`Render`] wherever the type is known,
+/// since its generic methods avoid the dynamic dispatch on every write.
+///
+/// # Example
+///
+/// ```
+/// use el::{Document, DynRender, Render, html::*};
+///
+/// let parts: Vec<Box<dyn DynRender>> = vec![
+///     Box::new(p("Element")),
+///     Box::new(html(body("Document")).into_document()),
+/// ];
+///
+/// let rendered = parts
+///     .iter()
+///     .map(|part| part.render_to_string().unwrap())
+///     .collect::<Vec<_>>();
+/// assert_eq!(
+///     rendered,
+///     ["<p>Element</p>", "<!DOCTYPE html><html><body>Document</body></html>"],
+/// );
+/// ```
+pub trait DynRender {
+    /// Render to a writer using custom [`RenderOptions`], see
+    /// [`Render::render_with`].
+    fn render_dyn(&self, w: &mut dyn fmt::Write, options: &RenderOptions) -> Result<()>;
+
+    /// Render to a [`FlushWrite`] using custom [`RenderOptions`], see
+    /// [`Render::render_streaming`].
+    fn render_streaming_dyn(&self, w: &mut dyn FlushWrite, options: &RenderOptions) -> Result<()>;
+
+    /// Render leniently to a writer, see [`Render::render_lenient`].
+    fn render_lenient_dyn(
+        &self,
+        w: &mut dyn fmt::Write,
+        options: &RenderOptions,
+        placeholder: &Placeholder,
+    ) -> Result<Vec<Error>>;
+}
+
+impl<T: Render + ?Sized> DynRender for T {
+    fn render_dyn(&self, mut w: &mut dyn fmt::Write, options: &RenderOptions) -> Result<()> {
+        self.render_with(&mut w, options)
+    }
+
+    fn render_streaming_dyn(
+        &self,
+        mut w: &mut dyn FlushWrite,
+        options: &RenderOptions,
+    ) -> Result<()> {
+        self.render_streaming(&mut w, options)
+    }
+
+    fn render_lenient_dyn(
+        &self,
+        mut w: &mut dyn fmt::Write,
+        options: &RenderOptions,
+        placeholder: &Placeholder,
+    ) -> Result<Vec<Error>> {
+        self.render_lenient(&mut w, options, placeholder)
+    }
+}
+
+impl Render for Box<dyn DynRender + '_> {
+    fn render<W: fmt::Write>(&self, w: &mut W) -> Result<()> {
+        self.render_with(w, &RenderOptions::default())
+    }
+
+    fn render_with<W: fmt::Write>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        (**self).render_dyn(w, options)
+    }
+
+    fn render_streaming<W: FlushWrite>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        (**self).render_streaming_dyn(w, options)
+    }
+
+    fn render_lenient<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        options: &RenderOptions,
+        placeholder: &Placeholder,
+    ) -> Result<Vec<Error>> {
+        (**self).render_lenient_dyn(w, options, placeholder)
+    }
+}
+
+/// Render a value repeatedly and panic if the outputs are not identical,
+/// see [`Render`].
+///
+/// The value is rendered twice, and once more after cloning it. Returns the
+/// rendered output, or the first error that occurred. This is intended for
+/// tests of code that relies on stable output, e.g. for caching.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use el::{assert_deterministic, html::*};
+///
+/// let attrs = HashMap::from([
+///     ("Title".to_string(), "first".to_string()),
+///     ("title".to_string(), "second".to_string()),
+///     ("data-x".to_string(), "1".to_string()),
+/// ]);
+/// let element = div((attrs, attr::class("a b"), attr::class("c")));
+/// assert_eq!(
+///     assert_deterministic(&element).unwrap(),
+///     r#"<div class="a b c" data-x="1" title="second"></div>"#,
+/// );
+/// ```
+#[track_caller]
+pub fn assert_deterministic<R: Render + Clone>(value: &R) -> Result<String> {
+    let first = value.render_to_string()?;
+    let again = value.render_to_string()?;
+    let cloned = value.clone().render_to_string()?;
+    for (name, other) in [("second", again), ("cloned", cloned)] {
+        if let Some(offset) = first_difference(&first, &other) {
+            panic!("nondeterministic rendering: {name} output differs at byte {offset}");
+        }
+    }
+    Ok(first)
+}
+
+fn first_difference(a: &str, b: &str) -> Option<usize> {
+    let offset = (a.bytes().zip(b.bytes())).position(|(a, b)| a != b);
+    offset.or_else(|| (a.len() != b.len()).then_some(a.len().min(b.len())))
+}
+
+impl Render for Document {
+    fn render<W: fmt::Write>(&self, w: &mut W) -> Result<()> {
+        self.render_with(w, &RenderOptions::default())
+    }
+
+    fn render_with<W: fmt::Write>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        Renderer::new(w, options).document(self)
+    }
+
+    fn render_streaming<W: FlushWrite>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        render_streaming(w, options, |r| r.document(self))
+    }
+
+    fn render_lenient<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        options: &RenderOptions,
+        placeholder: &Placeholder,
+    ) -> Result<Vec<Error>> {
+        render_lenient(w, options, placeholder, |r| r.document(self))
+    }
+}
+
+impl Render for [Content] {
+    fn render<W: fmt::Write>(&self, w: &mut W) -> Result<()> {
+        self.render_with(w, &RenderOptions::default())
+    }
+
+    fn render_with<W: fmt::Write>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        Renderer::new(w, options).contents(self)
+    }
+
+    fn render_streaming<W: FlushWrite>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        render_streaming(w, options, |r| r.contents(self))
+    }
+
+    fn render_lenient<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        options: &RenderOptions,
+        placeholder: &Placeholder,
+    ) -> Result<Vec<Error>> {
+        render_lenient(w, options, placeholder, |r| r.contents(self))
+    }
+}
+
+impl Render for Fragment {
+    fn render<W: fmt::Write>(&self, w: &mut W) -> Result<()> {
+        self.0.render(w)
+    }
+
+    fn render_with<W: fmt::Write>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        self.0.render_with(w, options)
+    }
+
+    fn render_streaming<W: FlushWrite>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        self.0.render_streaming(w, options)
+    }
+
+    fn render_lenient<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        options: &RenderOptions,
+        placeholder: &Placeholder,
+    ) -> Result<Vec<Error>> {
+        self.0.render_lenient(w, options, placeholder)
+    }
+}
+
+impl Render for Content {
+    fn render<W: fmt::Write>(&self, w: &mut W) -> Result<()> {
+        self.render_with(w, &RenderOptions::default())
+    }
+
+    fn render_with<W: fmt::Write>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        Renderer::new(w, options).content(self)
+    }
+
+    fn render_streaming<W: FlushWrite>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        render_streaming(w, options, |r| r.content(self))
+    }
+
+    fn render_lenient<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        options: &RenderOptions,
+        placeholder: &Placeholder,
+    ) -> Result<Vec<Error>> {
+        render_lenient(w, options, placeholder, |r| r.content(self))
+    }
+}
+
+impl Render for Element {
+    fn render<W: fmt::Write>(&self, w: &mut W) -> Result<()> {
+        self.render_with(w, &RenderOptions::default())
+    }
+
+    fn render_with<W: fmt::Write>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        Renderer::new(w, options).element(self)
+    }
+
+    fn render_streaming<W: FlushWrite>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        render_streaming(w, options, |r| r.element(self))
+    }
+
+    fn render_lenient<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        options: &RenderOptions,
+        placeholder: &Placeholder,
+    ) -> Result<Vec<Error>> {
+        render_lenient(w, options, placeholder, |r| r.element(self))
+    }
+}
+
+impl Render for ElementRef<'_> {
+    fn render<W: fmt::Write>(&self, w: &mut W) -> Result<()> {
+        self.render_with(w, &RenderOptions::default())
+    }
+
+    fn render_with<W: fmt::Write>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        Renderer::new(w, options).element_ref(self)
+    }
+}
+
+impl Render for ContentRef<'_> {
+    fn render<W: fmt::Write>(&self, w: &mut W) -> Result<()> {
+        self.render_with(w, &RenderOptions::default())
+    }
+
+    fn render_with<W: fmt::Write>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        Renderer::new(w, options).content_ref(self)
+    }
+}
+
+fn render_streaming<W: FlushWrite>(
+    w: &mut W,
+    options: &RenderOptions,
+    f: impl FnOnce(&mut Renderer<'_, W>) -> Result<()>,
+) -> Result<()> {
+    let mut r = Renderer::new(w, options);
+    r.flush = Some(W::flush_chunk);
+    f(&mut r)
+}
+
+fn render_lenient<W: fmt::Write>(
+    w: &mut W,
+    options: &RenderOptions,
+    placeholder: &Placeholder,
+    f: impl FnOnce(&mut Renderer<'_, W>) -> Result<()>,
+) -> Result<Vec<Error>> {
+    let mut r = Renderer::new(w, options);
+    r.lenient = Some(Lenient {
+        placeholder,
+        path: vec![],
+        errors: vec![],
+    });
+    f(&mut r)?;
+    Ok(r.lenient.map(|l| l.errors).unwrap_or_default())
+}
+
+/// A writer that discards everything written to it, for checking whether
+/// rendering would succeed.
+struct Discard;
+
+impl fmt::Write for Discard {
+    fn write_str(&mut self, _: &str) -> fmt::Result {
+        Ok(())
+    }
+}
+
+/// Collects chunks for [`Render::render_chunks_with`].
+#[derive(Default)]
+struct Chunks {
+    chunks: Vec<String>,
+    current: String,
+}
+
+impl fmt::Write for Chunks {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.current.push_str(s);
+        Ok(())
+    }
+}
+
+impl FlushWrite for Chunks {
+    fn flush_chunk(&mut self) -> fmt::Result {
+        if !self.current.is_empty() {
+            self.chunks.push(mem::take(&mut self.current));
+        }
+        Ok(())
+    }
+}
+
+/// Adapts an [`io::Write`] for rendering, see
+/// [`Render::render_to_writer_with_options`].
+struct IoWriter<W> {
+    w: W,
+    encoding: Encoding,
+    /// The error that made the last write fail, if any.
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let result = match self.encoding {
+            Encoding::Utf8 => self.w.write_all(s.as_bytes()),
+            encoding => match encoding.encode(s) {
+                Some(bytes) => self.w.write_all(&bytes),
+                None => Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    Error::new(ErrorCause::Unencodable {
+                        text: s.to_string(),
+                    }),
+                )),
+            },
+        };
+        result.map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+struct Renderer<'a, W> {
+    w: &'a mut W,
+    options: &'a RenderOptions,
+    /// Called at every [`Content::Flush`] marker when rendering to a
+    /// [`FlushWrite`], see [`Render::render_streaming`].
+    flush: Option<fn(&mut W) -> fmt::Result>,
+    /// When rendering a [`Document`], the element that [`Content::Trailer`]s
+    /// are moved into, and the trailers themselves.
+    trailers: Option<(*const Element, Vec<Content>)>,
+    /// When rendering leniently, see [`Render::render_lenient`].
+    lenient: Option<Lenient<'a>>,
+    /// The number of nodes rendered so far, see [`RenderOptions::max_nodes`].
+    nodes: usize,
+    /// When rendering started, if there is a [`RenderOptions::time_limit`].
+    started: Option<Instant>,
+    /// Whether the current element is a descendant of a foreign element, see
+    /// [`Self::kind`].
+    foreign: bool,
+    /// The indentation depth of the current element, see
+    /// [`RenderOptions::pretty`].
+    depth: usize,
+    /// Whether the current element is a descendant of an element that must not
+    /// be reformatted, see [`RenderOptions::pretty`].
+    compact: bool,
+}
+
+/// How the children of an element are rendered, see [`RenderOptions::pretty`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    /// Exactly as they are.
+    Compact,
+    /// Each on its own line, indented by one more level than the element if
+    /// `indent` is set.
+    Lines { indent: bool },
+}
+
+struct Lenient<'a> {
+    placeholder: &'a Placeholder,
+    /// The path from the topmost element to the element currently being
+    /// rendered.
+    path: Vec<(usize, Option<String>)>,
+    errors: Vec<Error>,
+}
+
+impl<'a, W: fmt::Write> Renderer<'a, W> {
+    fn new(w: &'a mut W, options: &'a RenderOptions) -> Self {
+        Self {
+            w,
+            options,
+            flush: None,
+            trailers: None,
+            lenient: None,
+            nodes: 0,
+            started: options.time_limit.map(|_| Instant::now()),
+            foreign: false,
+            depth: 0,
+            compact: false,
+        }
+    }
+
+    /// A renderer with the same options and context as this one, but writing
+    /// to a different output.
+    fn nested<'b, W2: fmt::Write>(&self, w: &'b mut W2) -> Renderer<'b, W2>
+    where
+        'a: 'b,
+    {
+        let mut renderer = Renderer::new(w, self.options);
+        renderer.foreign = self.foreign;
+        renderer.depth = self.depth;
+        renderer.compact = self.compact;
+        renderer
+    }
+
+    /// The kind an element is rendered as.
+    ///
+    /// Like in the HTML parser, descendants of foreign elements are foreign
+    /// elements themselves, no matter how they were constructed. Only the
+    /// children of integration points (e.g. `<foreignObject>`) are HTML again,
+    /// see [`check::is_integration_point`].
+    fn kind(&self, kind: ElementKind) -> ElementKind {
+        if self.foreign {
+            ElementKind::Foreign
+        } else {
+            kind
+        }
+    }
+
+    /// Count a node towards the render budget.
+    fn budget(&mut self) -> Result<()> {
+        self.nodes += 1;
+        let too_many = self.options.max_nodes.is_some_and(|max| self.nodes > max);
+        // Checking the time is comparatively expensive
+        let too_long = self.nodes.is_multiple_of(64)
+            && (self.started.zip(self.options.time_limit))
+                .is_some_and(|(started, limit)| started.elapsed() > limit);
+        if too_many || too_long {
+            return Err(Error::new(ErrorCause::BudgetExceeded));
+        }
+        Ok(())
+    }
+
+    fn document(&mut self, document: &Document) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("render_document").entered();
+        let result = self.document_inner(document);
+        if let Err(error) = &result {
+            trace_error(error, false);
+        }
+        result
+    }
+
+    fn document_inner(&mut self, document: &Document) -> Result<()> {
+        let root = &document.0;
+        let target = root
+            .children
+            .iter()
+            .find_map(|c| match c {
+                Content::Element(el) if el.name == "body" => Some(el),
+                _ => None,
+            })
+            .unwrap_or(root);
+        let mut trailers = vec![];
+        collect_trailers(&root.children, &mut trailers);
+        self.trailers = Some((target, trailers));
+
+        if self.options.uppercase_doctype {
+            self.w.write_str("<!DOCTYPE HTML>")?;
+        } else {
+            self.content(&Content::doctype())?;
+        }
+        if self.options.pretty {
+            self.w.write_str(self.options.newline())?;
+        }
+        self.element(root)?;
+        if self.options.trailing_newline {
+            self.w.write_str(self.options.newline())?;
+        }
+        Ok(())
+    }
+
+    fn contents(&mut self, contents: &[Content]) -> Result<()> {
+        for content in contents {
+            self.budget()?;
+            self.content(content)?;
+        }
+        Ok(())
+    }
+
+    fn content(&mut self, content: &Content) -> Result<()> {
+        match self.content_inner(content) {
+            // Elements recover by themselves. Other content fails before
+            // anything is written.
+            Err(error) if !matches!(content, Content::Element(_)) => self.recover(error),
+            result => result,
+        }
+    }
+
+    #[allow(deprecated)]
+    fn content_inner(&mut self, content: &Content) -> Result<()> {
+        match content {
+            Content::Raw(text) => {
+                self.options.encoding.check(text)?;
+                write!(self.w, "{text}")?;
+            }
+            Content::Trusted(html) => {
+                self.options.encoding.check(html.as_str())?;
+                write!(self.w, "{html}")?;
+            }
+            Content::Text(text) => render_text(&mut self.w, text, false, self.options.encoding)?,
+            Content::Comment(text) => {
+                self.options.encoding.check(text)?;
+                render_comment(&mut self.w, text, false)?;
+            }
+            Content::Element(element) => self.element(element)?,
+            Content::Flush => {
+                if let Some(flush) = self.flush {
+                    flush(self.w)?;
+                }
+            }
+            // Trailers are rendered by the target element when rendering a
+            // document, and in place otherwise.
+            Content::Trailer(_) if self.trailers.is_some() => {}
+            Content::Trailer(content) => self.content(content)?,
+        }
+        Ok(())
+    }
+
+    fn element(&mut self, element: &Element) -> Result<()> {
+        if self.lenient.is_some() {
+            return self.lenient_element(element);
+        }
+        self.labelled_element(element)
+    }
+
+    fn labelled_element(&mut self, element: &Element) -> Result<()> {
+        let result = match &element.label {
+            Some(label) if self.options.debug_labels => {
+                self.content(&Content::comment(format!(" {label} ")))?;
+                self.element_inner(element)?;
+                self.content(&Content::comment(format!(" /{label} ")))
+            }
+            _ => self.element_inner(element),
+        };
+
+        // The innermost element is closest to the cause of the error
+        result.map_err(|mut e| {
+            e.location = e.location.or(element.location);
+            if e.label.is_none() {
+                e.label.clone_from(&element.label);
+            }
+            e
+        })
+    }
+
+    fn lenient_element(&mut self, element: &Element) -> Result<()> {
+        let check = |element: &Element| self.nested(&mut Discard).element(element);
+
+        if check(element).is_ok() {
+            // Nothing can go wrong, so the element can be rendered normally.
+            let lenient = self.lenient.take();
+            let result = self.labelled_element(element);
+            self.lenient = lenient;
+            return result;
+        }
+
+        // Find out whether the element itself is responsible for the error or
+        // only its descendants by replacing all child elements with something
+        // harmless. Comments are allowed in exactly the same places as
+        // elements. Trailers are handled wherever they end up.
+        // The children are never cloned, since this happens again for every
+        // failing ancestor.
+        let shallow = Element {
+            name: element.name.clone(),
+            kind: element.kind,
+            attributes: element.attributes.clone(),
+            children: (element.children.iter())
+                .map(|child| match child {
+                    Content::Element(_) => Content::comment(""),
+                    Content::Trailer(_) => Content::Flush,
+                    child => child.clone(),
+                })
+                .collect(),
+            label: element.label.clone(),
+            location: element.location,
+            whitespace: element.whitespace,
+        };
+        let Err(mut error) = check(&shallow) else {
+            // The failing descendants are replaced instead.
+            return self.labelled_element(element);
+        };
+        if let Some((i, name)) = error.reverse_path.last_mut() {
+            if let Some(child) = element.children.get(*i) {
+                *name = path_segment(*i, child).1;
+            }
+        }
+
+        self.recover(error)
+    }
+
+    /// Replace content that failed to render with a placeholder when
+    /// rendering leniently.
+    ///
+    /// Must only be called if nothing has been written for the content yet.
+    fn recover(&mut self, mut error: Error) -> Result<()> {
+        let Some(lenient) = &self.lenient else {
+            return Err(error);
+        };
+        if let ErrorCause::Format(_) | ErrorCause::BudgetExceeded = error.cause {
+            return Err(error);
+        }
+
+        error
+            .reverse_path
+            .extend(lenient.path.iter().rev().cloned());
+        self.placeholder(lenient.placeholder, &error)?;
+        trace_error(&error, true);
+        if let Some(lenient) = &mut self.lenient {
+            lenient.errors.push(error);
+        }
+        Ok(())
+    }
+
+    fn placeholder(&mut self, placeholder: &Placeholder, error: &Error) -> Result<()> {
+        let content = match placeholder {
+            Placeholder::Comment => Content::comment(format!(" {error} ")),
+            Placeholder::Empty => return Ok(()),
+            Placeholder::Content(content) => content.clone(),
+        };
+        let mut result = String::new();
+        if self.nested(&mut result).content(&content).is_ok() {
+            self.w.write_str(&result)?;
+        }
+        Ok(())
+    }
+
+    fn element_inner(&mut self, element: &Element) -> Result<()> {
+        let kind = self.kind(element.kind);
+        let attributes = (element.attributes.iter()).map(|(name, value)| (&name[..], &value[..]));
+        if !self.open_tag(&element.name, kind, attributes, &element.children)? {
+            return Ok(());
+        }
+
+        // Children
+        let layout = self.layout(element, kind);
+        let integration = check::is_integration_point(&element.name, element.attr_ci("encoding"));
+        let foreign = mem::replace(
+            &mut self.foreign,
+            kind == ElementKind::Foreign && !integration,
+        );
+        let compact = mem::replace(&mut self.compact, layout == Layout::Compact);
+        let depth = self.depth;
+        if layout == (Layout::Lines { indent: true }) {
+            self.depth += 1;
+        }
+        let result = self
+            .children(element, kind, layout)
+            .and_then(|()| self.trailers(element, layout));
+        self.foreign = foreign;
+        self.compact = compact;
+        self.depth = depth;
+        result?;
+
+        if layout != Layout::Compact {
+            self.line_break()?;
+        }
+        self.close_tag(&element.name, kind)
+    }
+
+    fn children(&mut self, element: &Element, kind: ElementKind, layout: Layout) -> Result<()> {
+        for (i, child) in element.children.iter().enumerate() {
+            if layout != Layout::Compact {
+                match child {
+                    // Only whitespace is left in this layout
+                    Content::Text(_) => continue,
+                    Content::Flush => {}
+                    Content::Trailer(_) if self.trailers.is_some() => {}
+                    _ => self.line_break()?,
+                }
+            }
+            if let Some(lenient) = &mut self.lenient {
+                lenient.path.push(path_segment(i, child));
+            }
+            let result = (self.child(&element.name, kind, child)).map_err(|e| e.at(i, child));
+            if let Some(lenient) = &mut self.lenient {
+                lenient.path.pop();
+            }
+            result?;
+        }
+        Ok(())
+    }
+
+    /// Render the [`Content::Trailer`]s of a [`Document`] if the element is
+    /// their target.
+    fn trailers(&mut self, element: &Element, layout: Layout) -> Result<()> {
+        let Some((target, trailers)) = &mut self.trailers else {
+            return Ok(());
+        };
+        if !std::ptr::eq(*target, element) {
+            return Ok(());
+        }
+        let trailers = mem::take(trailers);
+        for trailer in &trailers {
+            if layout != Layout::Compact {
+                self.line_break()?;
+            }
+            self.content(trailer)?;
+        }
+        Ok(())
+    }
+
+    /// Decide how to render the children of an element, see
+    /// [`RenderOptions::pretty`].
+    fn layout(&self, element: &Element, kind: ElementKind) -> Layout {
+        if !self.options.pretty
+            || self.compact
+            || kind != ElementKind::Normal
+            || element.whitespace == Whitespace::Preserve
+            || !is_block(&element.name)
+            || text::PREFORMATTED_ELEMENTS.contains(&&element.name[..])
+        {
+            return Layout::Compact;
+        }
+        // Only use lines if there is at least one line
+        let mut lines = (self.trailers.as_ref()).is_some_and(|(target, trailers)| {
+            std::ptr::eq(*target, element) && !trailers.is_empty()
+        });
+        for child in &element.children {
+            match child {
+                Content::Text(text) if text.chars().all(|c| c.is_ascii_whitespace()) => {}
+                Content::Element(child)
+                    if child.kind != ElementKind::Foreign && is_block(&child.name) =>
+                {
+                    lines = true;
+                }
+                Content::Comment(_) => lines = true,
+                Content::Trailer(_) => lines |= self.trailers.is_none(),
+                Content::Flush => {}
+                _ => return Layout::Compact,
+            }
+        }
+        if !lines {
+            return Layout::Compact;
+        }
+        Layout::Lines {
+            indent: element.whitespace != Whitespace::NoIndent,
+        }
+    }
+
+    /// Start a new line at the current indentation depth.
+    fn line_break(&mut self) -> Result<()> {
+        self.w.write_str(self.options.newline())?;
+        for _ in 0..self.depth {
+            self.w.write_str("  ")?;
+        }
+        Ok(())
+    }
+
+    /// Check the element's name and attributes, then write its opening tag.
+    ///
+    /// Returns whether the children and closing tag still need to be written.
+    fn open_tag<'b, C>(
+        &mut self,
+        name: &str,
+        kind: ElementKind,
+        attributes: impl Iterator<Item = (&'b str, &'b str)> + Clone,
+        children: &[C],
+    ) -> Result<bool> {
+        // Checks
+        if !check::is_valid_tag_name(name) {
+            return Err(Error::new(ErrorCause::InvalidTagName {
+                name: name.to_string(),
+            }));
+        }
+        for (name, value) in attributes.clone() {
+            if !check::is_valid_attribute_name(name) {
+                return Err(Error::new(ErrorCause::InvalidAttrName {
+                    name: name.to_string(),
+                }));
+            }
+            if self.options.check_styles && name.eq_ignore_ascii_case("style") {
+                if let Err(error) = css::check_style(value) {
+                    return Err(Error::new(ErrorCause::UnsafeStyle {
+                        style: value.to_string(),
+                        error,
+                    }));
+                }
+            }
+        }
+
+        // Opening tag
+        let xml = self.xml(kind);
+        write!(self.w, "<{name}")?;
+        for (name, value) in attributes {
+            let sorted;
+            let value = if self.options.sort_classes && name.eq_ignore_ascii_case("class") {
+                sorted = TokenList::parse("class", " ", value).sorted().to_string();
+                &sorted
+            } else {
+                value
+            };
+            write!(self.w, " {name}")?;
+            if xml || !value.is_empty() {
+                write!(self.w, "=")?;
+                render_attribute_value(&mut self.w, value, xml, self.options.encoding)?;
+            }
+        }
+        if children.is_empty() {
+            // Closing early
+            match kind {
+                ElementKind::Void => write!(self.w, ">")?,
+                ElementKind::Foreign => write!(self.w, " />")?,
+                _ => write!(self.w, "></{name}>")?,
+            }
+            return Ok(false);
+        }
+        write!(self.w, ">")?;
+        Ok(true)
+    }
+
+    fn close_tag(&mut self, name: &str, kind: ElementKind) -> Result<()> {
+        if kind.allows_children() {
+            write!(self.w, "</{name}>")?;
+        }
+        Ok(())
+    }
+
+    #[allow(deprecated)]
+    fn child(&mut self, parent: &str, kind: ElementKind, child: &Content) -> Result<()> {
+        self.budget()?;
+        match child {
+            _ if !kind.allows_children() => Err(Error::new(ErrorCause::InvalidChild)),
+            Content::Raw(text) => {
+                self.check_raw_child(parent, kind, text)?;
+                self.content(child)
+            }
+            Content::Trusted(html) => {
+                self.check_raw_child(parent, kind, html.as_str())?;
+                self.content(child)
+            }
+            Content::Flush | Content::Trailer(_) => self.content(child),
+            Content::Text(text) => self.text_child(parent, kind, text),
+            _ if !kind.allows_elements() => Err(Error::new(ErrorCause::InvalidChild)),
+            Content::Comment(text) => self.comment_child(kind, text),
+            _ => self.content(child),
+        }
+    }
+
+    fn check_raw_child(&self, parent: &str, kind: ElementKind, text: &str) -> Result<()> {
+        if self.options.check_raw
+            && !kind.allows_elements()
+            && !check::is_valid_raw_text(parent, text)
+        {
+            return Err(Error::new(ErrorCause::InvalidRawText {
+                text: text.to_string(),
+            }));
+        }
+        Ok(())
+    }
+
+    fn text_child(&mut self, parent: &str, kind: ElementKind, text: &str) -> Result<()> {
+        if kind.escapes_text() {
+            let escape_end_tags =
+                kind == ElementKind::EscapableRawText && self.options.escape_end_tags;
+            render_text(&mut self.w, text, escape_end_tags, self.options.encoding)
+        } else if check::is_valid_raw_text(parent, text) {
+            self.options.encoding.check(text)?;
+            write!(self.w, "{text}").map_err(|e| e.into())
+        } else {
+            Err(Error::new(ErrorCause::InvalidRawText {
+                text: text.to_string(),
+            }))
+        }
+    }
+
+    fn comment_child(&mut self, kind: ElementKind, text: &str) -> Result<()> {
+        self.options.encoding.check(text)?;
+        let xml = self.xml(kind);
+        render_comment(&mut self.w, text, xml)
+    }
+
+    fn xml(&self, kind: ElementKind) -> bool {
+        self.options.foreign_xml && kind == ElementKind::Foreign
+    }
+
+    #[allow(deprecated)]
+    fn content_ref(&mut self, content: &ContentRef<'_>) -> Result<()> {
+        match content {
+            ContentRef::Raw(text) => {
+                self.options.encoding.check(text)?;
+                write!(self.w, "{text}")?;
+            }
+            ContentRef::Text(text) => render_text(&mut self.w, text, false, self.options.encoding)?,
+            ContentRef::Comment(text) => {
+                self.options.encoding.check(text)?;
+                render_comment(&mut self.w, text, false)?;
+            }
+            ContentRef::Element(element) => self.element_ref(element)?,
+            ContentRef::Owned(element) => self.element(element)?,
+        }
+        Ok(())
+    }
+
+    fn element_ref(&mut self, element: &ElementRef<'_>) -> Result<()> {
+        let kind = self.kind(element.kind);
+        let attributes = (element.attributes.iter()).map(|(name, value)| (*name, *value));
+        if !self.open_tag(element.name, kind, attributes, &element.children)? {
+            return Ok(());
+        }
+
+        let encoding = (element.attributes.iter())
+            .find(|(name, _)| name.eq_ignore_ascii_case("encoding"))
+            .map(|(_, value)| *value);
+        let integration = check::is_integration_point(element.name, encoding);
+        let foreign = mem::replace(
+            &mut self.foreign,
+            kind == ElementKind::Foreign && !integration,
+        );
+        let compact = mem::replace(&mut self.compact, true);
+        let result = self.children_ref(element, kind);
+        self.foreign = foreign;
+        self.compact = compact;
+        result?;
+
+        self.close_tag(element.name, kind)
+    }
+
+    #[allow(deprecated)]
+    fn children_ref(&mut self, element: &ElementRef<'_>, kind: ElementKind) -> Result<()> {
+        for (i, child) in element.children.iter().enumerate() {
+            let result = self.budget().and_then(|()| match child {
+                _ if !kind.allows_children() => Err(Error::new(ErrorCause::InvalidChild)),
+                ContentRef::Raw(text) => self
+                    .check_raw_child(element.name, kind, text)
+                    .and_then(|()| self.content_ref(child)),
+                ContentRef::Text(text) => self.text_child(element.name, kind, text),
+                _ if !kind.allows_elements() => Err(Error::new(ErrorCause::InvalidChild)),
+                ContentRef::Comment(text) => self.comment_child(kind, text),
+                _ => self.content_ref(child),
+            });
+            result.map_err(|mut e| {
+                let name = match child {
+                    ContentRef::Element(element) => Some(element.name.to_string()),
+                    ContentRef::Owned(element) => Some(element.name.clone()),
+                    _ => None,
+                };
+                e.reverse_path.push((i, name));
+                e
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Elements that may be put on their own line and whose children may be put
+/// on their own lines when pretty-printing, see [`RenderOptions::pretty`].
+#[rustfmt::skip]
+const LAYOUT_ELEMENTS: &[&str] = &[
+    "base", "col", "colgroup", "head", "link", "meta", "noscript", "optgroup", "option", "script",
+    "style", "td", "template", "th", "title",
+];
+
+fn is_block(name: &str) -> bool {
+    text::BLOCK_ELEMENTS.contains(&name)
+        || text::PARAGRAPH_ELEMENTS.contains(&name)
+        || LAYOUT_ELEMENTS.contains(&name)
+}
+
+fn collect_trailers(contents: &[Content], trailers: &mut Vec<Content>) {
+    for content in contents {
+        match content {
+            Content::Element(element) => collect_trailers(&element.children, trailers),
+            Content::Trailer(content) => {
+                let content = &**content;
+                collect_trailers(std::slice::from_ref(content), trailers);
+                if !matches!(content, Content::Trailer(_)) {
+                    trailers.push(content.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn render_text<W: fmt::Write>(
+    w: &mut W,
+    text: &str,
+    escape_end_tags: bool,
+    encoding: Encoding,
+) -> Result<()> {
+    // As far as I can tell, it should be sufficient to escape `&` and `<`.
+    // `>` is escaped too for symmetry, not for any real reason.
+    //
+    // Reasoning: Whenever we're inside tags, we're in one of these states,
+    // https://html.spec.whatwg.org/multipage/parsing.html#data-state
+    // https://html.spec.whatwg.org/multipage/parsing.html#rawtext-state
+    // https://html.spec.whatwg.org/multipage/parsing.html#rcdata-state
+
+    let mut previous = None;
+    for c in text.chars() {
+        match c {
+            '&' => write!(w, "&amp;")?,
+            '<' => write!(w, "&lt;")?,
+            '>' => write!(w, "&gt;")?,
+            '/' if escape_end_tags && previous == Some('<') => write!(w, "&#x2F;")?,
+            c if !encoding.can_encode(c) => write!(w, "&#x{:X};", c as u32)?,
+            c => write!(w, "{c}")?,
+        }
+        previous = Some(c);
+    }
+
+    Ok(())
+}
+
+fn render_comment<W: fmt::Write>(w: &mut W, text: &str, xml: bool) -> Result<()> {
+    write!(w, "<!--")?;
+
+    // A comment...
+    // - must not start with the string ">"
+    // - must not start with the string "->"
+    // - must not contain the strings "<!--", "-->", or "--!>"
+    // - must not end with the string "<!-"
+    //
+    // https://html.spec.whatwg.org/multipage/syntax.html#comments
+
+    let mut text = text
         .replace("<!--", "<!==")
         .replace("-->", "==>")
         .replace("--!>", "==!>");
 
+    // In XML, a comment additionally...
+    // - must not contain the string "--"
+    // - must not end with the string "-"
+    //
+    // https://www.w3.org/TR/xml/#sec-comments
+    if xml {
+        while text.contains("--") {
+            text = text.replace("--", "-=");
+        }
+    }
+
     if text.starts_with(">") || text.starts_with("->") {
         write!(w, " ")?;
     }
 
     write!(w, "{text}")?;
 
-    if text.ends_with("<!-") {
+    if text.ends_with("<!-") || (xml && text.ends_with('-')) {
         write!(w, " ")?;
     }
 
@@ -275,17 +2021,26 @@ fn render_comment<W: fmt::Write>(w: &mut W, text: &str) -> Result<()> {
     Ok(())
 }
 
-fn render_attribute_value<W: fmt::Write>(w: &mut W, text: &str) -> Result<()> {
+fn render_attribute_value<W: fmt::Write>(
+    w: &mut W,
+    text: &str,
+    xml: bool,
+    encoding: Encoding,
+) -> Result<()> {
     // Quoted attribute values are escaped like text, but the set of characters
-    // to escape is different.
+    // to escape is different. XML additionally forbids a literal `<`.
     //
     // https://html.spec.whatwg.org/multipage/syntax.html#attributes-2
+    // https://www.w3.org/TR/xml/#NT-AttValue
 
     write!(w, "\"")?;
 
     for c in text.chars() {
         match c {
             '"' => write!(w, "&quot;")?,
+            '&' if xml => write!(w, "&amp;")?,
+            '<' if xml => write!(w, "&lt;")?,
+            c if !encoding.can_encode(c) => write!(w, "&#x{:X};", c as u32)?,
             c => write!(w, "{c}")?,
         }
     }
@@ -0,0 +1,239 @@
+//! Rendering a set of related documents, e.g. for a static site.
+//!
+//! A [`SiteRender`] collects documents by their URL path and renders them all
+//! with the same [`RenderOptions`]. Fragments shared by many pages (like the
+//! navigation or footer) can be prerendered once using
+//! [`SiteRender::partial`] and included in every page as [`TrustedHtml`]
+//! without being rendered again. The site also keeps a registry of all assets
+//! (stylesheets, scripts, images, ...) referenced by its pages, so exporters
+//! know which files to copy.
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Element, html::{*, helpers::stylesheet}, site::SiteRender};
+//!
+//! let mut site = SiteRender::new();
+//! let links = (a((attr::href("/"), "Home")), a((attr::href("/about"), "About")));
+//! let nav = site.partial(&nav(links)).unwrap();
+//! let page = |name: &str, content: Element| {
+//!     html((
+//!         head((stylesheet("/style.css"), title(name))),
+//!         body((nav.clone(), main(content))),
+//!     ))
+//! };
+//!
+//! let site = site
+//!     .add("/", page("Home", p("Welcome!")))
+//!     .add("/about", page("About", img(attr::src("/me.png"))));
+//!
+//! let pages = site.render_all();
+//! assert_eq!(pages.len(), 2);
+//! assert!(pages["/about"].as_ref().unwrap().contains(r#"<a href="/about">About</a>"#));
+//! assert_eq!(SiteRender::file_path("/about").unwrap(), "about/index.html");
+//! assert_eq!(
+//!     site.assets().into_iter().collect::<Vec<_>>(),
+//!     ["/me.png", "/style.css"],
+//! );
+//! ```
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error, fmt,
+};
+
+use crate::{
+    url::url_scheme, Content, Document, Element, Render, RenderOptions, Result, TrustedHtml,
+};
+
+/// Attributes referencing assets, by element name.
+///
+/// Only some `<link>` elements reference assets, see [`LINK_ASSET_RELS`].
+const ASSET_ATTRIBUTES: &[(&str, &str)] = &[
+    ("audio", "src"),
+    ("embed", "src"),
+    ("iframe", "src"),
+    ("img", "src"),
+    ("input", "src"),
+    ("object", "data"),
+    ("script", "src"),
+    ("source", "src"),
+    ("track", "src"),
+    ("video", "poster"),
+    ("video", "src"),
+];
+
+/// Values of `rel` for which a `<link>` references an asset.
+const LINK_ASSET_RELS: &[&str] = &[
+    "apple-touch-icon",
+    "icon",
+    "manifest",
+    "mask-icon",
+    "modulepreload",
+    "preload",
+    "stylesheet",
+];
+
+/// A set of documents rendered together, see the [module
+/// documentation](self).
+#[derive(Debug, Clone, Default)]
+pub struct SiteRender {
+    options: RenderOptions,
+    pages: BTreeMap<String, Document>,
+    assets: BTreeSet<String>,
+}
+
+impl SiteRender {
+    /// Create a new empty site using the default render options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new empty site using custom render options for all pages and
+    /// partials.
+    pub fn with_options(options: RenderOptions) -> Self {
+        Self {
+            options,
+            ..Self::default()
+        }
+    }
+
+    /// Add a page in-place, replacing any previous page with the same path.
+    pub fn insert(&mut self, path: impl ToString, document: impl Into<Document>) {
+        self.pages.insert(path.to_string(), document.into());
+    }
+
+    /// A more builder-pattern-like version of [`Self::insert`].
+    pub fn add(mut self, path: impl ToString, document: impl Into<Document>) -> Self {
+        self.insert(path, document);
+        self
+    }
+
+    /// Prerender a fragment shared by multiple pages.
+    ///
+    /// The returned content can be cloned into every page and is included in
+    /// the output without being rendered again. Assets referenced by the
+    /// fragment are added to the site's registry.
+    pub fn partial(&mut self, element: &Element) -> Result<Content> {
+        let html = TrustedHtml::from_render_with(element, &self.options)?;
+        collect_assets(element, &mut self.assets);
+        Ok(Content::Trusted(html))
+    }
+
+    /// Register an asset that is not referenced by any element, e.g. one
+    /// loaded from a script.
+    pub fn register_asset(&mut self, url: impl ToString) {
+        self.assets.insert(url.to_string());
+    }
+
+    /// The paths of all pages, in order.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.pages.keys().map(|path| &path[..])
+    }
+
+    /// All assets referenced by the pages and partials of the site, or
+    /// registered using [`Self::register_asset`].
+    ///
+    /// Assets are collected from attributes like the `src` of `<img>` and
+    /// `<script>` elements and the `href` of stylesheets, icons and preloads,
+    /// including elements in [`Content::Trailer`]s. Fragment-only URLs and
+    /// URLs with a scheme (e.g. `https:` or `data:`) or host (e.g.
+    /// `//cdn.example.com/a.js`) are skipped, since they aren't files of the
+    /// site.
+    pub fn assets(&self) -> BTreeSet<&str> {
+        let mut assets = BTreeSet::new();
+        for document in self.pages.values() {
+            collect_assets(&document.0, &mut assets);
+        }
+        assets.extend(self.assets.iter().map(|asset| &asset[..]));
+        assets
+    }
+
+    /// Render a single page.
+    ///
+    /// Returns [`None`] if there is no page with that path.
+    pub fn render(&self, path: &str) -> Option<Result<String>> {
+        let document = self.pages.get(path)?;
+        Some(document.render_to_string_with(&self.options))
+    }
+
+    /// Render all pages, by path.
+    ///
+    /// Each page is rendered independently, so a page that fails to render
+    /// doesn't affect the others.
+    pub fn render_all(&self) -> BTreeMap<&str, Result<String>> {
+        (self.paths())
+            .filter_map(|path| Some((path, self.render(path)?)))
+            .collect()
+    }
+
+    /// The relative file path a page should be written to when exporting a
+    /// static site.
+    ///
+    /// Paths ending in `.html` are used directly. Other paths are treated as
+    /// directories containing an `index.html`, so that web servers serve them
+    /// at the original path.
+    ///
+    /// Returns an error if the path contains `.` or `..` segments,
+    /// backslashes or NUL characters, or starts with a drive prefix like
+    /// `C:`, since the file could then end up outside the export directory.
+    pub fn file_path(path: &str) -> std::result::Result<String, InvalidPath> {
+        let trimmed = path.trim_matches('/');
+        let invalid = trimmed.contains(['\\', '\0'])
+            || (trimmed.split('/').next()).is_some_and(|first| first.contains(':'))
+            || (trimmed.split('/')).any(|segment| segment == "." || segment == "..");
+        if invalid {
+            Err(InvalidPath(path.to_string()))
+        } else if trimmed.is_empty() {
+            Ok("index.html".to_string())
+        } else if trimmed.ends_with(".html") {
+            Ok(trimmed.to_string())
+        } else {
+            Ok(format!("{trimmed}/index.html"))
+        }
+    }
+}
+
+/// The error returned by [`SiteRender::file_path`] for paths that could
+/// escape the export directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidPath(pub String);
+
+impl fmt::Display for InvalidPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid page path {:?}", self.0)
+    }
+}
+
+impl error::Error for InvalidPath {}
+
+fn collect_assets<'a, S: From<&'a str> + Ord>(element: &'a Element, assets: &mut BTreeSet<S>) {
+    for element in std::iter::once(element).chain(element.descendants()) {
+        collect_element_assets(element, assets);
+    }
+}
+
+fn collect_element_assets<'a, S: From<&'a str> + Ord>(
+    element: &'a Element,
+    assets: &mut BTreeSet<S>,
+) {
+    let is_asset_link = element.name == "link"
+        && (element.get_tokens("rel", " ")).any(|rel| {
+            LINK_ASSET_RELS
+                .iter()
+                .any(|asset_rel| rel.eq_ignore_ascii_case(asset_rel))
+        });
+    let attributes = (ASSET_ATTRIBUTES.iter())
+        .filter(|(name, _)| element.name == *name)
+        .map(|(_, attribute)| *attribute)
+        .chain(is_asset_link.then_some("href"));
+    for attribute in attributes {
+        if let Some(url) = element.attr_ci(attribute) {
+            let url = url.trim();
+            let external = url_scheme(url).is_some() || url.starts_with("//");
+            if !url.is_empty() && !url.starts_with('#') && !external {
+                assets.insert(S::from(url));
+            }
+        }
+    }
+}
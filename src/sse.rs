@@ -0,0 +1,280 @@
+//! Framing of rendered HTML as [server-sent events][sse].
+//!
+//! Streaming UIs (e.g. using the htmx SSE extension) send HTML fragments as
+//! the `data` of server-sent events. Each line of an event's data must be sent
+//! in its own `data:` field, and lines may be split further where a line
+//! break doesn't change the HTML, see [`Event::max_line_length`].
+//!
+//! [sse]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+
+use std::fmt;
+
+use crate::{ElementKind, Render, Result};
+
+/// A single server-sent event with rendered HTML as its data.
+///
+/// # Example
+///
+/// ```
+/// use el::{html::*, sse::Event};
+///
+/// let event = Event::new(&pre("line 1\nline 2")).unwrap().event("update");
+/// assert_eq!(
+///     event.to_string(),
+///     "event: update\ndata: <pre>line 1\ndata: line 2</pre>\n\n",
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    event: Option<String>,
+    id: Option<String>,
+    retry: Option<u64>,
+    data: String,
+    max_line_length: Option<usize>,
+}
+
+impl Event {
+    /// Render an element (or anything else implementing [`Render`]) into the
+    /// data of a new event.
+    pub fn new<R: Render + ?Sized>(content: &R) -> Result<Self> {
+        Ok(Self::from_html(content.render_to_string()?))
+    }
+
+    /// Create a new event from already rendered HTML.
+    pub fn from_html(html: impl ToString) -> Self {
+        Self {
+            event: None,
+            id: None,
+            retry: None,
+            data: html.to_string(),
+            max_line_length: None,
+        }
+    }
+
+    /// Set the event type.
+    ///
+    /// Line breaks are removed since they would end the field early.
+    pub fn event(mut self, event: impl ToString) -> Self {
+        self.event = Some(single_line(event));
+        self
+    }
+
+    /// Set the event id.
+    ///
+    /// Line breaks and NUL characters are removed since they are not allowed
+    /// in ids.
+    pub fn id(mut self, id: impl ToString) -> Self {
+        self.id = Some(single_line(id).replace('\0', ""));
+        self
+    }
+
+    /// Set the reconnection time in milliseconds.
+    pub fn retry(mut self, millis: u64) -> Self {
+        self.retry = Some(millis);
+        self
+    }
+
+    /// Split long lines of data into multiple `data:` fields.
+    ///
+    /// The client joins `data:` fields with line breaks, so lines are only
+    /// split at a space between the attributes of a start tag, where a line
+    /// break means the same as the space it replaces. Text, attribute values
+    /// and the contents of elements like `<script>` are never split. Lines
+    /// may still exceed the limit if there is no such place.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::{html::*, sse::Event};
+    ///
+    /// let link = a((attr::href("/a"), attr::title("A > B"), "A > B"));
+    /// let event = Event::new(&link).unwrap().max_line_length(16);
+    /// assert_eq!(
+    ///     event.to_string(),
+    ///     "data: <a href=\"/a\"\ndata: title=\"A > B\">A &gt; B</a>\n\n",
+    /// );
+    /// ```
+    pub fn max_line_length(mut self, length: usize) -> Self {
+        self.max_line_length = Some(length);
+        self
+    }
+
+    /// The rendered HTML data of this event.
+    pub fn data(&self) -> &str {
+        &self.data
+    }
+}
+
+fn single_line(value: impl ToString) -> String {
+    value.to_string().replace(['\r', '\n'], "")
+}
+
+/// Byte offsets of the spaces between the attributes of start tags, which can
+/// be replaced by line breaks without changing the meaning of the HTML.
+///
+/// The contents of comments and of elements whose contents are not parsed as
+/// HTML (e.g. `<script>` or `<textarea>`) are skipped.
+fn breakable_spaces(html: &str) -> Vec<usize> {
+    let mut result = vec![];
+    let mut i = 0;
+    while let Some(offset) = html[i..].find('<') {
+        let start = i + offset;
+        let rest = &html[start..];
+        if rest.starts_with("<!--") {
+            match rest[2..].find("-->") {
+                Some(end) => i = start + 2 + end + 3,
+                None => break,
+            }
+            continue;
+        }
+        if !rest[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            // End tags, doctypes and text that only looks like a tag
+            i = start + 1;
+            continue;
+        }
+
+        let name_end = rest
+            .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+            .unwrap_or(rest.len());
+        let name = &rest[1..name_end];
+        let mut quote = None;
+        let mut after_equals = false;
+        let mut end = None;
+        for (j, c) in rest.char_indices().skip(name_end) {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '"' | '\'') if after_equals => quote = Some(c),
+                (None, '>') => {
+                    end = Some(start + j + 1);
+                    break;
+                }
+                (None, ' ') => result.push(start + j),
+                (None, _) => {}
+            }
+            if quote.is_none() && !c.is_ascii_whitespace() {
+                after_equals = c == '=';
+            }
+        }
+        let Some(end) = end else {
+            // Unterminated tag, nothing after it can be split safely.
+            result.retain(|i| *i < start);
+            break;
+        };
+        i = end;
+
+        let kind = ElementKind::for_html_tag(name);
+        let unparsed = matches!(kind, ElementKind::RawText | ElementKind::EscapableRawText)
+            || ["iframe", "noscript", "plaintext"]
+                .iter()
+                .any(|n| n.eq_ignore_ascii_case(name));
+        if unparsed {
+            let close = html[i..].match_indices("</").find(|(j, _)| {
+                (html[i + j + 2..].get(..name.len())).is_some_and(|n| n.eq_ignore_ascii_case(name))
+            });
+            match close {
+                Some((j, _)) => i += j + 2,
+                None => break,
+            }
+        }
+    }
+    result
+}
+
+/// Split a line at breakable spaces (see [`breakable_spaces`]) so that the
+/// parts are at most `max` bytes long, if possible. The spaces themselves are
+/// removed.
+fn chunk<'a>(line: &'a str, offset: usize, spaces: &[usize], max: usize) -> Vec<&'a str> {
+    let mut chunks = vec![];
+    let mut rest_start = 0;
+    let first = spaces.partition_point(|i| *i < offset);
+    let mut spaces = spaces[first..]
+        .iter()
+        .map(|i| i - offset)
+        .take_while(|i| *i < line.len())
+        .peekable();
+    while line.len() - rest_start > max {
+        let mut split = None;
+        while let Some(&i) = spaces.peek() {
+            if split.is_some() && i - rest_start > max {
+                break;
+            }
+            split = Some(i);
+            spaces.next();
+        }
+        let Some(i) = split else { break };
+        chunks.push(&line[rest_start..i]);
+        rest_start = i + 1;
+    }
+    chunks.push(&line[rest_start..]);
+    chunks
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(event) = &self.event {
+            writeln!(f, "event: {event}")?;
+        }
+        if let Some(id) = &self.id {
+            writeln!(f, "id: {id}")?;
+        }
+        if let Some(retry) = self.retry {
+            writeln!(f, "retry: {retry}")?;
+        }
+
+        // CRLF, CR and LF all end a line
+        // https://html.spec.whatwg.org/multipage/server-sent-events.html#parsing-an-event-stream
+        let data = self.data.replace("\r\n", "\n").replace('\r', "\n");
+        let spaces = match self.max_line_length {
+            Some(_) => breakable_spaces(&data),
+            None => vec![],
+        };
+        let mut offset = 0;
+        for line in data.split('\n') {
+            match self.max_line_length {
+                Some(max) => {
+                    for chunk in chunk(line, offset, &spaces, max) {
+                        writeln!(f, "data: {chunk}")?;
+                    }
+                }
+                None => writeln!(f, "data: {line}")?,
+            }
+            offset += line.len() + 1;
+        }
+
+        writeln!(f)
+    }
+}
+
+/// A sequence of [`Event`]s, rendered one after another.
+///
+/// With the `axum` feature, this type implements `IntoResponse` and is sent
+/// with the `text/event-stream` content type. The response contains exactly
+/// these events and then ends, so the client reconnects after the
+/// reconnection time (see [`Event::retry`]). For a connection that stays open
+/// and sends events as they happen, send the [`Display`](fmt::Display) output
+/// of each [`Event`] as its own frame of a streaming body instead.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventStream(pub Vec<Event>);
+
+impl EventStream {
+    /// Create a new empty stream.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an event to the stream.
+    pub fn with(mut self, event: Event) -> Self {
+        self.0.push(event);
+        self
+    }
+}
+
+impl fmt::Display for EventStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for event in &self.0 {
+            write!(f, "{event}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+use std::{borrow::Cow, fmt};
+
+use crate::{Content, Element, ElementKind, Render, RenderOptions, Result};
+
+/// A single bit of [`StaticElement`] content, see [`Content`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StaticContent {
+    /// A raw string to be rendered without any checks, see [`Content::Raw`].
+    ///
+    /// # Warning
+    ///
+    /// This is an escape hatch for including arbitrary text. Using it
+    /// incorrectly may result in security vulnerabilities in the rendered HTML.
+    #[cfg_attr(
+        feature = "deprecate-raw",
+        deprecated = "raw content is disallowed by the `deprecate-raw` feature of el"
+    )]
+    Raw(&'static str),
+    /// Plain text, see [`Content::Text`].
+    Text(&'static str),
+    /// An HTML comment, see [`Content::Comment`].
+    Comment(&'static str),
+    /// A child [`StaticElement`].
+    Element(StaticElement),
+}
+
+#[allow(deprecated)]
+impl From<StaticContent> for Content {
+    fn from(value: StaticContent) -> Self {
+        match value {
+            StaticContent::Raw(str) => Self::raw(str),
+            StaticContent::Text(str) => Self::text(str),
+            StaticContent::Comment(str) => Self::comment(str),
+            StaticContent::Element(element) => Self::Element(element.to_element()),
+        }
+    }
+}
+
+/// An element that can be constructed in `const` contexts.
+///
+/// Fixed fragments like footers or navigation bars can be defined as `static`
+/// or `const` items without any startup cost. They are converted to an
+/// [`Element`] when used as an [`crate::ElementComponent`], or rendered
+/// directly via [`Render`].
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, StaticContent::*, StaticElement, html::*};
+///
+/// static FOOTER: StaticElement = StaticElement::normal("footer")
+///     .with_attributes(&[("class", "site-footer")])
+///     .with_children(&[
+///         Text("© 2024 ACME & Co. "),
+///         Element(
+///             StaticElement::normal("a")
+///                 .with_attributes(&[("href", "/imprint")])
+///                 .with_children(&[Text("Imprint")]),
+///         ),
+///     ]);
+///
+/// let page = body((main("Content"), FOOTER));
+/// assert_eq!(
+///     page.render_to_string().unwrap(),
+///     concat!(
+///         r#"<body><main>Content</main><footer class="site-footer">"#,
+///         r#"© 2024 ACME &amp; Co. <a href="/imprint">Imprint</a></footer></body>"#,
+///     ),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaticElement {
+    /// The tag name of the element, see [`Element::name`].
+    pub name: &'static str,
+    /// What kind of element this is, see [`Element::kind`].
+    ///
+    /// # Warning
+    ///
+    /// The element kind affects the correctness of the rendered output.
+    /// Choosing an incorrect kind may result in security vulnerabilities in the
+    /// rendered HTML. See [`ElementKind`] for more details.
+    pub kind: ElementKind,
+    /// The attributes of the element as name-value pairs, see
+    /// [`Element::attributes`].
+    ///
+    /// Boolean attributes have an empty value. If a name appears multiple
+    /// times, the last value is used.
+    pub attributes: &'static [(&'static str, &'static str)],
+    /// The children of the element, see [`Element::children`].
+    pub children: &'static [StaticContent],
+}
+
+impl StaticElement {
+    /// Create a new element without attributes or children, see
+    /// [`Element::new`].
+    ///
+    /// # Warning
+    ///
+    /// The element kind affects the correctness of the rendered output.
+    /// Choosing an incorrect kind may result in security vulnerabilities in the
+    /// rendered HTML. See [`ElementKind`] for more details.
+    pub const fn new(name: &'static str, kind: ElementKind) -> Self {
+        Self {
+            name,
+            kind,
+            attributes: &[],
+            children: &[],
+        }
+    }
+
+    /// Create a new element of the kind [`ElementKind::Normal`], see
+    /// [`Element::normal`].
+    pub const fn normal(name: &'static str) -> Self {
+        Self::new(name, ElementKind::Normal)
+    }
+
+    /// Replace the element's attributes.
+    pub const fn with_attributes(
+        mut self,
+        attributes: &'static [(&'static str, &'static str)],
+    ) -> Self {
+        self.attributes = attributes;
+        self
+    }
+
+    /// Replace the element's children.
+    pub const fn with_children(mut self, children: &'static [StaticContent]) -> Self {
+        self.children = children;
+        self
+    }
+
+    /// Convert the element and its descendants into an [`Element`].
+    ///
+    /// Attribute names are borrowed, so only values and text are copied.
+    #[cfg_attr(feature = "track-caller", track_caller)]
+    pub fn to_element(&self) -> Element {
+        let mut element = Element::new(self.name, self.kind);
+        for (name, value) in self.attributes {
+            (element.attributes).insert(Cow::Borrowed(*name), value.to_string());
+        }
+        element.children = self.children.iter().map(|c| (*c).into()).collect();
+        element
+    }
+}
+
+impl From<StaticElement> for Element {
+    fn from(value: StaticElement) -> Self {
+        value.to_element()
+    }
+}
+
+impl From<&StaticElement> for Element {
+    fn from(value: &StaticElement) -> Self {
+        value.to_element()
+    }
+}
+
+impl From<StaticElement> for Content {
+    fn from(value: StaticElement) -> Self {
+        Self::Element(value.to_element())
+    }
+}
+
+impl From<&StaticElement> for Content {
+    fn from(value: &StaticElement) -> Self {
+        Self::Element(value.to_element())
+    }
+}
+
+/// Renders the element by converting it to an [`Element`] first.
+impl Render for StaticElement {
+    fn render<W: fmt::Write>(&self, w: &mut W) -> Result<()> {
+        self.to_element().render(w)
+    }
+
+    fn render_with<W: fmt::Write>(&self, w: &mut W, options: &RenderOptions) -> Result<()> {
+        self.to_element().render_with(w, options)
+    }
+}
@@ -0,0 +1,170 @@
+//! [Proptest] strategies for element trees.
+//!
+//! Useful for property-testing code that transforms or renders elements.
+//! [`element`] generates arbitrary trees that may fail to render, while
+//! [`valid_element`] only generates trees that render successfully with the
+//! default [`crate::RenderOptions`].
+//!
+//! [Element], [Content], [ElementKind], and [Attr] also implement proptest's
+//! [`Arbitrary`] trait using the strategies in this module.
+//!
+//! [Proptest]: https://docs.rs/proptest
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Render, strategy::valid_element};
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(element in valid_element())| {
+//!     prop_assert!(element.render_to_string().is_ok());
+//! });
+//! ```
+
+use proptest::{
+    collection::{btree_map, vec},
+    prelude::*,
+};
+
+use crate::{Attr, Content, Element, ElementKind};
+
+/// Tag names that are valid for all element kinds.
+pub fn tag_name() -> impl Strategy<Value = String> {
+    "[a-z][a-z0-9]{0,7}"
+}
+
+/// Attribute names that are always valid.
+pub fn attr_name() -> impl Strategy<Value = String> {
+    "[a-z][a-z0-9_-]{0,7}"
+}
+
+/// Any element kind.
+pub fn element_kind() -> impl Strategy<Value = ElementKind> {
+    prop_oneof![
+        Just(ElementKind::Void),
+        Just(ElementKind::Template),
+        Just(ElementKind::RawText),
+        Just(ElementKind::EscapableRawText),
+        Just(ElementKind::Foreign),
+        Just(ElementKind::Normal),
+    ]
+}
+
+/// Attributes with valid names and arbitrary values.
+pub fn attr() -> impl Strategy<Value = Attr> {
+    (attr_name(), any::<String>()).prop_map(|(name, value)| Attr::set(name, value))
+}
+
+/// Arbitrary non-element content.
+///
+/// Only [`Content::Raw`], [`Content::Text`] and [`Content::Comment`] are
+/// generated.
+#[allow(deprecated)]
+pub fn leaf_content() -> impl Strategy<Value = Content> {
+    prop_oneof![
+        any::<String>().prop_map(Content::Raw),
+        any::<String>().prop_map(Content::Text),
+        any::<String>().prop_map(Content::Comment),
+    ]
+}
+
+/// Arbitrary element trees.
+///
+/// Names, kinds, and children are chosen independently, so most generated
+/// trees fail to render.
+pub fn element() -> BoxedStrategy<Element> {
+    let leaf = (any::<String>(), element_kind()).prop_map(|(name, kind)| Element::new(name, kind));
+    leaf.prop_recursive(4, 32, 4, |inner| {
+        let child = prop_oneof![leaf_content(), inner.prop_map(Content::Element)];
+        (
+            any::<String>(),
+            element_kind(),
+            btree_map(any::<String>(), any::<String>(), 0..4),
+            vec(child, 0..4),
+        )
+            .prop_map(|(name, kind, attributes, children)| {
+                let mut element = Element::new(name, kind);
+                element.attributes = (attributes.into_iter())
+                    .map(|(name, value)| (name.into(), value))
+                    .collect();
+                element.children = children;
+                element
+            })
+    })
+    .boxed()
+}
+
+/// Arbitrary content, including elements from [`element`].
+pub fn content() -> BoxedStrategy<Content> {
+    prop_oneof![leaf_content(), element().prop_map(Content::Element)].boxed()
+}
+
+/// Element trees that render successfully with the default
+/// [`crate::RenderOptions`].
+pub fn valid_element() -> BoxedStrategy<Element> {
+    let attrs = || vec(attr(), 0..3);
+    let kind = || {
+        prop_oneof![
+            Just(ElementKind::Template),
+            Just(ElementKind::Foreign),
+            Just(ElementKind::Normal),
+        ]
+    };
+    let leaf = prop_oneof![
+        (tag_name(), attrs())
+            .prop_map(|(name, attrs)| Element::new(name, ElementKind::Void).with(attrs)),
+        (tag_name(), attrs(), "[^<]*").prop_map(|(name, attrs, text)| {
+            Element::new(name, ElementKind::RawText).with((attrs, text))
+        }),
+        (tag_name(), attrs(), any::<String>()).prop_map(|(name, attrs, text)| {
+            Element::new(name, ElementKind::EscapableRawText).with((attrs, text))
+        }),
+    ];
+    leaf.prop_recursive(4, 32, 4, move |inner| {
+        let child = prop_oneof![
+            any::<String>().prop_map(Content::Text),
+            any::<String>().prop_map(Content::Comment),
+            inner.prop_map(Content::Element),
+        ];
+        (tag_name(), kind(), attrs(), vec(child, 0..4)).prop_map(|(name, kind, attrs, children)| {
+            Element::new(name, kind).with((attrs, children))
+        })
+    })
+    .boxed()
+}
+
+impl Arbitrary for ElementKind {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        element_kind().boxed()
+    }
+}
+
+impl Arbitrary for Attr {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        attr().boxed()
+    }
+}
+
+impl Arbitrary for Content {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        content()
+    }
+}
+
+impl Arbitrary for Element {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        element()
+    }
+}
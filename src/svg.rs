@@ -1,6 +1,9 @@
 //! Definitions for all non-deprecated SVG elements
 //! ([MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element)).
 
+pub mod attr;
+pub mod sprite;
+
 use crate::{Element, ElementComponent, ElementKind};
 
 macro_rules! element {
@@ -10,8 +13,14 @@ macro_rules! element {
     ( $name:ident, $tag:expr ) => {
         #[doc = concat!("The `<", $tag, ">` tag")]
         #[doc = concat!("([MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/", $tag, ")).")]
+        #[cfg_attr(feature = "track-caller", track_caller)]
         pub fn $name(c: impl ElementComponent) -> Element {
-            Element::new($tag, ElementKind::Foreign).with(c)
+            let mut c = Some(c);
+            Element::new_with($tag, ElementKind::Foreign, &mut |element| {
+                if let Some(c) = c.take() {
+                    c.add_to_element(element);
+                }
+            })
         }
     };
 }
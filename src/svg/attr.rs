@@ -0,0 +1,365 @@
+//! Definitions for SVG attributes.
+//!
+//! SVG attribute names are case-sensitive and several of them use camel case
+//! (e.g. `viewBox`). The functions and types in this module always use the
+//! correct name, and their values are formatted the same way regardless of
+//! platform or locale (see [`Length`]).
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Length, Render, svg::{self, attr}};
+//!
+//! let icon = svg::svg((
+//!     attr::xmlns(),
+//!     attr::view_box(0.0, 0.0, 24.0, 24.0),
+//!     attr::width(Length::Em(1.0)),
+//!     svg::circle((attr::cx(12), attr::cy(12), attr::r(10), attr::fill("none"))),
+//!     svg::path((
+//!         attr::d("M7 12h10"),
+//!         attr::stroke("currentColor"),
+//!         attr::stroke_width(2),
+//!         attr::stroke_linecap("round"),
+//!     )),
+//! ));
+//! assert_eq!(
+//!     icon.render_to_string().unwrap(),
+//!     concat!(
+//!         r#"<svg viewBox="0 0 24 24" width="1em" xmlns="http://www.w3.org/2000/svg">"#,
+//!         r#"<circle cx="12" cy="12" fill="none" r="10" />"#,
+//!         r#"<path d="M7 12h10" stroke="currentColor" stroke-linecap="round" stroke-width="2" />"#,
+//!         "</svg>",
+//!     ),
+//! );
+//! ```
+
+use std::fmt;
+
+use crate::{length::Number, Attr, Element, ElementComponent, Length};
+
+/// The SVG namespace, used as the value of the `xmlns` attribute.
+pub const NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+macro_rules! attr_string {
+    ( $( $name:ident => $actual:expr; )* ) => {
+        $(
+            #[doc = concat!("Create (or replace) a `", $actual, "` attribute")]
+            #[doc = concat!("([MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/", $actual, ")).")]
+            pub fn $name(value: impl ToString) -> Attr {
+                Attr::set_static($actual, value)
+            }
+        )*
+    };
+}
+
+macro_rules! attr_length {
+    ( $( $name:ident => $actual:expr; )* ) => {
+        $(
+            #[doc = concat!("Create (or replace) a `", $actual, "` attribute")]
+            #[doc = concat!("([MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/", $actual, ")).")]
+            ///
+            /// The value is formatted like a [`Length`].
+            pub fn $name(value: impl Into<Length>) -> Attr {
+                Attr::set_static($actual, value.into())
+            }
+        )*
+    };
+}
+
+macro_rules! attr_number {
+    ( $( $name:ident => $actual:expr; )* ) => {
+        $(
+            #[doc = concat!("Create (or replace) a `", $actual, "` attribute")]
+            #[doc = concat!("([MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/", $actual, ")).")]
+            ///
+            /// The value is formatted like a [`Length`] without unit.
+            pub fn $name(value: f64) -> Attr {
+                Attr::set_static($actual, Number(value, 4))
+            }
+        )*
+    };
+}
+
+// SVG attributes A to Z
+
+attr_length! {
+    cx => "cx";
+    cy => "cy";
+}
+
+attr_string! {
+    d => "d";
+    fill => "fill";
+    fill_rule => "fill-rule";
+}
+
+attr_number! {
+    fill_opacity => "fill-opacity";
+}
+
+attr_length! {
+    height => "height";
+}
+
+attr_string! {
+    href => "href";
+}
+
+attr_number! {
+    opacity => "opacity";
+}
+
+attr_string! {
+    points => "points";
+}
+
+attr_length! {
+    r => "r";
+    rx => "rx";
+    ry => "ry";
+}
+
+attr_string! {
+    stroke => "stroke";
+    stroke_dasharray => "stroke-dasharray";
+    stroke_linecap => "stroke-linecap";
+    stroke_linejoin => "stroke-linejoin";
+}
+
+attr_number! {
+    stroke_opacity => "stroke-opacity";
+}
+
+attr_length! {
+    stroke_width => "stroke-width";
+}
+
+attr_string! {
+    transform => "transform";
+}
+
+/// Create (or replace) a `viewBox` attribute
+/// ([MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/viewBox)).
+///
+/// This is a shorthand for [`ViewBox::new`].
+pub fn view_box(min_x: f64, min_y: f64, width: f64, height: f64) -> ViewBox {
+    ViewBox::new(min_x, min_y, width, height)
+}
+
+attr_length! {
+    width => "width";
+    x => "x";
+    x1 => "x1";
+    x2 => "x2";
+}
+
+/// Create (or replace) an `xmlns` attribute with the SVG [`NAMESPACE`].
+///
+/// Browsers don't need it for SVG inside HTML, but standalone `.svg` files
+/// and SVG used as an image via a data URL do.
+pub fn xmlns() -> Attr {
+    Attr::set_static("xmlns", NAMESPACE)
+}
+
+attr_length! {
+    y => "y";
+    y1 => "y1";
+    y2 => "y2";
+}
+
+/// Create (or replace) a `viewBox` attribute
+/// ([MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/viewBox)).
+///
+/// The view box is the rectangle in user space that is mapped to the bounds
+/// of the viewport, given by its top left corner and its size. A negative
+/// width or height is an error, and a width or height of zero disables
+/// rendering of the element.
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, svg::{self, attr::ViewBox}};
+///
+/// let svg = svg::svg(ViewBox::new(0.0, 0.0, 24.0, 24.0));
+/// assert_eq!(svg.render_to_string().unwrap(), r#"<svg viewBox="0 0 24 24" />"#);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewBox {
+    /// The x coordinate of the top left corner.
+    pub min_x: f64,
+    /// The y coordinate of the top left corner.
+    pub min_y: f64,
+    /// The width of the rectangle.
+    pub width: f64,
+    /// The height of the rectangle.
+    pub height: f64,
+}
+
+impl ViewBox {
+    /// Create a view box from its top left corner and its size.
+    pub fn new(min_x: f64, min_y: f64, width: f64, height: f64) -> Self {
+        Self {
+            min_x,
+            min_y,
+            width,
+            height,
+        }
+    }
+
+    /// Create a view box with its top left corner at the origin.
+    pub fn size(width: f64, height: f64) -> Self {
+        Self::new(0.0, 0.0, width, height)
+    }
+}
+
+impl fmt::Display for ViewBox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            Number(self.min_x, 4),
+            Number(self.min_y, 4),
+            Number(self.width, 4),
+            Number(self.height, 4),
+        )
+    }
+}
+
+impl ElementComponent for ViewBox {
+    fn add_to_element(self, element: &mut Element) {
+        Attr::set_static("viewBox", self).add_to_element(element);
+    }
+}
+
+/// How the view box is aligned inside the viewport, see
+/// [`PreserveAspectRatio`].
+///
+/// The name of each variant gives the alignment along the x and y axis
+/// separately. For example, [`Self::XMidYMin`] centers the view box
+/// horizontally and aligns it with the top of the viewport.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Align {
+    /// Don't preserve the aspect ratio, but stretch the view box to fill the
+    /// viewport.
+    None,
+    /// Align with the left and top edges.
+    XMinYMin,
+    /// Center horizontally, align with the top edge.
+    XMidYMin,
+    /// Align with the right and top edges.
+    XMaxYMin,
+    /// Align with the left edge, center vertically.
+    XMinYMid,
+    /// Center both horizontally and vertically.
+    #[default]
+    XMidYMid,
+    /// Align with the right edge, center vertically.
+    XMaxYMid,
+    /// Align with the left and bottom edges.
+    XMinYMax,
+    /// Center horizontally, align with the bottom edge.
+    XMidYMax,
+    /// Align with the right and bottom edges.
+    XMaxYMax,
+}
+
+impl fmt::Display for Align {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::None => "none",
+            Self::XMinYMin => "xMinYMin",
+            Self::XMidYMin => "xMidYMin",
+            Self::XMaxYMin => "xMaxYMin",
+            Self::XMinYMid => "xMinYMid",
+            Self::XMidYMid => "xMidYMid",
+            Self::XMaxYMid => "xMaxYMid",
+            Self::XMinYMax => "xMinYMax",
+            Self::XMidYMax => "xMidYMax",
+            Self::XMaxYMax => "xMaxYMax",
+        })
+    }
+}
+
+/// Whether the view box is scaled to fit inside or to cover the viewport, see
+/// [`PreserveAspectRatio`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum MeetOrSlice {
+    /// Scale the view box as large as possible while keeping it entirely
+    /// visible.
+    #[default]
+    Meet,
+    /// Scale the view box as small as possible while covering the entire
+    /// viewport, cutting off parts of it.
+    Slice,
+}
+
+impl fmt::Display for MeetOrSlice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Meet => "meet",
+            Self::Slice => "slice",
+        })
+    }
+}
+
+/// Create (or replace) a `preserveAspectRatio` attribute
+/// ([MDN](https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/preserveAspectRatio)).
+///
+/// The default value centers the view box and scales it to fit inside the
+/// viewport, which is also what browsers do without the attribute.
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, svg::{self, attr::{Align, MeetOrSlice, PreserveAspectRatio, ViewBox}}};
+///
+/// let svg = svg::svg((
+///     ViewBox::size(100.0, 50.0),
+///     PreserveAspectRatio::new(Align::XMidYMin, MeetOrSlice::Slice),
+/// ));
+/// assert_eq!(
+///     svg.render_to_string().unwrap(),
+///     r#"<svg preserveAspectRatio="xMidYMin slice" viewBox="0 0 100 50" />"#,
+/// );
+///
+/// assert_eq!(PreserveAspectRatio::none().to_string(), "none");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PreserveAspectRatio {
+    /// How to align the view box.
+    pub align: Align,
+    /// How to scale the view box. Ignored if [`Self::align`] is
+    /// [`Align::None`].
+    pub meet_or_slice: MeetOrSlice,
+}
+
+impl PreserveAspectRatio {
+    /// Create a value from an alignment and a scaling mode.
+    pub fn new(align: Align, meet_or_slice: MeetOrSlice) -> Self {
+        Self {
+            align,
+            meet_or_slice,
+        }
+    }
+
+    /// Stretch the view box to fill the viewport, ignoring its aspect ratio.
+    pub fn none() -> Self {
+        Self::new(Align::None, MeetOrSlice::Meet)
+    }
+}
+
+impl fmt::Display for PreserveAspectRatio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.align, self.meet_or_slice) {
+            (Align::None, _) => write!(f, "none"),
+            (align, MeetOrSlice::Meet) => write!(f, "{align}"),
+            (align, meet_or_slice) => write!(f, "{align} {meet_or_slice}"),
+        }
+    }
+}
+
+impl ElementComponent for PreserveAspectRatio {
+    fn add_to_element(self, element: &mut Element) {
+        Attr::set_static("preserveAspectRatio", self).add_to_element(element);
+    }
+}
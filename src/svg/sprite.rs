@@ -0,0 +1,202 @@
+//! SVG sprite sheets for icons.
+//!
+//! A [`SpriteSheet`] holds named icons. Components reference icons using
+//! [`SpriteSheet::icon`], which creates a small `<svg>` pointing at a
+//! `<symbol>` via `<use>`. Once the page is assembled,
+//! [`SpriteSheet::inject`] adds a single hidden sprite `<svg>` to the
+//! document containing every icon the page references exactly once, no
+//! matter how many components use it.
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Render, html::*, svg::{self, sprite::SpriteSheet}};
+//!
+//! let icons = SpriteSheet::new()
+//!     .add("check", "0 0 24 24", svg::path(el::Attr::set("d", "M5 12l5 5L20 7")))
+//!     .add("cross", "0 0 24 24", svg::path(el::Attr::set("d", "M6 6l12 12M18 6L6 18")));
+//!
+//! let list = ul((
+//!     li((icons.icon("check"), "Done")),
+//!     li((icons.icon("check"), "Also done")),
+//! ));
+//! let mut page = html(body(list)).into_document();
+//! icons.inject(&mut page);
+//!
+//! assert_eq!(
+//!     page.render_to_string().unwrap(),
+//!     concat!(
+//!         "<!DOCTYPE html><html><body>",
+//!         r#"<svg aria-hidden="true" style="display: none">"#,
+//!         r#"<symbol id="icon-check" viewBox="0 0 24 24"><path d="M5 12l5 5L20 7" /></symbol>"#,
+//!         "</svg>",
+//!         r##"<ul><li><svg aria-hidden="true" class="icon"><use href="#icon-check" /></svg>Done</li>"##,
+//!         r##"<li><svg aria-hidden="true" class="icon"><use href="#icon-check" /></svg>Also done</li></ul>"##,
+//!         "</body></html>",
+//!     ),
+//! );
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    svg::{r#use, svg, symbol},
+    Attr, Content, Document, Element, ElementComponent,
+};
+
+/// A set of named icons, see the [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct SpriteSheet {
+    prefix: String,
+    icons: BTreeMap<String, Element>,
+}
+
+impl Default for SpriteSheet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpriteSheet {
+    /// Create a new empty sprite sheet whose symbol ids start with `icon-`.
+    pub fn new() -> Self {
+        Self::with_prefix("icon-")
+    }
+
+    /// Create a new empty sprite sheet whose symbol ids start with a custom
+    /// prefix, e.g. to avoid conflicts with other ids on the page.
+    pub fn with_prefix(prefix: impl ToString) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+            icons: BTreeMap::new(),
+        }
+    }
+
+    /// Register an icon in-place, replacing any previous icon with the same
+    /// name.
+    ///
+    /// The content is usually a few `<path>` elements, drawn in a coordinate
+    /// system defined by the `viewBox`.
+    pub fn insert(&mut self, name: impl ToString, view_box: &str, content: impl ElementComponent) {
+        let name = name.to_string();
+        let symbol = symbol((
+            Attr::set("id", self.id(&name)),
+            Attr::set("viewBox", view_box),
+            content,
+        ));
+        self.icons.insert(name, symbol);
+    }
+
+    /// A more builder-pattern-like version of [`Self::insert`].
+    pub fn add(
+        mut self,
+        name: impl ToString,
+        view_box: &str,
+        content: impl ElementComponent,
+    ) -> Self {
+        self.insert(name, view_box, content);
+        self
+    }
+
+    /// Register an existing `<svg>` element as an icon, keeping its
+    /// `viewBox` and children.
+    pub fn insert_svg(&mut self, name: impl ToString, svg: Element) {
+        let (_, _, mut attributes, children) = svg.into_parts();
+        let view_box = attributes.remove("viewBox").unwrap_or_default();
+        self.insert(name, &view_box, children);
+    }
+
+    /// The `id` of the symbol for an icon.
+    pub fn id(&self, name: &str) -> String {
+        format!("{}{name}", self.prefix)
+    }
+
+    /// Whether an icon with the given name was registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.icons.contains_key(name)
+    }
+
+    /// A reference to an icon, to be used wherever the icon should appear.
+    ///
+    /// The reference is an `<svg class="icon">` hidden from assistive
+    /// technology, so add a text alternative nearby if the icon conveys
+    /// meaning. Further attributes (e.g. `width`) can be added using
+    /// [`Element::with`].
+    ///
+    /// Returns [`None`] if no icon with that name was registered.
+    pub fn icon(&self, name: &str) -> Option<Element> {
+        if !self.contains(name) {
+            return None;
+        }
+        Some(svg((
+            Attr::set("aria-hidden", "true"),
+            Attr::set("class", "icon"),
+            r#use(Attr::set("href", format!("#{}", self.id(name)))),
+        )))
+    }
+
+    /// The names of all icons referenced by an element and its descendants.
+    pub fn used(&self, element: &Element) -> BTreeSet<String> {
+        let mut used = BTreeSet::new();
+        self.collect_used(element, &mut used);
+        used
+    }
+
+    fn collect_used(&self, element: &Element, used: &mut BTreeSet<String>) {
+        if element.name == "use" {
+            let href = element.get_attr("href").or(element.get_attr("xlink:href"));
+            let name = href
+                .and_then(|href| href.strip_prefix('#'))
+                .and_then(|id| id.strip_prefix(&self.prefix[..]))
+                .filter(|name| self.contains(name));
+            if let Some(name) = name {
+                used.insert(name.to_string());
+            }
+        }
+        for child in &element.children {
+            if let Content::Element(child) = child {
+                self.collect_used(child, used);
+            }
+        }
+    }
+
+    /// A hidden `<svg>` containing the symbols for the given icons.
+    ///
+    /// Names that were not registered are ignored.
+    pub fn sprite(&self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Element {
+        let names = (names.into_iter())
+            .map(|name| name.as_ref().to_string())
+            .collect::<BTreeSet<_>>();
+        let symbols = (names.iter())
+            .filter_map(|name| self.icons.get(name).cloned())
+            .collect::<Vec<_>>();
+        svg((
+            Attr::set("aria-hidden", "true"),
+            Attr::set("style", "display: none"),
+            symbols,
+        ))
+    }
+
+    /// Add a sprite containing all icons referenced by a document to the
+    /// start of its `<body>`, or the start of the document if it has no
+    /// `<body>`.
+    ///
+    /// Nothing is added if the document doesn't reference any icons.
+    pub fn inject(&self, document: &mut Document) {
+        let used = self.used(&document.0);
+        if used.is_empty() {
+            return;
+        }
+        let sprite = self.sprite(used);
+
+        for child in &mut document.0.children {
+            if let Content::Element(body) = child {
+                if body.name == "body" {
+                    body.children.insert(0, Content::Element(sprite));
+                    return;
+                }
+            }
+        }
+        document.0.children.insert(0, Content::Element(sprite));
+    }
+}
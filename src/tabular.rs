@@ -0,0 +1,171 @@
+//! Rendering structs as tables and definition lists.
+//!
+//! [`ToHtmlTable`] turns a list of structs into a `<table>` with one row per
+//! struct and one column per field. [`ToDl`] turns a single struct into a
+//! `<dl>` with one entry per field. Both are useful for admin backends and
+//! debug pages, and can be derived using the `derive` feature.
+//!
+//! Field values are converted into cells using [`ToCell`]. The derive macros
+//! support these attributes on fields:
+//!
+//! - `#[el(rename = "...")]` sets the column header or term. By default, it is
+//!   the field name with underscores replaced by spaces and the first letter in
+//!   uppercase (`user_id` becomes `User id`).
+//! - `#[el(skip)]` omits the field.
+//! - `#[el(with = "path")]` converts the field using a function that takes a
+//!   reference to the field and returns an [`ElementComponent`] instead of
+//!   using [`ToCell`].
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "derive")] {
+//! use el::{Render, html::*, tabular::{ToDl, ToHtmlTable, html_table}};
+//!
+//! #[derive(ToHtmlTable, ToDl)]
+//! struct User {
+//!     #[el(rename = "ID")]
+//!     id: u32,
+//!     display_name: String,
+//!     #[el(with = "yes_no")]
+//!     admin: bool,
+//!     #[el(skip)]
+//!     password_hash: String,
+//! }
+//!
+//! fn yes_no(value: &bool) -> &'static str {
+//!     if *value { "yes" } else { "no" }
+//! }
+//!
+//! let users = vec![
+//!     User { id: 1, display_name: "Alice".into(), admin: true, password_hash: "...".into() },
+//!     User { id: 2, display_name: "Bob".into(), admin: false, password_hash: "...".into() },
+//! ];
+//!
+//! assert_eq!(
+//!     html_table(&users).render_to_string().unwrap(),
+//!     concat!(
+//!         "<table><thead><tr>",
+//!         r#"<th scope="col">ID</th><th scope="col">Display name</th><th scope="col">Admin</th>"#,
+//!         "</tr></thead><tbody>",
+//!         "<tr><td>1</td><td>Alice</td><td>yes</td></tr>",
+//!         "<tr><td>2</td><td>Bob</td><td>no</td></tr>",
+//!         "</tbody></table>",
+//!     ),
+//! );
+//!
+//! assert_eq!(
+//!     users[1].to_dl().render_to_string().unwrap(),
+//!     "<dl><dt>ID</dt><dd>2</dd><dt>Display name</dt><dd>Bob</dd><dt>Admin</dt><dd>no</dd></dl>",
+//! );
+//! # }
+//! ```
+
+use crate::{
+    html::{dl, table, tbody, thead, tr},
+    Content, Element,
+};
+
+#[cfg(doc)]
+use crate::ElementComponent;
+
+#[cfg(feature = "derive")]
+pub use el_derive::{ToDl, ToHtmlTable};
+
+/// A value that can be displayed in a table cell or definition list, see the
+/// [module documentation](self).
+///
+/// Strings, characters, numbers and booleans are displayed as text.
+/// [`None`] results in an empty cell.
+pub trait ToCell {
+    /// Convert the value into cell content.
+    fn to_cell(&self) -> Content;
+}
+
+impl<T: ToCell + ?Sized> ToCell for &T {
+    fn to_cell(&self) -> Content {
+        (**self).to_cell()
+    }
+}
+
+impl<T: ToCell> ToCell for Option<T> {
+    fn to_cell(&self) -> Content {
+        match self {
+            Some(value) => value.to_cell(),
+            None => Content::text(""),
+        }
+    }
+}
+
+impl ToCell for Content {
+    fn to_cell(&self) -> Content {
+        self.clone()
+    }
+}
+
+impl ToCell for Element {
+    fn to_cell(&self) -> Content {
+        Content::Element(self.clone())
+    }
+}
+
+macro_rules! to_cell_via_to_string {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl ToCell for $t {
+                fn to_cell(&self) -> Content {
+                    Content::text(self)
+                }
+            }
+        )*
+    };
+}
+
+to_cell_via_to_string!(str, String, char, bool);
+to_cell_via_to_string!(i8, i16, i32, i64, i128, isize);
+to_cell_via_to_string!(u8, u16, u32, u64, u128, usize);
+to_cell_via_to_string!(f32, f64);
+
+/// A struct that can be displayed as a row of a table, see the [module
+/// documentation](self).
+pub trait ToHtmlTable {
+    /// The header cells (`<th>`), one per column.
+    fn table_header() -> Vec<Element>;
+
+    /// The cells (`<td>`) of this struct's row, one per column.
+    fn table_cells(&self) -> Vec<Element>;
+
+    /// This struct's row (`<tr>`).
+    fn table_row(&self) -> Element {
+        tr(self.table_cells())
+    }
+
+    /// A table containing only this struct's row, see [`html_table`].
+    fn to_html_table(&self) -> Element
+    where
+        Self: Sized,
+    {
+        html_table([self])
+    }
+}
+
+/// Create a `<table>` with a header row and one row per item.
+pub fn html_table<'a, T: ToHtmlTable + 'a>(items: impl IntoIterator<Item = &'a T>) -> Element {
+    let rows = items.into_iter().map(T::table_row).collect::<Vec<_>>();
+    table((thead(tr(T::table_header())), tbody(rows)))
+}
+
+/// A struct that can be displayed as a definition list, see the [module
+/// documentation](self).
+pub trait ToDl {
+    /// The term (`<dt>`) and description (`<dd>`) for each field.
+    fn dl_entries(&self) -> Vec<(Element, Element)>;
+
+    /// A `<dl>` containing all entries.
+    fn to_dl(&self) -> Element {
+        let children = (self.dl_entries().into_iter())
+            .flat_map(|(term, description)| [term, description])
+            .collect::<Vec<_>>();
+        dl(children)
+    }
+}
@@ -0,0 +1,120 @@
+//! Working with the contents of `<template>` elements
+//! ([MDN](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/template)).
+//!
+//! The children of a template are inert. Browsers parse them into a separate
+//! document fragment, and they only take effect once the fragment is cloned
+//! into the page. This module does the same on the server: it extracts the
+//! contents of a template and instantiates them with [`Slots`] filled in.
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Render, html::*, template::Slots};
+//!
+//! let card = template(div((
+//!     h2(slot((attr::name("title"), "Untitled"))),
+//!     slot(()),
+//! )));
+//!
+//! let slots = Slots::new().fill("title", "Hello").fill_default(p("World"));
+//! let instance = card.instantiate(&slots).unwrap();
+//! assert_eq!(
+//!     instance.render_to_string().unwrap(),
+//!     "<div><h2>Hello</h2><p>World</p></div>",
+//! );
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{Content, Element, ElementKind};
+
+/// Content used to fill the `<slot>` elements of a template, see
+/// [`Element::instantiate`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Slots {
+    named: HashMap<String, Vec<Content>>,
+    default: Option<Vec<Content>>,
+}
+
+impl Slots {
+    /// Create a new set of slots where no slot is filled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append content to the slot with the given name.
+    pub fn fill(mut self, name: impl ToString, content: impl Into<Content>) -> Self {
+        (self.named.entry(name.to_string()).or_default()).push(content.into());
+        self
+    }
+
+    /// Append content to the default slot, i.e. the `<slot>` without a name.
+    pub fn fill_default(mut self, content: impl Into<Content>) -> Self {
+        (self.default.get_or_insert_with(Vec::new)).push(content.into());
+        self
+    }
+
+    fn get(&self, name: Option<&str>) -> Option<&[Content]> {
+        match name {
+            Some(name) => self.named.get(name).map(|c| &c[..]),
+            None => self.default.as_deref(),
+        }
+    }
+}
+
+impl Element {
+    /// The inert contents of an element of the kind
+    /// [`ElementKind::Template`].
+    ///
+    /// Returns [`None`] for all other elements.
+    pub fn template_content(&self) -> Option<&[Content]> {
+        (self.kind == ElementKind::Template).then_some(&self.children[..])
+    }
+
+    /// Clone the contents of a template and replace its `<slot>` elements.
+    ///
+    /// Each `<slot>` is replaced by the content filled into the [`Slots`]
+    /// under its `name` attribute, or the default slot if it has no name. If
+    /// the slot is not filled, the children of the `<slot>` are used as
+    /// fallback content. Nested templates are left untouched.
+    ///
+    /// Returns [`None`] if the element is not of the kind
+    /// [`ElementKind::Template`].
+    pub fn instantiate(&self, slots: &Slots) -> Option<Vec<Content>> {
+        let content = self.template_content()?;
+        let mut result = vec![];
+        instantiate(content, slots, &mut result);
+        Some(result)
+    }
+}
+
+fn instantiate(content: &[Content], slots: &Slots, result: &mut Vec<Content>) {
+    for content in content {
+        let Content::Element(element) = content else {
+            result.push(content.clone());
+            continue;
+        };
+
+        if element.kind == ElementKind::Template {
+            result.push(content.clone());
+        } else if element.name == "slot" {
+            let name = element.attr_ci("name");
+            match slots.get(name) {
+                Some(filled) => result.extend(filled.iter().cloned()),
+                None => instantiate(&element.children, slots, result),
+            }
+        } else {
+            let mut children = vec![];
+            instantiate(&element.children, slots, &mut children);
+            result.push(Content::Element(Element {
+                name: element.name.clone(),
+                kind: element.kind,
+                attributes: element.attributes.clone(),
+                children,
+                label: element.label.clone(),
+                location: element.location,
+                whitespace: element.whitespace,
+            }));
+        }
+    }
+}
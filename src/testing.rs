@@ -0,0 +1,499 @@
+//! Golden file tests for rendered output.
+//!
+//! When migrating templates to el, the simplest way to make sure nothing
+//! changed is to compare the new output with the old one. [`Golden`] renders
+//! documents (or any other [`Render`] value) and compares them with files in a
+//! directory. Both sides are [`normalize`]d first, so line endings, attribute
+//! order and whitespace between tags don't cause spurious failures.
+//! Mismatches are reported as a line [`diff`].
+//!
+//! If the environment variable named by [`UPDATE_ENV`] is set (to anything
+//! but `0` or the empty string), the files are written instead of compared.
+//! This is how golden files are created initially and updated after
+//! intentional changes:
+//!
+//! ```text
+//! EL_UPDATE_GOLDEN=1 cargo test
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use el::{Document, html::*, testing::{Golden, GoldenError}};
+//!
+//! let dir = std::env::temp_dir().join(format!("el-golden-doc-{}", std::process::id()));
+//! let golden = Golden::new(&dir);
+//! let page = Document::from(html(body(h1("Hello"))));
+//!
+//! // Usually written by the migrated templates or with EL_UPDATE_GOLDEN=1
+//! golden.clone().update(true).check("index", &page).unwrap();
+//! assert!(dir.join("index.html").exists());
+//!
+//! // In the actual test, use `golden.assert("index", &page)` instead.
+//! golden.check("index", &page).unwrap();
+//!
+//! let changed = Document::from(html(body(h1("Goodbye"))));
+//! match golden.check("index", &changed) {
+//!     Err(GoldenError::Mismatch { diff, .. }) => {
+//!         assert!(diff.contains("-<h1>Hello</h1>"));
+//!         assert!(diff.contains("+<h1>Goodbye</h1>"));
+//!     }
+//!     _ => panic!("expected a mismatch"),
+//! }
+//! # std::fs::remove_dir_all(&dir).unwrap();
+//! ```
+
+use std::{
+    env, error, fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{text, ElementKind, Render, RenderOptions};
+
+/// The environment variable that makes [`Golden`] update files instead of
+/// comparing them.
+pub const UPDATE_ENV: &str = "EL_UPDATE_GOLDEN";
+
+/// Lines of unchanged context shown around each change in a [`diff`].
+const CONTEXT: usize = 2;
+
+/// The largest diff (number of expected lines times number of actual lines)
+/// that is computed line by line. Larger inputs are shown as fully replaced.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// An error that can occur while checking a golden file.
+#[derive(Debug)]
+pub enum GoldenError {
+    /// The value could not be rendered.
+    Render(crate::Error),
+    /// The golden file could not be read or written.
+    Io {
+        /// The path of the golden file.
+        path: PathBuf,
+        /// The underlying error.
+        error: io::Error,
+    },
+    /// The golden file doesn't exist yet.
+    Missing(PathBuf),
+    /// The rendered output differs from the golden file.
+    Mismatch {
+        /// The path of the golden file.
+        path: PathBuf,
+        /// A [`diff`] from the golden file to the rendered output.
+        diff: String,
+    },
+}
+
+impl fmt::Display for GoldenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Render(error) => write!(f, "failed to render: {error}"),
+            Self::Io { path, error } => write!(f, "failed to access {}: {error}", path.display()),
+            Self::Missing(path) => write!(
+                f,
+                "golden file {} is missing, set {UPDATE_ENV}=1 to create it",
+                path.display()
+            ),
+            Self::Mismatch { path, diff } => write!(
+                f,
+                "output differs from golden file {}, set {UPDATE_ENV}=1 to update it\n{diff}",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl error::Error for GoldenError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Render(error) => Some(error),
+            Self::Io { error, .. } => Some(error),
+            Self::Missing(_) | Self::Mismatch { .. } => None,
+        }
+    }
+}
+
+/// A directory of golden files.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug, Clone)]
+pub struct Golden {
+    dir: PathBuf,
+    options: RenderOptions,
+    update: bool,
+}
+
+impl Golden {
+    /// Use golden files in a directory, e.g.
+    /// `concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden")`.
+    ///
+    /// Values are rendered with [`RenderOptions::pretty`] and
+    /// [`RenderOptions::trailing_newline`] enabled, so the files are readable
+    /// and diffs are meaningful. Whether files are updated is determined by
+    /// [`UPDATE_ENV`].
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let update = env::var_os(UPDATE_ENV).is_some_and(|value| !value.is_empty() && value != "0");
+        Self {
+            dir: dir.into(),
+            options: RenderOptions::new().pretty(true).trailing_newline(true),
+            update,
+        }
+    }
+
+    /// Render values with custom [`RenderOptions`].
+    ///
+    /// When comparing with files produced by another tool, enabling
+    /// [`RenderOptions::uppercase_doctype`] may reduce the differences.
+    /// Formatting with [`RenderOptions::pretty`] doesn't matter since
+    /// whitespace between tags is [`normalize`]d.
+    pub fn options(mut self, options: RenderOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Set whether files are written instead of compared, overriding
+    /// [`UPDATE_ENV`].
+    pub fn update(mut self, update: bool) -> Self {
+        self.update = update;
+        self
+    }
+
+    /// The directory containing the golden files.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The path of the golden file with a name.
+    ///
+    /// Names may contain slashes to organize files in subdirectories. The
+    /// extension `.html` is appended.
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.html"))
+    }
+
+    /// Render a value and compare it with the golden file of that name, or
+    /// write the file if updating.
+    pub fn check(&self, name: &str, value: &impl Render) -> Result<(), GoldenError> {
+        let actual = value
+            .render_to_string_with(&self.options)
+            .map_err(GoldenError::Render)?;
+        let path = self.path(name);
+        let io_error = |error| GoldenError::Io {
+            path: path.clone(),
+            error,
+        };
+
+        if self.update {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(io_error)?;
+            }
+            return fs::write(&path, actual).map_err(io_error);
+        }
+
+        let expected = match fs::read_to_string(&path) {
+            Ok(expected) => expected,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                return Err(GoldenError::Missing(path));
+            }
+            Err(error) => return Err(io_error(error)),
+        };
+
+        let expected = normalize(&expected);
+        let actual = normalize(&actual);
+        if expected == actual {
+            Ok(())
+        } else {
+            let diff = diff(&expected, &actual);
+            Err(GoldenError::Mismatch { path, diff })
+        }
+    }
+
+    /// Like [`Self::check`], but panics with a readable message if the check
+    /// fails.
+    #[track_caller]
+    pub fn assert(&self, name: &str, value: &impl Render) {
+        if let Err(error) = self.check(name, value) {
+            panic!("{error}");
+        }
+    }
+}
+
+/// Normalize HTML for comparison.
+///
+/// Line endings are converted to `\n` and a leading byte order mark is removed.
+/// The attributes of start tags are sorted by name, separated by single spaces
+/// and their values quoted with `"` where possible. Whitespace between two tags
+/// becomes a single line break and indentation is removed, so documents
+/// formatted differently (e.g. with and without [`RenderOptions::pretty`])
+/// compare equal. The contents of elements like `<pre>`, `<textarea>` and
+/// `<script>` are kept as they are, except for trailing whitespace on every
+/// line. The text ends with exactly one line break unless it is empty.
+///
+/// # Example
+///
+/// ```
+/// use el::testing::normalize;
+///
+/// assert_eq!(normalize("\u{feff}<p>\r\n  Hi  \r\n</p>\n\n"), "<p>\nHi\n</p>\n");
+/// assert_eq!(
+///     normalize("<ul>\n  <li id=a class='x'>A</li>\n</ul>"),
+///     "<ul>\n<li class=\"x\" id=\"a\">A</li>\n</ul>\n",
+/// );
+/// assert_eq!(normalize("<ul><li>A</li></ul>"), normalize("<ul>\n  <li>A</li>\n</ul>"));
+/// assert_eq!(normalize("<pre>\n  a\n</pre>"), "<pre>\n  a\n</pre>\n");
+/// assert_eq!(normalize(" \n"), "");
+/// ```
+pub fn normalize(html: &str) -> String {
+    let html = html.strip_prefix('\u{feff}').unwrap_or(html);
+    let html = html.replace("\r\n", "\n").replace('\r', "\n");
+
+    let mut normalized = String::with_capacity(html.len());
+    let mut rest = &html[..];
+    while !rest.is_empty() {
+        let text_end = rest.find('<').unwrap_or(rest.len());
+        let (text, markup) = rest.split_at(text_end);
+        if text.trim().is_empty() {
+            if !normalized.is_empty() && !markup.is_empty() {
+                normalized.push('\n');
+            }
+        } else {
+            for (i, line) in text.split('\n').enumerate() {
+                if i > 0 {
+                    normalized.push('\n');
+                }
+                normalized.push_str(line.trim_start());
+            }
+        }
+        rest = markup;
+        if rest.is_empty() {
+            break;
+        }
+
+        let Some((tag, name, after)) = start_tag(rest) else {
+            // End tags, comments, doctypes and unterminated tags
+            let end = rest.find('>').map_or(rest.len(), |i| i + 1);
+            let end = match rest.starts_with("<!--") {
+                true => rest.find("-->").map_or(rest.len(), |i| i + 3),
+                false => end,
+            };
+            normalized.push_str(&rest[..end]);
+            rest = &rest[end..];
+            continue;
+        };
+        normalized.push_str(&tag);
+        rest = after;
+
+        let preserved = text::PREFORMATTED_ELEMENTS
+            .iter()
+            .any(|n| n.eq_ignore_ascii_case(name))
+            || matches!(
+                ElementKind::for_html_tag(name),
+                ElementKind::RawText | ElementKind::EscapableRawText
+            );
+        if preserved {
+            let end = rest
+                .match_indices("</")
+                .find(|(i, _)| {
+                    (rest[i + 2..].get(..name.len())).is_some_and(|n| n.eq_ignore_ascii_case(name))
+                })
+                .map_or(rest.len(), |(i, _)| i);
+            // Including the end tag, so no line break is added before it
+            let end = rest[end..].find('>').map_or(rest.len(), |i| end + i + 1);
+            normalized.push_str(&rest[..end]);
+            rest = &rest[end..];
+        }
+    }
+
+    let mut result = String::with_capacity(normalized.len());
+    for line in normalized.lines() {
+        result.push_str(line.trim_end());
+        result.push('\n');
+    }
+    let len = result.trim_end().len();
+    result.truncate(len);
+    if !result.is_empty() {
+        result.push('\n');
+    }
+    result
+}
+
+/// Parse a start tag at the beginning of `html` and write it with sorted
+/// attributes. Returns the normalized tag, the tag name and the rest of the
+/// input after the tag.
+fn start_tag(html: &str) -> Option<(String, &str, &str)> {
+    let rest = html.strip_prefix('<')?;
+    if !rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let name_end = rest
+        .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+        .unwrap_or(rest.len());
+    let (name, mut rest) = rest.split_at(name_end);
+
+    let mut attributes = vec![];
+    let self_closing = loop {
+        let trimmed = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == '/');
+        let skipped = &rest[..rest.len() - trimmed.len()];
+        rest = trimmed;
+        if let Some(after) = rest.strip_prefix('>') {
+            rest = after;
+            break skipped.ends_with('/');
+        }
+        if rest.is_empty() {
+            return None;
+        }
+
+        let name_end = rest
+            .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>' || c == '=')
+            .unwrap_or(rest.len())
+            .max(1);
+        let (attr_name, after) = rest.split_at(name_end);
+        rest = after;
+        let Some(after) = rest.trim_start().strip_prefix('=') else {
+            attributes.push((attr_name, String::from(attr_name)));
+            continue;
+        };
+        let after = after.trim_start();
+        let value_end = match after.chars().next() {
+            Some(quote @ ('"' | '\'')) => after[1..].find(quote)? + 2,
+            _ => after
+                .find(|c: char| c.is_ascii_whitespace() || c == '>')
+                .unwrap_or(after.len()),
+        };
+        let (value, after) = after.split_at(value_end);
+        let unquoted = match value.strip_prefix(['"', '\'']) {
+            Some(quoted) => &quoted[..quoted.len() - 1],
+            None => value,
+        };
+        let value = match unquoted.contains('"') {
+            true => value.to_string(),
+            false => format!("\"{unquoted}\""),
+        };
+        attributes.push((attr_name, format!("{attr_name}={value}")));
+        rest = after;
+    };
+
+    attributes.sort_by_key(|(name, _)| name.to_ascii_lowercase());
+    let mut tag = format!("<{name}");
+    for (_, attribute) in attributes {
+        tag.push(' ');
+        tag.push_str(&attribute);
+    }
+    if self_closing {
+        tag.push_str(" /");
+    }
+    tag.push('>');
+    Some((tag, name, rest))
+}
+
+/// A line-based diff from `expected` to `actual`.
+///
+/// Removed lines start with `-`, added lines with `+` and unchanged lines
+/// with a space. Only a few unchanged lines around each change are shown,
+/// skipped lines are marked with `...`. Identical inputs produce an empty
+/// diff.
+///
+/// # Example
+///
+/// ```
+/// use el::testing::diff;
+///
+/// assert_eq!(diff("a\nb\nc\n", "a\nB\nc\n"), " a\n-b\n+B\n c\n");
+/// assert_eq!(diff("a\n", "a\n"), "");
+/// ```
+pub fn diff(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+    let ops = diff_ops(&expected, &actual);
+    if ops.iter().all(|(op, _)| *op == ' ') {
+        return String::new();
+    }
+
+    // Mark unchanged lines close enough to a change to be shown.
+    let mut shown = vec![false; ops.len()];
+    for (i, (op, _)) in ops.iter().enumerate() {
+        if *op != ' ' {
+            let start = i.saturating_sub(CONTEXT);
+            let end = (i + CONTEXT + 1).min(ops.len());
+            shown[start..end].iter_mut().for_each(|s| *s = true);
+        }
+    }
+
+    let mut result = String::new();
+    let mut skipped = false;
+    for ((op, line), shown) in ops.iter().zip(shown) {
+        if shown {
+            skipped = false;
+            result.push(*op);
+            result.push_str(line);
+            result.push('\n');
+        } else if !skipped {
+            skipped = true;
+            result.push_str("...\n");
+        }
+    }
+    result
+}
+
+/// The lines of a diff, each with its operation (`' '`, `'-'` or `'+'`).
+fn diff_ops<'a>(expected: &[&'a str], actual: &[&'a str]) -> Vec<(char, &'a str)> {
+    // Unchanged lines at the start and end don't need the quadratic table.
+    let prefix = expected
+        .iter()
+        .zip(actual)
+        .take_while(|(e, a)| e == a)
+        .count();
+    let suffix = expected[prefix..]
+        .iter()
+        .rev()
+        .zip(actual[prefix..].iter().rev())
+        .take_while(|(e, a)| e == a)
+        .count();
+    let e = &expected[prefix..expected.len() - suffix];
+    let a = &actual[prefix..actual.len() - suffix];
+
+    let mut ops = expected[..prefix]
+        .iter()
+        .map(|line| (' ', *line))
+        .collect::<Vec<_>>();
+
+    if e.len().saturating_mul(a.len()) > MAX_DIFF_CELLS {
+        ops.extend(e.iter().map(|line| ('-', *line)));
+        ops.extend(a.iter().map(|line| ('+', *line)));
+    } else {
+        // lcs[i][j] is the length of the longest common subsequence of e[i..]
+        // and a[j..].
+        let mut lcs = vec![vec![0_usize; a.len() + 1]; e.len() + 1];
+        for i in (0..e.len()).rev() {
+            for j in (0..a.len()).rev() {
+                lcs[i][j] = if e[i] == a[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < e.len() || j < a.len() {
+            if i < e.len() && j < a.len() && e[i] == a[j] {
+                ops.push((' ', e[i]));
+                i += 1;
+                j += 1;
+            } else if j == a.len() || (i < e.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+                ops.push(('-', e[i]));
+                i += 1;
+            } else {
+                ops.push(('+', a[j]));
+                j += 1;
+            }
+        }
+    }
+
+    ops.extend(
+        expected[expected.len() - suffix..]
+            .iter()
+            .map(|line| (' ', *line)),
+    );
+    ops
+}
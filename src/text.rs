@@ -0,0 +1,384 @@
+//! Extracting the text of element trees.
+//!
+//! [`Element::inner_text`] extracts the displayed text, which is useful for
+//! generating meta descriptions or search indexes. [`Element::render_plaintext`]
+//! additionally keeps some of the structure (link targets, list bullets,
+//! underlined headings), which is useful for the plain-text alternative of an
+//! email generated from the same tree as the HTML version.
+//!
+//! # Example
+//!
+//! ```
+//! use el::html::*;
+//!
+//! let article = article((
+//!     h1("Release   notes"),
+//!     p(("Version ", strong("2.0"), " is out.")),
+//!     ul((li("Faster"), li("Smaller"))),
+//!     script("trackPageView()"),
+//! ));
+//!
+//! assert_eq!(
+//!     article.inner_text(),
+//!     "Release notes\n\nVersion 2.0 is out.\n\nFaster\nSmaller",
+//! );
+//! ```
+
+use crate::{url::url_scheme, Content, Document, Element, ElementKind};
+
+/// Elements that start and end on their own line.
+#[rustfmt::skip]
+pub(crate) const BLOCK_ELEMENTS: &[&str] = &[
+    "address", "article", "aside", "blockquote", "body", "caption", "dd", "details", "dialog",
+    "div", "dl", "dt", "fieldset", "figcaption", "figure", "footer", "form", "header", "hgroup",
+    "hr", "html", "legend", "li", "main", "menu", "nav", "ol", "pre", "search", "section",
+    "summary", "table", "tbody", "tfoot", "thead", "tr", "ul",
+];
+
+/// Elements that are separated from surrounding text by an empty line.
+#[rustfmt::skip]
+pub(crate) const PARAGRAPH_ELEMENTS: &[&str] = &["h1", "h2", "h3", "h4", "h5", "h6", "p"];
+
+/// Elements whose contents are never displayed as text.
+#[rustfmt::skip]
+const SKIPPED_ELEMENTS: &[&str] = &[
+    "head", "iframe", "noscript", "object", "script", "style", "template", "title",
+];
+
+/// Elements whose whitespace is displayed as-is.
+#[rustfmt::skip]
+pub(crate) const PREFORMATTED_ELEMENTS: &[&str] = &["listing", "plaintext", "pre", "textarea", "xmp"];
+
+#[derive(Debug)]
+enum Item {
+    /// Text whose whitespace has already been processed, and whether its
+    /// leading space may be removed.
+    Text(String, bool),
+    /// A required number of line breaks.
+    Breaks(usize),
+    /// Start prefixing lines, using the first prefix for the first line and
+    /// the second prefix for all following lines.
+    Push(String, String),
+    /// Stop using the most recently pushed prefix.
+    Pop,
+    /// Underline the current line with a character.
+    Underline(char),
+}
+
+#[derive(Default)]
+struct Collector {
+    plaintext: bool,
+    items: Vec<Item>,
+}
+
+impl Collector {
+    fn text(&mut self, text: &str, preformatted: bool) {
+        if preformatted {
+            self.items.push(Item::Text(text.to_string(), false));
+            return;
+        }
+
+        let mut collapsed = String::with_capacity(text.len());
+        let mut space = false;
+        for c in text.chars() {
+            if c.is_ascii_whitespace() {
+                space = true;
+            } else {
+                if space {
+                    collapsed.push(' ');
+                    space = false;
+                }
+                collapsed.push(c);
+            }
+        }
+        if space {
+            collapsed.push(' ');
+        }
+        if !collapsed.is_empty() {
+            self.items.push(Item::Text(collapsed, true));
+        }
+    }
+
+    fn verbatim(&mut self, text: &str) {
+        self.items.push(Item::Text(text.to_string(), false));
+    }
+
+    fn breaks(&mut self, n: usize) {
+        self.items.push(Item::Breaks(n));
+    }
+
+    fn element(&mut self, element: &Element, preformatted: bool) {
+        let name = element.name.as_str();
+        if element.has_attr_ci("hidden")
+            || (element.kind != ElementKind::Foreign && SKIPPED_ELEMENTS.contains(&name))
+        {
+            return;
+        }
+
+        let preformatted = preformatted || PREFORMATTED_ELEMENTS.contains(&name);
+        let breaks = if PARAGRAPH_ELEMENTS.contains(&name) {
+            2
+        } else if BLOCK_ELEMENTS.contains(&name) {
+            1
+        } else {
+            0
+        };
+
+        self.breaks(breaks);
+        if name == "br" {
+            self.verbatim("\n");
+        }
+        if self.plaintext {
+            self.plaintext_element(element, preformatted);
+        } else {
+            self.children(element, preformatted);
+        }
+        if name == "td" || name == "th" {
+            self.verbatim("\t");
+        }
+        self.breaks(breaks);
+    }
+
+    fn plaintext_element(&mut self, element: &Element, preformatted: bool) {
+        match element.name.as_str() {
+            "a" => {
+                self.children(element, preformatted);
+                let Some(href) = element.attr_ci("href") else {
+                    return;
+                };
+                let text = element.inner_text();
+                let target = href.strip_prefix("mailto:").unwrap_or(href);
+                if !(href.starts_with('#') || target == text || has_script_scheme(href)) {
+                    self.text(&format!(" ({href})"), false);
+                }
+            }
+            "blockquote" => {
+                self.items
+                    .push(Item::Push("> ".to_string(), "> ".to_string()));
+                self.children(element, preformatted);
+                self.items.push(Item::Pop);
+            }
+            level @ ("h1" | "h2" | "h3" | "h4" | "h5" | "h6") => {
+                self.children(element, preformatted);
+                let c = if level == "h1" { '=' } else { '-' };
+                self.items.push(Item::Underline(c));
+            }
+            "hr" => self.verbatim("----------"),
+            "img" => {
+                if let Some(alt) = element.attr_ci("alt") {
+                    self.text(alt, false);
+                }
+            }
+            list @ ("ol" | "ul" | "menu") => {
+                let mut number = element.get_int("start").unwrap_or(1);
+                for child in &element.children {
+                    match child {
+                        Content::Element(item) if item.name == "li" => {
+                            let bullet = if list == "ol" {
+                                let bullet = format!("{number}. ");
+                                number = number.saturating_add(1);
+                                bullet
+                            } else {
+                                "- ".to_string()
+                            };
+                            let indent = " ".repeat(bullet.len());
+                            self.items.push(Item::Push(bullet, indent));
+                            self.element(item, preformatted);
+                            self.items.push(Item::Pop);
+                        }
+                        Content::Text(text) => self.text(text, preformatted),
+                        Content::Element(child) => self.element(child, preformatted),
+                        _ => {}
+                    }
+                }
+            }
+            _ => self.children(element, preformatted),
+        }
+    }
+
+    fn children(&mut self, element: &Element, preformatted: bool) {
+        for child in &element.children {
+            match child {
+                Content::Text(text) => self.text(text, preformatted),
+                Content::Element(child) => self.element(child, preformatted),
+                _ => {}
+            }
+        }
+    }
+
+    fn finish(self) -> String {
+        let mut writer = Writer::default();
+        for item in self.items {
+            match item {
+                Item::Text(text, collapsible) => writer.text(&text, collapsible),
+                Item::Breaks(n) => writer.pending = writer.pending.max(n),
+                Item::Push(first, rest) => writer.prefixes.push((first, rest, false)),
+                Item::Pop => {
+                    writer.prefixes.pop();
+                }
+                Item::Underline(c) => writer.underline(c),
+            }
+        }
+        writer.trim_end();
+        writer.result
+    }
+}
+
+/// Assembles the final text from [`Item`]s.
+#[derive(Default)]
+struct Writer {
+    result: String,
+    /// Pending line breaks, written before the next text.
+    pending: usize,
+    /// Line prefixes, and whether their first prefix has been used.
+    prefixes: Vec<(String, String, bool)>,
+    /// Whether no text has been written on the current line.
+    at_line_start: bool,
+    /// Where the text of the current line starts, after its prefixes.
+    line_start: usize,
+}
+
+impl Writer {
+    fn trim_end(&mut self) {
+        let len = self.result.trim_end_matches([' ', '\t']).len();
+        self.result.truncate(len);
+    }
+
+    fn newline(&mut self, blank: bool) {
+        self.result.push('\n');
+        if blank {
+            let prefix = (self.prefixes.iter())
+                .filter(|(_, _, used)| *used)
+                .map(|(_, rest, _)| &rest[..])
+                .collect::<String>();
+            self.result.push_str(prefix.trim_end());
+        }
+        self.at_line_start = true;
+    }
+
+    fn prefix(&mut self) {
+        for (first, rest, used) in &mut self.prefixes {
+            self.result.push_str(if *used { rest } else { first });
+            *used = true;
+        }
+        self.at_line_start = false;
+        self.line_start = self.result.len();
+    }
+
+    fn text(&mut self, text: &str, collapsible: bool) {
+        if self.pending > 0 {
+            self.trim_end();
+            if !self.result.is_empty() {
+                for i in 0..self.pending {
+                    self.newline(i + 1 < self.pending);
+                }
+            }
+            self.pending = 0;
+        }
+        if self.result.is_empty() {
+            self.at_line_start = true;
+        }
+
+        for (i, mut line) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.newline(false);
+            }
+            if collapsible && (self.at_line_start || self.result.ends_with([' ', '\t'])) {
+                line = line.strip_prefix(' ').unwrap_or(line);
+            }
+            if line.is_empty() {
+                continue;
+            }
+            if self.at_line_start {
+                self.prefix();
+            }
+            self.result.push_str(line);
+        }
+    }
+
+    fn underline(&mut self, c: char) {
+        if self.at_line_start {
+            return;
+        }
+        self.trim_end();
+        let len = self.result[self.line_start..].chars().count();
+        self.newline(false);
+        self.prefix();
+        self.result.push_str(&c.to_string().repeat(len));
+    }
+}
+
+/// Whether a URL uses a scheme that executes code.
+fn has_script_scheme(url: &str) -> bool {
+    matches!(url_scheme(url).as_deref(), Some("javascript" | "vbscript"))
+}
+
+impl Element {
+    /// The text displayed by the element and its descendants, similar to the
+    /// [`innerText`][mdn] property in browsers.
+    ///
+    /// Whitespace in text is collapsed, except inside elements like `<pre>`.
+    /// Block elements like `<div>` or `<li>` are placed on their own lines,
+    /// while paragraphs and headings are separated by empty lines. Table
+    /// cells are separated by tabs. The contents of elements that are never
+    /// displayed (like `<script>` or elements with a `hidden` attribute),
+    /// comments, [`Content::Raw`] and [`Content::Trusted`] are skipped.
+    ///
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement/innerText
+    pub fn inner_text(&self) -> String {
+        let mut collector = Collector::default();
+        let preformatted = PREFORMATTED_ELEMENTS.contains(&self.name.as_str());
+        collector.children(self, preformatted);
+        collector.finish()
+    }
+
+    /// Render the element and its descendants as plain text.
+    ///
+    /// Text is extracted like in [`Self::inner_text`], with some additional
+    /// rules to keep the structure of the document readable:
+    ///
+    /// - Links are followed by their URL in parentheses, unless the URL is the
+    ///   same as the link text or points to the same page.
+    /// - List items start with `- ` or their number, and their contents are
+    ///   indented accordingly.
+    /// - Headings are underlined with `=` (for `<h1>`) or `-`.
+    /// - Lines in block quotes start with `> `.
+    /// - Images are replaced by their alt text.
+    /// - Thematic breaks (`<hr>`) are rendered as a line of `-`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use el::html::*;
+    ///
+    /// let email = div((
+    ///     h1("Welcome!"),
+    ///     p(("Please ", a((attr::href("https://example.com/confirm"), "confirm")), " your address.")),
+    ///     ol((li("Log in"), li(("Have ", em("fun"))))),
+    ///     blockquote(p("Quote")),
+    /// ));
+    ///
+    /// assert_eq!(
+    ///     email.render_plaintext(),
+    ///     "Welcome!\n========\n\n\
+    ///      Please confirm (https://example.com/confirm) your address.\n\n\
+    ///      1. Log in\n2. Have fun\n\n\
+    ///      > Quote",
+    /// );
+    /// ```
+    pub fn render_plaintext(&self) -> String {
+        let mut collector = Collector {
+            plaintext: true,
+            ..Collector::default()
+        };
+        collector.element(self, false);
+        collector.finish()
+    }
+}
+
+impl Document {
+    /// Render the document as plain text, see [`Element::render_plaintext`].
+    pub fn render_plaintext(&self) -> String {
+        self.0.render_plaintext()
+    }
+}
@@ -0,0 +1,229 @@
+//! Ordered sets of tokens in attribute values, see [`TokenList`].
+//!
+//! [`TokenList`] is re-exported from [`html::attr`](crate::html::attr), the
+//! token splitting is also used when rendering and querying elements.
+
+use std::{borrow::Cow, fmt};
+
+use crate::{Attr, Element, ElementComponent};
+
+/// An ordered set of tokens for attributes like `class` or `rel`.
+///
+/// Many attributes hold a list of tokens separated by spaces or commas. A token
+/// list keeps each token only once, in the order it was first inserted, and
+/// allows removing tokens again. When added to an element, only the tokens not
+/// yet present in the element's attribute are appended, like with
+/// [`Attr::tokens`].
+///
+/// If the separator is only whitespace, tokens are split at any whitespace.
+/// Otherwise, they are split at the separator without surrounding whitespace,
+/// and the tokens are trimmed. Empty tokens are always ignored.
+///
+/// The token list attributes in [`html::attr`] (e.g. [`attr::class`],
+/// [`attr::rel`], [`attr::sandbox`] or [`attr::headers`]) use the same rules.
+///
+/// [`html::attr`]: crate::html::attr
+/// [`attr::class`]: crate::html::attr::class
+/// [`attr::rel`]: crate::html::attr::rel
+/// [`attr::sandbox`]: crate::html::attr::sandbox
+/// [`attr::headers`]: crate::html::attr::headers
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, html::*, html::attr::TokenList};
+///
+/// let tags = TokenList::new("data-tags", ", ").with("b").with("a").with("b");
+/// let mut element = div((attr::class("card hidden"), tags));
+/// assert_eq!(
+///     element.render_to_string().unwrap(),
+///     r#"<div class="card hidden" data-tags="b, a"></div>"#,
+/// );
+///
+/// // Removing a token from an existing attribute
+/// let class = element.token_list("class", " ").without("hidden");
+/// element.add(class.replacing());
+/// assert_eq!(element.attributes["class"], "card");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenList {
+    name: Cow<'static, str>,
+    separator: Cow<'static, str>,
+    tokens: Vec<String>,
+}
+
+impl TokenList {
+    /// Create a new empty list for an attribute.
+    pub fn new(
+        name: impl Into<Cow<'static, str>>,
+        separator: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            separator: separator.into(),
+            tokens: vec![],
+        }
+    }
+
+    /// Create a new list from an attribute value by splitting it into tokens.
+    pub fn parse(
+        name: impl Into<Cow<'static, str>>,
+        separator: impl Into<Cow<'static, str>>,
+        value: &str,
+    ) -> Self {
+        let mut list = Self::new(name, separator);
+        let tokens = split_tokens(value, &list.separator).collect::<Vec<_>>();
+        list.extend(tokens);
+        list
+    }
+
+    /// The name of the attribute.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The separator between tokens.
+    pub fn separator(&self) -> &str {
+        &self.separator
+    }
+
+    /// Insert a token into the list if it is not already present.
+    ///
+    /// The token is split at the separator first, so a single call can insert
+    /// multiple tokens.
+    pub fn insert(&mut self, token: impl ToString) {
+        let token = token.to_string();
+        for token in split_tokens(&token, &self.separator) {
+            if !self.contains(token) {
+                self.tokens.push(token.to_string());
+            }
+        }
+    }
+
+    /// A more builder-pattern-like version of [`Self::insert`].
+    pub fn with(mut self, token: impl ToString) -> Self {
+        self.insert(token);
+        self
+    }
+
+    /// Remove a token from the list, returning whether it was present.
+    pub fn remove(&mut self, token: &str) -> bool {
+        let len = self.tokens.len();
+        self.tokens.retain(|t| t != token);
+        self.tokens.len() != len
+    }
+
+    /// A more builder-pattern-like version of [`Self::remove`].
+    pub fn without(mut self, token: &str) -> Self {
+        self.remove(token);
+        self
+    }
+
+    /// Whether the list contains a token.
+    pub fn contains(&self, token: &str) -> bool {
+        self.tokens.iter().any(|t| t == token)
+    }
+
+    /// The number of tokens in the list.
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Whether the list contains no tokens.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Iterate over the tokens in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.tokens.iter().map(|t| &t[..])
+    }
+
+    /// Sort the tokens lexicographically instead of by insertion order.
+    pub fn sort(&mut self) {
+        self.tokens.sort_unstable();
+    }
+
+    /// A more builder-pattern-like version of [`Self::sort`].
+    pub fn sorted(mut self) -> Self {
+        self.sort();
+        self
+    }
+
+    /// An attribute that replaces the element's attribute with exactly the
+    /// tokens of this list, instead of adding them to it.
+    pub fn replacing(self) -> Attr {
+        let value = self.to_string();
+        Attr::set(self.name, value)
+    }
+}
+
+impl fmt::Display for TokenList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, token) in self.tokens.iter().enumerate() {
+            if i > 0 {
+                write!(f, "{}", self.separator)?;
+            }
+            write!(f, "{token}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: ToString> Extend<T> for TokenList {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for token in iter {
+            self.insert(token);
+        }
+    }
+}
+
+impl ElementComponent for TokenList {
+    fn add_to_element(self, element: &mut Element) {
+        // An empty list should not produce an empty attribute.
+        if !self.is_empty() {
+            let value = self.to_string();
+            Attr::tokens(self.name, value, self.separator).add_to_element(element);
+        }
+    }
+}
+
+impl Element {
+    /// The tokens of an attribute, or an empty [`TokenList`] if the element
+    /// doesn't have the attribute.
+    pub fn token_list(
+        &self,
+        name: impl Into<Cow<'static, str>>,
+        separator: impl Into<Cow<'static, str>>,
+    ) -> TokenList {
+        let name = name.into();
+        let value = self.attributes.get(&name).map_or("", |v| &v[..]);
+        TokenList::parse(name, separator, value)
+    }
+}
+
+/// Split an attribute value into tokens, see [`TokenList`].
+pub(crate) fn split_tokens<'a>(value: &'a str, separator: &str) -> impl Iterator<Item = &'a str> {
+    let separator = separator.trim().to_string();
+    let mut rest = Some(value);
+    std::iter::from_fn(move || loop {
+        let current = rest?;
+        let (token, next) = if separator.is_empty() {
+            let current = current.trim_start();
+            match current.find(char::is_whitespace) {
+                Some(i) => (&current[..i], Some(&current[i..])),
+                None => (current, None),
+            }
+        } else {
+            match current.split_once(&separator[..]) {
+                Some((token, next)) => (token, Some(next)),
+                None => (current, None),
+            }
+        };
+        rest = next;
+        let token = token.trim();
+        if !token.is_empty() {
+            return Some(token);
+        }
+    })
+}
@@ -0,0 +1,117 @@
+use std::fmt;
+
+use crate::{Content, Element, Render, RenderOptions, Result};
+
+/// HTML that is rendered without escaping because it is known to be safe.
+///
+/// Like [`Content::Raw`], trusted HTML is included in the output verbatim.
+/// Unlike raw content, it can only be created through a few constructors,
+/// each of which makes a claim about where the HTML comes from:
+///
+/// - [`Self::from_static`] for HTML written by the programmer,
+/// - [`Self::from_render`] for HTML rendered by el itself,
+/// - [`Self::assume_trusted`] for everything else, e.g. the output of an HTML
+///   sanitizer or markdown renderer.
+///
+/// Searching for these constructors (especially [`Self::assume_trusted`])
+/// finds every place where unescaped HTML enters the output, so they can be
+/// audited. Sanitizers and markdown renderers should wrap
+/// [`Self::assume_trusted`] in their own functions, so that the chain of
+/// custody stays visible. Trusted HTML is added to elements as
+/// [`Content::Trusted`].
+///
+/// The `deprecate-raw` feature flag doesn't affect trusted HTML, so projects
+/// that ban [`Content::Raw`] can use it instead.
+///
+/// With the optional `serde` feature, trusted HTML can also be deserialized.
+/// Only deserialize elements from sources you would pass to
+/// [`Self::assume_trusted`] as well, e.g. your own cache or database.
+///
+/// # Example
+///
+/// ```
+/// use el::{Render, TrustedHtml, html::*};
+///
+/// fn sanitize(html: &str) -> TrustedHtml {
+///     // A real sanitizer would do a lot more here.
+///     TrustedHtml::assume_trusted(html.replace('<', "&lt;"))
+/// }
+///
+/// let icon = TrustedHtml::from_static("<i class=\"icon\"></i>");
+/// let comment = sanitize("<script>alert(1)</script>");
+/// assert_eq!(
+///     div((icon, p(comment))).render_to_string().unwrap(),
+///     r#"<div><i class="icon"></i><p>&lt;script>alert(1)&lt;/script></p></div>"#,
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrustedHtml(String);
+
+impl TrustedHtml {
+    /// Trust HTML that is part of the source code.
+    ///
+    /// This is meant for string literals and other constants, so the HTML is
+    /// visible wherever it is used. The `'static` bound doesn't enforce this,
+    /// since runtime strings can be turned into `&'static str` too, e.g. with
+    /// [`String::leak`]. Use [`Self::assume_trusted`] for those instead.
+    pub fn from_static(html: &'static str) -> Self {
+        Self(html.to_string())
+    }
+
+    /// Trust the output of rendering an element or document with el.
+    ///
+    /// This is useful for caching rendered fragments and including them in
+    /// later renders without rendering them again.
+    pub fn from_render(value: &impl Render) -> Result<Self> {
+        value.render_to_string().map(Self)
+    }
+
+    /// Like [`Self::from_render`], but with custom [`RenderOptions`].
+    pub fn from_render_with(value: &impl Render, options: &RenderOptions) -> Result<Self> {
+        value.render_to_string_with(options).map(Self)
+    }
+
+    /// Trust arbitrary HTML.
+    ///
+    /// # Warning
+    ///
+    /// The caller is responsible for making sure the HTML is safe to include
+    /// in a page, e.g. because it was produced by a sanitizer. Passing
+    /// untrusted input here may result in security vulnerabilities in the
+    /// rendered HTML.
+    pub fn assume_trusted(html: impl ToString) -> Self {
+        Self(html.to_string())
+    }
+
+    /// The HTML as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Convert into the HTML as a string.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl fmt::Display for TrustedHtml {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<TrustedHtml> for Content {
+    fn from(value: TrustedHtml) -> Self {
+        Self::Trusted(value)
+    }
+}
+
+impl TryFrom<&Element> for TrustedHtml {
+    type Error = crate::Error;
+
+    fn try_from(value: &Element) -> Result<Self> {
+        Self::from_render(value)
+    }
+}
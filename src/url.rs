@@ -0,0 +1,33 @@
+//! Helpers for checking URLs in attribute values.
+
+/// Attributes that contain a URL that may be navigated to or executed.
+#[rustfmt::skip]
+pub(crate) const URL_ATTRIBUTES: &[&str] = &[
+    "action", "data", "formaction", "href", "poster", "src", "xlink:href",
+];
+
+/// Whether a URL uses the `javascript:` scheme, see [`url_scheme`].
+pub(crate) fn has_javascript_scheme(url: &str) -> bool {
+    url_scheme(url).as_deref() == Some("javascript")
+}
+
+/// The scheme of a URL in lowercase, or [`None`] if it is relative.
+///
+/// Like the [URL parser][spec] in browsers, leading spaces and control
+/// characters are skipped and tabs and newlines anywhere are ignored.
+///
+/// [spec]: https://url.spec.whatwg.org/#concept-basic-url-parser
+pub(crate) fn url_scheme(url: &str) -> Option<String> {
+    let mut chars = (url.trim_start_matches(|c: char| c <= ' ').chars())
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'));
+    let mut scheme = String::new();
+    for c in chars.by_ref() {
+        match c {
+            ':' if !scheme.is_empty() => return Some(scheme),
+            'a'..='z' | 'A'..='Z' => scheme.push(c.to_ascii_lowercase()),
+            '0'..='9' | '+' | '-' | '.' if !scheme.is_empty() => scheme.push(c),
+            _ => break,
+        }
+    }
+    None
+}